@@ -0,0 +1,307 @@
+//! Derive macro for the [dbase](https://docs.rs/dbase) crate,
+//! enabled with its `derive` feature and re-exported as `dbase::DbaseTable`.
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Fields, GenericArgument, LitInt, LitStr, PathArguments,
+    Type,
+};
+
+/// Length of Character fields without `#[dbase(len = ...)]`
+const DEFAULT_CHARACTER_LENGTH: u8 = 254;
+/// Width of Numeric and Float fields without `#[dbase(width = ...)]`
+const DEFAULT_NUMERIC_WIDTH: u8 = 24;
+/// Decimals of Numeric and Float fields without `#[dbase(decimals = ...)]`
+const DEFAULT_NUMERIC_DECIMALS: u8 = 15;
+/// Maximum length of a field name, in bytes
+const MAX_FIELD_NAME_LENGTH: usize = 11;
+
+/// Implements `ReadableRecord`, `WritableRecord` and `RecordCompare` for a struct,
+/// and adds a `table_builder()` function returning a `TableWriterBuilder`
+/// with one field per struct field, in the same order.
+///
+/// | Rust type                     | dBase field |
+/// |-------------------------------|-------------|
+/// | `String`, `Option<String>`    | Character   |
+/// | `f64`, `Option<f64>`          | Numeric     |
+/// | `f32`, `Option<f32>`          | Float       |
+/// | `bool`, `Option<bool>`        | Logical     |
+/// | `Date`, `Option<Date>`        | Date        |
+/// | `i32`, `Option<i32>`          | Integer     |
+/// | `DateTime`, `Option<DateTime>`| DateTime    |
+///
+/// Field attributes:
+///
+/// - `#[dbase(len = 50)]`: length of a Character field (default 254)
+/// - `#[dbase(width = 12, decimals = 3)]`: width and decimals of a Numeric
+///   or Float field (default 24 and 15)
+/// - `#[dbase(rename = "NAME")]`: name of the field in the table
+///   (default the name of the struct field)
+///
+/// # Example
+///
+/// ```
+/// use dbase::DbaseTable;
+///
+/// #[derive(DbaseTable, Debug, PartialEq)]
+/// struct Station {
+///     #[dbase(len = 50)]
+///     name: String,
+///     #[dbase(width = 12, decimals = 3)]
+///     length: f64,
+///     open: bool,
+/// }
+///
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut dst = std::io::Cursor::new(Vec::<u8>::new());
+/// let mut writer = Station::table_builder().build_with_dest(&mut dst)?;
+/// let station = Station { name: "Van Dorn Street".to_string(), length: 1.5, open: true };
+/// writer.write_record(&station)?;
+/// drop(writer);
+///
+/// dst.set_position(0);
+/// let stations = dbase::Reader::new(dst)?.read_as::<Station>()?;
+/// assert_eq!(stations, vec![station]);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Fields of other types are rejected with a compile error.
+#[proc_macro_derive(DbaseTable, attributes(dbase))]
+pub fn derive_dbase_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// dBase field created for a struct field
+enum Kind {
+    Character,
+    Numeric,
+    Float,
+    Logical,
+    Date,
+    Integer,
+    DateTime,
+}
+
+/// What the `#[dbase(...)]` attributes of a struct field give
+#[derive(Default)]
+struct FieldAttributes {
+    len: Option<u8>,
+    width: Option<u8>,
+    decimals: Option<u8>,
+    rename: Option<String>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) if !fields.named.is_empty() => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "DbaseTable can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "DbaseTable can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut add_fields = vec![];
+    let mut reads = vec![];
+    let mut writes = vec![];
+    let mut inserts = vec![];
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named fields have an ident");
+        let ty = &field.ty;
+        let attributes = parse_attributes(field)?;
+        let kind = field_kind(ty)?;
+
+        let name = attributes
+            .rename
+            .clone()
+            .unwrap_or_else(|| ident.to_string());
+        if name.is_empty() || name.len() > MAX_FIELD_NAME_LENGTH {
+            return Err(syn::Error::new(
+                ident.span(),
+                format!(
+                    "the dBase field name '{}' must be 1 to {} bytes long, use #[dbase(rename = \"...\")]",
+                    name, MAX_FIELD_NAME_LENGTH
+                ),
+            ));
+        }
+        let field_name = quote! {
+            <::dbase::FieldName as ::core::convert::TryFrom<&str>>::try_from(#name)
+                .expect("the length of the name was checked")
+        };
+
+        check_attributes(&attributes, &kind, field.span())?;
+        let width = attributes.width.unwrap_or(DEFAULT_NUMERIC_WIDTH);
+        let decimals = attributes.decimals.unwrap_or(DEFAULT_NUMERIC_DECIMALS);
+        add_fields.push(match kind {
+            Kind::Character => {
+                let len = attributes.len.unwrap_or(DEFAULT_CHARACTER_LENGTH);
+                quote! { .add_character_field(#field_name, #len) }
+            }
+            Kind::Numeric => quote! { .add_numeric_field(#field_name, #width, #decimals) },
+            Kind::Float => quote! { .add_float_field(#field_name, #width, #decimals) },
+            Kind::Logical => quote! { .add_logical_field(#field_name) },
+            Kind::Date => quote! { .add_date_field(#field_name) },
+            Kind::Integer => quote! { .add_integer_field(#field_name) },
+            Kind::DateTime => quote! { .add_datetime_field(#field_name) },
+        });
+        reads.push(quote! {
+            #ident: field_iterator.read_next_field_as::<#ty>()?.value
+        });
+        writes.push(quote! {
+            field_writer.write_next_field_value(&self.#ident)?;
+        });
+        inserts.push(quote! {
+            record.insert(
+                #name.to_owned(),
+                ::dbase::FieldValue::from(::core::clone::Clone::clone(&self.#ident)),
+            );
+        });
+    }
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns a builder with the fields of the struct, in the same order
+            #vis fn table_builder() -> ::dbase::TableWriterBuilder {
+                ::dbase::TableWriterBuilder::new()
+                    #(#add_fields)*
+            }
+        }
+
+        impl #impl_generics ::dbase::ReadableRecord for #name #ty_generics #where_clause {
+            fn read_using<Source, MemoSource>(
+                field_iterator: &mut ::dbase::FieldIterator<Source, MemoSource>,
+            ) -> ::core::result::Result<Self, ::dbase::FieldIOError>
+            where
+                Source: ::std::io::Read + ::std::io::Seek,
+                MemoSource: ::std::io::Read + ::std::io::Seek,
+            {
+                ::core::result::Result::Ok(Self {
+                    #(#reads),*
+                })
+            }
+        }
+
+        impl #impl_generics ::dbase::WritableRecord for #name #ty_generics #where_clause {
+            fn write_using<'a, W>(
+                &self,
+                field_writer: &mut ::dbase::FieldWriter<'a, W>,
+            ) -> ::core::result::Result<(), ::dbase::FieldIOError>
+            where
+                W: ::std::io::Write,
+            {
+                #(#writes)*
+                ::core::result::Result::Ok(())
+            }
+        }
+
+        impl #impl_generics ::dbase::RecordCompare for #name #ty_generics #where_clause {
+            fn to_record(&self) -> ::dbase::Record {
+                let mut record = ::dbase::Record::default();
+                #(#inserts)*
+                record
+            }
+        }
+    })
+}
+
+fn parse_attributes(field: &syn::Field) -> syn::Result<FieldAttributes> {
+    let mut attributes = FieldAttributes::default();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("dbase"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("len") {
+                attributes.len = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            } else if meta.path.is_ident("width") {
+                attributes.width = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            } else if meta.path.is_ident("decimals") {
+                attributes.decimals = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            } else if meta.path.is_ident("rename") {
+                attributes.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else {
+                return Err(meta.error("expected `len`, `width`, `decimals` or `rename`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(attributes)
+}
+
+/// Rejects the attributes that do not apply to the kind of field
+fn check_attributes(attributes: &FieldAttributes, kind: &Kind, span: Span) -> syn::Result<()> {
+    let is_character = matches!(kind, Kind::Character);
+    let is_numeric = matches!(kind, Kind::Numeric | Kind::Float);
+    if attributes.len.is_some() && !is_character {
+        return Err(syn::Error::new(span, "`len` only applies to String fields"));
+    }
+    if (attributes.width.is_some() || attributes.decimals.is_some()) && !is_numeric {
+        return Err(syn::Error::new(
+            span,
+            "`width` and `decimals` only apply to f64 and f32 fields",
+        ));
+    }
+    if attributes.len == Some(0) || attributes.width == Some(0) {
+        return Err(syn::Error::new(span, "a field cannot have a length of 0"));
+    }
+    Ok(())
+}
+
+/// Returns the kind of dBase field for the type of a struct field,
+/// `Option`s of the supported types give the same kind
+fn field_kind(ty: &Type) -> syn::Result<Kind> {
+    let unsupported = || {
+        syn::Error::new(
+            ty.span(),
+            "unsupported type for a dBase field, expected String, f64, f32, bool, \
+             Date, i32, DateTime or an Option of one of them",
+        )
+    };
+    let Type::Path(path) = ty else {
+        return Err(unsupported());
+    };
+    let segment = path.path.segments.last().ok_or_else(unsupported)?;
+    let kind = match segment.ident.to_string().as_str() {
+        "Option" => {
+            let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+                return Err(unsupported());
+            };
+            match arguments.args.first() {
+                Some(GenericArgument::Type(inner)) if !is_option(inner) => field_kind(inner)?,
+                _ => return Err(unsupported()),
+            }
+        }
+        "String" => Kind::Character,
+        "f64" => Kind::Numeric,
+        "f32" => Kind::Float,
+        "bool" => Kind::Logical,
+        "Date" => Kind::Date,
+        "i32" => Kind::Integer,
+        "DateTime" => Kind::DateTime,
+        _ => return Err(unsupported()),
+    };
+    Ok(kind)
+}
+
+fn is_option(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.path.segments.last().is_some_and(|s| s.ident == "Option"))
+}
@@ -0,0 +1,8 @@
+#[derive(dbase::DbaseTable)]
+struct Station {
+    name: String,
+    #[dbase(len = 10)]
+    length: f64,
+}
+
+fn main() {}
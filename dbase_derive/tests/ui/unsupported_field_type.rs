@@ -0,0 +1,7 @@
+#[derive(dbase::DbaseTable)]
+struct Station {
+    name: String,
+    id: u64,
+}
+
+fn main() {}
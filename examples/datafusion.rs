@@ -37,7 +37,7 @@ async fn main() -> Result<()> {
     df.show().await?;
 
     // alternatively, we can manually create and register the table
-    let stations_table = DbaseTable::new("./tests/data/stations.dbf");
+    let stations_table = DbaseTable::try_new("./tests/data/stations.dbf")?;
     ctx.register_table("stations2", Arc::new(stations_table))
         .expect("failed to register table");
 
@@ -0,0 +1,59 @@
+//! Reads arbitrary bytes as a dBase file, nothing should panic.
+//!
+//! Run with a bounded number of iterations using:
+//! `cargo +nightly fuzz run read -- -runs=1000000`
+//!
+//! Inputs that crashed are worth adding to `tests/data/fuzz`
+//! so that they are replayed by the tests.
+#![no_main]
+
+use std::io::Cursor;
+
+use libfuzzer_sys::fuzz_target;
+
+const MAX_RECORDS: usize = 4096;
+
+fn read_all_records(data: &[u8], memo: Option<&[u8]>) {
+    let reader = match memo {
+        Some(memo) => dbase::ReaderBuilder::<_, dbase::UnicodeLossy>::new(Cursor::new(data))
+            .with_memo(Cursor::new(memo))
+            .build(),
+        None => dbase::Reader::new(Cursor::new(data)),
+    };
+    if let Ok(mut reader) = reader {
+        for record in reader.iter_records() {
+            let _ = record;
+        }
+    }
+}
+
+fn read_all_fields(data: &[u8]) {
+    if let Ok(mut file) = dbase::File::open(Cursor::new(data)) {
+        let num_fields = file.fields().len();
+        // The header may claim billions of records
+        let num_records = file.num_records().min(MAX_RECORDS);
+        for index in 0..num_records {
+            let Some(mut record) = file.record(index) else {
+                break;
+            };
+            for field_index in 0..num_fields {
+                if let Some(mut field) = record.field(dbase::FieldIndex(field_index)) {
+                    let _ = field.read();
+                }
+            }
+        }
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    read_all_records(data, None);
+    read_all_fields(data);
+
+    // The first 2 bytes give where to split the rest between the dbf and the memo
+    if data.len() > 2 {
+        let split = usize::from(u16::from_le_bytes([data[0], data[1]]));
+        let rest = &data[2..];
+        let (dbf, memo) = rest.split_at(split.min(rest.len()));
+        read_all_records(dbf, Some(memo));
+    }
+});
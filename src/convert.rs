@@ -0,0 +1,227 @@
+//! Copy of a table, re-encoding its text with another encoding
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::encoding::DynEncoding;
+use crate::field::types::FieldType;
+use crate::limits::MAX_CHARACTER_FIELD_LENGTH;
+use crate::schema::Incompatibility;
+use crate::{Encoding, Error, ErrorKind, FieldInfo, FieldValue, File, Reader, TableWriterBuilder};
+
+/// What to do with Character values that need more bytes than
+/// the length of their field once encoded with the new encoding
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// The conversion fails with [ErrorKind::IncompatibleRecord]
+    Error,
+    /// The field is made longer, up to [MAX_CHARACTER_FIELD_LENGTH] bytes,
+    /// values that still do not fit are truncated.
+    ///
+    /// Fields longer than 255 bytes use the decimal count byte, like Clipper and FoxPro,
+    /// see [FieldInfo::length].
+    #[default]
+    Widen,
+    /// The field keeps its length, the values are truncated
+    Truncate,
+}
+
+/// Summary of a conversion made by [convert_encoding]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConversionReport {
+    num_records: usize,
+    widened_fields: BTreeMap<String, u16>,
+    truncated_values: BTreeMap<String, usize>,
+}
+
+impl ConversionReport {
+    /// Number of records written to the new table
+    pub fn num_records(&self) -> usize {
+        self.num_records
+    }
+
+    /// New length of the fields that were made longer
+    pub fn widened_fields(&self) -> &BTreeMap<String, u16> {
+        &self.widened_fields
+    }
+
+    /// Number of values truncated, for the fields that had at least one
+    pub fn truncated_values(&self) -> &BTreeMap<String, usize> {
+        &self.truncated_values
+    }
+
+    /// Number of values of the field that were truncated
+    pub fn num_truncated(&self, field_name: &str) -> usize {
+        self.truncated_values.get(field_name).copied().unwrap_or(0)
+    }
+}
+
+/// Encoded sizes of the values of a Character field
+#[derive(Copy, Clone, Default)]
+struct EncodedSizes {
+    max: usize,
+    num_longer_than_field: usize,
+    num_longer_than_max: usize,
+}
+
+/// Copies the table at `src_path` to `dst_path`, re-encoding its Character values
+/// with `dst_encoding`, using [OverflowPolicy::Widen].
+///
+/// See [convert_encoding_with_policy].
+pub fn convert_encoding<P1, P2, E1, E2>(
+    src_path: P1,
+    dst_path: P2,
+    src_encoding: Option<E1>,
+    dst_encoding: E2,
+) -> Result<ConversionReport, Error>
+where
+    P1: AsRef<Path>,
+    P2: AsRef<Path>,
+    E1: Encoding + 'static,
+    E2: Encoding + 'static,
+{
+    convert_encoding_with_policy(
+        src_path,
+        dst_path,
+        src_encoding,
+        dst_encoding,
+        OverflowPolicy::default(),
+    )
+}
+
+/// Copies the table at `src_path` to `dst_path`, re-encoding its Character values
+/// and Memo values with `dst_encoding`.
+/// The code page mark of the new table is the one of `dst_encoding`.
+///
+/// The source is decoded with `src_encoding`, or with the encoding
+/// of its code page mark when `None`.
+///
+/// The records are read twice, once to find the length the values need,
+/// and once to write them, they are not all kept in memory.
+/// Deleted records are not copied.
+///
+/// The memo file of the new table is created next to it when the table has Memo fields.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// # let tmp_dir = tempfile::tempdir().unwrap();
+/// # let dst_path = tmp_dir.path().join("stations.dbf");
+/// let report = dbase::convert_encoding(
+///     "tests/data/stations.dbf",
+///     &dst_path,
+///     None::<dbase::Unicode>,
+///     dbase::UnicodeLossy,
+/// )?;
+/// assert_eq!(report.num_records(), 86);
+/// # Ok(())
+/// # }
+/// ```
+pub fn convert_encoding_with_policy<P1, P2, E1, E2>(
+    src_path: P1,
+    dst_path: P2,
+    src_encoding: Option<E1>,
+    dst_encoding: E2,
+    policy: OverflowPolicy,
+) -> Result<ConversionReport, Error>
+where
+    P1: AsRef<Path>,
+    P2: AsRef<Path>,
+    E1: Encoding + 'static,
+    E2: Encoding + 'static,
+{
+    let src_encoding = src_encoding.map(DynEncoding::new);
+    let mut reader = Reader::open_path(src_path.as_ref(), src_encoding.clone())?;
+
+    let character_fields = reader
+        .fields()
+        .iter()
+        .filter(|info| info.field_type == FieldType::Character)
+        .cloned()
+        .collect::<Vec<FieldInfo>>();
+    let mut sizes = vec![EncodedSizes::default(); character_fields.len()];
+    let mut record_num = 0;
+    reader.for_each_record(|record| {
+        for (info, sizes) in character_fields.iter().zip(sizes.iter_mut()) {
+            let Some(FieldValue::Character(Some(text))) = record.get(info.name()) else {
+                continue;
+            };
+            let size = dst_encoding
+                .encode(text)
+                .map_err(|error| Error {
+                    record_num,
+                    field: Some(info.clone()),
+                    field_index: None,
+                    kind: error.into(),
+                })?
+                .len();
+
+            if size > info.length() as usize {
+                if policy == OverflowPolicy::Error {
+                    return Err(Error {
+                        record_num,
+                        field: Some(info.clone()),
+                        field_index: None,
+                        kind: ErrorKind::IncompatibleRecord(Incompatibility::ValueTooLong {
+                            name: info.name().to_string(),
+                            max: info.length() as usize,
+                            actual: size,
+                        }),
+                    });
+                }
+                sizes.num_longer_than_field += 1;
+            }
+            if size > MAX_CHARACTER_FIELD_LENGTH {
+                sizes.num_longer_than_max += 1;
+            }
+            sizes.max = sizes.max.max(size);
+        }
+        record_num += 1;
+        Ok(())
+    })?;
+
+    let mut report = ConversionReport {
+        num_records: record_num,
+        ..ConversionReport::default()
+    };
+    let mut table_info = reader.into_table_info();
+    for (info, sizes) in character_fields.iter().zip(sizes.iter()) {
+        let num_truncated = match policy {
+            OverflowPolicy::Error => 0,
+            OverflowPolicy::Widen => {
+                if sizes.max > info.length() as usize {
+                    let length = sizes.max.min(MAX_CHARACTER_FIELD_LENGTH) as u16;
+                    if let Some(field) = table_info
+                        .fields_info
+                        .iter_mut()
+                        .find(|field| field.name == info.name)
+                    {
+                        field.field_length = length;
+                    }
+                    report
+                        .widened_fields
+                        .insert(info.name().to_string(), length);
+                }
+                sizes.num_longer_than_max
+            }
+            OverflowPolicy::Truncate => sizes.num_longer_than_field,
+        };
+        if num_truncated > 0 {
+            report
+                .truncated_values
+                .insert(info.name().to_string(), num_truncated);
+        }
+    }
+
+    // Written with a File, which creates the memo file
+    let table_info = TableWriterBuilder::from_table_info(table_info)
+        .set_encoding(dst_encoding)
+        .build_table_info()?;
+    let mut dst = File::create(dst_path, table_info)?;
+    let mut reader = Reader::open_path(src_path.as_ref(), src_encoding)?;
+    reader.for_each_record(|record| dst.append_record(record))?;
+    dst.sync_all()
+        .map_err(|error| Error::io_error(error, record_num))?;
+
+    Ok(report)
+}
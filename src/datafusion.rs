@@ -1,14 +1,17 @@
-use crate::{file::BufReadWriteFile, FieldType, FieldValue, File as DbaseFile};
+use crate::{
+    file::BufReadWriteFile, Encoding, Error, ErrorKind, FieldIndex, FieldInfo, FieldName,
+    FieldType, FieldValue, File as DbaseFile, TableWriterBuilder, Warning,
+};
 use async_trait::async_trait;
 use datafusion::arrow::array::{
-    ArrayBuilder, ArrayRef, BooleanBuilder, Date32Builder, Float32Builder, Float64Builder,
-    Int32Builder, Int64Builder, StringBuilder,
+    ArrayBuilder, ArrayRef, BinaryBuilder, BooleanBuilder, Date32Builder, Float32Builder,
+    Float64Builder, Int32Builder, Int64Builder, StringBuilder, TimestampSecondBuilder,
 };
-use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::datasource::provider::TableProviderFactory;
 use datafusion::datasource::{TableProvider, TableType};
-use datafusion::error::Result;
+use datafusion::error::{DataFusionError, Result};
 use datafusion::execution::context::{SessionState, TaskContext};
 use datafusion::physical_plan::expressions::PhysicalSortExpr;
 use datafusion::physical_plan::memory::MemoryStream;
@@ -17,16 +20,174 @@ use datafusion::physical_plan::{
     Statistics,
 };
 use datafusion::prelude::*;
-use datafusion_expr::CreateExternalTable;
+use datafusion::scalar::ScalarValue;
+use datafusion_expr::{BinaryExpr, CreateExternalTable, Operator, TableProviderFilterPushDown};
 use std::any::Any;
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt::{Debug, Formatter};
 
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
+/// Length used for Character fields when no length hint is given
+const DEFAULT_CHARACTER_LENGTH: u8 = 254;
+/// Length used for Numeric fields created from Int64 or float types
+/// when no length hint is given
+const DEFAULT_NUMERIC_LENGTH: u8 = 24;
+/// Number of decimals for Numeric/Float fields created from float types
+const DEFAULT_NUMERIC_DECIMALS: u8 = 15;
+/// Numeric fields without decimals and at most this long
+/// always hold values that fit in an Int64
+const MAX_INT64_NUMERIC_LENGTH: u8 = 18;
+
+/// Returns the arrow DataType used to represent values of the field type,
+/// and whether the arrow field should be nullable.
+///
+/// Types for which the dBase format cannot represent a missing value
+/// (e.g. Integer, Double) are never nullable.
+///
+/// # Example
+///
+/// ```
+/// use datafusion::arrow::datatypes::DataType;
+/// use dbase::{field_type_to_arrow, FieldType};
+///
+/// assert_eq!(field_type_to_arrow(FieldType::Character, true), (DataType::Utf8, true));
+/// assert_eq!(field_type_to_arrow(FieldType::Integer, true), (DataType::Int32, false));
+/// ```
+pub fn field_type_to_arrow(ft: FieldType, nullable: bool) -> (DataType, bool) {
+    let (data_type, can_be_null) = match ft {
+        FieldType::Character => (DataType::Utf8, true),
+        FieldType::Currency => (DataType::Float64, false),
+        FieldType::Date => (DataType::Date32, true), // days
+        FieldType::DateTime | FieldType::Timestamp => {
+            (DataType::Timestamp(TimeUnit::Second, None), false)
+        }
+        FieldType::Double => (DataType::Float64, false),
+        FieldType::Float => (DataType::Float32, true),
+        FieldType::Integer => (DataType::Int32, false),
+        FieldType::Logical => (DataType::Boolean, true),
+        FieldType::Memo => (DataType::Utf8, false),
+        FieldType::Numeric => (DataType::Float64, true),
+        FieldType::SystemNullFlags => (DataType::Binary, false),
+    };
+    (data_type, nullable && can_be_null)
+}
+
+/// Returns the arrow DataType used to represent values of the field,
+/// and whether the arrow field should be nullable.
+///
+/// Same as [field_type_to_arrow], except for Numeric fields without decimals
+/// that are at most 18 digits long, which are represented as Int64,
+/// binary Character fields, represented as Binary, and Double fields stored as text,
+/// represented like Numeric fields.
+pub fn field_info_to_arrow(info: &FieldInfo, nullable: bool) -> (DataType, bool) {
+    match info.field_type {
+        FieldType::Character if info.flags.is_binary() => (DataType::Binary, false),
+        FieldType::Double if info.is_text_double() => {
+            field_type_to_arrow(FieldType::Numeric, nullable)
+        }
+        FieldType::Numeric
+            if info.num_decimal_places == 0
+                && info.field_length <= u16::from(MAX_INT64_NUMERIC_LENGTH) =>
+        {
+            (DataType::Int64, nullable)
+        }
+        field_type => field_type_to_arrow(field_type, nullable),
+    }
+}
+
+/// Creates the dBase field info that can hold values of the arrow field.
+///
+/// Character and Numeric fields need a length, `length_hint` is used for them,
+/// if not given, Character fields are 254 bytes long, Numeric ones 24 bytes
+/// (18 for Int64, so that they are read back as Int64).
+///
+/// Float64 is mapped to Numeric so that the file stays readable by dBase III tools.
+pub fn arrow_to_field_info(field: &Field, length_hint: Option<u8>) -> Result<FieldInfo, Error> {
+    let name = FieldName::try_from(field.name().as_str()).map_err(|msg| Error {
+        record_num: 0,
+        field: None,
+        field_index: None,
+        kind: ErrorKind::Message(format!("Invalid field name '{}': {}", field.name(), msg)),
+    })?;
+
+    let numeric_length = length_hint.unwrap_or(DEFAULT_NUMERIC_LENGTH);
+    let numeric_decimals = DEFAULT_NUMERIC_DECIMALS.min(numeric_length.saturating_sub(2));
+
+    let info = match field.data_type() {
+        DataType::Utf8 | DataType::LargeUtf8 => FieldInfo::new(
+            name,
+            FieldType::Character,
+            length_hint.unwrap_or(DEFAULT_CHARACTER_LENGTH),
+        ),
+        DataType::Boolean => FieldInfo::new(name, FieldType::Logical, 1),
+        DataType::Date32 => FieldInfo::new(name, FieldType::Date, 8),
+        DataType::Timestamp(_, _) => FieldInfo::new(name, FieldType::DateTime, 8),
+        DataType::Int8 | DataType::Int16 | DataType::Int32 => {
+            FieldInfo::new(name, FieldType::Integer, 4)
+        }
+        DataType::Int64 => FieldInfo::new(
+            name,
+            FieldType::Numeric,
+            length_hint.unwrap_or(MAX_INT64_NUMERIC_LENGTH),
+        ),
+        DataType::Float32 => {
+            let mut info = FieldInfo::new(name, FieldType::Float, numeric_length);
+            info.num_decimal_places = numeric_decimals;
+            info
+        }
+        DataType::Float64 => {
+            let mut info = FieldInfo::new(name, FieldType::Numeric, numeric_length);
+            info.num_decimal_places = numeric_decimals;
+            info
+        }
+        other => {
+            return Err(Error {
+                record_num: 0,
+                field: None,
+                field_index: None,
+                kind: ErrorKind::Message(format!(
+                    "The arrow type {} of field '{}' has no dBase equivalent",
+                    other,
+                    field.name()
+                )),
+            })
+        }
+    };
+    Ok(info)
+}
+
+impl TableWriterBuilder {
+    /// Creates a builder with fields matching the arrow schema
+    ///
+    /// `lengths` gives the length of Character and Numeric fields, by field name,
+    /// see [arrow_to_field_info] for the defaults.
+    pub fn from_arrow_schema(
+        schema: &Schema,
+        lengths: &HashMap<String, u8>,
+    ) -> Result<Self, Error> {
+        schema
+            .fields()
+            .iter()
+            .try_fold(Self::new(), |builder, field| {
+                let info = arrow_to_field_info(field, lengths.get(field.name()).copied())?;
+                Ok(builder.add_field_info(info))
+            })
+    }
+}
+
 pub struct DbaseTable {
     path: String,
     file: Arc<Mutex<DbaseFile<BufReadWriteFile>>>,
+    /// Whether `column = 'text'` filters on Character fields are checked
+    /// before reading the fields of the records
+    filter_pushdown: bool,
+    /// Number of field values decoded by the scans of the table,
+    /// the fields compared by pushed down filters are not decoded
+    values_read: Arc<AtomicUsize>,
 }
 
 impl Clone for DbaseTable {
@@ -34,40 +195,121 @@ impl Clone for DbaseTable {
         return DbaseTable {
             path: self.path.clone(),
             file: self.file.clone(),
+            filter_pushdown: self.filter_pushdown,
+            values_read: self.values_read.clone(),
+        };
+    }
+}
+
+/// A `column = 'text'` filter on a Character field, checked on the bytes
+/// of the records so that the records that do not match are not read
+#[derive(Debug, Clone)]
+struct CharacterEquality {
+    field_index: FieldIndex,
+    /// The text, encoded with the encoding of the table
+    encoded: Vec<u8>,
+}
+
+impl CharacterEquality {
+    /// Creates the filter for `expr`, if it is a `column = 'text'`
+    /// comparison on a Character field of the file
+    fn try_new(file: &DbaseFile<BufReadWriteFile>, expr: &Expr) -> Option<Self> {
+        let Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::Eq,
+            right,
+        }) = expr
+        else {
+            return None;
+        };
+        let (column, text) = match (left.as_ref(), right.as_ref()) {
+            (Expr::Column(column), Expr::Literal(ScalarValue::Utf8(Some(text))))
+            | (Expr::Literal(ScalarValue::Utf8(Some(text))), Expr::Column(column)) => {
+                (column, text)
+            }
+            _ => return None,
         };
+        // Bytes that cannot be decoded may be read as the replacement character
+        if text.contains(char::REPLACEMENT_CHARACTER) {
+            return None;
+        }
+        let field_index = file.fields().iter().position(|field| {
+            field.name().to_lowercase() == column.name
+                && field.field_type == FieldType::Character
+                && !field.flags.is_binary()
+        })?;
+        let encoded = file.encoding.encode(text).ok()?.into_owned();
+        Some(Self {
+            field_index: FieldIndex(field_index),
+            encoded,
+        })
     }
 }
 
 impl DbaseTable {
-    pub fn new<P: AsRef<Path> + Debug>(path: P) -> Self {
-        let file = DbaseFile::open_read_only(&path)
-            .expect(format!("Could not find file {:?} or corresponding memo file", &path).as_str());
-        return DbaseTable {
-            path: path
-                .as_ref()
-                .to_str()
-                .expect("Path contains non-unicode characters")
-                .to_string(),
+    /// Opens the table at `path`, and its memo file if it has memo fields
+    pub fn try_new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = DbaseFile::open_read_only(path).map_err(|error| {
+            DataFusionError::Plan(format!(
+                "Could not open dBase table {}: {}",
+                path.display(),
+                error
+            ))
+        })?;
+        let path = path.to_str().ok_or_else(|| {
+            DataFusionError::Plan(format!(
+                "The path {} contains non-unicode characters",
+                path.display()
+            ))
+        })?;
+        Ok(DbaseTable {
+            path: path.to_string(),
             file: Arc::new(Mutex::new(file)),
-        };
+            filter_pushdown: true,
+            values_read: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    /// Sets whether `column = 'text'` filters on Character fields are used
+    /// to skip the records that do not match before reading their fields.
+    ///
+    /// Enabled by default, DataFusion still applies the filters to the records read.
+    pub fn with_filter_pushdown(mut self, enabled: bool) -> Self {
+        self.filter_pushdown = enabled;
+        self
+    }
+
+    /// Opens the table at `path`, panics if it cannot be opened
+    #[deprecated(note = "use DbaseTable::try_new, which returns an error")]
+    pub fn new<P: AsRef<Path> + Debug>(path: P) -> Self {
+        Self::try_new(&path).unwrap_or_else(|error| panic!("Could not open {:?}: {}", path, error))
     }
 
     pub fn num_records(&self) -> usize {
         return self.file.lock().unwrap().num_records();
     }
 
+    /// Returns the warnings collected by the queries since the last call,
+    /// such as the Numeric values that do not fit in their Int64 column
+    pub fn take_warnings(&self) -> Vec<Warning> {
+        self.file.lock().unwrap().take_warnings()
+    }
+
     pub(crate) async fn create_physical_plan(
         &self,
         projections: Option<&Vec<usize>>,
         limit: Option<usize>,
+        filters: Vec<CharacterEquality>,
         schema: SchemaRef,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         Ok(Arc::new(DbaseExec::new(
             projections,
             limit,
+            filters,
             schema,
             self.clone(),
-        )))
+        )?))
     }
 }
 
@@ -84,18 +326,7 @@ impl TableProvider for DbaseTable {
         let arrow_fields: Vec<_> = dbase_fields
             .into_iter()
             .map(|field| {
-                let ftype = match field.field_type {
-                    FieldType::Character => DataType::Utf8,
-                    FieldType::Currency => DataType::Float64,
-                    FieldType::Date => DataType::Date32, // days
-                    FieldType::DateTime => DataType::Int64,
-                    FieldType::Double => DataType::Float64,
-                    FieldType::Float => DataType::Float32,
-                    FieldType::Integer => DataType::Int32,
-                    FieldType::Logical => DataType::Boolean,
-                    FieldType::Memo => DataType::Utf8,
-                    FieldType::Numeric => DataType::Float64,
-                };
+                let (ftype, _) = field_info_to_arrow(field, true);
                 Field::new(field.name().to_lowercase(), ftype, true)
             })
             .collect();
@@ -107,16 +338,42 @@ impl TableProvider for DbaseTable {
         TableType::Base
     }
 
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        let file = self.file.lock().unwrap();
+        Ok(filters
+            .iter()
+            .map(|filter| {
+                if self.filter_pushdown && CharacterEquality::try_new(&file, filter).is_some() {
+                    // Only the records that cannot match are skipped
+                    TableProviderFilterPushDown::Inexact
+                } else {
+                    TableProviderFilterPushDown::Unsupported
+                }
+            })
+            .collect())
+    }
+
     async fn scan(
         &self,
         _state: &SessionState,
         projection: Option<&Vec<usize>>,
-        // filters and limit can be used here to inject some push-down operations if needed
-        _filters: &[Expr],
+        filters: &[Expr],
         limit: Option<usize>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
+        let filters = if self.filter_pushdown {
+            let file = self.file.lock().unwrap();
+            filters
+                .iter()
+                .filter_map(|filter| CharacterEquality::try_new(&file, filter))
+                .collect()
+        } else {
+            vec![]
+        };
         return self
-            .create_physical_plan(projection, limit, self.schema())
+            .create_physical_plan(projection, limit, filters, self.schema())
             .await;
     }
 }
@@ -126,16 +383,23 @@ struct DbaseExec {
     projected_schema: SchemaRef,
     projections: Vec<usize>,
     limit: usize,
+    /// Filters the records must match to be read
+    filters: Vec<CharacterEquality>,
 }
 
 impl DbaseExec {
     fn new(
         projections: Option<&Vec<usize>>,
         limit: Option<usize>,
+        filters: Vec<CharacterEquality>,
         schema: SchemaRef,
         db: DbaseTable,
-    ) -> Self {
-        let projected_schema = project_schema(&schema, projections).unwrap();
+    ) -> Result<Self> {
+        let projected_schema = project_schema(&schema, projections)?;
+        // Fail when planning rather than when executing
+        for field in projected_schema.fields() {
+            new_column_builder(field.data_type(), 0)?;
+        }
 
         let projections = match projections {
             Some(proj) => proj.to_vec(),
@@ -144,12 +408,13 @@ impl DbaseExec {
 
         let limit = limit.unwrap_or(db.num_records());
 
-        Self {
+        Ok(Self {
             table: db,
             projected_schema,
             projections,
             limit,
-        }
+            filters,
+        })
     }
 }
 
@@ -211,28 +476,7 @@ impl ExecutionPlan for DbaseExec {
         let schema_fields = schema.fields();
 
         for field in schema_fields {
-            match field.data_type() {
-                DataType::Utf8 => column_builders.push(Box::new(StringBuilder::with_capacity(
-                    num_records,
-                    num_records * 10,
-                ))), // assume 10 chars per string
-                DataType::Int32 => {
-                    column_builders.push(Box::new(Int32Builder::with_capacity(num_records)))
-                }
-                DataType::Int64 => {
-                    column_builders.push(Box::new(Int64Builder::with_capacity(num_records)))
-                }
-                DataType::Date32 => {
-                    column_builders.push(Box::new(Date32Builder::with_capacity(num_records)))
-                }
-                DataType::Float64 => {
-                    column_builders.push(Box::new(Float64Builder::with_capacity(num_records)))
-                }
-                DataType::Boolean => {
-                    column_builders.push(Box::new(BooleanBuilder::with_capacity(num_records)))
-                }
-                _ => panic!("Unsupported field type"),
-            };
+            column_builders.push(new_column_builder(field.data_type(), num_records)?);
         }
 
         let dbase_fields: Vec<_> = dbase_file
@@ -242,19 +486,39 @@ impl ExecutionPlan for DbaseExec {
             .collect();
 
         let mut records = dbase_file.records();
+        let mut warnings = vec![];
 
         let mut i = 0;
+        let mut values_read = 0;
+        let mut next_record_index = 0;
         while let Some(mut record) = records.next() {
+            let record_index = next_record_index;
+            next_record_index += 1;
             if record.is_deleted().unwrap() {
                 continue;
             }
+            let mut matches = true;
+            for filter in &self.filters {
+                let bytes = record
+                    .trimmed_field_bytes(filter.field_index)
+                    .map_err(|error| DataFusionError::External(Box::new(error)))?;
+                if bytes != filter.encoded.as_slice() {
+                    matches = false;
+                    break;
+                }
+            }
+            if !matches {
+                continue;
+            }
             if i >= self.limit {
                 break;
             }
             i += 1;
 
             for (j, &proj) in self.projections.iter().enumerate() {
-                match record.field(dbase_fields[proj]).unwrap().read().unwrap() {
+                let value = record.field(dbase_fields[proj]).unwrap().read().unwrap();
+                values_read += 1;
+                match value {
                     FieldValue::Character(c) => match c {
                         Some(c) => column_builders[j]
                             .as_any_mut()
@@ -267,6 +531,11 @@ impl ExecutionPlan for DbaseExec {
                             .unwrap()
                             .append_null(),
                     },
+                    FieldValue::CharacterBinary(bytes) => column_builders[j]
+                        .as_any_mut()
+                        .downcast_mut::<BinaryBuilder>()
+                        .unwrap()
+                        .append_value(bytes),
                     FieldValue::Currency(f) => column_builders[j]
                         .as_any_mut()
                         .downcast_mut::<Float64Builder>()
@@ -285,13 +554,11 @@ impl ExecutionPlan for DbaseExec {
                             .unwrap()
                             .append_null(),
                     },
-                    FieldValue::DateTime(d) => match d {
-                        d => column_builders[j]
-                            .as_any_mut()
-                            .downcast_mut::<Int64Builder>()
-                            .unwrap()
-                            .append_value(d.to_unix_timestamp()),
-                    },
+                    FieldValue::DateTime(d) => column_builders[j]
+                        .as_any_mut()
+                        .downcast_mut::<TimestampSecondBuilder>()
+                        .unwrap()
+                        .append_value(d.to_unix_timestamp()),
                     FieldValue::Double(d) => column_builders[j]
                         .as_any_mut()
                         .downcast_mut::<Float64Builder>()
@@ -331,22 +598,48 @@ impl ExecutionPlan for DbaseExec {
                         .downcast_mut::<StringBuilder>()
                         .unwrap()
                         .append_value(m.escape_default().to_string()),
-                    FieldValue::Numeric(n) => match n {
-                        Some(n) => column_builders[j]
-                            .as_any_mut()
-                            .downcast_mut::<Float64Builder>()
-                            .unwrap()
-                            .append_value(n),
-                        None => column_builders[j]
-                            .as_any_mut()
-                            .downcast_mut::<Float64Builder>()
-                            .unwrap()
-                            .append_null(),
-                    },
+                    FieldValue::Numeric(n) => {
+                        let builder = column_builders[j].as_any_mut();
+                        if let Some(builder) = builder.downcast_mut::<Int64Builder>() {
+                            let value = n.and_then(numeric_to_i64);
+                            if let (None, Some(n)) = (value, n) {
+                                let field_name = schema_fields[j].name();
+                                let added = warnings.iter_mut().find_map(|warning| match warning {
+                                    Warning::Int64Overflow {
+                                        field_name: name,
+                                        count,
+                                        ..
+                                    } if name == field_name => Some(count),
+                                    _ => None,
+                                });
+                                match added {
+                                    Some(count) => *count += 1,
+                                    None => warnings.push(Warning::Int64Overflow {
+                                        record_index,
+                                        field_name: field_name.to_string(),
+                                        value: n.to_string(),
+                                        count: 1,
+                                    }),
+                                }
+                            }
+                            builder.append_option(value);
+                        } else {
+                            builder
+                                .downcast_mut::<Float64Builder>()
+                                .unwrap()
+                                .append_option(n);
+                        }
+                    }
                 }
             }
         }
 
+        drop(records);
+        dbase_file.add_warnings(warnings);
+        self.table
+            .values_read
+            .fetch_add(values_read, Ordering::Relaxed);
+
         let array_refs: Vec<ArrayRef> = column_builders
             .iter_mut()
             .map(|builder| builder.finish())
@@ -367,6 +660,43 @@ impl ExecutionPlan for DbaseExec {
     }
 }
 
+/// Creates the builder of the column of the arrow `data_type`,
+/// fails for types the fields are never read as
+fn new_column_builder(data_type: &DataType, capacity: usize) -> Result<Box<dyn ArrayBuilder>> {
+    let builder: Box<dyn ArrayBuilder> = match data_type {
+        // assume 10 chars per string
+        DataType::Utf8 => Box::new(StringBuilder::with_capacity(capacity, capacity * 10)),
+        DataType::Binary => Box::new(BinaryBuilder::with_capacity(capacity, capacity * 10)),
+        DataType::Int32 => Box::new(Int32Builder::with_capacity(capacity)),
+        DataType::Int64 => Box::new(Int64Builder::with_capacity(capacity)),
+        DataType::Date32 => Box::new(Date32Builder::with_capacity(capacity)),
+        DataType::Timestamp(TimeUnit::Second, None) => {
+            Box::new(TimestampSecondBuilder::with_capacity(capacity))
+        }
+        DataType::Float32 => Box::new(Float32Builder::with_capacity(capacity)),
+        DataType::Float64 => Box::new(Float64Builder::with_capacity(capacity)),
+        DataType::Boolean => Box::new(BooleanBuilder::with_capacity(capacity)),
+        data_type => {
+            return Err(DataFusionError::Plan(format!(
+                "Unsupported column type {} for a dBase table",
+                data_type
+            )))
+        }
+    };
+    Ok(builder)
+}
+
+/// Converts the value of a Numeric field read as Int64,
+/// returns None if it does not fit.
+fn numeric_to_i64(value: f64) -> Option<i64> {
+    // i64::MAX is not representable as f64, the cast rounds it up to 2^63
+    if value.is_finite() && value >= i64::MIN as f64 && value < i64::MAX as f64 {
+        Some(value.round() as i64)
+    } else {
+        None
+    }
+}
+
 pub struct DbaseTableFactory {}
 
 #[async_trait]
@@ -376,7 +706,7 @@ impl TableProviderFactory for DbaseTableFactory {
         _: &SessionState,
         cmd: &CreateExternalTable,
     ) -> Result<Arc<dyn TableProvider>> {
-        let table = DbaseTable::new(&cmd.location);
+        let table = DbaseTable::try_new(&cmd.location)?;
 
         Ok(Arc::new(table))
     }
@@ -386,7 +716,7 @@ impl TableProviderFactory for DbaseTableFactory {
 mod test {
     use super::*;
 
-    use datafusion::arrow::array::StringArray;
+    use datafusion::arrow::array::{Int64Array, StringArray, TimestampSecondArray};
     use datafusion::error::Result;
     use datafusion::execution::context::SessionState;
     use datafusion::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
@@ -454,4 +784,158 @@ mod test {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_arrow_schema_round_trip() {
+        let table = DbaseTable::try_new("tests/data/stations.dbf").unwrap();
+        let schema = table.schema();
+        assert_eq!(schema.field(0), &Field::new("name", DataType::Utf8, true));
+
+        let lengths = HashMap::from([("name".to_string(), 100u8)]);
+        let table_info = TableWriterBuilder::from_arrow_schema(&schema, &lengths)
+            .unwrap()
+            .build_table_info()
+            .unwrap();
+
+        let dbase_file = table.file.lock().unwrap();
+        assert_eq!(table_info.fields_info.len(), dbase_file.fields().len());
+        for (info, original) in table_info.fields_info.iter().zip(dbase_file.fields()) {
+            assert_eq!(info.name(), original.name());
+            assert_eq!(info.field_type(), original.field_type());
+        }
+        assert_eq!(table_info.fields_info[0].length(), 100);
+        assert_eq!(table_info.fields_info[1].length(), 254);
+    }
+
+    #[test]
+    fn test_field_type_arrow_mapping() {
+        for ft in [
+            FieldType::Character,
+            FieldType::Date,
+            FieldType::Float,
+            FieldType::Integer,
+            FieldType::Logical,
+            FieldType::Numeric,
+        ] {
+            let (data_type, nullable) = field_type_to_arrow(ft, true);
+            let info = arrow_to_field_info(&Field::new("f", data_type, nullable), None).unwrap();
+            assert_eq!(info.field_type(), ft);
+        }
+
+        let error = arrow_to_field_info(&Field::new("f", DataType::Binary, true), None);
+        assert!(error.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_integer_numeric_and_timestamp_columns() -> Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("integers.dbf");
+        let mut writer = TableWriterBuilder::new()
+            .add_numeric_field(FieldName::try_from("id").unwrap(), 10, 0)
+            .add_numeric_field(FieldName::try_from("price").unwrap(), 10, 2)
+            .add_datetime_field(FieldName::try_from("created").unwrap())
+            .build_with_file_dest(&path)
+            .unwrap();
+        let created =
+            crate::DateTime::new(crate::Date::new(15, 3, 2021), crate::Time::new(10, 30, 0));
+        for (id, price) in [(Some(41.0), 1.5), (Some(42.0), 2.25), (None, 3.0)] {
+            let mut record = crate::Record::default();
+            record.insert("id".to_string(), FieldValue::Numeric(id));
+            record.insert("price".to_string(), FieldValue::Numeric(Some(price)));
+            record.insert("created".to_string(), FieldValue::DateTime(created));
+            writer.write_record(&record).unwrap();
+        }
+        drop(writer);
+
+        let table = DbaseTable::try_new(&path)?;
+        let schema = table.schema();
+        assert_eq!(schema.field(0).data_type(), &DataType::Int64);
+        assert_eq!(schema.field(1).data_type(), &DataType::Float64);
+        assert_eq!(
+            schema.field(2).data_type(),
+            &DataType::Timestamp(TimeUnit::Second, None)
+        );
+
+        let ctx = SessionContext::new();
+        ctx.register_table("integers", Arc::new(table))?;
+        let result = ctx
+            .sql("select id, created from integers where id = 42")
+            .await?
+            .collect()
+            .await?;
+        assert_eq!(result[0].column(0).as_ref(), &Int64Array::from(vec![42]));
+        assert_eq!(
+            result[0].column(1).as_ref(),
+            &TimestampSecondArray::from(vec![created.to_unix_timestamp()])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_numeric_to_int64_mapping() {
+        let integer = FieldInfo::new(FieldName::try_from("id").unwrap(), FieldType::Numeric, 18);
+        assert_eq!(field_info_to_arrow(&integer, true), (DataType::Int64, true));
+
+        let too_long = FieldInfo::new(FieldName::try_from("id").unwrap(), FieldType::Numeric, 19);
+        assert_eq!(
+            field_info_to_arrow(&too_long, true),
+            (DataType::Float64, true)
+        );
+
+        let mut decimal =
+            FieldInfo::new(FieldName::try_from("id").unwrap(), FieldType::Numeric, 10);
+        decimal.num_decimal_places = 2;
+        assert_eq!(
+            field_info_to_arrow(&decimal, true),
+            (DataType::Float64, true)
+        );
+
+        assert_eq!(numeric_to_i64(42.0), Some(42));
+        assert_eq!(numeric_to_i64(1e19), None);
+        assert_eq!(numeric_to_i64(f64::NAN), None);
+    }
+
+    #[tokio::test]
+    async fn test_create_external_table_with_missing_file() {
+        let cfg = RuntimeConfig::new();
+        let env = RuntimeEnv::new(cfg).unwrap();
+        let ses = SessionConfig::new();
+        let mut state = SessionState::with_config_rt(ses, Arc::new(env));
+        state
+            .table_factories_mut()
+            .insert("DBASE".to_string(), Arc::new(DbaseTableFactory {}));
+        let ctx = SessionContext::with_state(state);
+
+        // The statement fails instead of panicking
+        let sql =
+            "create external table missing stored as dbase location './tests/data/missing.dbf'";
+        let error = ctx.sql(sql).await.unwrap_err();
+        let message = error.to_string();
+        assert!(
+            message.contains("Could not open dBase table"),
+            "{}",
+            message
+        );
+        assert!(message.contains("missing.dbf"), "{}", message);
+    }
+
+    #[tokio::test]
+    async fn test_character_equality_pushdown() -> Result<()> {
+        let sql = "select name from stations where line = 'blue' order by name";
+        let mut results = vec![];
+        let mut values_read = vec![];
+        for pushdown in [false, true] {
+            let table =
+                DbaseTable::try_new("tests/data/stations.dbf")?.with_filter_pushdown(pushdown);
+            let ctx = SessionContext::new();
+            ctx.register_table("stations", Arc::new(table.clone()))?;
+            results.push(ctx.sql(sql).await?.collect().await?);
+            values_read.push(table.values_read.load(Ordering::Relaxed));
+        }
+        assert!(results[0][0].num_rows() > 0);
+        assert_eq!(results[0], results[1]);
+        // Only the records of the blue line are read
+        assert!(values_read[1] < values_read[0]);
+        Ok(())
+    }
 }
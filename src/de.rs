@@ -5,7 +5,8 @@ use serde::de::{DeserializeOwned, DeserializeSeed, IntoDeserializer, SeqAccess,
 use serde::Deserializer;
 
 use crate::{
-    ErrorKind, FieldConversionError, FieldIOError, FieldIterator, FieldValue, ReadableRecord,
+    DateTime, ErrorKind, FieldConversionError, FieldIOError, FieldIterator, FieldValue,
+    ReadableRecord,
 };
 
 impl<'de, 'a, 'f, R1, R2> SeqAccess<'de> for &mut FieldIterator<'a, R1, R2>
@@ -215,6 +216,10 @@ where
                 self.skip_next_field()?;
                 visitor.visit_none()
             }
+            FieldValue::DateTime(datetime) if datetime == DateTime::EMPTY => {
+                self.skip_next_field()?;
+                visitor.visit_none()
+            }
             _ => visitor.visit_some(self),
         }
     }
@@ -288,12 +293,15 @@ where
     fn deserialize_struct<V>(
         self,
         _name: &'static str,
-        _fields: &'static [&'static str],
+        fields: &'static [&'static str],
         visitor: V,
     ) -> Result<<V as Visitor<'de>>::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
+        if self.options.check_field_names {
+            self.check_next_field_names(fields.iter().copied())?;
+        }
         visitor.visit_seq(self)
     }
 
@@ -345,6 +353,7 @@ impl serde::de::Error for FieldIOError {
     fn custom<T: Display>(msg: T) -> Self {
         Self {
             field: None,
+            field_index: None,
             kind: ErrorKind::Message(msg.to_string()),
         }
     }
@@ -49,6 +49,117 @@ pub trait Encoding: EncodingClone + AsCodePageMark + Send {
     fn decode<'a>(&self, bytes: &'a [u8]) -> Result<Cow<'a, str>, DecodeError>;
 
     fn encode<'a>(&self, s: &'a str) -> Result<Cow<'a, [u8]>, EncodeError>;
+
+    /// Returns the offset of the first byte that cannot be decoded,
+    /// `None` when all the bytes can be decoded.
+    ///
+    /// The default implementation decodes the bytes one by one,
+    /// which is right for the encodings with one byte per character.
+    fn invalid_byte_offset(&self, bytes: &[u8]) -> Option<usize> {
+        (0..bytes.len()).find(|&i| self.decode(&bytes[i..=i]).is_err())
+    }
+
+    /// Encodes the longest prefix of `s` that fits in `max_bytes` once encoded.
+    ///
+    /// Unlike cutting the result of [Self::encode], a character
+    /// is never split, the returned bytes are valid in the encoding.
+    ///
+    /// The default implementation encodes progressively shorter prefixes
+    /// until one fits.
+    fn encode_prefix_fitting<'a>(
+        &self,
+        s: &'a str,
+        max_bytes: usize,
+    ) -> Result<Cow<'a, [u8]>, EncodeError> {
+        let encoded = self.encode(s)?;
+        if encoded.len() <= max_bytes {
+            return Ok(encoded);
+        }
+
+        // The encoded size grows with the number of characters,
+        // so the longest prefix that fits can be searched by bisection
+        let char_starts = s.char_indices().map(|(i, _)| i).collect::<Vec<_>>();
+        let (mut fitting, mut too_long) = (0, char_starts.len());
+        let mut prefix = Cow::Borrowed(&[][..]);
+        while too_long - fitting > 1 {
+            let middle = (fitting + too_long) / 2;
+            let candidate = self.encode(&s[..char_starts[middle]])?;
+            if candidate.len() <= max_bytes {
+                fitting = middle;
+                prefix = candidate;
+            } else {
+                too_long = middle;
+            }
+        }
+        Ok(prefix)
+    }
+}
+
+/// Returns the longest prefix of `s` that is at most `max_bytes` long
+/// and does not end in the middle of a character
+fn utf8_prefix_fitting(s: &str, max_bytes: usize) -> &str {
+    if s.len() <= max_bytes {
+        return s;
+    }
+    let mut end = max_bytes;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+/// Decodes `bytes`, the bytes that cannot be decoded are replaced by `U+FFFD`
+/// and their offsets are pushed to `invalid_offsets`.
+///
+/// An error is returned only if the encoding fails without telling which byte is invalid.
+pub(crate) fn decode_lossy<'a, E: Encoding + ?Sized>(
+    encoding: &E,
+    bytes: &'a [u8],
+    invalid_offsets: &mut Vec<usize>,
+) -> Result<Cow<'a, str>, DecodeError> {
+    let error = match encoding.decode(bytes) {
+        Ok(text) => return Ok(text),
+        Err(error) => error,
+    };
+    let mut text = String::new();
+    let mut start = 0;
+    loop {
+        let rest = &bytes[start..];
+        match encoding.decode(rest) {
+            Ok(decoded) => {
+                text.push_str(&decoded);
+                return Ok(text.into());
+            }
+            Err(_) => {
+                let Some(offset) = encoding.invalid_byte_offset(rest) else {
+                    return Err(error);
+                };
+                let Ok(valid) = encoding.decode(&rest[..offset]) else {
+                    return Err(error);
+                };
+                text.push_str(&valid);
+                text.push(char::REPLACEMENT_CHARACTER);
+                invalid_offsets.push(start + offset);
+                start += offset + 1;
+            }
+        }
+    }
+}
+
+/// Encodes `text`, an error gives the offset of the first character that cannot be encoded
+pub(crate) fn encode_with_offset<'a, E: Encoding + ?Sized>(
+    encoding: &E,
+    text: &'a str,
+) -> Result<Cow<'a, [u8]>, EncodeError> {
+    encoding.encode(text).map_err(|error| {
+        let offset = text
+            .char_indices()
+            .find(|(i, c)| encoding.encode(&text[*i..*i + c.len_utf8()]).is_err());
+        match offset {
+            Some((offset, _)) => error.at_offset(offset, text),
+            None => error,
+        }
+    })
 }
 
 /// Trait to be able to clone a `Box<dyn Encoding>`
@@ -95,6 +206,14 @@ impl Encoding for UnicodeLossy {
     fn encode<'a>(&self, s: &'a str) -> Result<Cow<'a, [u8]>, EncodeError> {
         Ok(s.as_bytes().into())
     }
+
+    fn encode_prefix_fitting<'a>(
+        &self,
+        s: &'a str,
+        max_bytes: usize,
+    ) -> Result<Cow<'a, [u8]>, EncodeError> {
+        Ok(utf8_prefix_fitting(s, max_bytes).as_bytes().into())
+    }
 }
 
 /// Tries to decode as Unicode, if unrepresentable characters are found, an [`Err`] is returned.
@@ -105,9 +224,23 @@ impl Encoding for Unicode {
             .map_err(DecodeError::FromUtf8)
     }
 
+    fn invalid_byte_offset(&self, bytes: &[u8]) -> Option<usize> {
+        std::str::from_utf8(bytes)
+            .err()
+            .map(|error| error.valid_up_to())
+    }
+
     fn encode<'a>(&self, s: &'a str) -> Result<Cow<'a, [u8]>, EncodeError> {
         Ok(s.as_bytes().into())
     }
+
+    fn encode_prefix_fitting<'a>(
+        &self,
+        s: &'a str,
+        max_bytes: usize,
+    ) -> Result<Cow<'a, [u8]>, EncodeError> {
+        Ok(utf8_prefix_fitting(s, max_bytes).as_bytes().into())
+    }
 }
 
 /// Tries to decode as ASCII, if unrepresentable characters are found, an [`Err`] is returned.
@@ -129,6 +262,14 @@ impl Encoding for Ascii {
     fn encode<'a>(&self, s: &'a str) -> Result<Cow<'a, [u8]>, EncodeError> {
         Ok(s.as_bytes().into())
     }
+
+    fn encode_prefix_fitting<'a>(
+        &self,
+        s: &'a str,
+        max_bytes: usize,
+    ) -> Result<Cow<'a, [u8]>, EncodeError> {
+        Ok(utf8_prefix_fitting(s, max_bytes).as_bytes().into())
+    }
 }
 
 #[derive(Clone)]
@@ -155,7 +296,19 @@ impl Encoding for DynEncoding {
         self.inner.decode(bytes)
     }
 
+    fn invalid_byte_offset(&self, bytes: &[u8]) -> Option<usize> {
+        self.inner.invalid_byte_offset(bytes)
+    }
+
     fn encode<'a>(&self, s: &'a str) -> Result<Cow<'a, [u8]>, EncodeError> {
         self.inner.encode(s)
     }
+
+    fn encode_prefix_fitting<'a>(
+        &self,
+        s: &'a str,
+        max_bytes: usize,
+    ) -> Result<Cow<'a, [u8]>, EncodeError> {
+        self.inner.encode_prefix_fitting(s, max_bytes)
+    }
 }
@@ -1,4 +1,5 @@
-use crate::{CodePageMark, FieldConversionError, FieldInfo};
+use crate::schema::Incompatibility;
+use crate::{CodePageMark, FieldConversionError, FieldIndex, FieldInfo, FieldType};
 use std::string::FromUtf8Error;
 
 #[derive(Debug)]
@@ -16,7 +17,11 @@ pub enum ErrorKind {
     /// and the that additional memo file could not be found / was not given
     MissingMemoFile,
     /// Something went wrong when we tried to open the associated memo file
-    ErrorOpeningMemoFile(std::io::Error),
+    ErrorOpeningMemoFile {
+        /// The path of the memo file that was tried
+        path: std::path::PathBuf,
+        error: std::io::Error,
+    },
     /// The conversion from a FieldValue to another type could not be made
     BadConversion(FieldConversionError),
     /// End of the record, there are no more fields
@@ -34,6 +39,111 @@ pub enum ErrorKind {
     StringDecodeError(DecodeError),
     /// A string from the database could not be encoded
     StringEncodeError(EncodeError),
+    /// The length of a field is smaller than what its type needs
+    FieldLengthTooSmall {
+        expected: usize,
+        actual: usize,
+    },
+    /// The header of the file is not valid
+    InvalidHeader(String),
+    /// A field with the same name (ignoring the case, see
+    /// [Record::get_ignoring_case](crate::Record::get_ignoring_case)) was already added to the writer
+    DuplicateFieldName,
+    /// A field with a length of 0 was added to the writer
+    ZeroLengthField,
+    /// Adding the field would make the size of a record
+    /// exceed what a dBase file can store
+    RecordTooLarge,
+    /// The name of the field read is not the one expected,
+    /// the fields of the struct are not in the order of the table
+    FieldNameMismatch {
+        expected: String,
+        actual: String,
+    },
+    /// The table has more fields than its version allows
+    FieldCountExceeded {
+        max: usize,
+    },
+    /// A value was given for an autoincrement field
+    /// while appending with [crate::AutoIncrementPolicy::Error]
+    AutoIncrementValueGiven,
+    /// The record checked before being written does not match the fields of the table
+    IncompatibleRecord(Incompatibility),
+    /// The range of records is reversed or goes past the last record
+    InvalidRecordRange {
+        start: usize,
+        end: usize,
+        num_records: usize,
+    },
+    /// The Numeric or Float value needs more characters than the length of the field
+    NumericValueTooWide {
+        width: usize,
+        required: usize,
+    },
+    /// The deletion flag of the record is neither a space nor `*`,
+    /// see [ReadingOptions::strict_deletion_flag](crate::ReadingOptions::strict_deletion_flag)
+    InvalidDeletionFlag(u8),
+    /// The index of the memo block stored in a Memo field is not in the memo file
+    InvalidMemoIndex {
+        index: u32,
+        /// Index of the block after the last memo, as written in the header
+        /// of the memo file, 0 when it is not known
+        next_available_block_index: u32,
+    },
+    /// The length of the memo at the block `index`, as written in the memo file,
+    /// goes past the end of the memo file
+    CorruptMemo {
+        index: u32,
+        length: u64,
+        /// Number of bytes from the start of the memo to the end of the memo file
+        available: u64,
+    },
+    /// The memo at the block `index` is longer than
+    /// [ReadingOptions::max_memo_size](crate::ReadingOptions::max_memo_size)
+    MemoTooLarge {
+        index: u32,
+        length: u64,
+        max_size: usize,
+    },
+    /// The memo of `length` bytes cannot be written,
+    /// the memo file cannot address it
+    MemoDoesNotFit {
+        length: usize,
+    },
+    /// The memo cannot be written as its data contains the byte ending
+    /// the memos of the memo file (0x1A for dBase III, 0x1F for dBase IV)
+    MemoContainsTerminator {
+        terminator: u8,
+        /// Position of the first terminator in the data
+        position: usize,
+    },
+    /// The fields of an existing table are not the expected ones,
+    /// see [File::open_or_create](crate::File::open_or_create) and [File::reload](crate::File::reload)
+    SchemaMismatch {
+        /// Description of each difference
+        differences: Vec<String>,
+    },
+    /// The text cannot be converted to a value of the field,
+    /// see [FieldWriter::write_next_field_from_str](crate::FieldWriter::write_next_field_from_str)
+    InvalidFieldText {
+        text: String,
+        field_type: FieldType,
+    },
+    /// The table has more records than
+    /// [ReadingOptions::max_materialized_records](crate::ReadingOptions::max_materialized_records)
+    /// allows to read at once
+    TooManyRecords {
+        num_records: u32,
+        max: u32,
+    },
+    /// The content of a Numeric, Float, Date or Memo field could not be parsed
+    InvalidFieldContent {
+        field_type: FieldType,
+        /// Bytes of the field, as read from the file
+        raw: Vec<u8>,
+        /// The parsing error, a [ErrorKind::ParseFloatError] or [ErrorKind::ParseIntError]
+        source: Box<ErrorKind>,
+    },
     Message(String),
 }
 
@@ -42,6 +152,7 @@ pub enum ErrorKind {
 pub struct Error {
     pub(crate) record_num: usize,
     pub(crate) field: Option<FieldInfo>,
+    pub(crate) field_index: Option<FieldIndex>,
     pub(crate) kind: ErrorKind,
 }
 
@@ -50,6 +161,7 @@ impl Error {
         Self {
             record_num: current_record,
             field: field_error.field,
+            field_index: field_error.field_index,
             kind: field_error.kind,
         }
     }
@@ -58,6 +170,7 @@ impl Error {
         Self {
             record_num: current_record,
             field: None,
+            field_index: None,
             kind: ErrorKind::IoError(error),
         }
     }
@@ -79,26 +192,53 @@ impl Error {
     pub fn field(&self) -> &Option<FieldInfo> {
         &self.field
     }
+
+    /// Returns the position, in the record, of the field for which the error occurred
+    ///
+    /// Unlike the [field](Self::field) name, it tells apart fields with the same name.
+    pub fn field_index(&self) -> Option<FieldIndex> {
+        self.field_index
+    }
 }
 
 #[derive(Debug)]
 pub struct FieldIOError {
     pub(crate) field: Option<FieldInfo>,
+    pub(crate) field_index: Option<FieldIndex>,
     pub(crate) kind: ErrorKind,
 }
 
 impl FieldIOError {
     pub fn new(kind: ErrorKind, field: Option<FieldInfo>) -> Self {
-        Self { field, kind }
+        Self {
+            field,
+            field_index: None,
+            kind,
+        }
     }
 
     pub(crate) fn end_of_record() -> Self {
         Self {
             field: None,
+            field_index: None,
             kind: ErrorKind::EndOfRecord,
         }
     }
 
+    /// Sets the index of the field the error is about,
+    /// unless the error is not about a field or the index was already set
+    pub(crate) fn at_field_index(mut self, index: usize) -> Self {
+        if self.field.is_some() && self.field_index.is_none() {
+            self.field_index = Some(FieldIndex(index));
+        }
+        self
+    }
+
+    /// Returns the position, in the record, of the field for which the error occurred
+    pub fn field_index(&self) -> Option<FieldIndex> {
+        self.field_index
+    }
+
     pub fn kind(&self) -> &ErrorKind {
         &self.kind
     }
@@ -146,21 +286,25 @@ impl From<FieldConversionError> for FieldIOError {
     }
 }
 
+/// Writes where the error happened, like `, field #3 'NAME'`
+fn write_field_location(
+    f: &mut std::fmt::Formatter<'_>,
+    field: &Option<FieldInfo>,
+    field_index: Option<FieldIndex>,
+) -> std::fmt::Result {
+    match (field_index, field) {
+        (Some(index), Some(field)) => write!(f, ", field #{} '{}'", index.0, field.name),
+        (None, Some(field)) => write!(f, ", field '{}'", field.name),
+        (Some(index), None) => write!(f, ", field #{}", index.0),
+        (None, None) => Ok(()),
+    }
+}
+
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Some(field_info) = &self.field {
-            write!(
-                f,
-                "Error {{ record_num: {}, kind: {}, {} }}",
-                self.record_num, self.kind, field_info
-            )
-        } else {
-            write!(
-                f,
-                "Error {{ record_num: {}, kind: {} }}",
-                self.record_num, self.kind
-            )
-        }
+        write!(f, "Error {{ record_num: {}", self.record_num)?;
+        write_field_location(f, &self.field, self.field_index)?;
+        write!(f, ", kind: {} }}", self.kind)
     }
 }
 
@@ -170,7 +314,25 @@ impl std::fmt::Display for Error {
 //     }
 // }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.kind.parse_error()
+    }
+}
+
+impl ErrorKind {
+    /// Returns the parsing error wrapped by [ErrorKind::InvalidFieldContent]
+    fn parse_error(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ErrorKind::InvalidFieldContent { source, .. } => match source.as_ref() {
+                ErrorKind::ParseFloatError(err) => Some(err),
+                ErrorKind::ParseIntError(err) => Some(err),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
 
 impl std::fmt::Display for ErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -186,11 +348,12 @@ impl std::fmt::Display for ErrorKind {
                 write!(f, "The FieldType code '{}' is note a valid one", c)
             }
             ErrorKind::MissingMemoFile => write!(f, "The memo file could not be found"),
-            ErrorKind::ErrorOpeningMemoFile(err) => {
+            ErrorKind::ErrorOpeningMemoFile { path, error } => {
                 write!(
                     f,
-                    "An error occurred when trying to open the memo file: {}",
-                    err
+                    "An error occurred when trying to open the memo file '{}': {}",
+                    path.display(),
+                    error
                 )
             }
             ErrorKind::BadConversion(err) => write!(f, "The convertion cannot be made: {}", err),
@@ -218,6 +381,128 @@ impl std::fmt::Display for ErrorKind {
             ErrorKind::UnsupportedCodePage(code) => {
                 write!(f, "The code page '{:?}' is not supported", code)
             }
+            ErrorKind::FieldLengthTooSmall { expected, actual } => write!(
+                f,
+                "The field is {} bytes long, its type needs at least {} bytes",
+                actual, expected
+            ),
+            ErrorKind::InvalidHeader(msg) => write!(f, "The header is not valid: {}", msg),
+            ErrorKind::DuplicateFieldName => {
+                write!(f, "A field with the same name was already added")
+            }
+            ErrorKind::ZeroLengthField => write!(f, "A field cannot have a length of 0"),
+            ErrorKind::RecordTooLarge => write!(
+                f,
+                "The record size would exceed the maximum allowed by the version of the table"
+            ),
+            ErrorKind::FieldNameMismatch { expected, actual } => write!(
+                f,
+                "Expected to read the field '{}' but the next field is '{}'",
+                expected, actual
+            ),
+            ErrorKind::FieldCountExceeded { max } => write!(
+                f,
+                "The table has more fields than the maximum of {} allowed by its version",
+                max
+            ),
+            ErrorKind::AutoIncrementValueGiven => write!(
+                f,
+                "The values of autoincrement fields are generated and cannot be given"
+            ),
+            ErrorKind::IncompatibleRecord(incompatibility) => write!(
+                f,
+                "The record does not match the table: {}",
+                incompatibility
+            ),
+            ErrorKind::InvalidRecordRange {
+                start,
+                end,
+                num_records,
+            } => write!(
+                f,
+                "The range of records {}..{} is not valid, the file has {} records",
+                start, end, num_records
+            ),
+            ErrorKind::NumericValueTooWide { width, required } => write!(
+                f,
+                "The number needs {} characters, the field can only hold {}",
+                required, width
+            ),
+            ErrorKind::InvalidDeletionFlag(byte) => write!(
+                f,
+                "The deletion flag of the record is 0x{:02X}, expected 0x20 (' ') or 0x2A ('*')",
+                byte
+            ),
+            ErrorKind::InvalidMemoIndex {
+                index,
+                next_available_block_index,
+            } => write!(
+                f,
+                "The memo block index {} is not in the memo file (next available block: {})",
+                index, next_available_block_index
+            ),
+            ErrorKind::CorruptMemo {
+                index,
+                length,
+                available,
+            } => write!(
+                f,
+                "The memo at block {} has a length of {} bytes but only {} bytes remain in the memo file",
+                index, length, available
+            ),
+            ErrorKind::MemoTooLarge {
+                index,
+                length,
+                max_size,
+            } => write!(
+                f,
+                "The memo at block {} has a length of {} bytes, more than the maximum of {} bytes",
+                index, length, max_size
+            ),
+            ErrorKind::MemoDoesNotFit { length } => write!(
+                f,
+                "The memo of {} bytes does not fit in the memo file",
+                length
+            ),
+            ErrorKind::MemoContainsTerminator {
+                terminator,
+                position,
+            } => write!(
+                f,
+                "The memo contains the memo terminator 0x{:02X} at byte {}, \
+                 it cannot be written in this memo file",
+                terminator, position
+            ),
+            ErrorKind::SchemaMismatch { ref differences } => write!(
+                f,
+                "The fields of the table are not the expected ones: {}",
+                differences.join(", ")
+            ),
+            ErrorKind::InvalidFieldText {
+                ref text,
+                field_type,
+            } => write!(
+                f,
+                "The text '{}' cannot be converted to a value of a {:?} field",
+                text, field_type
+            ),
+            ErrorKind::TooManyRecords { num_records, max } => write!(
+                f,
+                "The table has {} records, more than the {} that can be read at once, \
+                 iterate over the records instead",
+                num_records, max
+            ),
+            ErrorKind::InvalidFieldContent {
+                field_type,
+                ref raw,
+                ref source,
+            } => write!(
+                f,
+                "The content '{}' of a {:?} field could not be parsed: {}",
+                raw.escape_ascii(),
+                field_type,
+                source
+            ),
             ErrorKind::Message(ref msg) => write!(f, "{}", msg),
         }
     }
@@ -225,15 +510,31 @@ impl std::fmt::Display for ErrorKind {
 
 impl std::fmt::Display for FieldIOError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Some(field_info) = &self.field {
-            write!(f, "FieldIOError {{ kind: {}, {} }}", self.kind, field_info)
-        } else {
-            write!(f, "FieldIOError {{ kind: {:?} }}", self.kind)
-        }
+        write!(f, "FieldIOError {{ kind: {}", self.kind)?;
+        write_field_location(f, &self.field, self.field_index)?;
+        write!(f, " }}")
     }
 }
 
-impl std::error::Error for FieldIOError {}
+impl std::error::Error for FieldIOError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.kind.parse_error()
+    }
+}
+
+/// Maximum number of bytes kept in [DecodeError::InvalidBytes] and [EncodeError::InvalidText]
+const MAX_ERROR_BYTES: usize = 16;
+
+/// Writes the bytes in hexadecimal, separated by spaces
+fn write_hex_bytes(f: &mut std::fmt::Formatter<'_>, bytes: &[u8]) -> std::fmt::Result {
+    for (i, byte) in bytes.iter().enumerate() {
+        if i != 0 {
+            write!(f, " ")?;
+        }
+        write!(f, "{:02X}", byte)?;
+    }
+    Ok(())
+}
 
 #[derive(Debug)]
 #[non_exhaustive]
@@ -243,6 +544,27 @@ pub enum DecodeError {
     NotAscii,
     #[cfg(feature = "yore")]
     Yore(yore::DecodeError),
+    /// The bytes of a field could not be decoded
+    InvalidBytes {
+        /// Offset, within the field, of the first byte that could not be decoded
+        offset: usize,
+        /// Bytes of the field starting at `offset`, at most 16 of them
+        bytes: Vec<u8>,
+        /// The error returned by the encoding
+        error: Box<DecodeError>,
+    },
+}
+
+impl DecodeError {
+    /// Adds the position of the invalid byte, `field_bytes` being all the bytes of the field
+    pub(crate) fn at_offset(self, offset: usize, field_bytes: &[u8]) -> Self {
+        let end = field_bytes.len().min(offset + MAX_ERROR_BYTES);
+        Self::InvalidBytes {
+            offset,
+            bytes: field_bytes.get(offset..end).unwrap_or_default().to_vec(),
+            error: Box::new(self),
+        }
+    }
 }
 
 impl From<String> for DecodeError {
@@ -260,7 +582,18 @@ impl From<yore::DecodeError> for DecodeError {
 
 impl std::fmt::Display for DecodeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            DecodeError::InvalidBytes {
+                offset,
+                bytes,
+                error,
+            } => {
+                write!(f, "invalid byte at offset {} (", offset)?;
+                write_hex_bytes(f, bytes)?;
+                write!(f, "): {}", error)
+            }
+            _ => write!(f, "{:?}", self),
+        }
     }
 }
 
@@ -272,6 +605,31 @@ pub enum EncodeError {
     Message(String),
     #[cfg(feature = "yore")]
     Yore(yore::EncodeError),
+    /// A text could not be encoded
+    InvalidText {
+        /// Offset, within the text, of the first character that could not be encoded
+        offset: usize,
+        /// UTF-8 bytes of the text starting at `offset`, at most 16 of them
+        bytes: Vec<u8>,
+        /// The error returned by the encoding
+        error: Box<EncodeError>,
+    },
+}
+
+impl EncodeError {
+    /// Adds the position of the character that could not be encoded in `text`
+    pub(crate) fn at_offset(self, offset: usize, text: &str) -> Self {
+        let end = text.len().min(offset + MAX_ERROR_BYTES);
+        Self::InvalidText {
+            offset,
+            bytes: text
+                .as_bytes()
+                .get(offset..end)
+                .unwrap_or_default()
+                .to_vec(),
+            error: Box::new(self),
+        }
+    }
 }
 
 impl From<String> for EncodeError {
@@ -289,7 +647,18 @@ impl From<yore::EncodeError> for EncodeError {
 
 impl std::fmt::Display for EncodeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            EncodeError::InvalidText {
+                offset,
+                bytes,
+                error,
+            } => {
+                write!(f, "cannot encode the character at offset {} (", offset)?;
+                write_hex_bytes(f, bytes)?;
+                write!(f, "): {}", error)
+            }
+            _ => write!(f, "{:?}", self),
+        }
     }
 }
 
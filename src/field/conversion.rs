@@ -1,4 +1,5 @@
-use super::{types, FieldType, FieldValue};
+use super::{types, FieldInfo, FieldType, FieldValue};
+use crate::writing::value_from_text;
 
 /// Errors that can happen when trying to convert a FieldValue into
 /// a more concrete type
@@ -16,6 +17,8 @@ pub enum FieldConversionError {
     /// The value written is the file was only pad bytes / uninitialized
     /// and the user tried to convert it into a non Option-Type
     NoneValue,
+    /// Error of a [FromFieldValue] or [ToFieldValue] implementation
+    Custom(String),
 }
 
 impl std::fmt::Display for FieldConversionError {
@@ -28,12 +31,157 @@ impl std::fmt::Display for FieldConversionError {
             FieldConversionError::NoneValue => {
                 write!(f, "Value is not initialized, which is not allowed")
             }
+            FieldConversionError::Custom(message) => write!(f, "{}", message),
         }
     }
 }
 
 impl std::error::Error for FieldConversionError {}
 
+/// Conversion of the value read from a field into a type,
+/// used by [FieldIterator::read_next_field_as](crate::FieldIterator::read_next_field_as),
+/// [FieldRef::read_as](crate::FieldRef::read_as) and
+/// [RecordRef::read_field_as](crate::RecordRef::read_field_as).
+///
+/// It is implemented for the types that can be converted with [TryFrom],
+/// implement it for your own types to read them directly.
+///
+/// # Example
+///
+/// ```
+/// use dbase::{FieldConversionError, FieldInfo, FieldValue, FromFieldValue};
+///
+/// /// Stored as `Y` or `N` in a Character field
+/// struct YesNo(bool);
+///
+/// impl FromFieldValue for YesNo {
+///     fn from_field(value: FieldValue, info: &FieldInfo) -> Result<Self, FieldConversionError> {
+///         match value {
+///             FieldValue::Character(Some(text)) if text == "Y" => Ok(YesNo(true)),
+///             FieldValue::Character(Some(text)) if text == "N" => Ok(YesNo(false)),
+///             value => Err(FieldConversionError::Custom(format!(
+///                 "'{}' is not Y or N in field {}", value, info.name()
+///             ))),
+///         }
+///     }
+/// }
+/// ```
+pub trait FromFieldValue: Sized {
+    /// Converts the `value` read from the field described by `info`
+    fn from_field(value: FieldValue, info: &FieldInfo) -> Result<Self, FieldConversionError>;
+}
+
+impl<T> FromFieldValue for T
+where
+    T: TryFrom<FieldValue, Error = FieldConversionError>,
+{
+    fn from_field(value: FieldValue, _info: &FieldInfo) -> Result<Self, FieldConversionError> {
+        T::try_from(value)
+    }
+}
+
+/// Conversion of a type into the value written in a field,
+/// used by [FieldWriter::write_next_field_as](crate::FieldWriter::write_next_field_as).
+///
+/// It is implemented for the types that can be converted [Into] a [FieldValue],
+/// implement it for your own types to write them directly.
+pub trait ToFieldValue {
+    /// Converts the value to the one written in the field described by `info`
+    fn to_field_value(&self, info: &FieldInfo) -> Result<FieldValue, FieldConversionError>;
+}
+
+impl<T> ToFieldValue for T
+where
+    T: Clone + Into<FieldValue>,
+{
+    fn to_field_value(&self, _info: &FieldInfo) -> Result<FieldValue, FieldConversionError> {
+        Ok(self.clone().into())
+    }
+}
+
+/// Whether [FieldValue::coerce_to] can convert values of the type `from` to the type `to`,
+/// for Character values it depends on their text
+pub(crate) fn can_coerce(from: FieldType, to: FieldType) -> bool {
+    from == to
+        || matches!(
+            (from, to),
+            (FieldType::Float, FieldType::Numeric)
+                | (FieldType::Numeric, FieldType::Float)
+                | (FieldType::Integer, FieldType::Numeric | FieldType::Double)
+                | (FieldType::Double, FieldType::Currency)
+                | (FieldType::Currency, FieldType::Double)
+                | (FieldType::Date, FieldType::Character)
+        )
+        || (from == FieldType::Character && to != FieldType::Memo)
+}
+
+impl FieldValue {
+    /// Converts the value to a value of the `field_type`
+    ///
+    /// | From      | To                                            |
+    /// |-----------|-----------------------------------------------|
+    /// | Float     | Numeric                                       |
+    /// | Numeric   | Float                                         |
+    /// | Integer   | Numeric, Double                               |
+    /// | Double    | Currency                                      |
+    /// | Currency  | Double                                        |
+    /// | Date      | Character, formatted as `YYYYMMDD`            |
+    /// | Character | any type but Memo, the text is parsed like by [FieldWriter::write_next_field_from_str](crate::FieldWriter::write_next_field_from_str) |
+    ///
+    /// Values already of the `field_type` are returned as is,
+    /// the other conversions fail with [FieldConversionError::FieldTypeNotAsExpected],
+    /// Character values whose text cannot be parsed with [FieldConversionError::IncompatibleType].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldType, FieldValue};
+    ///
+    /// let value = FieldValue::Integer(42).coerce_to(FieldType::Numeric).unwrap();
+    /// assert_eq!(value, FieldValue::Numeric(Some(42.0)));
+    /// assert!(FieldValue::Logical(Some(true)).coerce_to(FieldType::Character).is_err());
+    /// ```
+    pub fn coerce_to(&self, field_type: FieldType) -> Result<FieldValue, FieldConversionError> {
+        let value = match (self, field_type) {
+            (value, field_type) if value.field_type() == field_type => value.clone(),
+            (FieldValue::Float(value), FieldType::Numeric) => {
+                FieldValue::Numeric(value.map(f64::from))
+            }
+            (FieldValue::Numeric(value), FieldType::Float) => {
+                FieldValue::Float(value.map(|value| value as f32))
+            }
+            (FieldValue::Integer(value), FieldType::Numeric) => {
+                FieldValue::Numeric(Some(f64::from(*value)))
+            }
+            (FieldValue::Integer(value), FieldType::Double) => {
+                FieldValue::Double(f64::from(*value))
+            }
+            (FieldValue::Double(value), FieldType::Currency) => FieldValue::Currency(*value),
+            (FieldValue::Currency(value), FieldType::Double) => FieldValue::Double(*value),
+            (FieldValue::Date(date), FieldType::Character) => {
+                FieldValue::Character(date.map(|date| date.to_string()))
+            }
+            (FieldValue::Character(text), field_type)
+                if can_coerce(FieldType::Character, field_type) =>
+            {
+                let text = text.as_deref().unwrap_or_default().trim();
+                match (field_type, text.is_empty()) {
+                    (FieldType::DateTime, true) => return Err(FieldConversionError::NoneValue),
+                    _ => value_from_text(text, field_type)
+                        .ok_or(FieldConversionError::IncompatibleType)?,
+                }
+            }
+            _ => {
+                return Err(FieldConversionError::FieldTypeNotAsExpected {
+                    expected: field_type,
+                    actual: self.field_type(),
+                })
+            }
+        };
+        Ok(value)
+    }
+}
+
 macro_rules! impl_try_from_field_value_for_ {
     (FieldValue::$variant:ident => $out_type:ty) => {
         impl TryFrom<FieldValue> for $out_type {
@@ -85,6 +233,15 @@ impl_try_from_field_value_for_!(FieldValue::Logical(Some(b)) => bool);
 
 impl_try_from_field_value_for_!(FieldValue::Integer => i32);
 
+/// Integer fields cannot store a missing value, the result is never `None`
+impl TryFrom<FieldValue> for Option<i32> {
+    type Error = FieldConversionError;
+
+    fn try_from(value: FieldValue) -> Result<Self, Self::Error> {
+        i32::try_from(value).map(Some)
+    }
+}
+
 impl TryFrom<FieldValue> for f64 {
     type Error = FieldConversionError;
 
@@ -102,6 +259,16 @@ impl TryFrom<FieldValue> for f64 {
 // Fox Pro types
 impl_try_from_field_value_for_!(FieldValue::DateTime => types::DateTime);
 
+/// [DateTime::EMPTY](types::DateTime::EMPTY) gives `None`
+impl TryFrom<FieldValue> for Option<types::DateTime> {
+    type Error = FieldConversionError;
+
+    fn try_from(value: FieldValue) -> Result<Self, Self::Error> {
+        let datetime = types::DateTime::try_from(value)?;
+        Ok((datetime != types::DateTime::EMPTY).then_some(datetime))
+    }
+}
+
 macro_rules! impl_from_type_for_field_value (
     ($t:ty => FieldValue::$variant:ident) => {
         impl From<$t> for FieldValue {
@@ -136,3 +303,18 @@ impl_from_type_for_field_value!(types::Date => FieldValue::Date(Some(v)));
 
 // Fox Pro types
 impl_from_type_for_field_value!(types::DateTime => FieldValue::DateTime);
+impl_from_type_for_field_value!(i32 => FieldValue::Integer);
+
+/// `None` gives `0`, as Integer fields cannot store a missing value
+impl From<Option<i32>> for FieldValue {
+    fn from(v: Option<i32>) -> Self {
+        FieldValue::Integer(v.unwrap_or(0))
+    }
+}
+
+/// `None` gives [DateTime::EMPTY](types::DateTime::EMPTY)
+impl From<Option<types::DateTime>> for FieldValue {
+    fn from(v: Option<types::DateTime>) -> Self {
+        FieldValue::DateTime(v.unwrap_or(types::DateTime::EMPTY))
+    }
+}
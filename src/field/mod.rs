@@ -2,18 +2,25 @@ use std::convert::TryFrom;
 use std::io::{Read, Write};
 use std::ops::Index;
 use std::slice::SliceIndex;
+use std::sync::Arc;
 
-use byteorder::{ReadBytesExt, WriteBytesExt};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
-mod conversion;
+pub(crate) mod conversion;
 pub mod types;
 
 use self::types::FieldType;
-use crate::{Encoding, ErrorKind, FieldValue};
-pub use conversion::FieldConversionError;
+use crate::{Encoding, ErrorKind, FieldValue, UnknownFieldTypeHandling, Version};
+pub use conversion::{FieldConversionError, FromFieldValue, ToFieldValue};
 
 pub(crate) const DELETION_FLAG_SIZE: usize = 1; // 1 byte
+/// A dbase file ends with this byte
+pub(crate) const FILE_TERMINATOR: u8 = 0x1A;
 const FIELD_NAME_LENGTH: usize = 11;
+/// Type of the autoincrement fields of dBase 7
+const AUTOINCREMENT_TYPE: u8 = b'+';
+/// Length of the Double fields that Clipper writes as text
+const TEXT_DOUBLE_LENGTH: u16 = 10;
 
 #[derive(Debug)]
 /// Wrapping struct to create a FieldName from a String.
@@ -46,20 +53,32 @@ impl TryFrom<&str> for FieldName {
 /// Struct giving the info for a record field
 #[derive(Debug, PartialEq, Clone)]
 pub struct FieldInfo {
-    /// The name of the field
-    pub(crate) name: String,
+    /// The name of the field,
+    /// shared with the [Record](crate::Record)s read
+    pub(crate) name: Arc<str>,
     /// The field type
     pub(crate) field_type: FieldType,
     pub(crate) displacement_field: [u8; 4],
-    pub(crate) field_length: u8,
+    /// Length in bytes of the field, for Character fields it can be more than 255,
+    /// see [FieldInfo::length]
+    pub(crate) field_length: u16,
     pub(crate) num_decimal_places: u8,
     pub(crate) flags: FieldFlags,
-    pub(crate) autoincrement_next_val: [u8; 5],
+    pub(crate) autoincrement_next_val: u32,
     pub(crate) autoincrement_step: u8,
+    /// Length of the skipped fields that are stored just before
+    /// this one in the record, see [UnknownFieldTypeHandling::Skip](crate::UnknownFieldTypeHandling::Skip)
+    pub(crate) hidden_bytes_before: usize,
+    /// The values of this Double field are stored as text, see [FieldInfo::is_text_double]
+    pub(crate) double_as_text: bool,
 }
 
 impl FieldInfo {
     pub(crate) const SIZE: usize = 32;
+    /// Size of a field descriptor in a dBase II file
+    pub(crate) const DBASE2_SIZE: usize = 16;
+    /// dBase II files can have at most 32 fields
+    pub(crate) const DBASE2_MAX_FIELDS: usize = 32;
 
     pub fn name(&self) -> &str {
         &self.name
@@ -69,31 +88,110 @@ impl FieldInfo {
         self.field_type
     }
 
-    pub fn length(&self) -> u8 {
+    /// Returns the length in bytes of the field
+    ///
+    /// The length is stored as a single byte, except for Character fields
+    /// which, like in Clipper and FoxPro, can be longer than 255 bytes:
+    /// their length is stored across the length byte and the decimal count byte
+    /// (`length + 256 * decimal count`), up to [MAX_CHARACTER_FIELD_LENGTH](crate::limits::MAX_CHARACTER_FIELD_LENGTH).
+    /// When the length byte alone gives the record size of the header,
+    /// the decimal count byte is not part of the length.
+    pub fn length(&self) -> u16 {
         self.field_length
     }
 
+    /// Number of digits after the decimal point, used by Numeric and Float fields
+    ///
+    /// 0 for Character fields, unless the decimal count byte is not part
+    /// of their length, see [FieldInfo::length].
+    pub fn num_decimal_places(&self) -> u8 {
+        self.num_decimal_places
+    }
+
+    /// Returns the flags of the field
+    pub fn flags(&self) -> FieldFlags {
+        self.flags
+    }
+
+    /// Returns whether this Double (`B`) field stores its values as text,
+    /// like a Numeric field, instead of as binary little endian `f64`.
+    ///
+    /// The `B` type is a binary double in FoxPro files, but Clipper and dBase III / IV
+    /// files use it for numbers written as text. Which one is used is decided when
+    /// the file is opened from the version of the file and the length of the field:
+    ///
+    /// - 8 bytes in a FoxPro file: binary, read as [FieldValue::Double]
+    /// - 10 bytes, or a dBase III / IV file: text, read as [FieldValue::Numeric]
+    ///
+    /// Other `B` fields cannot be read and opening the file fails.
+    pub fn is_text_double(&self) -> bool {
+        self.field_type == FieldType::Double && self.double_as_text
+    }
+
+    /// Decides how the values of a Double field are stored, see [FieldInfo::is_text_double]
+    pub(crate) fn resolve_double_storage(&mut self, version: Version) -> Result<(), ErrorKind> {
+        if self.field_type != FieldType::Double {
+            return Ok(());
+        }
+        let binary_length = FieldType::Double.size().unwrap_or(8);
+        if self.field_length == u16::from(binary_length) && version.is_foxpro() {
+            self.double_as_text = false;
+        } else if self.field_length == TEXT_DOUBLE_LENGTH
+            || matches!(version, Version::DBase3 { .. } | Version::DBase4 { .. })
+        {
+            self.double_as_text = true;
+        } else {
+            return Err(ErrorKind::InvalidHeader(format!(
+                "the 'B' field '{}' is {} bytes long, it is neither a binary double \
+                 ({} bytes in a FoxPro file) nor a number written as text \
+                 ({} bytes, or a dBase III / IV file), the file version is {:?}",
+                self.name, self.field_length, binary_length, TEXT_DOUBLE_LENGTH, version
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the next value and the step of autoincrement fields,
+    /// `None` for the other fields.
+    ///
+    /// The values of autoincrement fields are generated when appending records,
+    /// see [AutoIncrementPolicy](crate::AutoIncrementPolicy).
+    pub fn autoincrement(&self) -> Option<(u32, u8)> {
+        if self.field_type == FieldType::Integer && self.flags.is_autoincrement() {
+            Some((self.autoincrement_next_val, self.autoincrement_step))
+        } else {
+            None
+        }
+    }
+
+    /// Moves the autoincrement counter to the value of the next record
+    pub(crate) fn advance_autoincrement(&mut self) {
+        if self.autoincrement().is_some() {
+            self.autoincrement_next_val = self
+                .autoincrement_next_val
+                .wrapping_add(u32::from(self.autoincrement_step));
+        }
+    }
+
     pub(crate) fn new(name: FieldName, field_type: FieldType, length: u8) -> Self {
         Self {
-            name: name.0,
+            name: name.0.into(),
             field_type,
             displacement_field: [0u8; 4],
-            field_length: length,
+            field_length: u16::from(length),
             num_decimal_places: 0,
             flags: FieldFlags::default(),
-            autoincrement_next_val: [0u8; 5],
+            autoincrement_next_val: 0,
             autoincrement_step: 0u8,
+            hidden_bytes_before: 0,
+            double_as_text: false,
         }
     }
 
-    pub(crate) fn read_from<T: Read>(source: &mut T) -> Result<Self, ErrorKind> {
-        Self::read_with_encoding(source, &crate::encoding::Ascii)
-    }
-
     /// Reads with the given encoding.
     ///
     /// The encoding is used only for the name
-    fn read_with_encoding<T: Read, E: Encoding>(
+    pub(crate) fn read_from<T: Read, E: Encoding>(
         source: &mut T,
         encoding: &E,
     ) -> Result<Self, ErrorKind> {
@@ -104,35 +202,81 @@ impl FieldInfo {
         let mut displacement_field = [0u8; 4];
         source.read_exact(&mut displacement_field)?;
 
-        let record_length = source.read_u8()?;
-        let num_decimal_places = source.read_u8()?;
+        let length_byte = source.read_u8()?;
+        let decimal_count_byte = source.read_u8()?;
 
-        let flags = FieldFlags(source.read_u8()?);
+        let mut flags = FieldFlags(source.read_u8()?);
 
-        let mut autoincrement_next_val = [0u8; 5];
-
-        source.read_exact(&mut autoincrement_next_val)?;
+        let autoincrement_next_val = source.read_u32::<LittleEndian>()?;
         let autoincrement_step = source.read_u8()?;
 
-        let mut _reserved = [0u8; 7];
+        let mut _reserved = [0u8; 8];
         source.read_exact(&mut _reserved)?;
 
-        let s = encoding
-            .decode(&name)?
-            .trim_matches(|c| c == '\u{0}')
-            .to_owned();
-
-        let field_type = FieldType::try_from(field_type as char)?;
+        let name = decode_name(&name, encoding)?;
+        // dBase 7 marks autoincrement fields with their own type,
+        // they are Integer fields with the autoincrement flags in Visual FoxPro
+        let field_type = if field_type == AUTOINCREMENT_TYPE {
+            flags.0 |= FieldFlags::AUTOINCREMENT;
+            FieldType::Integer
+        } else {
+            FieldType::try_from(field_type as char)?
+        };
+        // Character fields have no decimal places, the byte is used to store
+        // the high byte of lengths above 255
+        let (field_length, num_decimal_places) = if field_type == FieldType::Character {
+            (u16::from_le_bytes([length_byte, decimal_count_byte]), 0)
+        } else {
+            (u16::from(length_byte), decimal_count_byte)
+        };
 
         Ok(Self {
-            name: s,
+            name: name.into(),
             field_type,
             displacement_field,
-            field_length: record_length,
+            field_length,
             num_decimal_places,
             flags,
             autoincrement_next_val,
             autoincrement_step,
+            hidden_bytes_before: 0,
+            double_as_text: false,
+        })
+    }
+
+    /// Reads a dBase II field descriptor
+    ///
+    /// | offset | content                         |
+    /// |--------|---------------------------------|
+    /// | 0..11  | name                            |
+    /// | 11     | type (only C, N, L are valid)   |
+    /// | 12     | length                          |
+    /// | 13..15 | address of the field in memory  |
+    /// | 15     | number of decimal places        |
+    fn read_dbase2_from<E: Encoding>(
+        bytes: &[u8; Self::DBASE2_SIZE],
+        encoding: &E,
+    ) -> Result<Self, ErrorKind> {
+        let name = decode_name(&bytes[..FIELD_NAME_LENGTH], encoding)?;
+
+        let field_type = match bytes[11] {
+            b'C' => FieldType::Character,
+            b'N' => FieldType::Numeric,
+            b'L' => FieldType::Logical,
+            other => return Err(ErrorKind::InvalidFieldType(other as char)),
+        };
+
+        Ok(Self {
+            name: name.into(),
+            field_type,
+            displacement_field: [0u8; 4],
+            field_length: u16::from(bytes[12]),
+            num_decimal_places: bytes[15],
+            flags: FieldFlags::default(),
+            autoincrement_next_val: 0,
+            autoincrement_step: 0u8,
+            hidden_bytes_before: 0,
+            double_as_text: false,
         })
     }
 
@@ -144,48 +288,263 @@ impl FieldInfo {
 
         dest.write_u8(u8::from(self.field_type))?;
         dest.write_all(&self.displacement_field)?;
-        dest.write_u8(self.field_length)?;
-        dest.write_u8(self.num_decimal_places)?;
+        let [length_byte, high_length_byte] = self.field_length.to_le_bytes();
+        dest.write_u8(length_byte)?;
+        if self.field_type == FieldType::Character {
+            dest.write_u8(high_length_byte)?;
+        } else {
+            dest.write_u8(self.num_decimal_places)?;
+        }
         dest.write_u8(self.flags.0)?;
-        dest.write_all(&self.autoincrement_next_val)?;
+        dest.write_u32::<LittleEndian>(self.autoincrement_next_val)?;
         dest.write_u8(self.autoincrement_step)?;
 
-        let reserved = [0u8; 7];
+        let reserved = [0u8; 8];
         dest.write_all(&reserved)?;
 
         Ok(())
     }
 }
 
+/// Returns a buffer large enough for the data of any of the fields,
+/// Character fields can be longer than [MAX_FIELD_LENGTH](crate::limits::MAX_FIELD_LENGTH)
+pub(crate) fn field_data_buffer(fields_info: &[FieldInfo]) -> Vec<u8> {
+    let max_length = fields_info
+        .iter()
+        .map(|info| usize::from(info.field_length))
+        .fold(crate::limits::MAX_FIELD_LENGTH, usize::max);
+    vec![0u8; max_length]
+}
+
+/// Returns whether two field names are the same, ignoring the case.
+///
+/// Names are compared after decoding: ASCII names ignore the ASCII case,
+/// others are compared lowercased (with `ß` as `ss`), which is not full
+/// Unicode case folding. The public docs describe it on
+/// [Record::get_ignoring_case](crate::Record::get_ignoring_case).
+pub(crate) fn names_match(a: &str, b: &str) -> bool {
+    if a.is_ascii() && b.is_ascii() {
+        a.eq_ignore_ascii_case(b)
+    } else {
+        fold_case(a) == fold_case(b)
+    }
+}
+
+fn fold_case(name: &str) -> String {
+    name.to_lowercase().replace('ß', "ss")
+}
+
+/// Decodes the name of a field descriptor,
+/// which ends at the first null byte or after 11 bytes
+fn decode_name<E: Encoding>(bytes: &[u8], encoding: &E) -> Result<String, ErrorKind> {
+    let len = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    Ok(encoding.decode(&bytes[..len])?.into_owned())
+}
+
 pub struct FieldsInfo {
     pub(crate) inner: Vec<FieldInfo>,
+    pub(crate) warnings: Vec<SchemaWarning>,
+    /// Length of the skipped fields that are stored after the last field
+    pub(crate) hidden_bytes_after: usize,
+    /// Position of the descriptor of each field in the header,
+    /// descriptors of skipped fields are counted
+    pub(crate) descriptor_indices: Vec<usize>,
 }
 
 impl FieldsInfo {
-    pub(crate) fn read_from<R: Read>(source: &mut R, num_fields: usize) -> Result<Self, ErrorKind> {
-        let mut fields_info = Vec::<FieldInfo>::with_capacity(num_fields);
-        for _ in 0..num_fields {
-            let info = FieldInfo::read_from(source)?;
+    /// Reads the fields descriptors, their names are decoded with `encoding`
+    ///
+    /// `available_size` is the number of bytes between the header and the first record
+    /// (backlink excluded). The descriptors end with a terminator, when the terminator
+    /// is missing or a descriptor has a type byte that cannot be a field type,
+    /// a [SchemaWarning] is added and the following bytes are ignored.
+    ///
+    /// When `skip_system_columns` is true, the fields flagged as system columns
+    /// are skipped like fields of unknown type.
+    pub(crate) fn read_from<R: Read, E: Encoding>(
+        source: &mut R,
+        available_size: usize,
+        on_unknown_type: UnknownFieldTypeHandling,
+        skip_system_columns: bool,
+        encoding: &E,
+    ) -> Result<Self, ErrorKind> {
+        let max_num_fields = available_size / FieldInfo::SIZE;
+        let mut descriptors = Vec::<[u8; FieldInfo::SIZE]>::with_capacity(max_num_fields);
+        let mut warning = None;
+        loop {
+            if descriptors.len() == max_num_fields {
+                let has_terminator = available_size > max_num_fields * FieldInfo::SIZE
+                    && source.read_u8()? == crate::reading::TERMINATOR_VALUE;
+                if !has_terminator {
+                    warning = Some(SchemaWarning::MissingTerminator {
+                        num_descriptors: descriptors.len(),
+                    });
+                }
+                break;
+            }
+            let mut bytes = [0u8; FieldInfo::SIZE];
+            bytes[0] = source.read_u8()?;
+            if bytes[0] == crate::reading::TERMINATOR_VALUE {
+                break;
+            }
+            // Without terminator, the last descriptor ends where the records start,
+            // a file too short to hold it is missing the terminator, not the descriptor
+            let ends_at_records = (descriptors.len() + 1) * FieldInfo::SIZE == available_size;
+            match source.read_exact(&mut bytes[1..]) {
+                Err(error)
+                    if ends_at_records && error.kind() == std::io::ErrorKind::UnexpectedEof =>
+                {
+                    warning = Some(SchemaWarning::MissingTerminator {
+                        num_descriptors: descriptors.len(),
+                    });
+                    break;
+                }
+                result => result?,
+            }
+            if !bytes[11].is_ascii_graphic() {
+                warning = Some(SchemaWarning::InvalidDescriptor {
+                    descriptor_index: descriptors.len(),
+                    type_byte: bytes[11],
+                });
+                break;
+            }
+            descriptors.push(bytes);
+        }
+
+        let flags_pos = skip_system_columns.then_some(18);
+        let mut fields_info =
+            Self::from_descriptors(descriptors, 11, 16, flags_pos, on_unknown_type, |bytes| {
+                FieldInfo::read_from(&mut &bytes[..], encoding)
+            })?;
+        fields_info.warnings.extend(warning);
+        Ok(fields_info)
+    }
+
+    /// Reads the fields descriptors of a dBase II file,
+    /// they end with a terminator or after the 32nd field
+    pub(crate) fn read_dbase2_from<R: Read, E: Encoding>(
+        source: &mut R,
+        on_unknown_type: UnknownFieldTypeHandling,
+        encoding: &E,
+    ) -> Result<Self, ErrorKind> {
+        let mut descriptors = Vec::<[u8; FieldInfo::DBASE2_SIZE]>::new();
+        while descriptors.len() < FieldInfo::DBASE2_MAX_FIELDS {
+            let mut bytes = [0u8; FieldInfo::DBASE2_SIZE];
+            bytes[0] = source.read_u8()?;
+            if bytes[0] == crate::reading::TERMINATOR_VALUE {
+                break;
+            }
+            source.read_exact(&mut bytes[1..])?;
+            descriptors.push(bytes);
+        }
+
+        Self::from_descriptors(descriptors, 11, 12, None, on_unknown_type, |bytes| {
+            FieldInfo::read_dbase2_from(bytes, encoding)
+        })
+    }
+
+    /// Parses the raw field descriptors, `type_pos` and `length_pos`
+    /// are the positions of the type and length bytes in a descriptor.
+    ///
+    /// Fields whose flags, at `flags_pos`, mark them as system columns are skipped.
+    fn from_descriptors<const N: usize>(
+        descriptors: Vec<[u8; N]>,
+        type_pos: usize,
+        length_pos: usize,
+        flags_pos: Option<usize>,
+        on_unknown_type: UnknownFieldTypeHandling,
+        parse: impl Fn(&[u8; N]) -> Result<FieldInfo, ErrorKind>,
+    ) -> Result<Self, ErrorKind> {
+        let mut fields_info = Vec::<FieldInfo>::with_capacity(descriptors.len());
+        let mut descriptor_indices = Vec::<usize>::with_capacity(descriptors.len());
+        let mut hidden_bytes = 0;
+        for (descriptor_index, mut bytes) in descriptors.into_iter().enumerate() {
+            if flags_pos.is_some_and(|pos| FieldFlags(bytes[pos]).is_system_column()) {
+                hidden_bytes += usize::from(bytes[length_pos]);
+                continue;
+            }
+            let is_known = FieldType::from(bytes[type_pos] as char).is_some()
+                || bytes[type_pos] == AUTOINCREMENT_TYPE;
+            match on_unknown_type {
+                UnknownFieldTypeHandling::Error => {}
+                _ if is_known => {}
+                UnknownFieldTypeHandling::TreatAsCharacter => bytes[type_pos] = b'C',
+                UnknownFieldTypeHandling::Skip => {
+                    hidden_bytes += usize::from(bytes[length_pos]);
+                    continue;
+                }
+            }
+            let mut info = parse(&bytes)?;
+            info.hidden_bytes_before = std::mem::take(&mut hidden_bytes);
             fields_info.push(info);
+            descriptor_indices.push(descriptor_index);
         }
 
-        Ok(Self { inner: fields_info })
+        let mut fields_info = Self::new(fields_info);
+        fields_info.hidden_bytes_after = hidden_bytes;
+        fields_info.descriptor_indices = descriptor_indices;
+        Ok(fields_info)
+    }
+
+    pub(crate) fn new(fields_info: Vec<FieldInfo>) -> Self {
+        let warnings = SchemaWarning::detect(&fields_info);
+        Self {
+            descriptor_indices: (0..fields_info.len()).collect(),
+            inner: fields_info,
+            warnings,
+            hidden_bytes_after: 0,
+        }
     }
 
     pub(crate) fn field_position_in_record(&self, index: usize) -> Option<usize> {
+        let hidden_bytes = self.inner.get(index)?.hidden_bytes_before;
         self.inner
             .get(..index)
-            .map(|slc| slc.iter().map(|i| i.field_length as usize).sum::<usize>())
-            .map(|s| s + DELETION_FLAG_SIZE)
+            .map(|slc| {
+                slc.iter()
+                    .map(|i| i.field_length as usize + i.hidden_bytes_before)
+                    .sum::<usize>()
+            })
+            .map(|s| s + hidden_bytes + DELETION_FLAG_SIZE)
+    }
+
+    /// Uses only the length byte for the length of Character fields,
+    /// the high byte goes back to the decimal count
+    pub(crate) fn use_short_character_lengths(&mut self) {
+        for info in &mut self.inner {
+            if info.field_type == FieldType::Character && info.field_length > 0xFF {
+                info.num_decimal_places = (info.field_length >> 8) as u8;
+                info.field_length &= 0xFF;
+            }
+        }
+    }
+
+    /// Positions in the record of the bytes of the hidden fields
+    /// (skipped system columns and fields of unknown type)
+    pub(crate) fn hidden_ranges(&self) -> Vec<std::ops::Range<usize>> {
+        let mut ranges = vec![];
+        let mut position = DELETION_FLAG_SIZE;
+        for info in &self.inner {
+            if info.hidden_bytes_before != 0 {
+                ranges.push(position..position + info.hidden_bytes_before);
+            }
+            position += info.hidden_bytes_before + usize::from(info.field_length);
+        }
+        if self.hidden_bytes_after != 0 {
+            ranges.push(position..position + self.hidden_bytes_after);
+        }
+        ranges
     }
 
     pub(crate) fn size_of_all_fields(&self) -> usize {
         self.inner
             .iter()
-            .map(|i| i.field_length as usize)
+            .map(|i| i.field_length as usize + i.hidden_bytes_before)
             .sum::<usize>()
+            + self.hidden_bytes_after
     }
 
+    #[cfg(feature = "std-fs")]
     pub(crate) fn at_least_one_field_is_memo(&self) -> bool {
         self.inner
             .iter()
@@ -218,6 +577,92 @@ where
     }
 }
 
+/// Oddities found in the fields definition of a file.
+///
+/// They do not prevent reading the file, but the records may not
+/// be what is expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaWarning {
+    /// The field declares a length of 0,
+    /// it shares its position in the record with the next field
+    ZeroLengthField { index: usize, name: String },
+    /// The field has the same name (ignoring the case, see
+    /// [Record::get_ignoring_case](crate::Record::get_ignoring_case)) as the field at `first_index`
+    DuplicateFieldName {
+        index: usize,
+        first_index: usize,
+        name: String,
+    },
+    /// The `num_descriptors` field descriptors are not followed by the terminator (`0x0D`),
+    /// the records start where the header says
+    MissingTerminator { num_descriptors: usize },
+    /// The descriptor at `descriptor_index` has a type byte that cannot be
+    /// a field type (e.g. a descriptor filled with zeros), it and the bytes up to
+    /// the first record are ignored
+    InvalidDescriptor {
+        descriptor_index: usize,
+        type_byte: u8,
+    },
+}
+
+impl SchemaWarning {
+    pub(crate) fn detect(fields_info: &[FieldInfo]) -> Vec<Self> {
+        let mut warnings = vec![];
+        for (index, info) in fields_info.iter().enumerate() {
+            if info.field_length == 0 {
+                warnings.push(Self::ZeroLengthField {
+                    index,
+                    name: info.name.to_string(),
+                });
+            }
+            if let Some(first_index) = fields_info[..index]
+                .iter()
+                .position(|other| names_match(&other.name, &info.name))
+            {
+                warnings.push(Self::DuplicateFieldName {
+                    index,
+                    first_index,
+                    name: info.name.to_string(),
+                });
+            }
+        }
+        warnings
+    }
+}
+
+impl std::fmt::Display for SchemaWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaWarning::ZeroLengthField { index, name } => {
+                write!(f, "Field '{}' (index {}) has a length of 0", name, index)
+            }
+            SchemaWarning::DuplicateFieldName {
+                index,
+                first_index,
+                name,
+            } => write!(
+                f,
+                "Field '{}' (index {}) has the same name as field at index {}",
+                name, index, first_index
+            ),
+            SchemaWarning::MissingTerminator { num_descriptors } => write!(
+                f,
+                "The {} field descriptors are not followed by a terminator",
+                num_descriptors
+            ),
+            SchemaWarning::InvalidDescriptor {
+                descriptor_index,
+                type_byte,
+            } => write!(
+                f,
+                "The field descriptor at index {} has an invalid type byte 0x{:02X}, \
+                 it and the following ones are ignored",
+                descriptor_index, type_byte
+            ),
+        }
+    }
+}
+
 impl std::fmt::Display for FieldInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -228,9 +673,12 @@ impl std::fmt::Display for FieldInfo {
     }
 }
 
+/// Flag written before the fields of each record
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub(crate) enum DeletionFlag {
+pub enum DeletionFlag {
+    /// A space (0x20)
     NotDeleted,
+    /// `*` (0x2A), the record is skipped by the [Reader](crate::Reader)
     Deleted,
 }
 
@@ -243,25 +691,59 @@ impl DeletionFlag {
     }
 
     pub(crate) const fn from_byte(byte: u8) -> Self {
-        match byte {
-            0x20 => Self::NotDeleted,
-            0x2A => Self::Deleted,
+        match Self::try_from_byte(byte) {
+            Some(flag) => flag,
             // Silently consider other values as not deleted
-            _ => Self::NotDeleted,
+            None => Self::NotDeleted,
         }
     }
 
-    pub(crate) fn read_from<T: Read>(source: &mut T) -> std::io::Result<Self> {
-        source.read_u8().map(Self::from_byte)
+    /// Returns `None` for the bytes that are not a deletion flag
+    pub(crate) const fn try_from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x20 => Some(Self::NotDeleted),
+            0x2A => Some(Self::Deleted),
+            _ => None,
+        }
     }
 
     pub(crate) fn write_to<T: Write>(self, dst: &mut T) -> std::io::Result<()> {
         dst.write_u8(self.to_byte())
     }
 }
-/// Flags describing a field
-#[derive(Debug, Copy, Clone, PartialEq, Default)]
-pub(crate) struct FieldFlags(u8);
+/// Flags describing a field, only used by Visual FoxPro
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct FieldFlags(pub(crate) u8);
+
+impl FieldFlags {
+    /// The field is a column used by the database, hidden to the users
+    pub(crate) const SYSTEM: u8 = 0x01;
+    /// The bytes of Character and Memo fields are not text of the table encoding
+    pub(crate) const BINARY: u8 = 0x04;
+    /// The values of the Integer field are generated when appending records
+    pub(crate) const AUTOINCREMENT: u8 = 0x0C;
+
+    /// Returns the flags byte as stored in the field descriptor
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Returns true if the field is a system column, like the `_NullFlags` column of Visual FoxPro,
+    /// see [ReadingOptions::include_system_columns](crate::ReadingOptions::include_system_columns)
+    pub fn is_system_column(self) -> bool {
+        self.0 & Self::SYSTEM != 0
+    }
+
+    /// Returns true if the field holds raw bytes that must not be decoded
+    pub fn is_binary(self) -> bool {
+        self.0 & Self::BINARY != 0
+    }
+
+    /// Returns true if the values of the field are generated when appending records
+    pub fn is_autoincrement(self) -> bool {
+        self.0 & Self::AUTOINCREMENT == Self::AUTOINCREMENT
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -280,7 +762,7 @@ mod test {
 
         cursor.set_position(0);
 
-        let read_field_info = FieldInfo::read_from(&mut cursor).unwrap();
+        let read_field_info = FieldInfo::read_from(&mut cursor, &crate::encoding::Ascii).unwrap();
 
         assert_eq!(read_field_info, field_info);
     }
@@ -1,14 +1,17 @@
+use std::borrow::Cow;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::io::{Read, Seek, Write};
 use std::str::FromStr;
 
+use crate::encoding::{decode_lossy, encode_with_offset};
 use crate::Encoding;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
 use crate::error::ErrorKind;
 use crate::field::FieldInfo;
 use crate::memo::MemoReader;
+use crate::reading::{DecodeMode, ReadingOptions};
 use crate::writing::WritableAsDbaseField;
 
 /// Enum listing all the field types we know of
@@ -27,6 +30,11 @@ pub enum FieldType {
     // Unknown
     Double,
     Memo,
+    /// `_NullFlags` system column of Visual FoxPro and dBase 7 (`0`),
+    /// its values are read as [FieldValue::CharacterBinary]
+    SystemNullFlags,
+    /// Timestamp of dBase 7 (`@`), read as a [FieldValue::DateTime]
+    Timestamp,
     //General,
     //BinaryCharacter,
     //BinaryMemo,
@@ -45,6 +53,8 @@ impl From<FieldType> for u8 {
             FieldType::Integer => 'I',
             FieldType::Double => 'B',
             FieldType::Memo => 'M',
+            FieldType::SystemNullFlags => '0',
+            FieldType::Timestamp => '@',
         };
         v as u8
     }
@@ -68,6 +78,8 @@ impl FieldType {
             // unknown version
             'B' => Some(FieldType::Double),
             'M' => Some(FieldType::Memo),
+            '0' => Some(FieldType::SystemNullFlags),
+            '@' => Some(FieldType::Timestamp),
             //'G' => Some(FieldType::General),
             //'C' => Some(FieldType::BinaryCharacter), ??
             //'M' => Some(FieldType::BinaryMemo),
@@ -87,7 +99,9 @@ impl FieldType {
             FieldType::Date => Some(8),
             FieldType::Integer => Some(std::mem::size_of::<i32>() as u8),
             FieldType::Currency => Some(std::mem::size_of::<f64>() as u8),
-            FieldType::DateTime => Some(2 * std::mem::size_of::<i32>() as u8),
+            FieldType::DateTime | FieldType::Timestamp => {
+                Some(2 * std::mem::size_of::<i32>() as u8)
+            }
             FieldType::Double => Some(std::mem::size_of::<f64>() as u8),
             _ => None,
         }
@@ -121,13 +135,27 @@ pub enum FieldValue {
     ///
     /// A string full of 'pad bytes' is considered `None`
     Character(Option<String>),
+    /// Value of a Character field flagged as binary (see [FieldFlags::is_binary](crate::FieldFlags::is_binary))
+    ///
+    /// The bytes are not decoded nor trimmed.
+    CharacterBinary(Vec<u8>),
     /// dBase type to represent numbers, stored as String in the file
+    ///
+    /// A field that is blank, filled with NUL bytes or with `*`
+    /// (written by dBase when the value overflows the field) is `None`
     Numeric(Option<f64>),
     /// dBase type for boolean values, stored as a character in the file
+    ///
+    /// `T`, `t`, `Y`, `y`, `1` and `0` are `true`, `F`, `f`, `N` and `n` are `false`,
+    /// any other character (`?`, a space, a NUL byte, `*`, ...) is `None`
     Logical(Option<bool>),
     /// dBase type for dates, stored as a string in the file
+    ///
+    /// A field that is blank, filled with NUL bytes or with `*` is `None`
     Date(Option<Date>),
     /// Another dBase type to represent numbers, stored as String in the file
+    ///
+    /// Same as [FieldValue::Numeric] for the `None` values
     Float(Option<f32>),
     //Visual FoxPro fields
     Integer(i32),
@@ -142,41 +170,87 @@ pub enum FieldValue {
     Memo(String),
 }
 
+/// Parses a number written as text, blank or `*` filled fields are `None`
+fn read_numeric_text<E: Encoding>(
+    field_bytes: &[u8],
+    field_info: &FieldInfo,
+    encoding: &E,
+) -> Result<Option<f64>, ErrorKind> {
+    let value = trim_field_data(field_bytes, TrimOption::BeginEnd);
+    if value.is_empty() || value.iter().all(|c| c == &b'*') {
+        Ok(None)
+    } else {
+        let value_str = encoding.decode(value)?;
+        let number = parse_number(&value_str)
+            .map_err(|error| invalid_content(field_bytes, field_info, error.into()))?;
+        Ok(Some(number))
+    }
+}
+
+/// Wraps the error of parsing the content of a field with the bytes of the field
+fn invalid_content(field_bytes: &[u8], field_info: &FieldInfo, source: ErrorKind) -> ErrorKind {
+    let length = field_bytes.len().min(field_info.length() as usize);
+    ErrorKind::InvalidFieldContent {
+        field_type: field_info.field_type,
+        raw: field_bytes[..length].to_vec(),
+        source: Box::new(source),
+    }
+}
+
+/// Parses a number, also accepting the `D` exponent marker
+/// of Fortran-era tools (`1.5D+03`)
+fn parse_number<F>(text: &str) -> Result<F, std::num::ParseFloatError>
+where
+    F: std::str::FromStr<Err = std::num::ParseFloatError>,
+{
+    text.parse::<F>().or_else(|error| {
+        if text.contains(['D', 'd']) {
+            text.replace(['D', 'd'], "E").parse::<F>()
+        } else {
+            Err(error)
+        }
+    })
+}
+
 impl FieldValue {
     pub(crate) fn read_from<T: Read + Seek, E: Encoding>(
         mut field_bytes: &[u8],
         memo_reader: &mut Option<MemoReader<T>>,
         field_info: &FieldInfo,
         encoding: &E,
-        character_option: TrimOption,
+        options: &ReadingOptions,
+        invalid_offsets: &mut Vec<usize>,
     ) -> Result<Self, ErrorKind> {
         debug_assert_eq!(field_bytes.len(), field_info.length() as usize);
         let value = match field_info.field_type {
-            FieldType::Logical => match field_bytes[0] as char {
-                ' ' | '?' => FieldValue::Logical(None),
+            FieldType::Logical => match field_bytes.first().copied().unwrap_or(b' ') as char {
+                ' ' | '?' | '*' => FieldValue::Logical(None),
                 '1' | '0' | 'T' | 't' | 'Y' | 'y' => FieldValue::Logical(Some(true)),
                 'N' | 'n' | 'F' | 'f' => FieldValue::Logical(Some(false)),
                 _ => FieldValue::Logical(None),
             },
-            FieldType::Character => {
-                // let value = read_string_of_len(&mut source, field_info.field_length)?;
-                let value = trim_field_data(field_bytes, character_option);
-                if value.is_empty() {
-                    FieldValue::Character(None)
-                } else {
-                    FieldValue::Character(Some(encoding.decode(value)?.to_string()))
-                }
+            FieldType::Character if field_info.flags.is_binary() => {
+                FieldValue::CharacterBinary(field_bytes.to_vec())
             }
-            FieldType::Numeric => {
-                // let value = read_string_of_len(&mut source, field_info.field_length)?;
+            FieldType::Character => FieldValue::Character(
+                read_character_str(field_bytes, field_info, encoding, options, invalid_offsets)?
+                    .map(Cow::into_owned),
+            ),
+            FieldType::Numeric if options.numeric_as_string => {
                 let value = trim_field_data(field_bytes, TrimOption::BeginEnd);
                 if value.is_empty() || value.iter().all(|c| c == &b'*') {
-                    FieldValue::Numeric(None)
+                    FieldValue::Character(None)
                 } else {
-                    let value_str = encoding.decode(value)?;
-                    FieldValue::Numeric(Some(value_str.parse::<f64>()?))
+                    FieldValue::Character(Some(encoding.decode(value)?.into_owned()))
                 }
             }
+            FieldType::Numeric => {
+                FieldValue::Numeric(read_numeric_text(field_bytes, field_info, encoding)?)
+            }
+            // Clipper and dBase III / IV write the values of 'B' fields as text
+            FieldType::Double if field_info.double_as_text => {
+                FieldValue::Numeric(read_numeric_text(field_bytes, field_info, encoding)?)
+            }
             FieldType::Float => {
                 // let value = read_string_of_len(&mut source, field_info.field_length)?;
                 let value = trim_field_data(field_bytes, TrimOption::BeginEnd);
@@ -184,56 +258,72 @@ impl FieldValue {
                     FieldValue::Float(None)
                 } else {
                     let value_str = encoding.decode(value)?;
-                    FieldValue::Float(Some(value_str.parse::<f32>()?))
+                    let number = parse_number(&value_str)
+                        .map_err(|error| invalid_content(field_bytes, field_info, error.into()))?;
+                    FieldValue::Float(Some(number))
                 }
             }
             FieldType::Date => {
                 // let value = read_string_of_len(&mut source, field_info.field_length)?;
                 let value = trim_field_data(field_bytes, TrimOption::BeginEnd);
-                if value.iter().all(|c| c == &b' ') {
+                // dBase fills the fields whose value overflows with `*`
+                if value.iter().all(|c| c == &b' ') || value.iter().all(|c| c == &b'*') {
                     FieldValue::Date(None)
                 } else {
                     let value_str = encoding.decode(value)?;
-                    FieldValue::Date(Some(value_str.parse::<Date>()?))
+                    let date = match options.two_digit_year_pivot {
+                        Some(pivot) => Date::parse_with_pivot(&value_str, pivot),
+                        None => value_str.parse::<Date>(),
+                    }
+                    .map_err(|error| invalid_content(field_bytes, field_info, error.into()))?;
+                    FieldValue::Date(Some(date))
                 }
             }
             FieldType::Integer => {
-                let mut le_bytes = [0u8; std::mem::size_of::<i32>()];
-                le_bytes.copy_from_slice(&field_bytes[..std::mem::size_of::<i32>()]);
-                FieldValue::Integer(i32::from_le_bytes(le_bytes))
+                FieldValue::Integer(i32::from_le_bytes(leading_bytes(field_bytes)?))
             }
             FieldType::Double => {
-                let mut le_bytes = [0u8; std::mem::size_of::<f64>()];
-                le_bytes.copy_from_slice(&field_bytes[..std::mem::size_of::<f64>()]);
-                FieldValue::Double(f64::from_le_bytes(le_bytes))
+                FieldValue::Double(f64::from_le_bytes(leading_bytes(field_bytes)?))
             }
             FieldType::Currency => {
-                let mut le_bytes = [0u8; std::mem::size_of::<f64>()];
-                le_bytes.copy_from_slice(&field_bytes[..std::mem::size_of::<f64>()]);
-                FieldValue::Currency(f64::from_le_bytes(le_bytes))
+                FieldValue::Currency(f64::from_le_bytes(leading_bytes(field_bytes)?))
             }
-            FieldType::DateTime => {
+            // The two words of a dBase 7 timestamp are the ones of a Visual FoxPro DateTime
+            FieldType::DateTime | FieldType::Timestamp => {
                 let mut source = std::io::Cursor::new(&mut field_bytes);
                 FieldValue::DateTime(DateTime::read_from(&mut source)?)
             }
+            FieldType::SystemNullFlags => FieldValue::CharacterBinary(field_bytes.to_vec()),
             FieldType::Memo => {
-                let index_in_memo = if field_info.field_length > 4 {
-                    // let string = read_string_of_len(&mut source, field_info.field_length)?;
-                    let trimmed_value = trim_field_data(field_bytes, TrimOption::BeginEnd);
-                    if trimmed_value.is_empty() {
-                        return Ok(FieldValue::Memo(String::from("")));
-                    } else {
-                        encoding.decode(trimmed_value)?.parse::<u32>()?
+                let next_available_block_index = memo_reader
+                    .as_ref()
+                    .and_then(MemoReader::next_available_block_index);
+                let index_in_memo = match read_memo_index(
+                    field_bytes,
+                    field_info,
+                    encoding,
+                    next_available_block_index,
+                ) {
+                    // Some writers store short texts in the field instead of a block index
+                    Err(ErrorKind::ParseIntError(_)) if options.lenient_memo => {
+                        let text = trim_field_data(field_bytes, TrimOption::BeginEnd);
+                        return Ok(FieldValue::Memo(encoding.decode(text)?.into_owned()));
                     }
-                } else {
-                    let mut le_bytes = [0u8; std::mem::size_of::<u32>()];
-                    le_bytes.copy_from_slice(&field_bytes[..std::mem::size_of::<u32>()]);
-                    u32::from_le_bytes(le_bytes)
+                    Err(error @ ErrorKind::ParseIntError(_)) => {
+                        return Err(invalid_content(field_bytes, field_info, error));
+                    }
+                    result => result?,
+                };
+                let Some(index_in_memo) = index_in_memo else {
+                    return Ok(FieldValue::Memo(String::from("")));
                 };
 
                 if let Some(memo_reader) = memo_reader {
-                    let data_from_memo = memo_reader.read_data_at(index_in_memo)?;
-                    FieldValue::Memo(encoding.decode(data_from_memo)?.to_string())
+                    FieldValue::Memo(memo_reader.read_text_at(
+                        index_in_memo,
+                        encoding,
+                        options.max_memo_size,
+                    )?)
                 } else {
                     return Err(ErrorKind::MissingMemoFile);
                 }
@@ -245,7 +335,7 @@ impl FieldValue {
     /// Returns the corresponding field type of the contained value
     pub fn field_type(&self) -> FieldType {
         match self {
-            FieldValue::Character(_) => FieldType::Character,
+            FieldValue::Character(_) | FieldValue::CharacterBinary(_) => FieldType::Character,
             FieldValue::Numeric(_) => FieldType::Numeric,
             FieldValue::Logical(_) => FieldType::Logical,
             FieldValue::Integer(_) => FieldType::Integer,
@@ -257,11 +347,222 @@ impl FieldValue {
             FieldValue::DateTime(_) => FieldType::DateTime,
         }
     }
+
+    /// Returns the number of bytes the value takes in a record of the field,
+    /// before being padded to the length of the field
+    ///
+    /// This is the length of the encoded text of Character values, the formatted width
+    /// of Numeric and Float values and the fixed size of the binary types.
+    /// Memo values are stored in the memo file, the record only has their block number,
+    /// so their size is the length of the field.
+    ///
+    /// Returns [ErrorKind::IncompatibleType] when the value is not of the type of the field,
+    /// or the error of the encoding when the text cannot be encoded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldValue, TableWriterBuilder, Unicode};
+    /// use std::convert::TryInto;
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let table_info = TableWriterBuilder::new()
+    ///     .add_character_field("NAME".try_into().unwrap(), 5)
+    ///     .build_table_info()?;
+    /// let info = &table_info.fields()[0];
+    /// let value = FieldValue::Character(Some("Crème".to_string()));
+    /// // 'è' takes 2 bytes in UTF-8
+    /// assert_eq!(value.encoded_size(info, &Unicode).unwrap(), 6);
+    /// assert!(!value.fits(info, &Unicode));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn encoded_size(
+        &self,
+        info: &FieldInfo,
+        encoding: &dyn Encoding,
+    ) -> Result<usize, ErrorKind> {
+        self.encoded_size_with(info, encoding, &mut Vec::new())
+    }
+
+    /// Same as [Self::encoded_size], with the buffer the value is written in
+    pub(crate) fn encoded_size_with<E: Encoding + ?Sized>(
+        &self,
+        info: &FieldInfo,
+        encoding: &E,
+        buffer: &mut Vec<u8>,
+    ) -> Result<usize, ErrorKind> {
+        if let FieldValue::Memo(_) = self {
+            return if info.field_type == FieldType::Memo {
+                Ok(usize::from(info.field_length))
+            } else {
+                Err(ErrorKind::IncompatibleType)
+            };
+        }
+        buffer.clear();
+        match self.write_as(info, encoding, buffer) {
+            Ok(()) => Ok(buffer.len()),
+            Err(ErrorKind::NumericValueTooWide { required, .. }) => Ok(required),
+            Err(kind) => Err(kind),
+        }
+    }
+
+    /// Returns whether the value can be written in the field without being truncated,
+    /// see [Self::encoded_size]
+    pub fn fits(&self, info: &FieldInfo, encoding: &dyn Encoding) -> bool {
+        self.encoded_size(info, encoding)
+            .is_ok_and(|size| size <= usize::from(info.field_length))
+    }
+
+    /// Returns true if the value is one of the `None` variants
+    ///
+    /// Integer, Double, Currency, DateTime and Memo values are never `None`.
+    pub fn is_none(&self) -> bool {
+        matches!(
+            self,
+            FieldValue::Character(None)
+                | FieldValue::Numeric(None)
+                | FieldValue::Logical(None)
+                | FieldValue::Date(None)
+                | FieldValue::Float(None)
+        )
+    }
+
+    /// Returns the text of Character and Memo values
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            FieldValue::Character(Some(text)) | FieldValue::Memo(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of Numeric, Float, Integer, Double and Currency values
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            FieldValue::Numeric(Some(value))
+            | FieldValue::Double(value)
+            | FieldValue::Currency(value) => Some(value),
+            FieldValue::Float(Some(value)) => Some(float_as_f64(value)),
+            FieldValue::Integer(value) => Some(f64::from(value)),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of Logical values
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            FieldValue::Logical(value) => value,
+            _ => None,
+        }
+    }
+
+    /// Returns the date of Date values
+    pub fn as_date(&self) -> Option<Date> {
+        match *self {
+            FieldValue::Date(value) => value,
+            _ => None,
+        }
+    }
+
+    /// Renders the value as text
+    ///
+    /// Numeric and Float values use the number of decimal places of the field,
+    /// so that the text matches what is written in the file.
+    /// Binary Character values are rendered in hexadecimal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// use dbase::{Date, DateFormat, DisplayStyle, FieldValue};
+    /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// let info = reader.fields()[0].clone();
+    ///
+    /// let style = DisplayStyle::default().date_format(DateFormat::Iso).null_text("NULL");
+    /// let date = FieldValue::Date(Some(Date::new(25, 12, 2020)));
+    /// assert_eq!(date.display_with(&info, &style), "2020-12-25");
+    /// assert_eq!(FieldValue::Character(None).display_with(&info, &style), "NULL");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn display_with(&self, info: &FieldInfo, style: &DisplayStyle) -> String {
+        let precision = info.num_decimal_places as usize;
+        match self {
+            FieldValue::Character(Some(text)) => text.clone(),
+            FieldValue::CharacterBinary(bytes) => {
+                bytes.iter().map(|byte| format!("{byte:02X}")).collect()
+            }
+            FieldValue::Numeric(Some(value)) => format_decimals(*value, precision),
+            FieldValue::Float(Some(value)) => format_decimals(float_as_f64(*value), precision),
+            FieldValue::Logical(Some(true)) => "T".to_string(),
+            FieldValue::Logical(Some(false)) => "F".to_string(),
+            FieldValue::Date(Some(date)) => style.format_date(date),
+            FieldValue::Integer(value) => value.to_string(),
+            FieldValue::Double(value) => value.to_string(),
+            FieldValue::Currency(value) => format!("{value:.4}"),
+            FieldValue::DateTime(date_time) => {
+                let time = date_time.time;
+                let separator = match style.date_format {
+                    DateFormat::Dbf => ' ',
+                    DateFormat::Iso => 'T',
+                };
+                format!(
+                    "{}{}{:02}:{:02}:{:02}",
+                    style.format_date(&date_time.date),
+                    separator,
+                    time.hours,
+                    time.minutes,
+                    time.seconds
+                )
+            }
+            FieldValue::Memo(text) => text.clone(),
+            FieldValue::Character(None)
+            | FieldValue::Numeric(None)
+            | FieldValue::Float(None)
+            | FieldValue::Logical(None)
+            | FieldValue::Date(None) => style.null_text.clone(),
+        }
+    }
 }
 
+/// Character and Memo values are written as they are, numbers with as few
+/// digits as possible (Currency with its 4 decimals), Logical values as `T`, `F` or `?`,
+/// dates and date times in the ISO format (`2024-03-01T12:30:00`),
+/// binary Character values in hexadecimal. Other `None` values are empty.
+///
+/// To use the number of decimals of the field, see [FieldValue::display_with].
 impl fmt::Display for FieldValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        let write_date = |f: &mut fmt::Formatter, date: &Date| {
+            write!(f, "{:04}-{:02}-{:02}", date.year, date.month, date.day)
+        };
+        match self {
+            FieldValue::Character(Some(text)) | FieldValue::Memo(text) => f.write_str(text),
+            FieldValue::CharacterBinary(bytes) => {
+                bytes.iter().try_for_each(|byte| write!(f, "{byte:02X}"))
+            }
+            FieldValue::Numeric(Some(value)) | FieldValue::Double(value) => write!(f, "{value}"),
+            FieldValue::Float(Some(value)) => write!(f, "{value}"),
+            FieldValue::Integer(value) => write!(f, "{value}"),
+            FieldValue::Currency(value) => write!(f, "{value:.4}"),
+            FieldValue::Logical(Some(true)) => f.write_str("T"),
+            FieldValue::Logical(Some(false)) => f.write_str("F"),
+            FieldValue::Logical(None) => f.write_str("?"),
+            FieldValue::Date(Some(date)) => write_date(f, date),
+            FieldValue::DateTime(date_time) => {
+                let time = date_time.time;
+                write_date(f, &date_time.date)?;
+                write!(
+                    f,
+                    "T{:02}:{:02}:{:02}",
+                    time.hours, time.minutes, time.seconds
+                )
+            }
+            FieldValue::Character(None)
+            | FieldValue::Numeric(None)
+            | FieldValue::Float(None)
+            | FieldValue::Date(None) => Ok(()),
+        }
     }
 }
 
@@ -273,7 +574,9 @@ impl fmt::Display for FieldValue {
 /// with just a very few checks.
 ///
 /// Also, dBase files do not have concept of timezones.
-#[derive(Debug, Copy, Clone, PartialEq)]
+///
+/// Dates are ordered by year, then month, then day.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Date {
     pub(crate) year: u32,
     pub(crate) month: u32,
@@ -314,6 +617,28 @@ impl Date {
         self.day
     }
 
+    /// Returns the current date, in UTC
+    pub fn today() -> Self {
+        time::OffsetDateTime::now_utc().date().into()
+    }
+
+    /// Returns the date `days` days after this one (before it when negative),
+    /// `None` if the year of the result is not between 0 and 9999.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let date = dbase::Date::new(28, 2, 2024);
+    /// assert_eq!(date.checked_add_days(1), Some(dbase::Date::new(29, 2, 2024)));
+    /// assert_eq!(date.checked_add_days(-59), Some(dbase::Date::new(31, 12, 2023)));
+    /// ```
+    pub fn checked_add_days(self, days: i32) -> Option<Self> {
+        let jdn = self.to_julian_day_number().checked_add(days)?;
+        let date = Self::julian_day_number_to_gregorian_date(jdn);
+        // Years before 0 wrap to large values
+        (date.year <= 9999).then_some(date)
+    }
+
     pub fn to_unix_days(&self) -> i32 {
         let julian_day = self.to_julian_day_number();
         return julian_day - 2440588;
@@ -321,20 +646,23 @@ impl Date {
 
     // https://en.wikipedia.org/wiki/Julian_day
     // at "Julian or Gregorian calendar from Julian day number"
-    fn julian_day_number_to_gregorian_date(jdn: i32) -> Date {
-        const Y: i32 = 4716;
-        const J: i32 = 1401;
-        const M: i32 = 2;
-        const N: i32 = 12;
-        const R: i32 = 4;
-        const P: i32 = 1461;
-        const V: i32 = 3;
-        const U: i32 = 5;
-        const S: i32 = 153;
-        const W: i32 = 2;
-        const B: i32 = 274_277;
-        const C: i32 = -38;
-
+    const fn julian_day_number_to_gregorian_date(jdn: i32) -> Date {
+        // Computations are done on i64 so that values read
+        // from corrupted files cannot overflow
+        const Y: i64 = 4716;
+        const J: i64 = 1401;
+        const M: i64 = 2;
+        const N: i64 = 12;
+        const R: i64 = 4;
+        const P: i64 = 1461;
+        const V: i64 = 3;
+        const U: i64 = 5;
+        const S: i64 = 153;
+        const W: i64 = 2;
+        const B: i64 = 274_277;
+        const C: i64 = -38;
+
+        let jdn = jdn as i64;
         let f = jdn + J + ((4 * jdn + B) / 146_097 * 3) / 4 + C;
         let e = R * f + V;
         let g = (e % P) / R;
@@ -352,30 +680,62 @@ impl Date {
     }
 
     fn to_julian_day_number(&self) -> i32 {
+        // On i64, as the year before January of year 0 is -1
         let (month, year) = if self.month > 2 {
-            (self.month - 3, self.year)
+            (i64::from(self.month) - 3, i64::from(self.year))
         } else {
-            (self.month + 9, self.year - 1)
+            (i64::from(self.month) + 9, i64::from(self.year) - 1)
         };
 
-        let century = year / 100;
+        let century = year.div_euclid(100);
         let decade = year - 100 * century;
 
-        ((146_097 * century) / 4
+        ((146_097 * century).div_euclid(4)
             + (1461 * decade) / 4
             + (153 * month + 2) / 5
-            + self.day
+            + i64::from(self.day)
             + 1_721_119) as i32
     }
 }
 
+impl Date {
+    /// Returns the year of a two digits year `yy`, years below
+    /// the `pivot` are in the 2000s, the others in the 1900s
+    pub(crate) fn year_from_two_digits(yy: u32, pivot: u32) -> u32 {
+        if yy < pivot {
+            2000 + yy
+        } else {
+            1900 + yy
+        }
+    }
+
+    /// Parses a `YYYYMMDD` date, or a `YYMMDD` date whose year is given by the `pivot`
+    pub(crate) fn parse_with_pivot(s: &str, pivot: u32) -> Result<Self, std::num::ParseIntError> {
+        if s.len() == 6 {
+            // Like `from_str`, 6 bytes may not be cut on char boundaries
+            let yy = s.get(0..2).unwrap_or_default().parse::<u32>()?;
+            let month = s.get(2..4).unwrap_or_default().parse::<u32>()?;
+            let day = s.get(4..6).unwrap_or_default().parse::<u32>()?;
+            Ok(Self {
+                year: Self::year_from_two_digits(yy, pivot),
+                month,
+                day,
+            })
+        } else {
+            s.parse()
+        }
+    }
+}
+
 impl FromStr for Date {
     type Err = std::num::ParseIntError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let year = s[0..4].parse::<u32>()?;
-        let month = s[4..6].parse::<u32>()?;
-        let day = s[6..8].parse::<u32>()?;
+        // `get` instead of indexing, as the string may be too short
+        // or not cut on char boundaries, an empty str gives a parse error
+        let year = s.get(0..4).unwrap_or_default().parse::<u32>()?;
+        let month = s.get(4..6).unwrap_or_default().parse::<u32>()?;
+        let day = s.get(6..8).unwrap_or_default().parse::<u32>()?;
 
         Ok(Self { year, month, day })
     }
@@ -412,7 +772,7 @@ impl From<time::Date> for Date {
 ///
 /// This is a very naive Time struct, very minimal verifications are done.
 ///
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Time {
     hours: u32,
     minutes: u32,
@@ -455,12 +815,15 @@ impl Time {
         self.seconds
     }
 
-    fn from_word(mut time_word: i32) -> Self {
-        let hours: u32 = (time_word / Self::HOURS_FACTOR) as u32;
-        time_word -= (hours * Self::HOURS_FACTOR as u32) as i32;
-        let minutes: u32 = (time_word / Self::MINUTES_FACTOR) as u32;
-        time_word -= (minutes * Self::MINUTES_FACTOR as u32) as i32;
-        let seconds: u32 = (time_word / Self::SECONDS_FACTOR) as u32;
+    fn from_word(time_word: i32) -> Self {
+        // A valid word is never negative, treating it as unsigned
+        // avoids overflows on corrupted data
+        let time_word = time_word as u32;
+        let hours: u32 = time_word / Self::HOURS_FACTOR as u32;
+        let time_word = time_word % Self::HOURS_FACTOR as u32;
+        let minutes: u32 = time_word / Self::MINUTES_FACTOR as u32;
+        let time_word = time_word % Self::MINUTES_FACTOR as u32;
+        let seconds: u32 = time_word / Self::SECONDS_FACTOR as u32;
         Self {
             hours,
             minutes,
@@ -477,13 +840,25 @@ impl Time {
 }
 
 /// FoxBase representation of a DateTime
-#[derive(Debug, Copy, Clone, PartialEq)]
+///
+/// DateTimes are ordered by date, then time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DateTime {
     date: Date,
     time: Time,
 }
 
 impl DateTime {
+    /// The value read from an empty DateTime field (stored as zeros)
+    pub const EMPTY: DateTime = DateTime {
+        date: Date::julian_day_number_to_gregorian_date(0),
+        time: Time {
+            hours: 0,
+            minutes: 0,
+            seconds: 0,
+        },
+    };
+
     /// Creates a new DateTime from a date and a time
     pub fn new(date: Date, time: Time) -> Self {
         Self { date, time }
@@ -522,7 +897,7 @@ impl DateTime {
 }
 
 impl WritableAsDbaseField for FieldValue {
-    fn write_as<E: Encoding, W: Write>(
+    fn write_as<E: Encoding + ?Sized, W: Write>(
         &self,
         field_info: &FieldInfo,
         encoding: &E,
@@ -533,6 +908,7 @@ impl WritableAsDbaseField for FieldValue {
         } else {
             match self {
                 FieldValue::Character(value) => value.write_as(field_info, encoding, dst),
+                FieldValue::CharacterBinary(value) => value.write_as(field_info, encoding, dst),
                 FieldValue::Numeric(value) => value.write_as(field_info, encoding, dst),
                 FieldValue::Logical(value) => value.write_as(field_info, encoding, dst),
                 FieldValue::Date(value) => value.write_as(field_info, encoding, dst),
@@ -541,14 +917,61 @@ impl WritableAsDbaseField for FieldValue {
                 FieldValue::Currency(value) => value.write_as(field_info, encoding, dst),
                 FieldValue::DateTime(value) => value.write_as(field_info, encoding, dst),
                 FieldValue::Double(value) => value.write_as(field_info, encoding, dst),
-                FieldValue::Memo(_) => unimplemented!("Cannot write memo"),
+                FieldValue::Memo(_) => Err(ErrorKind::Message(
+                    "writing Memo values is not supported".to_string(),
+                )),
             }
         }
     }
 }
 
+/// Formats a Numeric or Float value right-justified on the whole length of the field,
+/// padded with spaces on the left, as dBase does
+fn format_number(value: f64, field_info: &FieldInfo) -> Result<String, ErrorKind> {
+    let width = field_info.field_length as usize;
+    let string = format!(
+        "{:>width$}",
+        format_decimals(value, field_info.num_decimal_places as usize)
+    );
+    if string.len() > width {
+        return Err(ErrorKind::NumericValueTooWide {
+            width,
+            required: string.len(),
+        });
+    }
+    Ok(string)
+}
+
+/// Formats the value with `precision` decimals.
+///
+/// When the value has fewer decimals than that, its shortest text is padded
+/// with zeros: `12.345` with 15 decimals is `12.345000000000000`,
+/// not the `12.345000000000001` of its binary approximation.
+fn format_decimals(value: f64, precision: usize) -> String {
+    let shortest = value.to_string();
+    let decimals = shortest.find('.').map(|dot| shortest.len() - dot - 1);
+    match decimals {
+        _ if !value.is_finite() => format!("{value:.precision$}"),
+        Some(decimals) if decimals <= precision => {
+            format!("{shortest}{}", "0".repeat(precision - decimals))
+        }
+        None if precision > 0 => format!("{shortest}.{}", "0".repeat(precision)),
+        None => shortest,
+        Some(_) => format!("{value:.precision$}"),
+    }
+}
+
+/// Converts the value of a Float field to the f64 with the same shortest text,
+/// `12.345_f32 as f64` is `12.345000267028809`
+fn float_as_f64(value: f32) -> f64 {
+    value
+        .to_string()
+        .parse()
+        .expect("the text of a f32 is a valid f64")
+}
+
 impl WritableAsDbaseField for f64 {
-    fn write_as<E: Encoding, W: Write>(
+    fn write_as<E: Encoding + ?Sized, W: Write>(
         &self,
         field_info: &FieldInfo,
         encoding: &E,
@@ -556,11 +979,7 @@ impl WritableAsDbaseField for f64 {
     ) -> Result<(), ErrorKind> {
         match field_info.field_type {
             FieldType::Numeric => {
-                let string = format!(
-                    "{value:.precision$}",
-                    value = self,
-                    precision = field_info.num_decimal_places as usize
-                );
+                let string = format_number(*self, field_info)?;
                 let encoded_string = encoding.encode(&string)?;
                 dst.write_all(&*encoded_string)?;
                 Ok(())
@@ -574,8 +993,36 @@ impl WritableAsDbaseField for f64 {
     }
 }
 
+/// Raw bytes, written without being encoded, for binary Character fields
+impl WritableAsDbaseField for &[u8] {
+    fn write_as<E: Encoding + ?Sized, W: Write>(
+        &self,
+        field_info: &FieldInfo,
+        _encoding: &E,
+        dst: &mut W,
+    ) -> Result<(), ErrorKind> {
+        if field_info.field_type == FieldType::Character {
+            dst.write_all(self)?;
+            Ok(())
+        } else {
+            Err(ErrorKind::IncompatibleType)
+        }
+    }
+}
+
+impl WritableAsDbaseField for Vec<u8> {
+    fn write_as<E: Encoding + ?Sized, W: Write>(
+        &self,
+        field_info: &FieldInfo,
+        encoding: &E,
+        dst: &mut W,
+    ) -> Result<(), ErrorKind> {
+        self.as_slice().write_as(field_info, encoding, dst)
+    }
+}
+
 impl WritableAsDbaseField for Date {
-    fn write_as<E: Encoding, W: Write>(
+    fn write_as<E: Encoding + ?Sized, W: Write>(
         &self,
         field_info: &FieldInfo,
         encoding: &E,
@@ -593,7 +1040,7 @@ impl WritableAsDbaseField for Date {
 }
 
 impl WritableAsDbaseField for Option<Date> {
-    fn write_as<E: Encoding, W: Write>(
+    fn write_as<E: Encoding + ?Sized, W: Write>(
         &self,
         field_info: &FieldInfo,
         encoding: &E,
@@ -603,9 +1050,7 @@ impl WritableAsDbaseField for Option<Date> {
             if let Some(date) = self {
                 date.write_as(field_info, encoding, dst)?;
             } else {
-                for _ in 0..8 {
-                    dst.write_u8(b' ')?;
-                }
+                dst.write_all(&[b' '; 8])?;
             }
             Ok(())
         } else {
@@ -615,7 +1060,7 @@ impl WritableAsDbaseField for Option<Date> {
 }
 
 impl WritableAsDbaseField for Option<f64> {
-    fn write_as<E: Encoding, W: Write>(
+    fn write_as<E: Encoding + ?Sized, W: Write>(
         &self,
         field_info: &FieldInfo,
         encoding: &E,
@@ -634,18 +1079,14 @@ impl WritableAsDbaseField for Option<f64> {
 }
 
 impl WritableAsDbaseField for f32 {
-    fn write_as<E: Encoding, W: Write>(
+    fn write_as<E: Encoding + ?Sized, W: Write>(
         &self,
         field_info: &FieldInfo,
         encoding: &E,
         dst: &mut W,
     ) -> Result<(), ErrorKind> {
         if field_info.field_type == FieldType::Float {
-            let string = format!(
-                "{value:.precision$}",
-                value = self,
-                precision = field_info.num_decimal_places as usize
-            );
+            let string = format_number(float_as_f64(*self), field_info)?;
             let encoded_string = encoding.encode(&string)?;
             dst.write_all(&*encoded_string)?;
             Ok(())
@@ -656,7 +1097,7 @@ impl WritableAsDbaseField for f32 {
 }
 
 impl WritableAsDbaseField for Option<f32> {
-    fn write_as<E: Encoding, W: Write>(
+    fn write_as<E: Encoding + ?Sized, W: Write>(
         &self,
         field_info: &FieldInfo,
         encoding: &E,
@@ -674,14 +1115,14 @@ impl WritableAsDbaseField for Option<f32> {
 }
 
 impl WritableAsDbaseField for String {
-    fn write_as<E: Encoding, W: Write>(
+    fn write_as<E: Encoding + ?Sized, W: Write>(
         &self,
         field_info: &FieldInfo,
         encoding: &E,
         dst: &mut W,
     ) -> Result<(), ErrorKind> {
         if field_info.field_type == FieldType::Character {
-            let encoded_bytes = encoding.encode(self.as_str())?;
+            let encoded_bytes = encode_with_offset(encoding, self.as_str())?;
             dst.write_all(&*encoded_bytes)?;
             Ok(())
         } else {
@@ -691,7 +1132,7 @@ impl WritableAsDbaseField for String {
 }
 
 impl WritableAsDbaseField for Option<String> {
-    fn write_as<E: Encoding, W: Write>(
+    fn write_as<E: Encoding + ?Sized, W: Write>(
         &self,
         field_info: &FieldInfo,
         encoding: &E,
@@ -709,14 +1150,14 @@ impl WritableAsDbaseField for Option<String> {
 }
 
 impl WritableAsDbaseField for &str {
-    fn write_as<E: Encoding, W: Write>(
+    fn write_as<E: Encoding + ?Sized, W: Write>(
         &self,
         field_info: &FieldInfo,
         encoding: &E,
         dst: &mut W,
     ) -> Result<(), ErrorKind> {
         if field_info.field_type == FieldType::Character {
-            let encoded_bytes = encoding.encode(self)?;
+            let encoded_bytes = encode_with_offset(encoding, self)?;
             dst.write_all(&*encoded_bytes)?;
             Ok(())
         } else {
@@ -726,7 +1167,7 @@ impl WritableAsDbaseField for &str {
 }
 
 impl WritableAsDbaseField for bool {
-    fn write_as<E: Encoding, W: Write>(
+    fn write_as<E: Encoding + ?Sized, W: Write>(
         &self,
         field_info: &FieldInfo,
         encoding: &E,
@@ -748,7 +1189,7 @@ impl WritableAsDbaseField for bool {
 }
 
 impl WritableAsDbaseField for Option<bool> {
-    fn write_as<E: Encoding, W: Write>(
+    fn write_as<E: Encoding + ?Sized, W: Write>(
         &self,
         field_info: &FieldInfo,
         encoding: &E,
@@ -766,7 +1207,7 @@ impl WritableAsDbaseField for Option<bool> {
 }
 
 impl WritableAsDbaseField for i32 {
-    fn write_as<E: Encoding, W: Write>(
+    fn write_as<E: Encoding + ?Sized, W: Write>(
         &self,
         field_info: &FieldInfo,
         _encoding: &E,
@@ -781,8 +1222,21 @@ impl WritableAsDbaseField for i32 {
     }
 }
 
+/// Integer fields cannot store a missing value,
+/// `None` is written as `0`
+impl WritableAsDbaseField for Option<i32> {
+    fn write_as<E: Encoding + ?Sized, W: Write>(
+        &self,
+        field_info: &FieldInfo,
+        encoding: &E,
+        dst: &mut W,
+    ) -> Result<(), ErrorKind> {
+        self.unwrap_or(0).write_as(field_info, encoding, dst)
+    }
+}
+
 impl WritableAsDbaseField for DateTime {
-    fn write_as<E: Encoding, W: Write>(
+    fn write_as<E: Encoding + ?Sized, W: Write>(
         &self,
         field_info: &FieldInfo,
         _encoding: &E,
@@ -797,6 +1251,37 @@ impl WritableAsDbaseField for DateTime {
     }
 }
 
+/// `None` is written as zeros, like empty DateTime fields are stored,
+/// it is read back as [DateTime::EMPTY]
+impl WritableAsDbaseField for Option<DateTime> {
+    fn write_as<E: Encoding + ?Sized, W: Write>(
+        &self,
+        field_info: &FieldInfo,
+        encoding: &E,
+        dst: &mut W,
+    ) -> Result<(), ErrorKind> {
+        match self {
+            Some(datetime) => datetime.write_as(field_info, encoding, dst),
+            None if field_info.field_type == FieldType::DateTime => {
+                dst.write_all(&[0u8; 8])?;
+                Ok(())
+            }
+            None => Err(ErrorKind::IncompatibleType),
+        }
+    }
+}
+
+impl WritableAsDbaseField for &String {
+    fn write_as<E: Encoding + ?Sized, W: Write>(
+        &self,
+        field_info: &FieldInfo,
+        encoding: &E,
+        dst: &mut W,
+    ) -> Result<(), ErrorKind> {
+        self.as_str().write_as(field_info, encoding, dst)
+    }
+}
+
 #[cfg(feature = "serde")]
 mod de {
     use super::*;
@@ -821,8 +1306,8 @@ mod de {
                 where
                     E: serde::de::Error,
                 {
-                    let string = String::from_utf8(v).unwrap();
-                    Ok(Date::from_str(&string).unwrap())
+                    let string = String::from_utf8(v).map_err(E::custom)?;
+                    Date::from_str(&string).map_err(E::custom)
                 }
             }
             deserializer.deserialize_byte_buf(DateVisitor)
@@ -860,6 +1345,14 @@ mod de {
     }
 }
 
+/// Name of the newtype struct a [Date] is serialized as,
+/// so that the serializer of records only writes it in Date fields
+#[cfg(feature = "serde")]
+pub(crate) const DATE_STRUCT_NAME: &str = "dbase::Date";
+/// Same as [DATE_STRUCT_NAME] for [DateTime]
+#[cfg(feature = "serde")]
+pub(crate) const DATETIME_STRUCT_NAME: &str = "dbase::DateTime";
+
 #[cfg(feature = "serde")]
 mod ser {
     use super::*;
@@ -867,6 +1360,21 @@ mod ser {
     use serde::ser::Serialize;
     use serde::Serializer;
 
+    /// Bytes serialized with `serialize_bytes`
+    struct Bytes<'a>(&'a [u8]);
+
+    impl Serialize for Bytes<'_> {
+        fn serialize<S>(
+            &self,
+            serializer: S,
+        ) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+        where
+            S: Serializer,
+        {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+
     impl Serialize for Date {
         fn serialize<S>(
             &self,
@@ -875,7 +1383,8 @@ mod ser {
         where
             S: Serializer,
         {
-            serializer.serialize_bytes(self.to_string().as_bytes())
+            serializer
+                .serialize_newtype_struct(DATE_STRUCT_NAME, &Bytes(self.to_string().as_bytes()))
         }
     }
 
@@ -890,7 +1399,7 @@ mod ser {
             let mut bytes = [0u8; 8];
             bytes[..4].copy_from_slice(&self.date.to_julian_day_number().to_le_bytes());
             bytes[4..8].copy_from_slice(&self.time.to_time_word().to_le_bytes());
-            serializer.serialize_bytes(&bytes)
+            serializer.serialize_newtype_struct(DATETIME_STRUCT_NAME, &Bytes(&bytes))
         }
     }
 }
@@ -902,7 +1411,160 @@ pub enum TrimOption {
     BeginEnd,
 }
 
-fn trim_field_data(bytes: &[u8], option: TrimOption) -> &[u8] {
+/// How dates are rendered by [FieldValue::display_with]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DateFormat {
+    /// `YYYYMMDD`, as stored in the file
+    #[default]
+    Dbf,
+    /// `YYYY-MM-DD`
+    Iso,
+}
+
+/// Options of [FieldValue::display_with]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DisplayStyle {
+    pub(crate) date_format: DateFormat,
+    pub(crate) null_text: String,
+}
+
+impl DisplayStyle {
+    /// Sets the format of dates, [DateFormat::Dbf] by default
+    pub fn date_format(mut self, date_format: DateFormat) -> Self {
+        self.date_format = date_format;
+        self
+    }
+
+    /// Sets the text of `None` values, empty by default
+    pub fn null_text<S: Into<String>>(mut self, null_text: S) -> Self {
+        self.null_text = null_text.into();
+        self
+    }
+
+    fn format_date(&self, date: &Date) -> String {
+        match self.date_format {
+            DateFormat::Dbf => date.to_string(),
+            DateFormat::Iso => format!("{:04}-{:02}-{:02}", date.year, date.month, date.day),
+        }
+    }
+}
+
+/// Decodes the bytes of a Character field, the returned string borrows
+/// `field_bytes` when the encoding does not need to convert them.
+///
+/// `None` is returned when the field is empty.
+///
+/// With a lossy [DecodeMode], the offsets in `field_bytes` of the bytes
+/// that could not be decoded are pushed to `invalid_offsets`.
+pub(crate) fn read_character_str<'a, E: Encoding>(
+    field_bytes: &'a [u8],
+    field_info: &FieldInfo,
+    encoding: &E,
+    options: &ReadingOptions,
+    invalid_offsets: &mut Vec<usize>,
+) -> Result<Option<Cow<'a, str>>, ErrorKind> {
+    if field_info.field_type != FieldType::Character || field_info.flags.is_binary() {
+        return Err(ErrorKind::IncompatibleType);
+    }
+    let value = trim_field_data(field_bytes, options.character_trim);
+    if value.is_empty() {
+        return Ok(None);
+    }
+    // Offsets are given within the field, not within the trimmed value
+    let value_start = value.as_ptr() as usize - field_bytes.as_ptr() as usize;
+    let text =
+        match options.character_decode {
+            DecodeMode::Strict => encoding.decode(value).map_err(|error| {
+                match encoding.invalid_byte_offset(value) {
+                    Some(offset) => error.at_offset(value_start + offset, field_bytes),
+                    None => error,
+                }
+            })?,
+            DecodeMode::Lossy | DecodeMode::LossyWithReport => {
+                let num_offsets = invalid_offsets.len();
+                let text = decode_lossy(encoding, value, invalid_offsets)?;
+                for offset in &mut invalid_offsets[num_offsets..] {
+                    *offset += value_start;
+                }
+                text
+            }
+        };
+    Ok(Some(text))
+}
+
+/// Parses the index of the memo block stored in the bytes of a Memo field
+///
+/// Fields longer than 4 bytes store the index as text, the others as a little endian u32,
+/// `None` is returned when that text is empty.
+///
+/// As some writers do not follow this, when the bytes are not an index of the declared
+/// kind, or the index is not before `next_available_block_index` (when it is known),
+/// the other kind is tried before failing.
+pub(crate) fn read_memo_index<E: Encoding>(
+    field_bytes: &[u8],
+    field_info: &FieldInfo,
+    encoding: &E,
+    next_available_block_index: Option<u32>,
+) -> Result<Option<u32>, ErrorKind> {
+    let in_memo_file = |index: &u32| next_available_block_index.map_or(true, |next| *index < next);
+    let invalid_index = |index| ErrorKind::InvalidMemoIndex {
+        index,
+        next_available_block_index: next_available_block_index.unwrap_or(0),
+    };
+    let text_index = || -> Result<Option<u32>, ErrorKind> {
+        let trimmed_value = trim_field_data(field_bytes, TrimOption::BeginEnd);
+        if trimmed_value.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(encoding.decode(trimmed_value)?.parse::<u32>()?))
+        }
+    };
+
+    if field_info.field_length > 4 {
+        let declared = text_index();
+        match declared {
+            Ok(index) if index.as_ref().map_or(true, in_memo_file) => return Ok(index),
+            _ => {}
+        }
+        // A binary index padded with zeros
+        let binary_index = field_bytes
+            .get(4..)
+            .filter(|padding| padding.iter().all(|b| *b == 0))
+            .and_then(|_| leading_bytes(field_bytes).ok())
+            .map(u32::from_le_bytes)
+            .filter(in_memo_file);
+        match (binary_index, declared) {
+            (Some(index), _) => Ok(Some(index)),
+            (None, Ok(Some(index))) => Err(invalid_index(index)),
+            (None, Ok(None)) => Ok(None),
+            (None, Err(error)) => Err(error),
+        }
+    } else {
+        let index = u32::from_le_bytes(leading_bytes(field_bytes)?);
+        if in_memo_file(&index) {
+            return Ok(Some(index));
+        }
+        // An index written as text
+        match text_index() {
+            Ok(text_index) if text_index.as_ref().map_or(true, in_memo_file) => Ok(text_index),
+            _ => Err(invalid_index(index)),
+        }
+    }
+}
+
+/// Returns the first `N` bytes of the field data,
+/// fails if the field is shorter than what the field type requires.
+fn leading_bytes<const N: usize>(field_bytes: &[u8]) -> Result<[u8; N], ErrorKind> {
+    field_bytes
+        .get(..N)
+        .and_then(|bytes| <[u8; N]>::try_from(bytes).ok())
+        .ok_or(ErrorKind::FieldLengthTooSmall {
+            expected: N,
+            actual: field_bytes.len(),
+        })
+}
+
+pub(crate) fn trim_field_data(bytes: &[u8], option: TrimOption) -> &[u8] {
     // Value in the dbf file is surrounded by space characters (32u8). We discard them before
     // parsing the bytes into string. Doing so doubles the performance in comparison to
     // using String::trim() afterwards.
@@ -953,14 +1615,16 @@ mod test {
 
     fn create_temp_field_info(field_type: FieldType, len: u8) -> FieldInfo {
         FieldInfo {
-            name: "".to_owned(),
+            name: "".into(),
             field_type,
             displacement_field: [0u8; 4],
-            field_length: len,
+            field_length: u16::from(len),
             num_decimal_places: 0,
             flags: FieldFlags { 0: 0u8 },
-            autoincrement_next_val: [0u8; 5],
+            autoincrement_next_val: 0,
             autoincrement_step: 0u8,
+            hidden_bytes_before: 0,
+            double_as_text: false,
         }
     }
 
@@ -976,7 +1640,8 @@ mod test {
             &mut None,
             field_info,
             &encoding,
-            TrimOption::BeginEnd,
+            &ReadingOptions::default(),
+            &mut vec![],
         )
         .unwrap();
         assert_eq!(value, &read_value);
@@ -994,6 +1659,178 @@ mod test {
         test_we_can_read_back(&field_info, &date);
     }
 
+    #[test]
+    fn read_two_digit_year_date() {
+        let field_info = create_temp_field_info(FieldType::Date, 6);
+        let read = |bytes: &[u8], pivot| {
+            FieldValue::read_from::<std::io::Cursor<Vec<u8>>, _>(
+                bytes,
+                &mut None,
+                &field_info,
+                &UnicodeLossy,
+                &ReadingOptions::default().two_digit_year_pivot(pivot),
+                &mut vec![],
+            )
+        };
+
+        assert_eq!(
+            read(b"490315", Some(50)).unwrap(),
+            FieldValue::Date(Some(Date::new(15, 3, 2049)))
+        );
+        assert_eq!(
+            read(b"500315", Some(50)).unwrap(),
+            FieldValue::Date(Some(Date::new(15, 3, 1950)))
+        );
+        assert_eq!(
+            read(b"510315", Some(50)).unwrap(),
+            FieldValue::Date(Some(Date::new(15, 3, 1951)))
+        );
+        assert!(read(b"490315", None).is_err());
+        // Non ASCII text is an error, not a panic
+        assert!(Date::parse_with_pivot("4é031", 50).is_err());
+        assert!(read("4é031".as_bytes(), Some(50)).is_err());
+
+        // 8 digits dates are not affected by the pivot
+        let field_info = create_temp_field_info(FieldType::Date, 8);
+        let value = FieldValue::read_from::<std::io::Cursor<Vec<u8>>, _>(
+            b"19490315",
+            &mut None,
+            &field_info,
+            &UnicodeLossy,
+            &ReadingOptions::default().two_digit_year_pivot(Some(50)),
+            &mut vec![],
+        )
+        .unwrap();
+        assert_eq!(value, FieldValue::Date(Some(Date::new(15, 3, 1949))));
+    }
+
+    #[test]
+    fn read_numbers_in_scientific_notation() {
+        let read = |bytes: &[u8], field_type, options: &ReadingOptions| {
+            let field_info = create_temp_field_info(field_type, bytes.len() as u8);
+            FieldValue::read_from::<std::io::Cursor<Vec<u8>>, _>(
+                bytes,
+                &mut None,
+                &field_info,
+                &UnicodeLossy,
+                options,
+                &mut vec![],
+            )
+        };
+        let options = ReadingOptions::default();
+
+        for text in [&b"  1.5E+03"[..], b"  1.5e+03", b"  1.5D+03", b"   1.5d03"] {
+            assert_eq!(
+                read(text, FieldType::Numeric, &options).unwrap(),
+                FieldValue::Numeric(Some(1500.0))
+            );
+            assert_eq!(
+                read(text, FieldType::Float, &options).unwrap(),
+                FieldValue::Float(Some(1500.0))
+            );
+        }
+        assert!(read(b"   1.5X03", FieldType::Numeric, &options).is_err());
+
+        // 19 digits do not fit exactly in a f64
+        let options = ReadingOptions::default().numeric_as_string(true);
+        assert_eq!(
+            read(b" 1234567890123456789", FieldType::Numeric, &options).unwrap(),
+            FieldValue::Character(Some("1234567890123456789".to_string()))
+        );
+        assert_eq!(
+            read(b"    ", FieldType::Numeric, &options).unwrap(),
+            FieldValue::Character(None)
+        );
+        assert_eq!(
+            read(b" 1.5", FieldType::Float, &options).unwrap(),
+            FieldValue::Float(Some(1.5))
+        );
+    }
+
+    #[test]
+    fn read_asterisk_and_nul_filled_fields_as_none() {
+        let read = |bytes: &[u8], field_type| {
+            let field_info = create_temp_field_info(field_type, bytes.len() as u8);
+            FieldValue::read_from::<std::io::Cursor<Vec<u8>>, _>(
+                bytes,
+                &mut None,
+                &field_info,
+                &UnicodeLossy,
+                &ReadingOptions::default(),
+                &mut vec![],
+            )
+        };
+
+        for bytes in [&b"********"[..], &[0u8; 8]] {
+            assert_eq!(
+                read(bytes, FieldType::Date).unwrap(),
+                FieldValue::Date(None)
+            );
+            assert_eq!(
+                read(bytes, FieldType::Numeric).unwrap(),
+                FieldValue::Numeric(None)
+            );
+            assert_eq!(
+                read(bytes, FieldType::Float).unwrap(),
+                FieldValue::Float(None)
+            );
+        }
+        assert_eq!(
+            read(b"*", FieldType::Logical).unwrap(),
+            FieldValue::Logical(None)
+        );
+        assert_eq!(
+            read(b"\0", FieldType::Logical).unwrap(),
+            FieldValue::Logical(None)
+        );
+        // Only fields full of `*` are None
+        assert!(read(b"2019****", FieldType::Date).is_err());
+    }
+
+    #[test]
+    fn invalid_field_content_has_the_raw_bytes() {
+        use std::error::Error;
+
+        let read = |bytes: &[u8], field_type| {
+            let field_info = create_temp_field_info(field_type, bytes.len() as u8);
+            FieldValue::read_from::<std::io::Cursor<Vec<u8>>, _>(
+                bytes,
+                &mut None,
+                &field_info,
+                &UnicodeLossy,
+                &ReadingOptions::default(),
+                &mut vec![],
+            )
+            .map_err(|kind| crate::FieldIOError::new(kind, Some(field_info)))
+        };
+
+        let error = read(b"  12O4", FieldType::Numeric).unwrap_err();
+        match error.kind() {
+            ErrorKind::InvalidFieldContent {
+                field_type,
+                raw,
+                source,
+            } => {
+                assert_eq!(*field_type, FieldType::Numeric);
+                assert_eq!(raw, b"  12O4");
+                assert!(matches!(**source, ErrorKind::ParseFloatError(_)));
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+        assert!(error.to_string().contains("'  12O4'"));
+        assert!(error.source().is_some());
+
+        let error = read(b"2019\xE907\x01", FieldType::Date).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::InvalidFieldContent {
+                field_type: FieldType::Date,
+                ..
+            }
+        ));
+        assert!(error.to_string().contains("'2019\\xe907\\x01'"));
+    }
+
     #[test]
     fn test_write_read_empty_date() {
         let date = FieldValue::Date(None);
@@ -1043,6 +1880,73 @@ mod test {
         assert_eq!(date.to_julian_day_number(), 2458685);
     }
 
+    #[test]
+    fn date_and_time_ordering() {
+        let mut dates = vec![
+            Date::new(1, 1, 2024),
+            Date::new(31, 12, 2023),
+            Date::new(1, 2, 2023),
+            Date::new(31, 1, 2023),
+        ];
+        dates.sort();
+        assert_eq!(
+            dates,
+            vec![
+                Date::new(31, 1, 2023),
+                Date::new(1, 2, 2023),
+                Date::new(31, 12, 2023),
+                Date::new(1, 1, 2024),
+            ]
+        );
+        assert!(Time::new(9, 59, 59) < Time::new(10, 0, 0));
+        assert!(Time::new(10, 0, 0) < Time::new(10, 0, 1));
+
+        let new_year = Date::new(1, 1, 2024);
+        let last_second = DateTime::new(Date::new(31, 12, 2023), Time::new(23, 59, 59));
+        assert!(last_second < DateTime::new(new_year, Time::new(0, 0, 0)));
+        assert!(
+            DateTime::new(new_year, Time::new(0, 0, 0))
+                < DateTime::new(new_year, Time::new(0, 0, 1))
+        );
+
+        let unique = dates
+            .iter()
+            .chain(dates.iter())
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(unique.len(), dates.len());
+    }
+
+    #[test]
+    fn date_checked_add_days() {
+        let leap_day = Date::new(29, 2, 2024);
+        assert_eq!(Date::new(28, 2, 2024).checked_add_days(1), Some(leap_day));
+        assert_eq!(leap_day.checked_add_days(1), Some(Date::new(1, 3, 2024)));
+        assert_eq!(Date::new(1, 3, 2024).checked_add_days(-1), Some(leap_day));
+        assert_eq!(
+            Date::new(28, 2, 2023).checked_add_days(1),
+            Some(Date::new(1, 3, 2023))
+        );
+        // Divisible by 100 but not by 400, not a leap year
+        assert_eq!(
+            Date::new(28, 2, 1900).checked_add_days(1),
+            Some(Date::new(1, 3, 1900))
+        );
+        assert_eq!(
+            Date::new(28, 2, 2000).checked_add_days(1),
+            Some(Date::new(29, 2, 2000))
+        );
+        assert_eq!(
+            Date::new(31, 12, 2023).checked_add_days(366),
+            Some(Date::new(31, 12, 2024))
+        );
+        assert_eq!(leap_day.checked_add_days(0), Some(leap_day));
+
+        assert_eq!(Date::new(31, 12, 9999).checked_add_days(1), None);
+        assert_eq!(Date::new(1, 1, 0).checked_add_days(-1), None);
+        assert_eq!(leap_day.checked_add_days(i32::MAX), None);
+        assert_eq!(leap_day.checked_add_days(i32::MIN), None);
+    }
+
     #[test]
     fn test_to_unix_days() {
         let date = Date {
@@ -1053,9 +1957,497 @@ mod test {
         assert_eq!(date.to_unix_days(), 0);
     }
 
+    #[test]
+    fn test_parse_invalid_date_does_not_panic() {
+        assert!("2019".parse::<Date>().is_err());
+        assert!("é2019071".parse::<Date>().is_err());
+        assert!("".parse::<Date>().is_err());
+    }
+
+    #[test]
+    fn test_read_field_shorter_than_its_type() {
+        let encoding = UnicodeLossy;
+        for field_type in [FieldType::Integer, FieldType::Double, FieldType::Currency] {
+            let field_info = create_temp_field_info(field_type, 2);
+            let error = FieldValue::read_from::<Cursor<Vec<u8>>, _>(
+                &[0u8, 0u8],
+                &mut None,
+                &field_info,
+                &encoding,
+                &ReadingOptions::default(),
+                &mut vec![],
+            )
+            .unwrap_err();
+            assert!(matches!(
+                error,
+                ErrorKind::FieldLengthTooSmall { actual: 2, .. }
+            ));
+        }
+
+        let field_info = create_temp_field_info(FieldType::Logical, 0);
+        let value = FieldValue::read_from::<Cursor<Vec<u8>>, _>(
+            &[],
+            &mut None,
+            &field_info,
+            &encoding,
+            &ReadingOptions::default(),
+            &mut vec![],
+        )
+        .unwrap();
+        assert_eq!(value, FieldValue::Logical(None));
+    }
+
+    #[test]
+    fn test_time_from_negative_word() {
+        let _ = Time::from_word(i32::MIN);
+        let _ = Date::julian_day_number_to_gregorian_date(i32::MAX);
+        let _ = Date::julian_day_number_to_gregorian_date(i32::MIN);
+    }
+
     #[test]
     fn test_to_unix_timestamp() {
         let datetime = DateTime::new(Date::new(1, 1, 1970), Time::new(1, 1, 1));
         assert_eq!(datetime.to_unix_timestamp(), 3661);
     }
+
+    #[test]
+    fn test_display_with() {
+        let dbf = DisplayStyle::default();
+        let iso = DisplayStyle::default()
+            .date_format(DateFormat::Iso)
+            .null_text("NULL");
+        let mut numeric_info = create_temp_field_info(FieldType::Numeric, 10);
+        numeric_info.num_decimal_places = 2;
+        let mut float_info = create_temp_field_info(FieldType::Float, 10);
+        float_info.num_decimal_places = 3;
+        let info = create_temp_field_info(FieldType::Character, 10);
+
+        let date = Date::new(5, 3, 2021);
+        let date_time = DateTime::new(date, Time::new(8, 4, 9));
+        let cases = [
+            (
+                &info,
+                FieldValue::Character(Some("text".to_string())),
+                "text",
+                "text",
+            ),
+            (&info, FieldValue::Character(None), "", "NULL"),
+            (
+                &numeric_info,
+                FieldValue::Numeric(Some(1.5)),
+                "1.50",
+                "1.50",
+            ),
+            (&numeric_info, FieldValue::Numeric(None), "", "NULL"),
+            (&float_info, FieldValue::Float(Some(2.25)), "2.250", "2.250"),
+            (&float_info, FieldValue::Float(None), "", "NULL"),
+            (&info, FieldValue::Logical(Some(true)), "T", "T"),
+            (&info, FieldValue::Logical(Some(false)), "F", "F"),
+            (&info, FieldValue::Logical(None), "", "NULL"),
+            (
+                &info,
+                FieldValue::Date(Some(date)),
+                "20210305",
+                "2021-03-05",
+            ),
+            (&info, FieldValue::Date(None), "", "NULL"),
+            (&info, FieldValue::Integer(-42), "-42", "-42"),
+            (&info, FieldValue::Double(0.5), "0.5", "0.5"),
+            (&info, FieldValue::Currency(3.5), "3.5000", "3.5000"),
+            (
+                &info,
+                FieldValue::DateTime(date_time),
+                "20210305 08:04:09",
+                "2021-03-05T08:04:09",
+            ),
+            (&info, FieldValue::Memo("memo".to_string()), "memo", "memo"),
+        ];
+        for (info, value, expected_dbf, expected_iso) in cases {
+            assert_eq!(value.display_with(info, &dbf), expected_dbf, "{:?}", value);
+            assert_eq!(value.display_with(info, &iso), expected_iso, "{:?}", value);
+            assert_eq!(value.is_none(), expected_dbf.is_empty(), "{:?}", value);
+        }
+
+        // Numeric values are rendered as they are written, without the padding
+        let mut out = Vec::new();
+        FieldValue::Numeric(Some(12.345))
+            .write_as(&numeric_info, &UnicodeLossy, &mut out)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap().trim_start(),
+            FieldValue::Numeric(Some(12.345)).display_with(&numeric_info, &dbf)
+        );
+    }
+
+    #[test]
+    fn read_text_stored_in_memo_field() {
+        let info = create_temp_field_info(FieldType::Memo, 10);
+        let read = |options: &ReadingOptions| {
+            FieldValue::read_from::<Cursor<Vec<u8>>, _>(
+                b"HELLO     ",
+                &mut None,
+                &info,
+                &UnicodeLossy,
+                options,
+                &mut vec![],
+            )
+        };
+
+        let strict = read(&ReadingOptions::default());
+        assert!(matches!(
+            strict,
+            Err(ErrorKind::InvalidFieldContent {
+                field_type: FieldType::Memo,
+                ..
+            })
+        ));
+        let lenient = read(&ReadingOptions::default().lenient_memo(true)).unwrap();
+        assert_eq!(lenient, FieldValue::Memo("HELLO".to_string()));
+    }
+
+    #[test]
+    fn read_memo_index_of_the_other_kind() {
+        let text_info = create_temp_field_info(FieldType::Memo, 10);
+        let binary_info = create_temp_field_info(FieldType::Memo, 4);
+        let read = |bytes: &[u8], info: &FieldInfo, next_block: Option<u32>| {
+            read_memo_index(bytes, info, &UnicodeLossy, next_block)
+        };
+
+        // Text index in a text field
+        assert_eq!(read(b"         8", &text_info, Some(11)).unwrap(), Some(8));
+        assert_eq!(read(b"          ", &text_info, Some(11)).unwrap(), None);
+        // Binary index padded with zeros in a text field
+        let binary_in_text = [8u8, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            read(&binary_in_text, &text_info, Some(11)).unwrap(),
+            Some(8)
+        );
+        assert_eq!(read(&binary_in_text, &text_info, None).unwrap(), Some(8));
+        // Binary index in a binary field
+        assert_eq!(
+            read(&[8, 0, 0, 0], &binary_info, Some(11)).unwrap(),
+            Some(8)
+        );
+        assert_eq!(read(&[8, 0, 0, 0], &binary_info, None).unwrap(), Some(8));
+        // Text index in a binary field, only detected when the memo header is known
+        assert_eq!(read(b"   8", &binary_info, Some(11)).unwrap(), Some(8));
+        assert_eq!(read(b"    ", &binary_info, Some(11)).unwrap(), None);
+        assert_eq!(
+            read(b"   8", &binary_info, None).unwrap(),
+            Some(u32::from_le_bytes(*b"   8"))
+        );
+
+        // Indices of neither kind
+        assert!(matches!(
+            read(b"      12.5", &text_info, Some(11)),
+            Err(ErrorKind::ParseIntError(_))
+        ));
+        assert!(matches!(
+            read(b"        42", &text_info, Some(11)),
+            Err(ErrorKind::InvalidMemoIndex {
+                index: 42,
+                next_available_block_index: 11
+            })
+        ));
+        assert!(matches!(
+            read(&[42, 0, 0, 0], &binary_info, Some(11)),
+            Err(ErrorKind::InvalidMemoIndex {
+                index: 42,
+                next_available_block_index: 11
+            })
+        ));
+        assert!(matches!(
+            read(b"  42", &binary_info, Some(11)),
+            Err(ErrorKind::InvalidMemoIndex { .. })
+        ));
+    }
+
+    #[test]
+    fn read_memo_after_the_last_block() {
+        let mut memo = vec![0u8; 4 * 64];
+        // Next available block and block size, in big endian
+        memo[..4].copy_from_slice(&4u32.to_be_bytes());
+        memo[6..8].copy_from_slice(&64u16.to_be_bytes());
+        memo[64 + 7] = 2;
+        memo[64 + 8..64 + 10].copy_from_slice(b"hi");
+        let mut reader =
+            MemoReader::new(crate::memo::MemoFileType::FoxBaseMemo, Cursor::new(memo)).unwrap();
+        assert_eq!(reader.next_available_block_index(), Some(4));
+        assert_eq!(
+            reader.read_text_at(1, &UnicodeLossy, usize::MAX).unwrap(),
+            "hi"
+        );
+        for index in [4, 1000] {
+            assert!(matches!(
+                reader.read_text_at(index, &UnicodeLossy, usize::MAX),
+                Err(ErrorKind::InvalidMemoIndex {
+                    next_available_block_index: 4,
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn read_memo_with_absurd_length() {
+        let mut memo = vec![0u8; 2 * 64];
+        memo[..4].copy_from_slice(&2u32.to_be_bytes());
+        memo[6..8].copy_from_slice(&64u16.to_be_bytes());
+        memo[64 + 4..64 + 8].copy_from_slice(&u32::MAX.to_be_bytes());
+        let mut reader =
+            MemoReader::new(crate::memo::MemoFileType::FoxBaseMemo, Cursor::new(memo)).unwrap();
+        assert!(matches!(
+            reader.read_data_at(1, usize::MAX),
+            Err(ErrorKind::CorruptMemo {
+                index: 1,
+                length: 0xFFFF_FFFF,
+                available: 56,
+            })
+        ));
+        assert!(matches!(
+            reader.read_data_at_into(1, &mut std::io::sink()),
+            Err(ErrorKind::CorruptMemo { .. })
+        ));
+    }
+
+    #[test]
+    fn read_dbase4_memo_filling_its_block() {
+        // The length of the block header counts its 8 bytes
+        let text = [b'a'; 504];
+        let mut memo = vec![0u8; 512];
+        memo[..4].copy_from_slice(&2u32.to_le_bytes());
+        memo.extend_from_slice(&0x0008_FFFFu32.to_le_bytes());
+        memo.extend_from_slice(&512u32.to_le_bytes());
+        memo.extend_from_slice(&text);
+        let mut reader =
+            MemoReader::new(crate::memo::MemoFileType::DbaseMemo4, Cursor::new(memo)).unwrap();
+        assert_eq!(reader.read_data_at(1, usize::MAX).unwrap(), &text[..]);
+        let mut streamed = vec![];
+        reader.read_data_at_into(1, &mut streamed).unwrap();
+        assert_eq!(streamed, text);
+
+        let mut memo = vec![0u8; 1024];
+        memo[..4].copy_from_slice(&2u32.to_le_bytes());
+        memo[512..516].copy_from_slice(&0x0008_FFFFu32.to_le_bytes());
+        memo[516..520].copy_from_slice(&4u32.to_le_bytes());
+        let mut reader =
+            MemoReader::new(crate::memo::MemoFileType::DbaseMemo4, Cursor::new(memo)).unwrap();
+        assert!(matches!(
+            reader.read_data_at(1, usize::MAX),
+            Err(ErrorKind::CorruptMemo {
+                index: 1,
+                length: 4,
+                available: 512,
+            })
+        ));
+    }
+
+    #[test]
+    fn read_memo_larger_than_the_max_size() {
+        let text = [&[b'a'; 8190][..], &[0u8; 10], &[b'b'; 100]].concat();
+        let mut memo = vec![0u8; 64];
+        memo[..4].copy_from_slice(&0u32.to_be_bytes());
+        memo[6..8].copy_from_slice(&64u16.to_be_bytes());
+        memo.extend_from_slice(&1u32.to_be_bytes());
+        memo.extend_from_slice(&(text.len() as u32 + 50).to_be_bytes());
+        memo.extend_from_slice(&text);
+        memo.extend_from_slice(&[0u8; 50]);
+        let mut reader =
+            MemoReader::new(crate::memo::MemoFileType::FoxBaseMemo, Cursor::new(memo)).unwrap();
+
+        assert!(matches!(
+            reader.read_data_at(1, 1000),
+            Err(ErrorKind::MemoTooLarge {
+                index: 1,
+                length: 8350,
+                max_size: 1000,
+            })
+        ));
+        assert_eq!(reader.read_data_at(1, usize::MAX).unwrap(), &text[..]);
+
+        let mut streamed = vec![];
+        let written = reader.read_data_at_into(1, &mut streamed).unwrap();
+        assert_eq!(written, text.len() as u64);
+        assert_eq!(streamed, text);
+    }
+
+    #[test]
+    fn read_character_with_invalid_utf8() {
+        let field_info = create_temp_field_info(FieldType::Character, 8);
+        // 'é' then a byte that cannot start a character
+        let bytes = b" \xC3\xA9a\xFFb  ";
+        let mut invalid_offsets = vec![];
+        let strict = read_character_str(
+            bytes,
+            &field_info,
+            &crate::Unicode,
+            &ReadingOptions::default(),
+            &mut invalid_offsets,
+        );
+        assert!(matches!(
+            strict,
+            Err(ErrorKind::StringDecodeError(
+                crate::DecodeError::InvalidBytes { offset: 4, .. }
+            ))
+        ));
+
+        let lossy = read_character_str(
+            bytes,
+            &field_info,
+            &crate::Unicode,
+            &ReadingOptions::default().character_decode(DecodeMode::Lossy),
+            &mut invalid_offsets,
+        )
+        .unwrap();
+        assert_eq!(lossy.as_deref(), Some("éa\u{FFFD}b"));
+        assert_eq!(invalid_offsets, vec![4]);
+    }
+
+    #[test]
+    fn display_field_values() {
+        let date = Date::new(1, 3, 2024);
+        let cases = [
+            (FieldValue::Character(Some("Yoshi".to_string())), "Yoshi"),
+            (FieldValue::Character(None), ""),
+            (FieldValue::CharacterBinary(vec![0x00, 0xAB]), "00AB"),
+            (FieldValue::Numeric(Some(12.5)), "12.5"),
+            (FieldValue::Numeric(None), ""),
+            (FieldValue::Float(Some(0.1)), "0.1"),
+            (FieldValue::Logical(Some(true)), "T"),
+            (FieldValue::Logical(Some(false)), "F"),
+            (FieldValue::Logical(None), "?"),
+            (FieldValue::Date(Some(date)), "2024-03-01"),
+            (FieldValue::Date(None), ""),
+            (FieldValue::Integer(-7), "-7"),
+            (FieldValue::Double(2.25), "2.25"),
+            (FieldValue::Currency(3.5), "3.5000"),
+            (
+                FieldValue::DateTime(DateTime::new(date, Time::new(12, 30, 5))),
+                "2024-03-01T12:30:05",
+            ),
+            (
+                FieldValue::Memo("line 1\nline 2".to_string()),
+                "line 1\nline 2",
+            ),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(value.to_string(), expected, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn field_value_accessors() {
+        let text = FieldValue::Character(Some("Yoshi".to_string()));
+        assert_eq!(text.as_str(), Some("Yoshi"));
+        assert_eq!(FieldValue::Memo("memo".to_string()).as_str(), Some("memo"));
+        assert_eq!(FieldValue::Character(None).as_str(), None);
+        assert_eq!(text.as_f64(), None);
+
+        assert_eq!(FieldValue::Numeric(Some(1.5)).as_f64(), Some(1.5));
+        assert_eq!(FieldValue::Float(Some(0.1)).as_f64(), Some(0.1));
+        assert_eq!(FieldValue::Integer(3).as_f64(), Some(3.0));
+        assert_eq!(FieldValue::Double(-2.0).as_f64(), Some(-2.0));
+        assert_eq!(FieldValue::Currency(4.25).as_f64(), Some(4.25));
+        assert_eq!(FieldValue::Numeric(None).as_f64(), None);
+        assert_eq!(FieldValue::Logical(Some(true)).as_f64(), None);
+
+        assert_eq!(FieldValue::Logical(Some(false)).as_bool(), Some(false));
+        assert_eq!(FieldValue::Logical(None).as_bool(), None);
+        assert_eq!(text.as_bool(), None);
+
+        let date = Date::new(1, 3, 2024);
+        assert_eq!(FieldValue::Date(Some(date)).as_date(), Some(date));
+        assert_eq!(FieldValue::Date(None).as_date(), None);
+        assert_eq!(text.as_date(), None);
+    }
+
+    #[test]
+    fn encoded_size_of_each_variant() {
+        let encoding = UnicodeLossy;
+        let size = |value: FieldValue, field_type, len| {
+            let info = create_temp_field_info(field_type, len);
+            value.encoded_size(&info, &encoding)
+        };
+
+        let text = FieldValue::Character(Some("Crème".to_string()));
+        assert_eq!(size(text.clone(), FieldType::Character, 10).unwrap(), 6);
+        assert_eq!(
+            size(FieldValue::Character(None), FieldType::Character, 10).unwrap(),
+            0
+        );
+        let binary = FieldValue::CharacterBinary(vec![0, 1, 2]);
+        assert_eq!(size(binary, FieldType::Character, 10).unwrap(), 3);
+
+        // Numbers are right-justified on the whole field
+        assert_eq!(
+            size(FieldValue::Numeric(Some(1.5)), FieldType::Numeric, 5).unwrap(),
+            5
+        );
+        assert_eq!(
+            size(FieldValue::Numeric(Some(123456.0)), FieldType::Numeric, 4).unwrap(),
+            6
+        );
+        assert_eq!(
+            size(FieldValue::Numeric(None), FieldType::Numeric, 5).unwrap(),
+            0
+        );
+        assert_eq!(
+            size(FieldValue::Float(Some(1234.0)), FieldType::Float, 3).unwrap(),
+            4
+        );
+
+        assert_eq!(
+            size(FieldValue::Logical(Some(true)), FieldType::Logical, 1).unwrap(),
+            1
+        );
+        assert_eq!(size(FieldValue::Date(None), FieldType::Date, 8).unwrap(), 8);
+        let date = FieldValue::Date(Some(Date::new(1, 3, 2024)));
+        assert_eq!(size(date, FieldType::Date, 8).unwrap(), 8);
+        assert_eq!(
+            size(FieldValue::Integer(7), FieldType::Integer, 4).unwrap(),
+            4
+        );
+        assert_eq!(
+            size(FieldValue::Double(1.0), FieldType::Double, 8).unwrap(),
+            8
+        );
+        assert_eq!(
+            size(FieldValue::Currency(1.0), FieldType::Currency, 8).unwrap(),
+            8
+        );
+        let datetime = FieldValue::DateTime(DateTime::EMPTY);
+        assert_eq!(size(datetime, FieldType::DateTime, 8).unwrap(), 8);
+        let memo = FieldValue::Memo("a long text".to_string());
+        assert_eq!(size(memo, FieldType::Memo, 10).unwrap(), 10);
+
+        assert!(matches!(
+            size(text, FieldType::Numeric, 10),
+            Err(ErrorKind::IncompatibleType)
+        ));
+    }
+
+    #[test]
+    fn fits_compares_with_the_field_length() {
+        let info = create_temp_field_info(FieldType::Character, 5);
+        assert!(FieldValue::Character(Some("Yoshi".to_string())).fits(&info, &UnicodeLossy));
+        assert!(!FieldValue::Character(Some("Crème".to_string())).fits(&info, &UnicodeLossy));
+        assert!(!FieldValue::Logical(Some(true)).fits(&info, &UnicodeLossy));
+
+        let info = create_temp_field_info(FieldType::Numeric, 4);
+        assert!(FieldValue::Numeric(Some(12.0)).fits(&info, &UnicodeLossy));
+        assert!(!FieldValue::Numeric(Some(12345.0)).fits(&info, &UnicodeLossy));
+    }
+
+    #[cfg(feature = "encoding_rs")]
+    #[test]
+    fn encoded_size_with_a_multibyte_encoding() {
+        let gbk = crate::encoding::EncodingRs::from(encoding_rs::GBK);
+        let info = create_temp_field_info(FieldType::Character, 4);
+        let text = FieldValue::Character(Some("测试".to_string()));
+        // 2 bytes per character in GBK, 3 in UTF-8
+        assert_eq!(text.encoded_size(&info, &gbk).unwrap(), 4);
+        assert!(text.fits(&info, &gbk));
+        assert_eq!(text.encoded_size(&info, &UnicodeLossy).unwrap(), 6);
+        assert!(!text.fits(&info, &UnicodeLossy));
+    }
 }
@@ -1,30 +1,50 @@
 use crate::encoding::DynEncoding;
-use crate::field::{DeletionFlag, FieldsInfo, DELETION_FLAG_SIZE};
-use crate::header::Header;
-use crate::memo::MemoReader;
-use crate::reading::{ReadingOptions, BACKLINK_SIZE, TERMINATOR_VALUE};
-use crate::writing::{write_header_parts, WritableAsDbaseField};
+use crate::field::types::{read_character_str, read_memo_index, FieldType};
+use crate::field::{
+    field_data_buffer, names_match, DeletionFlag, FieldsInfo, SchemaWarning, DELETION_FLAG_SIZE,
+    FILE_TERMINATOR,
+};
+use crate::header::{CodePageMark, Header, TableFlags};
+use crate::inner::Inner;
+use crate::memo::{MemoReader, MemoWriter};
+use crate::preview::{PreviewValues, TablePreview};
+use crate::reading::{ReadingOptions, RecordCounts, RecordParser, BACKLINK_SIZE};
+use crate::schema::field_differences;
+use crate::writing::{encode_backlink, write_header_parts, NamedValues, WritableAsDbaseField};
 use crate::ErrorKind::UnsupportedCodePage;
 use crate::{
-    Error, ErrorKind, FieldConversionError, FieldIOError, FieldInfo, FieldIterator, FieldValue,
-    FieldWriter, ReadableRecord, TableInfo, WritableRecord,
+    AutoIncrementPolicy, DisplayStyle, Encoding, Error, ErrorKind, FieldConversionError,
+    FieldIOError, FieldInfo, FieldValue, FieldWriter, FromFieldValue, ReadableRecord, TableInfo,
+    WritableRecord,
 };
-use byteorder::ReadBytesExt;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
-use std::io::{BufReader, BufWriter, Cursor, Read, Seek, SeekFrom, Write};
+#[cfg(feature = "std-fs")]
+use std::io::{BufReader, BufWriter};
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+#[cfg(feature = "std-fs")]
 use std::path::Path;
+use std::path::PathBuf;
 
 // Workaround the absence of File::try_clone with WASM/WASI without penalizing the other platforms
-#[cfg(target_family = "wasm")]
+#[cfg(all(feature = "std-fs", target_family = "wasm"))]
 type SharedFile = std::sync::Arc<std::fs::File>;
-#[cfg(not(target_family = "wasm"))]
+#[cfg(all(feature = "std-fs", not(target_family = "wasm")))]
 type SharedFile = std::fs::File;
 
+#[cfg(feature = "std-fs")]
 pub struct BufReadWriteFile {
     input: BufReader<SharedFile>,
     output: BufWriter<SharedFile>,
+    /// Whether the last operation was a write,
+    /// both halves share the same file cursor so switching between
+    /// reading and writing requires to re-sync them.
+    writing: bool,
 }
 
+#[cfg(feature = "std-fs")]
 impl BufReadWriteFile {
     fn new(file: SharedFile) -> std::io::Result<Self> {
         #[cfg(target_family = "wasm")]
@@ -34,18 +54,47 @@ impl BufReadWriteFile {
 
         let input = BufReader::new(file_);
         let output = BufWriter::new(file);
-        Ok(Self { input, output })
+        Ok(Self {
+            input,
+            output,
+            writing: false,
+        })
+    }
+
+    /// Drops the read buffer, placing the file cursor
+    /// where the reader logically is, so that writes happen there
+    fn start_writing(&mut self) -> std::io::Result<()> {
+        if !self.writing {
+            let position = self.input.stream_position()?;
+            self.input.seek(SeekFrom::Start(position))?;
+            self.writing = true;
+        }
+        Ok(())
+    }
+
+    /// Flushes the pending writes so that reads see them,
+    /// the read buffer was already dropped when writing started
+    fn start_reading(&mut self) -> std::io::Result<()> {
+        if self.writing {
+            self.output.flush()?;
+            self.writing = false;
+        }
+        Ok(())
     }
 }
 
+#[cfg(feature = "std-fs")]
 impl Read for BufReadWriteFile {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.start_reading()?;
         self.input.read(buf)
     }
 }
 
+#[cfg(feature = "std-fs")]
 impl Write for BufReadWriteFile {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.start_writing()?;
         self.output.write(buf)
     }
 
@@ -54,13 +103,79 @@ impl Write for BufReadWriteFile {
     }
 }
 
+/// Sources whose length can be reduced, needed by [File::truncate_records]
+pub trait Truncate {
+    /// Shortens the source to `len` bytes
+    fn truncate(&mut self, len: u64) -> std::io::Result<()>;
+}
+
+#[cfg(feature = "std-fs")]
+impl Truncate for BufReadWriteFile {
+    fn truncate(&mut self, len: u64) -> std::io::Result<()> {
+        self.output.flush()?;
+        self.output.get_ref().set_len(len)?;
+        // Seeking the BufReader drops its buffer, which may contain removed bytes
+        let position = self.input.stream_position()?.min(len);
+        self.seek(SeekFrom::Start(position))?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std-fs")]
+impl Truncate for std::fs::File {
+    fn truncate(&mut self, len: u64) -> std::io::Result<()> {
+        self.set_len(len)
+    }
+}
+
+impl<T: Truncate + ?Sized> Truncate for &mut T {
+    fn truncate(&mut self, len: u64) -> std::io::Result<()> {
+        (**self).truncate(len)
+    }
+}
+
+impl Truncate for Cursor<Vec<u8>> {
+    fn truncate(&mut self, len: u64) -> std::io::Result<()> {
+        let len = usize::try_from(len)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidInput, error))?;
+        self.get_mut().truncate(len);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std-fs")]
 impl Seek for BufReadWriteFile {
     fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        // Relative seeks are relative to the position of the half
+        // that was used last, convert them to an absolute position
+        let pos = match pos {
+            SeekFrom::Current(offset) => {
+                let current = if self.writing {
+                    self.output.stream_position()?
+                } else {
+                    self.input.stream_position()?
+                };
+                let target = current.checked_add_signed(offset).ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "invalid seek to a negative or overflowing position",
+                    )
+                })?;
+                SeekFrom::Start(target)
+            }
+            pos => pos,
+        };
+        self.output.flush()?;
+        self.writing = false;
         self.output.seek(pos)?;
+        // Seeking the BufReader drops its buffer, which may be stale
         self.input.seek(pos)
     }
 }
 
+/// Function writing the header of a [File]
+type SyncFn<T> = fn(&mut File<T>) -> std::io::Result<()>;
+
 /// Index to a field in a record
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug)]
 pub struct FieldIndex(pub usize);
@@ -113,11 +228,15 @@ where
 {
     fn seek_to_beginning(&mut self) -> Result<u64, FieldIOError> {
         let field_info = &self.file.fields_info[self.field_index.0];
+        let position = self.position_in_source();
 
         self.file
             .inner
-            .seek(SeekFrom::Start(self.position_in_source()))
-            .map_err(|e| FieldIOError::new(ErrorKind::IoError(e), Some(field_info.clone())))
+            .seek(SeekFrom::Start(position))
+            .map_err(|e| {
+                FieldIOError::new(ErrorKind::IoError(e), Some(field_info.clone()))
+                    .at_field_index(self.field_index.0)
+            })
     }
 }
 
@@ -141,27 +260,76 @@ where
             &mut self.file.memo_reader,
             field_info,
             &self.file.encoding,
-            self.file.options.character_trim,
+            &self.file.options,
+            &mut vec![],
         )
         .map_err(|e| {
             Error::new(
-                FieldIOError::new(e, Some(field_info.clone())),
+                FieldIOError::new(e, Some(field_info.clone())).at_field_index(self.field_index.0),
                 self.record_index.0,
             )
         })
     }
 
+    /// Reads the index of the memo block stored in the field, which must be a Memo field,
+    /// without reading the memo file.
+    ///
+    /// Returns `None` when the field is empty.
+    /// The text can then be read with [File::read_memo_text].
+    pub fn read_memo_index(&mut self) -> Result<Option<u32>, Error> {
+        self.file
+            .ensure_record_has_been_read_into_buffer(self.record_index)?;
+
+        let field_info = &self.file.fields_info[self.field_index.0];
+        let to_error = |kind| {
+            Error::new(
+                FieldIOError::new(kind, Some(field_info.clone()))
+                    .at_field_index(self.field_index.0),
+                self.record_index.0,
+            )
+        };
+        if field_info.field_type != FieldType::Memo {
+            return Err(to_error(ErrorKind::IncompatibleType));
+        }
+
+        let start_pos = self.position_in_record();
+        let field_bytes = &self.file.record_data_buffer.get_ref()
+            [start_pos..start_pos + field_info.field_length as usize];
+        let next_available_block_index = self
+            .file
+            .memo_reader
+            .as_ref()
+            .and_then(MemoReader::next_available_block_index);
+        read_memo_index(
+            field_bytes,
+            field_info,
+            &self.file.encoding,
+            next_available_block_index,
+        )
+        .map_err(to_error)
+    }
+
+    /// Reads the value of the field, which must be a Character field,
+    /// without copying its text when the encoding allows it.
+    ///
+    /// Returns `None` when the field is empty.
+    pub fn read_str(&mut self) -> Result<Option<Cow<'_, str>>, Error> {
+        self.file
+            .read_character_str(self.record_index, self.field_index)
+    }
+
     /// Reads and returns the value converted to the requested type
     pub fn read_as<ValueType>(&mut self) -> Result<ValueType, Error>
     where
-        ValueType: TryFrom<FieldValue, Error = FieldConversionError>,
+        ValueType: FromFieldValue,
     {
         let value = self.read()?;
 
-        let converted_value = ValueType::try_from(value).map_err(|e| {
-            let field_info = &self.file.fields_info[self.field_index.0];
+        let field_info = &self.file.fields_info[self.field_index.0];
+        let converted_value = ValueType::from_field(value, field_info).map_err(|e| {
             Error::new(
-                FieldIOError::new(ErrorKind::BadConversion(e), Some(field_info.clone())),
+                FieldIOError::new(ErrorKind::BadConversion(e), Some(field_info.clone()))
+                    .at_field_index(self.field_index.0),
                 self.record_index.0,
             )
         })?;
@@ -179,6 +347,7 @@ where
     where
         ValueType: WritableAsDbaseField,
     {
+        self.file.mark_dirty();
         self.file.file_position = self
             .seek_to_beginning()
             .map_err(|e| Error::new(e, self.record_index.0))?;
@@ -198,7 +367,8 @@ where
             .write_as(field_info, &self.file.encoding, &mut cursor)
             .map_err(|e| {
                 Error::new(
-                    FieldIOError::new(e, Some(field_info.clone())),
+                    FieldIOError::new(e, Some(field_info.clone()))
+                        .at_field_index(self.field_index.0),
                     self.record_index.0,
                 )
             })?;
@@ -207,7 +377,8 @@ where
 
         self.file.inner.write_all(buffer).map_err(|e| {
             Error::new(
-                FieldIOError::new(ErrorKind::IoError(e), Some(field_info.clone())),
+                FieldIOError::new(ErrorKind::IoError(e), Some(field_info.clone()))
+                    .at_field_index(self.field_index.0),
                 self.record_index.0,
             )
         })?;
@@ -260,9 +431,10 @@ where
     T: Seek,
 {
     pub fn seek_before_deletion_flag(&mut self) -> Result<u64, FieldIOError> {
+        let position = self.position_in_source();
         self.file
             .inner
-            .seek(SeekFrom::Start(self.position_in_source()))
+            .seek(SeekFrom::Start(position))
             .map_err(|e| FieldIOError::new(ErrorKind::IoError(e), None))
     }
 }
@@ -275,14 +447,51 @@ where
     ///
     /// - true -> the record is marked as deleted
     /// - false -> the record is **not** marked as deleted
+    ///
+    /// With [ReadingOptions::strict_deletion_flag], a flag that is neither
+    /// a space nor `*` returns [ErrorKind::InvalidDeletionFlag].
     pub fn is_deleted(&mut self) -> Result<bool, Error> {
         self.file
             .ensure_record_has_been_read_into_buffer(self.index)?;
-        let deletion_flag = DeletionFlag::from_byte(self.file.record_data_buffer.get_ref()[0]);
+        let flag_byte = self.file.record_data_buffer.get_ref()[0];
+        self.file
+            .warn_unexpected_deletion_flag(self.index.0, flag_byte);
+        let parser = RecordParser {
+            fields_info: &self.file.fields_info.inner,
+            encoding: &self.file.encoding,
+            options: &self.file.options,
+            selection: None,
+        };
+        let deletion_flag = parser.deletion_flag(flag_byte).map_err(|kind| Error {
+            record_num: self.index.0,
+            field: None,
+            field_index: None,
+            kind,
+        })?;
 
         Ok(deletion_flag == DeletionFlag::Deleted)
     }
 
+    /// Returns the bytes of a field without the spaces trimmed
+    /// by [ReadingOptions::character_trim], the bytes a Character value is decoded from
+    #[cfg(feature = "datafusion")]
+    pub(crate) fn trimmed_field_bytes(&mut self, field_index: FieldIndex) -> Result<&[u8], Error> {
+        self.file
+            .ensure_record_has_been_read_into_buffer(self.index)?;
+        let field_info = &self.file.fields_info[field_index.0];
+        let start_pos = self
+            .file
+            .fields_info
+            .field_position_in_record(field_index.0)
+            .expect("internal error: invalid field index");
+        let field_bytes = &self.file.record_data_buffer.get_ref()
+            [start_pos..start_pos + field_info.field_length as usize];
+        Ok(crate::field::types::trim_field_data(
+            field_bytes,
+            self.file.options.character_trim,
+        ))
+    }
+
     /// reads a field from the record
     ///
     /// Shortcut for `.field(index).unwrap().read().unwrap();`
@@ -299,7 +508,7 @@ where
     /// Shortcut for `.field(index).unwrap().read_as().unwrap();`
     pub fn read_field_as<ValueType>(&mut self, field_index: FieldIndex) -> Result<ValueType, Error>
     where
-        ValueType: TryFrom<FieldValue, Error = FieldConversionError>,
+        ValueType: FromFieldValue,
     {
         let record_index = self.index.0;
         let mut field = self
@@ -308,6 +517,17 @@ where
         field.read_as()
     }
 
+    /// reads a Character field from the record, the returned string borrows
+    /// the record buffer when the encoding allows it.
+    ///
+    /// Shortcut for `.field(index).unwrap().read_str().unwrap();`
+    pub fn read_field_str(
+        &mut self,
+        field_index: FieldIndex,
+    ) -> Result<Option<Cow<'_, str>>, Error> {
+        self.file.read_character_str(self.index, field_index)
+    }
+
     /// Reads the record
     pub fn read(&mut self) -> Result<crate::Record, Error> {
         self.read_as()
@@ -323,16 +543,19 @@ where
         self.file
             .record_data_buffer
             .set_position(DELETION_FLAG_SIZE as u64);
-        let mut field_iterator = FieldIterator {
-            source: &mut self.file.record_data_buffer,
-            fields_info: self.file.fields_info.iter().peekable(),
-            memo_reader: &mut self.file.memo_reader,
-            field_data_buffer: &mut self.file.field_data_buffer,
+        let parser = RecordParser {
+            fields_info: &self.file.fields_info.inner,
             encoding: &self.file.encoding,
-            options: self.file.options,
+            options: &self.file.options,
+            selection: None,
         };
-
-        R::read_using(&mut field_iterator).map_err(|error| Error::new(error, self.index.0))
+        let (record, _) = parser.read_fields(
+            &mut self.file.record_data_buffer,
+            &mut self.file.memo_reader,
+            &mut self.file.field_data_buffer,
+            R::read_using,
+        );
+        record.map_err(|error| Error::new(error, self.index.0))
     }
 }
 
@@ -362,8 +585,9 @@ where
     /// pointed by `self`.
     pub fn write<R>(&mut self, record: &R) -> Result<(), Error>
     where
-        R: WritableRecord,
+        R: WritableRecord + ?Sized,
     {
+        self.file.mark_dirty();
         self.file.record_data_buffer.get_mut().fill(0);
         self.file.record_data_buffer.get_mut()[0] = DeletionFlag::NotDeleted.to_byte();
         self.file.record_data_buffer.set_position(1);
@@ -373,6 +597,17 @@ where
             fields_info: self.file.fields_info.iter().peekable(),
             field_buffer: &mut Cursor::new(&mut self.file.field_data_buffer),
             encoding: &self.file.encoding,
+            wrote_non_ascii: false,
+            precheck: self.file.precheck,
+            coerce_types: self.file.coerce_types,
+            autoincrement: None,
+            num_fields: self.file.fields_info.len(),
+            truncated_values: vec![],
+            memo_writer: self
+                .file
+                .memo_reader
+                .as_mut()
+                .map(|memo| memo as &mut dyn MemoWriter),
         };
 
         record
@@ -382,9 +617,21 @@ where
         self.seek_before_deletion_flag()
             .map_err(|error| Error::new(error, self.index.0))?;
 
+        // The bytes of the hidden fields (like Visual FoxPro system columns)
+        // are skipped to keep the ones of the file
+        let record_bytes = self.file.record_data_buffer.get_ref();
+        let mut start = 0;
+        for hidden in self.file.fields_info.hidden_ranges() {
+            self.file
+                .inner
+                .write_all(&record_bytes[start..hidden.start])
+                .and_then(|_| self.file.inner.seek(SeekFrom::Current(hidden.len() as i64)))
+                .map_err(|error| Error::io_error(error, self.index.0))?;
+            start = hidden.end;
+        }
         self.file
             .inner
-            .write_all(self.file.record_data_buffer.get_ref())
+            .write_all(&record_bytes[start..])
             .map_err(|error| Error::io_error(error, self.index.0))?;
 
         // We don't need to update the file's inner position as we re-wrote the whole record
@@ -401,6 +648,8 @@ where
 pub struct FileRecordIterator<'a, T> {
     file: &'a mut File<T>,
     current_record: RecordIndex,
+    /// Index one past the last record to iterate over
+    end: RecordIndex,
 }
 
 impl<'a, T> FileRecordIterator<'a, T>
@@ -410,12 +659,169 @@ where
     // To implement iterator we need the Iterator trait to make use of GATs
     // which is not the case, to iteration will have to use the while let Some() pattern
     pub fn next<'s>(&'s mut self) -> Option<RecordRef<'s, T>> {
+        if self.current_record >= self.end {
+            return None;
+        }
         let record_ref = self.file.record(self.current_record.0);
         if let Some(_) = record_ref {
             self.current_record.0 += 1
         }
         record_ref
     }
+
+    /// Returns the index of the record that the next call to [Self::next] returns
+    ///
+    /// Useful to resume the iteration later with [File::records_from].
+    pub fn index(&self) -> RecordIndex {
+        self.current_record
+    }
+}
+
+/// What changed in a [File] reloaded with [File::reload]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ReloadReport {
+    /// Number of records before the reload
+    pub previous_num_records: usize,
+    /// Number of records in the header read by the reload
+    pub num_records: usize,
+}
+
+impl ReloadReport {
+    /// Returns the number of records added since the file was opened or last reloaded
+    pub fn new_records(&self) -> usize {
+        self.num_records.saturating_sub(self.previous_num_records)
+    }
+}
+
+/// Something unexpected found while reading or modifying a table, that did not prevent it
+/// because a fallback was used.
+///
+/// They are collected by [Reader](crate::Reader) and [File], and returned by their
+/// `take_warnings` method: no warnings means that the table was read as it is written.
+/// Warnings that can happen for many records are counted in a single warning.
+///
+/// The oddities of the fields definition are not warnings but [SchemaWarning]s,
+/// returned by the `schema_warnings` methods: they describe the table,
+/// so they stay available instead of being taken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// The records were modified but the table has a structural compound index (`.cdx`),
+    /// which was not updated. The index has to be rebuilt (or deleted, see [File::clear_cdx_flag])
+    /// before the table is used by programs relying on it, such as Visual FoxPro.
+    StaleStructuralIndex,
+    /// The header has no code page, or an invalid one, and no encoding was given:
+    /// strings are decoded with a lossy fallback encoding
+    UnknownCodePage(CodePageMark),
+    /// The record size of the header does not match the fields, the size of the fields is used
+    RecordSizeMismatch { header_size: u16, fields_size: u16 },
+    /// The deletion flag of `count` records is neither a space nor `*`,
+    /// they are read as not deleted (see [ReadingOptions::strict_deletion_flag]).
+    /// `record_index` and `byte` are the ones of the first of these records.
+    UnexpectedDeletionFlag {
+        record_index: usize,
+        byte: u8,
+        count: usize,
+    },
+    /// The file ends before the number of records of the header
+    MissingRecords {
+        num_records: usize,
+        records_found: usize,
+    },
+    /// The value of a Numeric field does not fit in the Int64 column
+    /// it is read into, it is read as null (`datafusion` feature).
+    /// `count` values of the field did not fit,
+    /// `record_index` and `value` are the ones of the first of them.
+    Int64Overflow {
+        record_index: usize,
+        field_name: String,
+        value: String,
+        count: usize,
+    },
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::StaleStructuralIndex => write!(
+                f,
+                "The records were modified but the structural index (.cdx) was not updated"
+            ),
+            Warning::UnknownCodePage(mark) => write!(
+                f,
+                "The code page of the header ({:?}) is not known, \
+                 strings are decoded with a lossy fallback encoding",
+                mark
+            ),
+            Warning::RecordSizeMismatch {
+                header_size,
+                fields_size,
+            } => write!(
+                f,
+                "The record size of the header ({}) does not match the fields ({}), \
+                 the size of the fields is used",
+                header_size, fields_size
+            ),
+            Warning::UnexpectedDeletionFlag {
+                record_index,
+                byte,
+                count,
+            } => {
+                write!(
+                    f,
+                    "The deletion flag of the record {} is {:#04x}",
+                    record_index, byte
+                )?;
+                if *count > 1 {
+                    write!(f, ", and {} other records have such flags", count - 1)?;
+                }
+                write!(f, ", the records are read as not deleted")
+            }
+            Warning::MissingRecords {
+                num_records,
+                records_found,
+            } => write!(
+                f,
+                "The header announces {} records but the file has only {}",
+                num_records, records_found
+            ),
+            Warning::Int64Overflow {
+                record_index,
+                field_name,
+                value,
+                count,
+            } => {
+                write!(
+                    f,
+                    "The value {} of the field '{}' of the record {} does not fit in an Int64",
+                    value, field_name, record_index
+                )?;
+                if *count > 1 {
+                    write!(f, ", nor {} other values of the field", count - 1)?;
+                }
+                write!(f, ", the values are read as null")
+            }
+        }
+    }
+}
+
+/// Adds the warning about an unexpected deletion flag, or counts it in the one already added
+pub(crate) fn add_unexpected_deletion_flag_warning(
+    warnings: &mut Vec<Warning>,
+    record_index: usize,
+    byte: u8,
+) {
+    let added = warnings.iter_mut().find_map(|warning| match warning {
+        Warning::UnexpectedDeletionFlag { count, .. } => Some(count),
+        _ => None,
+    });
+    match added {
+        Some(count) => *count += 1,
+        None => warnings.push(Warning::UnexpectedDeletionFlag {
+            record_index,
+            byte,
+            count: 1,
+        }),
+    }
 }
 
 /// Handle to a dBase File.
@@ -458,22 +864,47 @@ where
 /// # }
 /// ```
 pub struct File<T> {
-    pub(crate) inner: T,
+    pub(crate) inner: Inner<T>,
     memo_reader: Option<MemoReader<T>>,
     pub(crate) header: Header,
     pub(crate) fields_info: FieldsInfo,
     pub(crate) encoding: DynEncoding,
+    /// Path to the database container (Visual FoxPro only)
+    pub(crate) backlink: Option<String>,
     /// Buffer that contains a whole record worth of data
     /// It also contains the deletion flag
     record_data_buffer: Cursor<Vec<u8>>,
     /// Non-Memo field length is stored on a u8,
     /// so fields cannot exceed 255 bytes
-    field_data_buffer: [u8; 255],
+    field_data_buffer: Vec<u8>,
     pub(crate) options: ReadingOptions,
     /// We track the position in the file
     /// to avoid calling `seek` when we are reading buffer
     /// in order (0, 1, 2, etc)
     file_position: u64,
+    /// Index of the record where the file terminator was found,
+    /// when the header announces more records than the file has
+    end_of_data: Option<usize>,
+    /// Set when the content was modified but the header was not written since
+    dirty: bool,
+    /// Compare records with the fields before writing them
+    precheck: bool,
+    /// Convert the values of records to the type of their field
+    coerce_types: bool,
+    /// Path of the file, when it was opened from a path
+    path: Option<PathBuf>,
+    /// Warnings collected while modifying the file
+    warnings: Vec<Warning>,
+    /// Records counted in the unexpected deletion flag warning,
+    /// so that reading one of them again does not count it twice
+    unexpected_flag_records: HashSet<usize>,
+    /// How values given for autoincrement fields are handled when appending
+    autoincrement_policy: AutoIncrementPolicy,
+    /// Writes the header when the file is dropped.
+    ///
+    /// Drop cannot require `T: Write + Seek`, so the function is stored
+    /// by the operations that modify the file, which have this bound.
+    sync_on_drop: Option<SyncFn<T>>,
 }
 
 impl<T> File<T> {
@@ -482,11 +913,26 @@ impl<T> File<T> {
         self.fields_info.as_ref()
     }
 
+    /// Returns the path to the database (.dbc) the table belongs to
+    ///
+    /// Only Visual FoxPro tables can have one.
+    pub fn backlink(&self) -> Option<&str> {
+        self.backlink.as_deref()
+    }
+
+    /// Returns the oddities (zero length fields, duplicated names)
+    /// found in the fields definition
+    pub fn schema_warnings(&self) -> &[SchemaWarning] {
+        &self.fields_info.warnings
+    }
+
     /// Returns the field index that corresponds to the given name
+    ///
+    /// If several fields have the same name, the index of the first one is returned
     pub fn field_index(&self, name: &str) -> Option<FieldIndex> {
         self.fields_info
             .iter()
-            .position(|info| info.name.eq_ignore_ascii_case(name))
+            .position(|info| names_match(&info.name, name))
             .map(FieldIndex)
     }
 
@@ -495,95 +941,818 @@ impl<T> File<T> {
         self.header.num_records as usize
     }
 
+    /// Returns the flags of the header, telling for example whether
+    /// the table has a structural compound index
+    pub fn table_flags(&self) -> TableFlags {
+        self.header.table_flags
+    }
+
+    /// Returns the info that allow to create a new table with the same structure
+    ///
+    /// Also available when the file borrows its source, as with `File::open(&mut source)`.
+    pub fn table_info(&self) -> TableInfo {
+        TableInfo {
+            header: self.header,
+            fields_info: self.fields_info.inner.clone(),
+            encoding: self.encoding.clone(),
+            backlink: self.backlink.clone(),
+            code_page_mark: None,
+        }
+    }
+
+    /// Returns the path of the structural compound index (`.cdx`) of the table,
+    /// whether or not it exists.
+    ///
+    /// The index has the same stem as the table, with a `.cdx` extension
+    /// in the case of the table extension.
+    /// Returns `None` when the file was not opened from a path.
+    pub fn companion_cdx_path(&self) -> Option<PathBuf> {
+        let path = self.path.as_ref()?;
+        let extension_is_uppercase = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.chars().all(|c| c.is_ascii_uppercase()));
+        Some(path.with_extension(if extension_is_uppercase { "CDX" } else { "cdx" }))
+    }
+
+    /// Returns the warnings collected since the last call,
+    /// see [Warning]
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        self.unexpected_flag_records.clear();
+        std::mem::take(&mut self.warnings)
+    }
+
+    #[cfg(feature = "datafusion")]
+    pub(crate) fn add_warnings(&mut self, warnings: Vec<Warning>) {
+        self.warnings.extend(warnings);
+    }
+
+    /// Warns, once per record, about a deletion flag that is read as not deleted
+    fn warn_unexpected_deletion_flag(&mut self, record_index: usize, byte: u8) {
+        if DeletionFlag::try_from_byte(byte).is_none()
+            && !self.options.strict_deletion_flag
+            && self.unexpected_flag_records.insert(record_index)
+        {
+            add_unexpected_deletion_flag_warning(&mut self.warnings, record_index, byte);
+        }
+    }
+
     pub fn set_options(&mut self, options: ReadingOptions) {
         self.options = options;
     }
+
+    /// When enabled, [Record](crate::Record)s are compared with the fields before being written,
+    /// see [TableWriterBuilder::precheck_records](crate::TableWriterBuilder::precheck_records).
+    pub fn set_precheck_records(&mut self, enabled: bool) {
+        self.precheck = enabled;
+    }
+
+    /// When enabled, the values of [Record](crate::Record)s are converted to the type of their field,
+    /// see [TableWriterBuilder::coerce_types](crate::TableWriterBuilder::coerce_types).
+    pub fn set_coerce_types(&mut self, enabled: bool) {
+        self.coerce_types = enabled;
+    }
+
+    /// Sets how the values given for autoincrement fields are handled
+    /// by [Self::append_record] and [Self::append_records].
+    ///
+    /// Records written over existing ones keep the values they are given.
+    pub fn set_autoincrement_policy(&mut self, policy: AutoIncrementPolicy) {
+        self.autoincrement_policy = policy;
+    }
+
+    /// Returns whether the file was modified since its header was last written.
+    ///
+    /// A dirty file writes its header when dropped, errors are ignored,
+    /// call [Self::sync_all] to handle them.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Returns a reference to the source of the file.
+    ///
+    /// The file keeps track of the position of its source,
+    /// moving it (e.g. seeking a `&std::fs::File`) makes the next reads and writes wrong.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Writes the header if the file was modified, and returns the source of the file.
+    ///
+    /// The memo file, if any, is closed. The source is not flushed,
+    /// a `BufWriter` flushes when dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::convert::TryInto;
+    /// use std::io::Cursor;
+    ///
+    /// let table_info = dbase::TableWriterBuilder::new()
+    ///     .add_character_field("NAME".try_into().unwrap(), 20)
+    ///     .build_table_info()?;
+    /// let mut file = dbase::File::create_new(Cursor::new(Vec::<u8>::new()), table_info)?;
+    /// let mut record = dbase::Record::default();
+    /// record.insert("NAME".to_string(), "Yoshi".to_string().into());
+    /// file.append_record(&record)?;
+    ///
+    /// let bytes = file.into_inner()?.into_inner();
+    /// let records = dbase::Reader::new(Cursor::new(bytes))?.read()?;
+    /// assert_eq!(records, vec![record]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_inner(mut self) -> std::io::Result<T> {
+        if self.dirty {
+            if let Some(sync) = self.sync_on_drop {
+                sync(&mut self)?;
+            }
+        }
+        // The file is synced, dropping it must not use the source anymore
+        self.sync_on_drop = None;
+        Ok(self.inner.take())
+    }
 }
 
-impl<T: Read + Seek> File<T> {
-    /// creates of File using source as the storage space.
-    pub fn open(mut source: T) -> Result<Self, Error> {
-        let mut header =
-            Header::read_from(&mut source).map_err(|error| Error::io_error(error, 0))?;
+impl<T> Drop for File<T> {
+    fn drop(&mut self) {
+        if self.dirty {
+            if let Some(sync) = self.sync_on_drop {
+                let _ = sync(self);
+            }
+        }
+    }
+}
 
-        let offset = if header.file_type.is_visual_fox_pro() {
-            if BACKLINK_SIZE > header.offset_to_first_record {
-                panic!("Invalid file");
+/// What precedes the records in a dBase file
+pub(crate) struct TableDefinition {
+    pub(crate) header: Header,
+    pub(crate) fields_info: FieldsInfo,
+    pub(crate) encoding: DynEncoding,
+    pub(crate) backlink: Option<String>,
+    /// Fallbacks used while reading the definition
+    pub(crate) warnings: Vec<Warning>,
+}
+
+impl TableDefinition {
+    /// Reads the header and fields definition,
+    /// the source is left at the position of the first record
+    ///
+    /// When no `encoding` is given, the one of the code page of the header is used,
+    /// to decode the field names and the strings of the records.
+    pub(crate) fn read_from<T: Read + Seek>(
+        source: &mut T,
+        options: &ReadingOptions,
+        encoding: Option<DynEncoding>,
+    ) -> Result<Self, Error> {
+        let mut header_bytes = [0u8; Header::SIZE];
+        source
+            .read_exact(&mut header_bytes)
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        let is_dbase2 = Header::is_dbase2(&header_bytes);
+        let mut header = if is_dbase2 {
+            Header::read_dbase2_from(&mut &header_bytes[..])
+        } else {
+            Header::read_from(&mut &header_bytes[..])
+        }
+        .map_err(|error| Error::io_error(error, 0))?;
+        if let Some(pivot) = options.two_digit_year_pivot {
+            header.apply_two_digit_year_pivot(pivot);
+        }
+        let span = trace_span!(
+            "open_table",
+            version = ?header.file_type,
+            code_page = ?header.code_page_mark,
+            num_records = header.num_records,
+            num_fields = ::tracing::field::Empty,
+        );
+
+        let mut warnings = vec![];
+        let encoding = match encoding {
+            Some(encoding) => encoding,
+            None => {
+                if matches!(
+                    header.code_page_mark,
+                    CodePageMark::Undefined | CodePageMark::Invalid
+                ) {
+                    warnings.push(Warning::UnknownCodePage(header.code_page_mark));
+                    trace_event!(
+                        warn,
+                        code_page = ?header.code_page_mark,
+                        "no code page in the header, strings are decoded with a lossy fallback encoding"
+                    );
+                }
+                header.code_page_mark.to_encoding().ok_or_else(|| {
+                    let field_error =
+                        FieldIOError::new(UnsupportedCodePage(header.code_page_mark), None);
+                    let error = Error::new(field_error, 0);
+                    trace_event!(error, %error, "cannot open the table");
+                    error
+                })?
+            }
+        };
+
+        let fields_info = if is_dbase2 {
+            Self::read_dbase2_fields(source, options, &encoding)?
+        } else {
+            Self::read_fields(source, &header, options, &encoding)?
+        };
+
+        let backlink = if header.file_type.is_visual_fox_pro() {
+            let offset = header.offset_to_first_record - BACKLINK_SIZE;
+            source
+                .seek(SeekFrom::Start(u64::from(offset)))
+                .map_err(|error| Error::io_error(error, 0))?;
+            let mut block = [0u8; BACKLINK_SIZE as usize];
+            source
+                .read_exact(&mut block)
+                .map_err(|error| Error::io_error(error, 0))?;
+            let len = block.iter().position(|b| *b == 0).unwrap_or(block.len());
+            if len == 0 {
+                None
+            } else {
+                let path = encoding.decode(&block[..len]).map_err(|error| Error {
+                    record_num: 0,
+                    field: None,
+                    field_index: None,
+                    kind: ErrorKind::StringDecodeError(error),
+                })?;
+                Some(path.into_owned())
             }
-            header.offset_to_first_record - BACKLINK_SIZE
         } else {
-            header.offset_to_first_record
+            None
         };
-        let num_fields =
-            (offset as usize - Header::SIZE - std::mem::size_of::<u8>()) / FieldInfo::SIZE;
 
-        let fields_info =
-            FieldsInfo::read_from(&mut source, num_fields).map_err(|error| Error {
+        source
+            .seek(SeekFrom::Start(u64::from(header.offset_to_first_record)))
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        // Some file seems not to include the DELETION_FLAG_SIZE into the record size,
+        // but we rely on it
+        let size_of_record = (DELETION_FLAG_SIZE + fields_info.size_of_all_fields()) as u16;
+        if header.size_of_record != size_of_record {
+            warnings.push(Warning::RecordSizeMismatch {
+                header_size: header.size_of_record,
+                fields_size: size_of_record,
+            });
+            trace_event!(
+                warn,
+                header_size = header.size_of_record,
+                fields_size = size_of_record,
+                "the record size of the header does not match the fields, the size of the fields is used"
+            );
+        }
+        header.size_of_record = size_of_record;
+        trace_record!(span, "num_fields", fields_info.len());
+
+        Ok(Self {
+            header,
+            fields_info,
+            encoding,
+            backlink,
+            warnings,
+        })
+    }
+
+    /// Reads the fields definition that follows the header
+    /// (for all versions but dBase II)
+    fn read_fields<T: Read + Seek>(
+        source: &mut T,
+        header: &Header,
+        options: &ReadingOptions,
+        encoding: &DynEncoding,
+    ) -> Result<FieldsInfo, Error> {
+        let offset = if header.file_type.is_visual_fox_pro() {
+            header.offset_to_first_record.checked_sub(BACKLINK_SIZE)
+        } else {
+            Some(header.offset_to_first_record)
+        };
+        // Some writers omit the terminator, the size is not rounded
+        // to a number of descriptors here
+        let available_size = offset
+            .map(usize::from)
+            .and_then(|offset| offset.checked_sub(Header::SIZE))
+            .filter(|size| *size > 0)
+            .ok_or_else(|| Error {
                 record_num: 0,
                 field: None,
-                kind: error,
+                field_index: None,
+                kind: ErrorKind::InvalidHeader(format!(
+                    "offset to first record ({}) is too small",
+                    header.offset_to_first_record
+                )),
             })?;
 
-        let terminator = source
-            .read_u8()
-            .map_err(|error| Error::io_error(error, 0))?;
+        let skip_system_columns =
+            header.file_type.is_visual_fox_pro() && !options.include_system_columns;
+        let mut fields_info = FieldsInfo::read_from(
+            source,
+            available_size,
+            options.on_unknown_field_type,
+            skip_system_columns,
+            encoding,
+        )
+        .map_err(|error| Error {
+            record_num: 0,
+            field: None,
+            field_index: None,
+            kind: error,
+        })?;
+        // Clipper and FoxPro store the high byte of long Character lengths in the
+        // decimal count byte, other programs may leave a value in it: the byte is part
+        // of the length unless the length byte alone gives the record size of the header
+        let record_size = DELETION_FLAG_SIZE + fields_info.size_of_all_fields();
+        let high_bytes_size = fields_info
+            .inner
+            .iter()
+            .filter(|info| info.field_type == FieldType::Character)
+            .map(|info| usize::from(info.field_length & 0xFF00))
+            .sum::<usize>();
+        if high_bytes_size != 0
+            && record_size != usize::from(header.size_of_record)
+            && record_size - high_bytes_size == usize::from(header.size_of_record)
+        {
+            fields_info.use_short_character_lengths();
+        }
+        for (index, info) in fields_info.inner.iter_mut().enumerate() {
+            info.resolve_double_storage(header.file_type)
+                .map_err(|error| Error {
+                    record_num: 0,
+                    field: Some(info.clone()),
+                    field_index: Some(FieldIndex(index)),
+                    kind: error,
+                })?;
+        }
 
-        debug_assert_eq!(terminator, TERMINATOR_VALUE);
+        Ok(fields_info)
+    }
 
+    /// Reads the fields definition of a dBase II file
+    fn read_dbase2_fields<T: Read + Seek>(
+        source: &mut T,
+        options: &ReadingOptions,
+        encoding: &DynEncoding,
+    ) -> Result<FieldsInfo, Error> {
         source
-            .seek(SeekFrom::Start(u64::from(header.offset_to_first_record)))
+            .seek(SeekFrom::Start(Header::DBASE2_SIZE as u64))
             .map_err(|error| Error::io_error(error, 0))?;
+        FieldsInfo::read_dbase2_from(source, options.on_unknown_field_type, encoding).map_err(
+            |error| Error {
+                record_num: 0,
+                field: None,
+                field_index: None,
+                kind: error,
+            },
+        )
+    }
+}
 
-        let encoding = header.code_page_mark.to_encoding().ok_or_else(|| {
-            let field_error = FieldIOError::new(UnsupportedCodePage(header.code_page_mark), None);
-            Error::new(field_error, 0)
-        })?;
+impl<T: Read + Seek> File<T> {
+    /// creates of File using source as the storage space.
+    ///
+    /// The source can be borrowed (`File::open(&mut source)`), to get it back
+    /// once the `File` is dropped.
+    pub fn open(source: T) -> Result<Self, Error> {
+        Self::open_with_reading_options(source, ReadingOptions::default())
+    }
+
+    /// Creates a File using source as the storage space, with reading options
+    /// used when the fields are read, like [ReadingOptions::on_unknown_field_type]
+    /// or [ReadingOptions::include_system_columns].
+    pub fn open_with_reading_options(
+        mut source: T,
+        options: ReadingOptions,
+    ) -> Result<Self, Error> {
+        let TableDefinition {
+            header,
+            fields_info,
+            encoding,
+            backlink,
+            warnings,
+        } = TableDefinition::read_from(&mut source, &options, None)?;
 
         let record_size: usize = DELETION_FLAG_SIZE + fields_info.size_of_all_fields();
         let record_data_buffer = Cursor::new(vec![0u8; record_size]);
-        // Some file seems not to include the DELETION_FLAG_SIZE into the record size,
-        // but we rely on it
-        header.size_of_record = record_size as u16;
-        // debug_assert_eq!(record_size - DELETION_FLAG_SIZE, header.size_of_record as usize);
+        let field_data_buffer = field_data_buffer(&fields_info.inner);
 
         Ok(Self {
-            inner: source,
+            inner: Inner::new(source),
             memo_reader: None,
             header,
             fields_info,
             encoding,
+            backlink,
             record_data_buffer,
-            field_data_buffer: [0u8; 255],
-            options: ReadingOptions::default(),
+            field_data_buffer,
+            options,
             file_position: header.offset_to_first_record as u64,
+            end_of_data: None,
+            dirty: false,
+            precheck: false,
+            coerce_types: false,
+            autoincrement_policy: AutoIncrementPolicy::default(),
+            path: None,
+            warnings,
+            unexpected_flag_records: HashSet::new(),
+            sync_on_drop: None,
         })
     }
 
-    /// Returns a reference to the record at the given index.
+    /// Reads the header again, to see the records added by other programs
+    /// since the file was opened.
     ///
-    /// Returns None if no record exist for the given index
-    pub fn record(&mut self, index: usize) -> Option<RecordRef<'_, T>> {
-        if index >= self.header.num_records as usize {
-            None
-        } else {
-            let record_ref = RecordRef {
-                file: self,
-                index: RecordIndex(index),
-            };
-            Some(record_ref)
+    /// The number of records and the date of the last update are updated,
+    /// the records read afterwards are read from the source again.
+    ///
+    /// Fails with [ErrorKind::SchemaMismatch] if the fields of the table changed,
+    /// and when this `File` has changes whose header is not written yet
+    /// (see [Self::is_dirty]).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut file = dbase::File::open_read_only("data.dbf")?;
+    /// let mut next = file.num_records();
+    /// loop {
+    ///     file.reload()?;
+    ///     let mut records = file.records_from(dbase::RecordIndex(next))?;
+    ///     while let Some(mut record) = records.next() {
+    ///         println!("{:?}", record.read()?);
+    ///     }
+    ///     next = records.index().0;
+    ///     std::thread::sleep(std::time::Duration::from_secs(1));
+    /// }
+    /// # }
+    /// ```
+    pub fn reload(&mut self) -> Result<ReloadReport, Error> {
+        let error = |kind| Error {
+            record_num: 0,
+            field: None,
+            field_index: None,
+            kind,
+        };
+        if self.dirty {
+            return Err(error(ErrorKind::Message(
+                "the file has changes whose header is not written, it cannot be reloaded"
+                    .to_string(),
+            )));
         }
-    }
 
-    /// Returns an iterator over the records in the file.
-    ///
-    /// Always starts at the first record
+        // Whatever happens, the next reads go to the source
+        self.file_position = u64::MAX;
+        self.inner
+            .seek(SeekFrom::Start(0))
+            .map_err(|error| Error::io_error(error, 0))?;
+        let definition = TableDefinition::read_from(
+            &mut *self.inner,
+            &self.options,
+            Some(self.encoding.clone()),
+        )?;
+
+        let mut differences =
+            field_differences(&self.fields_info.inner, &definition.fields_info.inner);
+        if differences.is_empty()
+            && (definition.header.offset_to_first_record != self.header.offset_to_first_record
+                || definition.header.size_of_record != self.header.size_of_record)
+        {
+            differences.push("the records are not at the same place".to_string());
+        }
+        if !differences.is_empty() {
+            return Err(error(ErrorKind::SchemaMismatch { differences }));
+        }
+
+        let report = ReloadReport {
+            previous_num_records: self.num_records(),
+            num_records: definition.header.num_records as usize,
+        };
+        // The memos of the new records are after the end of the memo file that was read
+        if let Some(memo_reader) = self.memo_reader.as_mut() {
+            memo_reader
+                .reload()
+                .map_err(|error| Error::io_error(error, 0))?;
+        }
+        self.header = definition.header;
+        self.end_of_data = None;
+        Ok(report)
+    }
+
+    /// Counts the records of the file, to check the number of records of the header,
+    /// without reading them: only the deletion flag of each record is read.
+    ///
+    /// See [Reader::count_records](crate::Reader::count_records).
+    pub fn count_records(&mut self) -> Result<RecordCounts, Error> {
+        RecordCounts::scan(&mut *self.inner, &self.header).map_err(|err| Error::io_error(err, 0))
+    }
+
+    /// Reads the first `n` records into a [TablePreview] to print them as a text table,
+    /// records marked as deleted are included and start with `*`.
+    pub fn preview(&mut self, n: usize) -> Result<TablePreview, Error> {
+        let mut preview = TablePreview::new(self.fields());
+        for index in 0..n {
+            let Some(mut record) = self.record(index) else {
+                break;
+            };
+            let deleted = record.is_deleted()?;
+            let values = record.read_as::<PreviewValues>()?;
+            preview.push(self.fields_info.as_ref(), values, deleted);
+        }
+        Ok(preview)
+    }
+
+    /// Returns a reference to the record at the given index.
+    ///
+    /// Returns None if no record exist for the given index,
+    /// which is also the case for the records that would be after the file terminator
+    /// when the header announces more records than the file has.
+    pub fn record(&mut self, index: usize) -> Option<RecordRef<'_, T>> {
+        if index >= self.header.num_records as usize || self.is_past_end_of_data(RecordIndex(index))
+        {
+            None
+        } else {
+            let record_ref = RecordRef {
+                file: self,
+                index: RecordIndex(index),
+            };
+            Some(record_ref)
+        }
+    }
+
+    /// Returns an iterator over the records in the file.
+    ///
+    /// Always starts at the first record
     pub fn records(&mut self) -> FileRecordIterator<'_, T> {
+        let end = RecordIndex(self.num_records());
         FileRecordIterator {
             file: self,
             current_record: RecordIndex(0),
+            end,
         }
     }
 
+    /// Reads the text of the memo stored at the block `index`
+    /// of the memo file, see [FieldRef::read_memo_index].
+    pub fn read_memo_text(&mut self, index: u32) -> Result<String, Error> {
+        let memo_reader = self.memo_reader.as_mut().ok_or(Error {
+            record_num: 0,
+            field: None,
+            field_index: None,
+            kind: ErrorKind::MissingMemoFile,
+        })?;
+        memo_reader
+            .read_text_at(index, &self.encoding, self.options.max_memo_size)
+            .map_err(|kind| Error {
+                record_num: 0,
+                field: None,
+                field_index: None,
+                kind,
+            })
+    }
+
+    /// Copies the bytes of the memo stored at the block `index` to `dst`,
+    /// without decoding them, and returns the number of bytes written.
+    ///
+    /// Unlike [Self::read_memo_text], the memo is not held in memory
+    /// and [ReadingOptions::max_memo_size] does not apply.
+    pub fn read_memo_into(&mut self, index: u32, dst: &mut dyn Write) -> Result<u64, Error> {
+        let memo_reader = self.memo_reader.as_mut().ok_or(Error {
+            record_num: 0,
+            field: None,
+            field_index: None,
+            kind: ErrorKind::MissingMemoFile,
+        })?;
+        memo_reader
+            .read_data_at_into(index, dst)
+            .map_err(|kind| Error {
+                record_num: 0,
+                field: None,
+                field_index: None,
+                kind,
+            })
+    }
+
+    /// Returns an iterator over the records in the file,
+    /// starting at the record at `start`.
+    ///
+    /// `start` can be equal to the number of records, in which case
+    /// the iterator is empty.
+    pub fn records_from(&mut self, start: RecordIndex) -> Result<FileRecordIterator<'_, T>, Error> {
+        let end = self.num_records();
+        self.records_range(start.0..end)
+    }
+
+    /// Returns an iterator over the records with an index in the `range`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = dbase::File::open_read_only("tests/data/stations.dbf")?;
+    /// let mut records = file.records_range(2..4)?;
+    /// while let Some(mut record) = records.next() {
+    ///     let record = record.read()?;
+    /// }
+    /// assert_eq!(records.index(), dbase::RecordIndex(4));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn records_range(
+        &mut self,
+        range: std::ops::Range<usize>,
+    ) -> Result<FileRecordIterator<'_, T>, Error> {
+        let num_records = self.num_records();
+        if range.start > range.end || range.end > num_records {
+            return Err(Error {
+                record_num: range.start,
+                field: None,
+                field_index: None,
+                kind: ErrorKind::InvalidRecordRange {
+                    start: range.start,
+                    end: range.end,
+                    num_records,
+                },
+            });
+        }
+        Ok(FileRecordIterator {
+            file: self,
+            current_record: RecordIndex(range.start),
+            end: RecordIndex(range.end),
+        })
+    }
+
+    /// Reads a Character field of a record, borrowing the record buffer
+    fn read_character_str(
+        &mut self,
+        record_index: RecordIndex,
+        field_index: FieldIndex,
+    ) -> Result<Option<Cow<'_, str>>, Error> {
+        if field_index.0 >= self.fields_info.len() {
+            return Err(Error::new(FieldIOError::end_of_record(), record_index.0));
+        }
+        self.ensure_record_has_been_read_into_buffer(record_index)?;
+
+        let field_info = &self.fields_info[field_index.0];
+        let start_pos = self
+            .fields_info
+            .field_position_in_record(field_index.0)
+            .expect("internal error: invalid field index");
+        let field_bytes = &self.record_data_buffer.get_ref()
+            [start_pos..start_pos + field_info.field_length as usize];
+        read_character_str(
+            field_bytes,
+            field_info,
+            &self.encoding,
+            &self.options,
+            &mut vec![],
+        )
+        .map_err(|kind| {
+            Error::new(
+                FieldIOError::new(kind, Some(field_info.clone())).at_field_index(field_index.0),
+                record_index.0,
+            )
+        })
+    }
+
+    /// Returns the index of the first record, not marked as deleted,
+    /// whose field `field` has a value equal to `value`.
+    ///
+    /// Only the bytes of the field are read for each record.
+    /// Values that cannot be converted to `V` (e.g. empty values) do not match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = dbase::File::open_read_only("tests/data/stations.dbf")?;
+    /// let name = file.field_index("name").unwrap();
+    /// let index = file.find_record_by(name, &"Metro Center".to_string())?;
+    /// assert_eq!(index, Some(dbase::RecordIndex(5)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_record_by<V>(
+        &mut self,
+        field: FieldIndex,
+        value: &V,
+    ) -> Result<Option<RecordIndex>, Error>
+    where
+        V: PartialEq + TryFrom<FieldValue, Error = FieldConversionError>,
+    {
+        let mut found = None;
+        self.scan_field(field, |index, field_value| {
+            if V::try_from(field_value).is_ok_and(|v| v == *value) {
+                found = Some(index);
+            }
+            found.is_none()
+        })?;
+        Ok(found)
+    }
+
+    /// Builds an index of the records, not marked as deleted, by the value of their field `field`,
+    /// to do repeated lookups without scanning the file each time.
+    ///
+    /// The values are rendered as text with [FieldValue::display_with],
+    /// empty values are not indexed and when several records have the same value,
+    /// the first one is kept.
+    ///
+    /// The index is not updated when the file is modified.
+    pub fn build_index(
+        &mut self,
+        field: FieldIndex,
+    ) -> Result<HashMap<String, RecordIndex>, Error> {
+        let mut index = HashMap::new();
+        let style = DisplayStyle::default();
+        let field_info = self
+            .fields_info
+            .iter()
+            .nth(field.0)
+            .cloned()
+            .ok_or_else(|| Error::new(FieldIOError::end_of_record(), 0))?;
+        self.scan_field(field, |record_index, value| {
+            if !value.is_none() {
+                index
+                    .entry(value.display_with(&field_info, &style))
+                    .or_insert(record_index);
+            }
+            true
+        })?;
+        Ok(index)
+    }
+
+    /// Reads the value of the field `field` of each record, not marked as deleted,
+    /// until `f` returns false.
+    ///
+    /// Only the deletion flag and the field bytes are read.
+    fn scan_field(
+        &mut self,
+        field: FieldIndex,
+        mut f: impl FnMut(RecordIndex, FieldValue) -> bool,
+    ) -> Result<(), Error> {
+        if field.0 >= self.fields_info.len() {
+            return Err(Error::new(FieldIOError::end_of_record(), 0));
+        }
+        // The record buffer is not updated, and the position will change
+        self.file_position = u64::MAX;
+
+        let field_info = &self.fields_info[field.0];
+        let field_position = self
+            .fields_info
+            .field_position_in_record(field.0)
+            .expect("internal error: invalid field index");
+        let end = self.end_of_data.unwrap_or(self.num_records());
+        let field_bytes = &mut self.field_data_buffer[..field_info.field_length as usize];
+        for record_index in 0..end {
+            let to_error = |error| Error::io_error(error, record_index);
+            let record_position = self.header.record_position(record_index).ok_or(Error {
+                record_num: record_index,
+                field: None,
+                field_index: None,
+                kind: ErrorKind::InvalidRecordRange {
+                    start: record_index,
+                    end,
+                    num_records: self.header.num_records as usize,
+                },
+            })? as u64;
+            self.inner
+                .seek(SeekFrom::Start(record_position))
+                .map_err(to_error)?;
+            let flag = self.inner.read_u8().map_err(to_error)?;
+            if flag == FILE_TERMINATOR {
+                self.end_of_data = Some(record_index);
+                break;
+            }
+            if DeletionFlag::from_byte(flag) == DeletionFlag::Deleted {
+                continue;
+            }
+            self.inner
+                .seek(SeekFrom::Current(
+                    (field_position - DELETION_FLAG_SIZE) as i64,
+                ))
+                .map_err(to_error)?;
+            self.inner.read_exact(field_bytes).map_err(to_error)?;
+            let value = FieldValue::read_from(
+                field_bytes,
+                &mut self.memo_reader,
+                field_info,
+                &self.encoding,
+                &self.options,
+                &mut vec![],
+            )
+            .map_err(|kind| {
+                Error::new(
+                    FieldIOError::new(kind, Some(field_info.clone())).at_field_index(field.0),
+                    record_index,
+                )
+            })?;
+            if !f(RecordIndex(record_index), value) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
     /// Returns true if it read from the source, false otherwise (used in tests).
     fn ensure_record_has_been_read_into_buffer(
         &mut self,
@@ -610,17 +1779,61 @@ impl<T: Read + Seek> File<T> {
                 .map_err(|e| Error::io_error(e, record_index.0))?;
         }
 
-        self.inner
-            .read_exact(self.record_data_buffer.get_mut())
-            .map_err(|e| Error::io_error(e, record_index.0))?;
+        if let Err(e) = self.inner.read_exact(self.record_data_buffer.get_mut()) {
+            // The position in the source is unknown, force a seek for the next read
+            self.file_position = u64::MAX;
+            return Err(Error::io_error(e, record_index.0));
+        }
         self.file_position += self.record_data_buffer.get_mut().len() as u64;
         Ok(true)
     }
+
+    /// Returns true if the record is after the file terminator,
+    /// meaning that the header announces more records than the file has.
+    fn is_past_end_of_data(&mut self, record_index: RecordIndex) -> bool {
+        if let Some(end) = self.end_of_data {
+            if record_index.0 >= end {
+                return true;
+            }
+        }
+        let is_terminator = match self.ensure_record_has_been_read_into_buffer(record_index) {
+            Ok(_) => self.record_data_buffer.get_ref()[0] == FILE_TERMINATOR,
+            Err(error) => matches!(
+                error.kind(),
+                ErrorKind::IoError(e) if e.kind() == std::io::ErrorKind::UnexpectedEof
+            ),
+        };
+        if is_terminator {
+            self.end_of_data = Some(record_index.0);
+        }
+        is_terminator
+    }
 }
 
 impl<T: Write + Seek> File<T> {
-    pub fn create_new(mut dst: T, table_info: TableInfo) -> Result<Self, Error> {
-        write_header_parts(&mut dst, &table_info.header, &table_info.fields_info)?;
+    pub fn create_new(mut dst: T, mut table_info: TableInfo) -> Result<Self, Error> {
+        // The hidden fields of the source, like system columns, are not copied
+        for info in &mut table_info.fields_info {
+            info.hidden_bytes_before = 0;
+        }
+        let backlink = table_info
+            .backlink
+            .as_deref()
+            .map(|path| encode_backlink(path, &table_info.encoding))
+            .transpose()
+            .map_err(|kind| Error {
+                record_num: 0,
+                field: None,
+                field_index: None,
+                kind,
+            })?
+            .unwrap_or_default();
+        write_header_parts(
+            &mut dst,
+            &table_info.header,
+            &table_info.fields_info,
+            &backlink,
+        )?;
         let record_size: usize = DELETION_FLAG_SIZE
             + table_info
                 .fields_info
@@ -628,20 +1841,29 @@ impl<T: Write + Seek> File<T> {
                 .map(|i| i.field_length as usize)
                 .sum::<usize>();
         let record_data_buffer = Cursor::new(vec![0u8; record_size]);
+        let field_data_buffer = field_data_buffer(&table_info.fields_info);
         let file_position = table_info.header.offset_to_first_record as u64;
         debug_assert_eq!(file_position, dst.stream_position().unwrap());
         Ok(Self {
-            inner: dst,
+            inner: Inner::new(dst),
             memo_reader: None,
             header: table_info.header,
-            fields_info: FieldsInfo {
-                inner: table_info.fields_info,
-            },
+            fields_info: FieldsInfo::new(table_info.fields_info),
             encoding: table_info.encoding,
+            backlink: table_info.backlink,
             record_data_buffer,
-            field_data_buffer: [0u8; 255],
+            field_data_buffer,
             options: ReadingOptions::default(),
             file_position,
+            end_of_data: None,
+            dirty: false,
+            precheck: false,
+            coerce_types: false,
+            autoincrement_policy: AutoIncrementPolicy::default(),
+            path: None,
+            warnings: vec![],
+            unexpected_flag_records: HashSet::new(),
+            sync_on_drop: None,
         })
     }
 
@@ -652,10 +1874,63 @@ impl<T: Write + Seek> File<T> {
         self.append_records(std::slice::from_ref(record))
     }
 
+    /// Appends a record with the given deletion flag, a record appended with
+    /// [DeletionFlag::Deleted] is a tombstone that readers skip.
+    ///
+    /// The bytes of the fields are the ones [Self::append_record] would write.
+    pub fn append_record_with_flag<R>(
+        &mut self,
+        record: &R,
+        flag: DeletionFlag,
+    ) -> Result<(), Error>
+    where
+        R: WritableRecord,
+    {
+        self.append_records_with_flag(std::slice::from_ref(record), flag)
+    }
+
+    /// Appends a record whose values are given with the name of their field, in any order,
+    /// see [TableWriter::write_named_values](crate::TableWriter::write_named_values).
+    pub fn append_named_values<'v>(
+        &mut self,
+        values: impl IntoIterator<Item = (&'v str, &'v FieldValue)>,
+    ) -> Result<(), Error> {
+        self.append_record(&NamedValues::new(values))
+    }
+
+    /// Appends the records at the end of the file and writes the updated header.
+    ///
+    /// The number of records is the one read when opening the file,
+    /// the file must not be modified by other handles in the meantime,
+    /// which an exclusive lock guarantees (see [OpenOptions::exclusive_lock]).
+    ///
+    /// The progress callback of the options (see [ReadingOptions::with_progress])
+    /// is called as records are appended.
     pub fn append_records<R>(&mut self, records: &[R]) -> Result<(), Error>
     where
         R: WritableRecord,
     {
+        self.append_records_with_flag(records, DeletionFlag::NotDeleted)
+    }
+
+    fn append_records_with_flag<R>(
+        &mut self,
+        records: &[R],
+        flag: DeletionFlag,
+    ) -> Result<(), Error>
+    where
+        R: WritableRecord,
+    {
+        if matches!(self.header.file_type, crate::header::Version::DBase2) {
+            return Err(Error {
+                record_num: self.num_records(),
+                field: None,
+                field_index: None,
+                kind: ErrorKind::Message("Writing dBase II files is not supported".to_string()),
+            });
+        }
+        self.mark_dirty();
+
         assert_eq!(
             self.header
                 .num_records
@@ -672,26 +1947,53 @@ impl<T: Write + Seek> File<T> {
             .seek(SeekFrom::Start(end_of_last_record))
             .map_err(|error| Error::io_error(error, self.num_records()))?;
 
-        for record in records {
+        for (i, record) in records.iter().enumerate() {
             let current_record_index = self.header.num_records + 1;
 
             let mut field_writer = FieldWriter {
-                dst: &mut self.inner,
+                dst: &mut *self.inner,
                 fields_info: self.fields_info.iter().peekable(),
                 field_buffer: &mut Cursor::new(&mut self.field_data_buffer),
                 encoding: &self.encoding,
+                wrote_non_ascii: false,
+                precheck: self.precheck,
+                coerce_types: self.coerce_types,
+                autoincrement: Some(self.autoincrement_policy),
+                num_fields: self.fields_info.len(),
+                truncated_values: vec![],
+                memo_writer: self
+                    .memo_reader
+                    .as_mut()
+                    .map(|memo| memo as &mut dyn MemoWriter),
             };
 
             field_writer
-                .write_deletion_flag()
+                .write_deletion_flag(flag)
                 .map_err(|error| Error::io_error(error, current_record_index as usize))?;
 
             record
                 .write_using(&mut field_writer)
                 .map_err(|error| Error::new(error, current_record_index as usize))?;
+            // The hidden fields of new records are zeroed
+            self.inner
+                .write_all(&vec![0u8; self.fields_info.hidden_bytes_after])
+                .map_err(|error| Error::io_error(error, current_record_index as usize))?;
 
             self.header.num_records = current_record_index;
+            self.fields_info
+                .inner
+                .iter_mut()
+                .for_each(FieldInfo::advance_autoincrement);
+            self.options
+                .progress
+                .report(i as u32 + 1, records.len() as u32);
         }
+        // The terminator, if there was one, was replaced by the first new record
+        self.inner
+            .write_u8(FILE_TERMINATOR)
+            .map_err(|error| Error::io_error(error, self.num_records()))?;
+        // The record buffer still holds the last record read, but the position changed
+        self.file_position = u64::MAX;
 
         self.sync_all()
             .map_err(|error| Error::io_error(error, self.num_records()))?;
@@ -699,52 +2001,376 @@ impl<T: Write + Seek> File<T> {
         Ok(())
     }
 
+    /// Flushes the data that was written but is still buffered
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+
+    /// Marks the file as modified, its header will be written when dropped
+    fn mark_dirty(&mut self) {
+        self.dirty = true;
+        self.sync_on_drop = Some(|file| file.sync_all());
+        if self.header.table_flags.has_structural_cdx()
+            && !self.warnings.contains(&Warning::StaleStructuralIndex)
+        {
+            self.warnings.push(Warning::StaleStructuralIndex);
+        }
+    }
+
+    /// Removes the flag telling that the table has a structural compound index,
+    /// to be used once the `.cdx` file (see [Self::companion_cdx_path]) is deleted.
+    ///
+    /// The header is written on [Self::sync_all] or when the file is dropped.
+    pub fn clear_cdx_flag(&mut self) {
+        self.header.table_flags.0 &= !TableFlags::STRUCTURAL_CDX;
+        self.mark_dirty();
+    }
+
+    /// Writes the header, updating its last update date if the file was modified
+    ///
+    /// The header may still be buffered, it is written to the file before
+    /// the lock taken when opening it (if any) is released on drop.
     pub fn sync_all(&mut self) -> std::io::Result<()> {
+        if self.dirty {
+            self.header.update_date();
+        }
         let current_pos = self.inner.stream_position()?;
         self.inner.seek(SeekFrom::Start(0))?;
-        self.header.write_to(&mut self.inner)?;
+        self.header.write_to(&mut *self.inner)?;
+        for (info, descriptor_index) in self
+            .fields_info
+            .iter()
+            .zip(self.fields_info.descriptor_indices.iter())
+        {
+            if let Some((next_value, _)) = info.autoincrement() {
+                // the next value is stored after the name, type, displacement,
+                // length, decimal count and flags of the descriptor
+                let position = Header::SIZE + descriptor_index * FieldInfo::SIZE + 19;
+                self.inner.seek(SeekFrom::Start(position as u64))?;
+                self.inner.write_u32::<LittleEndian>(next_value)?;
+            }
+        }
         self.inner.seek(SeekFrom::Start(current_pos))?;
+        self.dirty = false;
         Ok(())
     }
 }
 
+impl<T: Write + Seek + Truncate> File<T> {
+    /// Removes the records after the first `n` ones, the file is shortened
+    /// and ends with the file terminator.
+    ///
+    /// The rest of the header is left intact, it is written before returning.
+    /// Memo blocks of the removed records stay in the memo file.
+    ///
+    /// Returns [ErrorKind::InvalidRecordRange] when `n` is greater than the number of records.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut source = std::io::Cursor::new(std::fs::read("tests/data/stations.dbf").unwrap());
+    /// let mut file = dbase::File::open(&mut source)?;
+    /// file.truncate_records(2)?;
+    /// assert_eq!(file.num_records(), 2);
+    /// drop(file);
+    ///
+    /// source.set_position(0);
+    /// assert_eq!(dbase::Reader::new(source)?.read()?.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn truncate_records(&mut self, n: usize) -> Result<(), Error> {
+        let num_records = self.num_records();
+        if n > num_records {
+            return Err(Error {
+                record_num: n,
+                field: None,
+                field_index: None,
+                kind: ErrorKind::InvalidRecordRange {
+                    start: n,
+                    end: num_records,
+                    num_records,
+                },
+            });
+        }
+        self.mark_dirty();
+
+        let end_of_last_record = self.header.offset_to_first_record as u64
+            + (n as u64 * self.header.size_of_record as u64);
+        let to_error = |error| Error::io_error(error, n);
+        self.inner
+            .seek(SeekFrom::Start(end_of_last_record))
+            .map_err(to_error)?;
+        self.inner.write_u8(FILE_TERMINATOR).map_err(to_error)?;
+        self.inner.flush().map_err(to_error)?;
+        self.inner
+            .truncate(end_of_last_record + 1)
+            .map_err(to_error)?;
+
+        self.header.num_records = n as u32;
+        self.end_of_data = self.end_of_data.filter(|end| *end < n);
+        // The record buffer may hold a removed record
+        self.file_position = u64::MAX;
+
+        self.sync_all().map_err(to_error)
+    }
+}
+
+impl<T: Read + Write + Seek> File<T> {
+    /// Overwrites the first record, not marked as deleted, whose field `key_field`
+    /// has a value equal to `key` with `record`, or appends `record` if there is none.
+    ///
+    /// Returns the index of the record that was written.
+    ///
+    /// See [Self::find_record_by].
+    pub fn upsert_by_key<V, R>(
+        &mut self,
+        key_field: FieldIndex,
+        key: &V,
+        record: &R,
+    ) -> Result<RecordIndex, Error>
+    where
+        V: PartialEq + TryFrom<FieldValue, Error = FieldConversionError>,
+        R: WritableRecord,
+    {
+        match self.find_record_by(key_field, key)? {
+            Some(index) => {
+                self.record(index.0)
+                    .ok_or_else(|| Error::new(FieldIOError::end_of_record(), index.0))?
+                    .write(record)?;
+                Ok(index)
+            }
+            None => {
+                let index = RecordIndex(self.num_records());
+                self.append_record(record)?;
+                Ok(index)
+            }
+        }
+    }
+}
+
+/// Lock taken on a file opened with [OpenOptions]
+#[cfg(feature = "locking")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum FileLock {
+    Shared,
+    Exclusive,
+}
+
+#[cfg(feature = "locking")]
+fn lock_file(file: &std::fs::File, lock: FileLock, wait: bool) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use rustix::fs::{flock, FlockOperation};
+        let operation = match (lock, wait) {
+            (FileLock::Shared, true) => FlockOperation::LockShared,
+            (FileLock::Shared, false) => FlockOperation::NonBlockingLockShared,
+            (FileLock::Exclusive, true) => FlockOperation::LockExclusive,
+            (FileLock::Exclusive, false) => FlockOperation::NonBlockingLockExclusive,
+        };
+        flock(file, operation).map_err(std::io::Error::from)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (file, lock, wait);
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "Locking files is only supported on Unix",
+        ))
+    }
+}
+
+/// Options to open a [File], wraps [std::fs::OpenOptions].
+///
+/// With the `locking` feature, an advisory lock can be taken on the file
+/// right after opening it, it is released when the [File] is dropped.
+/// Other processes are only kept out if they lock the file too.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// # let tmp_dir = tempfile::tempdir().unwrap();
+/// # let path = tmp_dir.path().join("stations.dbf");
+/// # std::fs::copy("tests/data/stations.dbf", &path).unwrap();
+/// let mut options = dbase::File::open_options();
+/// options.read(true).write(true);
+/// # #[cfg(all(unix, feature = "locking"))]
+/// options.exclusive_lock(true);
+/// let mut file = options.open(&path)?;
+/// assert_eq!(file.num_records(), 86);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "std-fs")]
+#[derive(Clone, Debug)]
+pub struct OpenOptions {
+    options: std::fs::OpenOptions,
+    #[cfg(feature = "locking")]
+    lock: Option<FileLock>,
+    #[cfg(feature = "locking")]
+    wait_for_lock: bool,
+}
+
+#[cfg(feature = "std-fs")]
+impl Default for OpenOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std-fs")]
+impl From<std::fs::OpenOptions> for OpenOptions {
+    fn from(options: std::fs::OpenOptions) -> Self {
+        Self {
+            options,
+            #[cfg(feature = "locking")]
+            lock: None,
+            #[cfg(feature = "locking")]
+            wait_for_lock: false,
+        }
+    }
+}
+
+#[cfg(feature = "std-fs")]
+impl OpenOptions {
+    /// Creates options with every option set to false, see [std::fs::OpenOptions::new]
+    pub fn new() -> Self {
+        Self::from(std::fs::OpenOptions::new())
+    }
+
+    /// See [std::fs::OpenOptions::read]
+    pub fn read(&mut self, read: bool) -> &mut Self {
+        self.options.read(read);
+        self
+    }
+
+    /// See [std::fs::OpenOptions::write]
+    pub fn write(&mut self, write: bool) -> &mut Self {
+        self.options.write(write);
+        self
+    }
+
+    /// See [std::fs::OpenOptions::create]
+    pub fn create(&mut self, create: bool) -> &mut Self {
+        self.options.create(create);
+        self
+    }
+
+    /// See [std::fs::OpenOptions::truncate]
+    pub fn truncate(&mut self, truncate: bool) -> &mut Self {
+        self.options.truncate(truncate);
+        self
+    }
+
+    /// Takes a shared lock on the file, several handles can hold one at the same time,
+    /// but not while an exclusive lock is held.
+    ///
+    /// Replaces the exclusive lock if it was set.
+    #[cfg(feature = "locking")]
+    pub fn shared_lock(&mut self, enabled: bool) -> &mut Self {
+        self.set_lock(FileLock::Shared, enabled);
+        self
+    }
+
+    /// Takes an exclusive lock on the file, no other handle can hold a lock at the same time.
+    ///
+    /// Replaces the shared lock if it was set.
+    #[cfg(feature = "locking")]
+    pub fn exclusive_lock(&mut self, enabled: bool) -> &mut Self {
+        self.set_lock(FileLock::Exclusive, enabled);
+        self
+    }
+
+    /// Whether opening waits until the lock can be taken,
+    /// by default opening fails with an [std::io::ErrorKind::WouldBlock] error
+    /// when the lock is held by another handle.
+    #[cfg(feature = "locking")]
+    pub fn wait_for_lock(&mut self, enabled: bool) -> &mut Self {
+        self.wait_for_lock = enabled;
+        self
+    }
+
+    #[cfg(feature = "locking")]
+    fn set_lock(&mut self, lock: FileLock, enabled: bool) {
+        if enabled {
+            self.lock = Some(lock);
+        } else if self.lock == Some(lock) {
+            self.lock = None;
+        }
+    }
+
+    /// Opens the file at `path` with these options
+    pub fn open<P: AsRef<Path>>(&self, path: P) -> Result<File<BufReadWriteFile>, Error> {
+        let file = self
+            .options
+            .open(path.as_ref())
+            .map_err(|error| Error::io_error(error, 0))?;
+        #[cfg(feature = "locking")]
+        if let Some(lock) = self.lock {
+            lock_file(&file, lock, self.wait_for_lock)
+                .map_err(|error| Error::io_error(error, 0))?;
+        }
+        let source =
+            BufReadWriteFile::new(file.into()).map_err(|error| Error::io_error(error, 0))?;
+        let mut file = File::open(source)?;
+        file.path = Some(path.as_ref().to_path_buf());
+        Ok(file)
+    }
+}
+
+#[cfg(feature = "std-fs")]
 impl File<BufReadWriteFile> {
-    pub fn open_with_options<P: AsRef<Path>>(
+    /// Returns new [OpenOptions], to open a file with a lock for example
+    pub fn open_options() -> OpenOptions {
+        OpenOptions::new()
+    }
+
+    /// Opens the file at `path` with the given options,
+    /// either [OpenOptions] or [std::fs::OpenOptions]
+    pub fn open_with_options<P: AsRef<Path>, O: Into<OpenOptions>>(
         path: P,
-        options: std::fs::OpenOptions,
+        options: O,
     ) -> Result<Self, Error> {
-        let file = options
-            .open(path)
-            .map_err(|error| Error::io_error(error, 0))?;
-        let source = BufReadWriteFile::new(file.into()).unwrap();
-        File::open(source)
+        options.into().open(path)
     }
 
     /// Opens an existing dBase file in read only mode
     pub fn open_read_only<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
         let file = std::fs::File::open(path.as_ref()).map_err(|error| Error::io_error(error, 0))?;
 
-        let mut file = File::open(BufReadWriteFile::new(file.into()).unwrap())?;
-        if file.fields_info.at_least_one_field_is_memo() {
-            let p = path.as_ref();
-            let memo_type = file.header.file_type.supported_memo_type();
-            if let Some(mt) = memo_type {
-                let memo_path = p.with_extension(mt.extension());
+        let source =
+            BufReadWriteFile::new(file.into()).map_err(|error| Error::io_error(error, 0))?;
+        let mut file = File::open(source)?;
+        file.path = Some(path.as_ref().to_path_buf());
+        file.open_memo_file(path.as_ref(), false)?;
+        Ok(file)
+    }
 
-                let memo_file = std::fs::File::open(memo_path).map_err(|error| Error {
-                    record_num: 0,
-                    field: None,
-                    kind: ErrorKind::ErrorOpeningMemoFile(error),
-                })?;
+    /// Opens the memo file that goes along the table at `path`,
+    /// when the table has Memo fields and its version has memo files.
+    ///
+    /// With `write`, the memo file is opened to be written too.
+    fn open_memo_file(&mut self, path: &Path, write: bool) -> Result<(), Error> {
+        if !self.fields_info.at_least_one_field_is_memo() {
+            return Ok(());
+        }
+        if let Some(mt) = self.header.file_type.supported_memo_type() {
+            let memo_file = mt.open_memo_file(path, write).map_err(|kind| Error {
+                record_num: 0,
+                field: None,
+                field_index: None,
+                kind,
+            })?;
 
-                let memo_reader = BufReadWriteFile::new(memo_file.into())
-                    .and_then(|memo_file| MemoReader::new(mt, memo_file))
-                    .map_err(|error| Error::io_error(error, 0))?;
+            let memo_reader = BufReadWriteFile::new(memo_file.into())
+                .and_then(|memo_file| MemoReader::new(mt, memo_file))
+                .map_err(|error| Error::io_error(error, 0))?;
 
-                file.memo_reader = Some(memo_reader);
-            }
+            self.memo_reader = Some(memo_reader);
         }
-        Ok(file)
+        Ok(())
     }
 
     /// Opens an existing dBase file in write only mode
@@ -760,23 +2386,151 @@ impl File<BufReadWriteFile> {
     }
 
     /// Opens an existing dBase file in read **and** write mode
+    ///
+    /// The memo file of a table with Memo fields, when it exists, is opened in read
+    /// and write mode too, so that the Memo values of the records written are stored in it.
     pub fn open_read_write<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
         let mut options = std::fs::OpenOptions::new();
         options.read(true).write(true).create(false).truncate(false);
 
-        File::open_with_options(path, options)
+        let mut file = File::open_with_options(path.as_ref(), options)?;
+        match file.open_memo_file(path.as_ref(), true) {
+            Err(error)
+                if matches!(
+                    error.kind(),
+                    ErrorKind::ErrorOpeningMemoFile { error, .. }
+                        if error.kind() == std::io::ErrorKind::NotFound
+                ) =>
+            {
+                Ok(file)
+            }
+            result => result.map(|()| file),
+        }
+    }
+
+    /// Opens the file at `path` in read and write mode if it exists,
+    /// or creates it with the fields of the `table_info`.
+    ///
+    /// The fields of an existing file must be the ones of the `table_info`
+    /// (same names, types, lengths and decimals, in the same order),
+    /// otherwise [ErrorKind::SchemaMismatch] describing the differences is returned.
+    /// The other information of the header, like the number of records
+    /// or the date of the last update, is not compared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let path = dir.path().join("data.dbf");
+    /// let table_info = dbase::TableWriterBuilder::new()
+    ///     .add_character_field("NAME".try_into().unwrap(), 50)
+    ///     .build_table_info()?;
+    /// let mut file = dbase::File::open_or_create(&path, table_info.clone())?;
+    /// file.append_record(&dbase::Record::from_iter([(
+    ///     "NAME",
+    ///     dbase::FieldValue::Character(Some("Paris".to_string())),
+    /// )]))?;
+    /// drop(file);
+    ///
+    /// let file = dbase::File::open_or_create(&path, table_info)?;
+    /// assert_eq!(file.num_records(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open_or_create<P: AsRef<Path>>(path: P, table_info: TableInfo) -> Result<Self, Error> {
+        let file = match File::open_read_write(path.as_ref()) {
+            Ok(file) => file,
+            Err(error)
+                if matches!(
+                    error.kind(),
+                    ErrorKind::IoError(io_error) if io_error.kind() == std::io::ErrorKind::NotFound
+                ) =>
+            {
+                return File::create(path, table_info);
+            }
+            Err(error) => return Err(error),
+        };
+        let differences = field_differences(&table_info.fields_info, &file.fields_info.inner);
+        if differences.is_empty() {
+            Ok(file)
+        } else {
+            Err(Error {
+                record_num: 0,
+                field: None,
+                field_index: None,
+                kind: ErrorKind::SchemaMismatch { differences },
+            })
+        }
     }
 
     /// This function will create a file if it does not exist, and will truncate it if it does.
+    ///
+    /// When the table has Memo fields, its memo file is created too, next to it
+    /// with the extension of the memo type of the version (see [MemoFileType::extension]).
+    /// The Memo values of the records written are stored in it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let path = dir.path().join("notes.dbf");
+    /// use dbase::{FieldValue, Record};
+    ///
+    /// let table_info = dbase::TableWriterBuilder::new()
+    ///     .add_memo_field("NOTE".try_into().unwrap())
+    ///     .build_table_info()?;
+    /// let mut file = dbase::File::create(&path, table_info)?;
+    /// let note = "a text longer than a field".repeat(20);
+    /// file.append_record(&Record::from_iter([("NOTE", FieldValue::Memo(note.clone()))]))?;
+    /// assert!(path.with_extension("dbt").exists());
+    /// drop(file);
+    ///
+    /// let records = dbase::read(&path)?;
+    /// assert_eq!(records[0].get("NOTE"), Some(&FieldValue::Memo(note)));
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn create<P: AsRef<Path>>(path: P, table_info: TableInfo) -> Result<Self, Error> {
-        let file = std::fs::File::create(path).map_err(|error| Error::io_error(error, 0))?;
+        let memo_type = table_info
+            .fields_info
+            .iter()
+            .any(|info| info.field_type == FieldType::Memo)
+            .then(|| table_info.header.file_type.supported_memo_type())
+            .flatten();
+        // Opened to be read too, like the memo file
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path.as_ref())
+            .map_err(|error| Error::io_error(error, 0))?;
 
-        File::create_new(BufReadWriteFile::new(file.into()).unwrap(), table_info)
+        let dst = BufReadWriteFile::new(file.into()).map_err(|error| Error::io_error(error, 0))?;
+        let mut file = File::create_new(dst, table_info)?;
+        if let Some(memo_type) = memo_type {
+            let [memo_path, _] = memo_type.memo_path_candidates(path.as_ref());
+            let memo_reader = std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&memo_path)
+                .and_then(BufReadWriteFile::new)
+                .and_then(|memo_file| MemoReader::create(memo_type, memo_file))
+                .map_err(|error| Error::io_error(error, 0))?;
+            file.memo_reader = Some(memo_reader);
+        }
+        file.path = Some(path.as_ref().to_path_buf());
+        Ok(file)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "std-fs")]
     #[test]
     fn ensure_record_has_been_read_into_buffer() {
         let mut file = crate::File::open_read_only("tests/data/stations.dbf").unwrap();
@@ -56,6 +56,19 @@ pub enum CodePageMark {
 }
 
 impl CodePageMark {
+    /// Returns the byte that stores the mark in the header
+    ///
+    /// [CodePageMark::Invalid] is stored as 0, like [CodePageMark::Undefined].
+    pub fn as_byte(self) -> u8 {
+        u8::from(self)
+    }
+
+    /// Returns the mark stored as `byte` in the header,
+    /// [CodePageMark::Invalid] when the byte is not a known mark
+    pub fn from_byte(byte: u8) -> Self {
+        Self::from(byte)
+    }
+
     pub(crate) fn to_encoding(self) -> Option<DynEncoding> {
         #[allow(
             unused_assignments,
@@ -225,16 +238,52 @@ impl From<CodePageMark> for u8 {
 /// Known version of dBase files
 #[derive(Debug, Copy, Clone)]
 pub enum Version {
+    /// dBase II, its header and fields descriptors have a different layout,
+    /// such files can only be read
+    DBase2,
     FoxBase,
-    DBase3 { supports_memo: bool },
+    DBase3 {
+        supports_memo: bool,
+    },
     VisualFoxPro,
-    DBase4 { supports_memo: bool },
-    FoxPro2 { supports_memo: bool },
+    DBase4 {
+        supports_memo: bool,
+    },
+    FoxPro2 {
+        supports_memo: bool,
+    },
     Unknown(u8),
 }
 
 impl Version {
-    pub(crate) fn supported_memo_type(self) -> Option<MemoFileType> {
+    /// Returns the version of the same family that has a memo file
+    pub(crate) fn with_memo(self) -> Self {
+        match self {
+            Version::DBase3 { .. } => Version::DBase3 {
+                supports_memo: true,
+            },
+            Version::DBase4 { .. } => Version::DBase4 {
+                supports_memo: true,
+            },
+            Version::FoxPro2 { .. } => Version::FoxPro2 {
+                supports_memo: true,
+            },
+            other => other,
+        }
+    }
+
+    /// Returns the type of the memo file that goes along tables of this version,
+    /// `None` when the version does not support memo fields.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{MemoFileType, Version};
+    /// let version = Version::FoxPro2 { supports_memo: true };
+    /// assert_eq!(version.supported_memo_type(), Some(MemoFileType::FoxBaseMemo));
+    /// assert_eq!(MemoFileType::FoxBaseMemo.extension(), "fpt");
+    /// ```
+    pub fn supported_memo_type(self) -> Option<MemoFileType> {
         match self {
             Version::FoxBase => Some(MemoFileType::FoxBaseMemo),
             Version::DBase3 {
@@ -263,12 +312,20 @@ impl Version {
     pub(crate) fn is_visual_fox_pro(self) -> bool {
         matches!(self, Version::VisualFoxPro)
     }
+
+    /// Returns whether the file was written by a FoxPro program (FoxBase, FoxPro 2 or Visual FoxPro)
+    pub(crate) fn is_foxpro(self) -> bool {
+        matches!(
+            self,
+            Version::FoxBase | Version::FoxPro2 { .. } | Version::VisualFoxPro
+        )
+    }
 }
 
 impl From<Version> for u8 {
     fn from(v: Version) -> u8 {
         match v {
-            Version::FoxBase => 0x02,
+            Version::DBase2 | Version::FoxBase => 0x02,
             Version::DBase3 {
                 supports_memo: false,
             } => 0x03,
@@ -324,23 +381,72 @@ impl From<u8> for Version {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-pub struct TableFlags(u8);
+/// Flags of the header, only used by Visual FoxPro
+///
+/// When writing, the memo flag is computed from the fields,
+/// see [TableWriterBuilder::set_table_flags](crate::TableWriterBuilder::set_table_flags).
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct TableFlags(pub(crate) u8);
 
 impl TableFlags {
+    /// The table has a structural compound index (`.cdx`)
+    pub(crate) const STRUCTURAL_CDX: u8 = 0x01;
+    /// The table has a Memo field
+    pub(crate) const MEMO: u8 = 0x02;
+    /// The table belongs to a database (`.dbc`)
+    pub(crate) const DATABASE: u8 = 0x04;
+
+    /// Creates the flags from their meaning
+    pub fn new(structural_cdx: bool, has_memo_field: bool, is_a_database: bool) -> Self {
+        let flag = |enabled: bool, bit: u8| if enabled { bit } else { 0 };
+        Self(
+            flag(structural_cdx, Self::STRUCTURAL_CDX)
+                | flag(has_memo_field, Self::MEMO)
+                | flag(is_a_database, Self::DATABASE),
+        )
+    }
+
+    /// Creates the flags from the raw flags byte
+    pub fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw flags byte
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns whether the table has a structural compound index (`.cdx`),
+    /// that the program that wrote the table keeps up to date with the records
     pub fn has_structural_cdx(&self) -> bool {
-        (self.0 & 0x01) == 1
+        (self.0 & Self::STRUCTURAL_CDX) == Self::STRUCTURAL_CDX
     }
 
+    /// Returns whether the table has a Memo field
     pub fn has_memo_field(&self) -> bool {
-        (self.0 & 0x02) == 2
+        (self.0 & Self::MEMO) == Self::MEMO
     }
 
+    /// Returns whether the table belongs to a database (`.dbc`)
     pub fn is_a_database(&self) -> bool {
-        (self.0 & 0x03) == 1
+        (self.0 & Self::DATABASE) == Self::DATABASE
     }
 }
 
+/// Bytes of the header that the format reserves.
+///
+/// They are zeros in the headers we create, but some programs store
+/// their own markers there, so the ones read are written back as is.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ReservedBytes {
+    /// Bytes 12 and 13
+    pub(crate) after_record_size: [u8; 2],
+    /// Bytes 16 to 27, used by dBase IV for multi-user processing
+    pub(crate) multi_user: [u8; 12],
+    /// Bytes 30 and 31
+    pub(crate) after_code_page: [u8; 2],
+}
+
 /// Definition of the header struct stored at the beginning
 /// of each dBase file
 #[derive(Debug, Copy, Clone)]
@@ -354,10 +460,15 @@ pub struct Header {
     pub encryption_flag: u8,
     pub table_flags: TableFlags,
     pub code_page_mark: CodePageMark,
+    pub(crate) reserved: ReservedBytes,
 }
 
 impl Header {
     pub(crate) const SIZE: usize = 32;
+    /// Size of the part of a dBase II header that precedes the fields descriptors
+    pub(crate) const DBASE2_SIZE: usize = 8;
+    /// dBase II headers have a fixed size: room for 32 field descriptors and the terminator
+    pub(crate) const DBASE2_TOTAL_SIZE: u16 = 521;
 
     pub(crate) fn new(num_records: u32, offset: u16, size_of_records: u16) -> Self {
         let current_date = Self::get_today_date();
@@ -373,16 +484,31 @@ impl Header {
             encryption_flag: 0,
             table_flags: TableFlags(0),
             code_page_mark: CodePageMark::Undefined,
+            reserved: ReservedBytes::default(),
         }
     }
 
     fn get_today_date() -> Date {
-        let current_date = time::OffsetDateTime::now_utc().date();
-        // The year will be saved a a u8 offset from 1900
-        if current_date.year() < 1900 || current_date.year() > 2155 {
-            panic!("the year current date is out of range");
+        Self::clamp_date(time::OffsetDateTime::now_utc().date())
+    }
+
+    /// The year is saved as a u8 offset from 1900,
+    /// dates out of the 1900 - 2155 range are saturated
+    fn clamp_date(date: time::Date) -> Date {
+        if date.year() < 1900 {
+            Date::new(1, 1, 1900)
+        } else if date.year() > 2155 {
+            Date::new(31, 12, 2155)
         } else {
-            current_date.into()
+            date.into()
+        }
+    }
+
+    /// Reads the year of the last update as a two digits year,
+    /// when it is below 2000, see [ReadingOptions::two_digit_year_pivot](crate::ReadingOptions::two_digit_year_pivot)
+    pub(crate) fn apply_two_digit_year_pivot(&mut self, pivot: u32) {
+        if self.last_update.year < 2000 {
+            self.last_update.year = Date::year_from_two_digits(self.last_update.year - 1900, pivot);
         }
     }
 
@@ -405,20 +531,19 @@ impl Header {
         let offset_to_first_record = source.read_u16::<LittleEndian>()?;
         let size_of_record = source.read_u16::<LittleEndian>()?;
 
-        let _reserved = source.read_u16::<LittleEndian>()?;
+        let mut reserved = ReservedBytes::default();
+        source.read_exact(&mut reserved.after_record_size)?;
 
         let is_transaction_incomplete = (source.read_u8()? != 0) as bool;
         let encryption_flag = source.read_u8()?;
 
-        let mut _reserved = [0u8; 12];
-        source.read_exact(&mut _reserved)?;
+        source.read_exact(&mut reserved.multi_user)?;
 
         let table_flags = TableFlags(source.read_u8()?);
 
         let code_page_mark = source.read_u8().map(From::from)?;
 
-        let _reserved = source.read_u8()?;
-        let _reserved = source.read_u8()?;
+        source.read_exact(&mut reserved.after_code_page)?;
 
         Ok(Self {
             file_type,
@@ -430,13 +555,71 @@ impl Header {
             size_of_record,
             table_flags,
             code_page_mark,
+            reserved,
+        })
+    }
+
+    /// Returns whether the first bytes of a file are the ones of a dBase II file.
+    ///
+    /// dBase II and FoxBase share the same version byte (0x02),
+    /// so the rest of the bytes are checked to be a plausible dBase II header
+    /// (last update date, record size) followed by a plausible first field descriptor.
+    /// In a FoxBase file, the byte where the first field type would be is reserved (0).
+    pub(crate) fn is_dbase2(bytes: &[u8; Self::SIZE]) -> bool {
+        let month = bytes[3];
+        let day = bytes[4];
+        let size_of_record = u16::from_le_bytes([bytes[6], bytes[7]]);
+        let first_field_name = bytes[8];
+        let first_field_type = bytes[Self::DBASE2_SIZE + 11];
+
+        bytes[0] == 0x02
+            && (1..=12).contains(&month)
+            && (1..=31).contains(&day)
+            && size_of_record > 1
+            && first_field_name.is_ascii_alphabetic()
+            && matches!(first_field_type, b'C' | b'N' | b'L')
+    }
+
+    /// Reads the header of a dBase II file
+    ///
+    /// | offset | content                                  |
+    /// |--------|------------------------------------------|
+    /// | 0      | version (0x02)                           |
+    /// | 1..3   | number of records                        |
+    /// | 3..6   | date of last update (month, day, year)   |
+    /// | 6..8   | size of a record                         |
+    pub(crate) fn read_dbase2_from<T: Read>(source: &mut T) -> Result<Self, std::io::Error> {
+        let _version = source.read_u8()?;
+        let num_records = source.read_u16::<LittleEndian>()?;
+
+        let mut date_bytes = [0u8; 3];
+        source.read_exact(&mut date_bytes)?;
+        let last_update = Date {
+            year: 1900u32 + date_bytes[2] as u32,
+            month: date_bytes[0] as u32,
+            day: date_bytes[1] as u32,
+        };
+
+        let size_of_record = source.read_u16::<LittleEndian>()?;
+
+        Ok(Self {
+            file_type: Version::DBase2,
+            last_update,
+            num_records: u32::from(num_records),
+            offset_to_first_record: Self::DBASE2_TOTAL_SIZE,
+            size_of_record,
+            is_transaction_incomplete: false,
+            encryption_flag: 0,
+            table_flags: TableFlags(0),
+            code_page_mark: CodePageMark::Undefined,
+            reserved: ReservedBytes::default(),
         })
     }
 
     pub(crate) fn write_to<T: Write>(&self, dest: &mut T) -> std::io::Result<()> {
         dest.write_u8(u8::from(self.file_type))?;
 
-        dest.write_u8((self.last_update.year() - 1900) as u8)?;
+        dest.write_u8((self.last_update.year().clamp(1900, 2155) - 1900) as u8)?;
         dest.write_u8(self.last_update.month() as u8)?;
         dest.write_u8(self.last_update.day() as u8)?;
 
@@ -444,19 +627,15 @@ impl Header {
         dest.write_u16::<LittleEndian>(self.offset_to_first_record)?;
         dest.write_u16::<LittleEndian>(self.size_of_record)?;
 
-        // Reserved
-        dest.write_u16::<LittleEndian>(0)?;
+        dest.write_all(&self.reserved.after_record_size)?;
         dest.write_u8(u8::from(self.is_transaction_incomplete))?;
         dest.write_u8(self.encryption_flag)?;
 
-        let _reserved = [0u8; 12];
-        dest.write_all(&_reserved)?;
+        dest.write_all(&self.reserved.multi_user)?;
 
         dest.write_u8(self.table_flags.0)?;
         dest.write_u8(self.code_page_mark.into())?;
-        // Reserved
-        dest.write_u8(0)?;
-        dest.write_u8(0)?;
+        dest.write_all(&self.reserved.after_code_page)?;
         Ok(())
     }
 
@@ -515,4 +694,56 @@ mod test {
 
         assert_eq!(hdr_bytes_written, hdr_bytes);
     }
+
+    #[test]
+    fn dbase2_detection() {
+        let mut file = File::open("tests/data/dbase2.dbf").unwrap();
+        let mut bytes = [0u8; Header::SIZE];
+        file.read_exact(&mut bytes).unwrap();
+        assert!(Header::is_dbase2(&bytes));
+
+        let hdr = Header::read_dbase2_from(&mut &bytes[..]).unwrap();
+        assert_eq!(hdr.num_records, 3);
+        assert_eq!(hdr.size_of_record, 22);
+        assert_eq!(hdr.last_update, Date::new(14, 7, 1984));
+
+        // A FoxBase header shares the version byte
+        let mut foxbase = Header::new(3, 97, 22);
+        foxbase.file_type = Version::FoxBase;
+        let mut out = Cursor::new(Vec::<u8>::new());
+        foxbase.write_to(&mut out).unwrap();
+        let bytes: [u8; Header::SIZE] = out.into_inner().try_into().unwrap();
+        assert_eq!(bytes[0], 0x02);
+        assert!(!Header::is_dbase2(&bytes));
+    }
+
+    #[test]
+    fn header_dates_out_of_range_are_saturated() {
+        let date = time::Date::from_calendar_date(2200, time::Month::March, 1).unwrap();
+        assert_eq!(Header::clamp_date(date), Date::new(31, 12, 2155));
+        let date = time::Date::from_calendar_date(1850, time::Month::March, 1).unwrap();
+        assert_eq!(Header::clamp_date(date), Date::new(1, 1, 1900));
+
+        let mut hdr = Header::new(0, 33, 1);
+        hdr.last_update = Date::new(1, 3, 2200);
+        let mut out = Cursor::new(Vec::<u8>::new());
+        hdr.write_to(&mut out).unwrap();
+        assert_eq!(out.get_ref()[1], 255);
+    }
+
+    #[test]
+    fn header_two_digit_year() {
+        let mut hdr = Header::new(0, 33, 1);
+        hdr.last_update = Date::new(1, 3, 1905);
+        hdr.apply_two_digit_year_pivot(50);
+        assert_eq!(hdr.last_update, Date::new(1, 3, 2005));
+
+        hdr.last_update = Date::new(1, 3, 1999);
+        hdr.apply_two_digit_year_pivot(50);
+        assert_eq!(hdr.last_update, Date::new(1, 3, 1999));
+
+        hdr.last_update = Date::new(1, 3, 2010);
+        hdr.apply_two_digit_year_pivot(50);
+        assert_eq!(hdr.last_update, Date::new(1, 3, 2010));
+    }
 }
@@ -0,0 +1,34 @@
+use std::ops::{Deref, DerefMut};
+
+/// Source or destination owned by a struct that implements `Drop`
+///
+/// The fields of such a struct cannot be moved out, so its `into_inner`
+/// method takes the value instead, and the struct is then dropped
+/// without using it.
+#[derive(Debug)]
+pub(crate) struct Inner<T>(Option<T>);
+
+impl<T> Inner<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self(Some(value))
+    }
+
+    /// Moves the value out, it must not be used afterwards
+    pub(crate) fn take(&mut self) -> T {
+        self.0.take().expect("the inner value was already taken")
+    }
+}
+
+impl<T> Deref for Inner<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.0.as_ref().expect("the inner value was taken")
+    }
+}
+
+impl<T> DerefMut for Inner<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.0.as_mut().expect("the inner value was taken")
+    }
+}
@@ -0,0 +1,317 @@
+//! Conversions between [Record]s and [serde_json::Value]s
+use serde_json::{Map, Number, Value};
+
+use crate::field::names_match;
+use crate::field::types::{Date, DateTime, FieldType, FieldValue, Time};
+use crate::{Error, ErrorKind, FieldInfo, Record};
+
+fn number_from_f64(value: f64) -> Value {
+    // NaN and infinity cannot be represented in JSON
+    Number::from_f64(value).map_or(Value::Null, Value::Number)
+}
+
+fn date_to_iso(date: &Date) -> String {
+    format!("{:04}-{:02}-{:02}", date.year, date.month, date.day)
+}
+
+fn datetime_to_iso(datetime: &DateTime) -> String {
+    let time = datetime.time();
+    format!(
+        "{}T{:02}:{:02}:{:02}",
+        date_to_iso(&datetime.date()),
+        time.hours(),
+        time.minutes(),
+        time.seconds()
+    )
+}
+
+impl From<&FieldValue> for Value {
+    /// Converts the value to JSON
+    ///
+    /// - `None` values are `null`
+    /// - Dates and DateTimes are ISO 8601 strings (`YYYY-MM-DD`, `YYYY-MM-DDTHH:MM:SS`)
+    /// - Memos are strings
+    /// - Binary Character values are arrays of bytes
+    fn from(value: &FieldValue) -> Self {
+        match value {
+            FieldValue::Character(s) => s.clone().map_or(Value::Null, Value::String),
+            FieldValue::CharacterBinary(bytes) => Value::from(bytes.as_slice()),
+            FieldValue::Numeric(n) => n.map_or(Value::Null, number_from_f64),
+            FieldValue::Logical(b) => b.map_or(Value::Null, Value::Bool),
+            FieldValue::Date(d) => d
+                .as_ref()
+                .map_or(Value::Null, |d| Value::String(date_to_iso(d))),
+            FieldValue::Float(f) => f.map_or(Value::Null, |f| number_from_f64(f64::from(f))),
+            FieldValue::Integer(i) => Value::from(*i),
+            FieldValue::Currency(c) => number_from_f64(*c),
+            FieldValue::DateTime(dt) => Value::String(datetime_to_iso(dt)),
+            FieldValue::Double(d) => number_from_f64(*d),
+            FieldValue::Memo(m) => Value::String(m.clone()),
+        }
+    }
+}
+
+impl From<&Record> for Value {
+    /// Converts the record to a JSON object, the keys are the field names
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// let records = reader.read()?;
+    /// let json = serde_json::Value::from(&records[0]);
+    /// assert_eq!(json["name"], "linestring1");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn from(record: &Record) -> Self {
+        let map = record
+            .as_ref()
+            .iter()
+            .map(|(name, value)| (name.to_string(), Value::from(value)))
+            .collect::<Map<String, Value>>();
+        Value::Object(map)
+    }
+}
+
+fn parse_iso_date(s: &str) -> Option<Date> {
+    let digits = s.replace('-', "");
+    if digits.len() != 8 {
+        return None;
+    }
+    let date = digits.parse::<Date>().ok()?;
+    if date.month > 12 || date.day > 31 {
+        None
+    } else {
+        Some(date)
+    }
+}
+
+fn parse_iso_datetime(s: &str) -> Option<DateTime> {
+    let (date, time) = s.split_once(['T', ' ']).unwrap_or((s, "00:00:00"));
+    let date = parse_iso_date(date)?;
+
+    let mut parts = time.trim_end_matches('Z').splitn(3, ':');
+    let mut next_part = || -> Option<u32> {
+        match parts.next() {
+            // Fractional seconds are dropped
+            Some(part) => part.split('.').next()?.parse().ok(),
+            None => Some(0),
+        }
+    };
+    let (hours, minutes, seconds) = (next_part()?, next_part()?, next_part()?);
+    if hours > 24 || minutes > 60 || seconds > 60 {
+        return None;
+    }
+    Some(DateTime::new(date, Time::new(hours, minutes, seconds)))
+}
+
+fn parse_logical(s: &str) -> Option<Option<bool>> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "" | "?" => Some(None),
+        "t" | "y" | "true" | "yes" => Some(Some(true)),
+        "f" | "n" | "false" | "no" => Some(Some(false)),
+        _ => None,
+    }
+}
+
+fn as_f64(value: &Value) -> Option<Option<f64>> {
+    match value {
+        Value::Null => Some(None),
+        Value::Number(n) => n.as_f64().map(Some),
+        Value::String(s) if s.trim().is_empty() => Some(None),
+        Value::String(s) => s.trim().parse().ok().map(Some),
+        _ => None,
+    }
+}
+
+fn as_string(value: &Value) -> Option<Option<String>> {
+    match value {
+        Value::Null => Some(None),
+        Value::String(s) => Some(Some(s.clone())),
+        Value::Number(n) => Some(Some(n.to_string())),
+        Value::Bool(b) => Some(Some(b.to_string())),
+        _ => None,
+    }
+}
+
+/// Reads the array of bytes of a binary Character field
+fn binary_from_json(value: &Value) -> Option<FieldValue> {
+    let bytes = match value {
+        Value::Null => vec![],
+        Value::Array(values) => values
+            .iter()
+            .map(|v| v.as_u64().and_then(|b| u8::try_from(b).ok()))
+            .collect::<Option<Vec<u8>>>()?,
+        _ => return None,
+    };
+    Some(FieldValue::CharacterBinary(bytes))
+}
+
+/// Coerces the JSON value into a value of the type of the field
+fn field_value_from_json(value: &Value, field_type: FieldType) -> Option<FieldValue> {
+    let field_value = match field_type {
+        FieldType::Character => FieldValue::Character(as_string(value)?),
+        FieldType::Numeric => FieldValue::Numeric(as_f64(value)?),
+        FieldType::Float => FieldValue::Float(as_f64(value)?.map(|f| f as f32)),
+        FieldType::Logical => match value {
+            Value::Null => FieldValue::Logical(None),
+            Value::Bool(b) => FieldValue::Logical(Some(*b)),
+            Value::String(s) => FieldValue::Logical(parse_logical(s)?),
+            _ => return None,
+        },
+        FieldType::Date => match value {
+            Value::Null => FieldValue::Date(None),
+            Value::String(s) if s.trim().is_empty() => FieldValue::Date(None),
+            Value::String(s) => FieldValue::Date(Some(parse_iso_date(s.trim())?)),
+            _ => return None,
+        },
+        FieldType::Integer => {
+            let i = match value {
+                Value::Number(n) => n.as_i64()?,
+                Value::String(s) => s.trim().parse().ok()?,
+                _ => return None,
+            };
+            FieldValue::Integer(i32::try_from(i).ok()?)
+        }
+        FieldType::Currency => FieldValue::Currency(as_f64(value)??),
+        FieldType::Double => FieldValue::Double(as_f64(value)??),
+        FieldType::DateTime | FieldType::Timestamp => match value {
+            Value::String(s) => FieldValue::DateTime(parse_iso_datetime(s.trim())?),
+            _ => return None,
+        },
+        FieldType::Memo => FieldValue::Memo(as_string(value)?.unwrap_or_default()),
+        FieldType::SystemNullFlags => binary_from_json(value)?,
+    };
+    Some(field_value)
+}
+
+impl Record {
+    /// Creates a record from a JSON object, the values are converted
+    /// to the type of the corresponding field.
+    ///
+    /// Keys are matched with field names ignoring the case (see [Record::get_ignoring_case]),
+    /// missing keys and `null` give `None` values.
+    ///
+    /// Strings are parsed when the field is not a Character field
+    /// (numbers, `"T"`/`"F"`, `"true"`/`"false"`, ISO 8601 dates),
+    /// numbers and booleans are formatted when the field is a Character field.
+    ///
+    /// An error is returned if the value is not an object
+    /// or if a value cannot be converted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let json = serde_json::json!({
+    ///     "name": "Dalaran",
+    ///     "marker-col": "#0f0f0f",
+    ///     "marker-sym": "underground",
+    ///     "line": "purple",
+    /// });
+    /// let record = dbase::Record::from_json(&json, reader.fields())?;
+    /// assert_eq!(
+    ///     record.get("name"),
+    ///     Some(&dbase::FieldValue::Character(Some("Dalaran".to_string())))
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_json(value: &Value, fields: &[FieldInfo]) -> Result<Record, Error> {
+        let object = value.as_object().ok_or_else(|| Error {
+            record_num: 0,
+            field: None,
+            field_index: None,
+            kind: ErrorKind::Message(format!("Expected a JSON object, got '{}'", value)),
+        })?;
+
+        let mut record = Record::default();
+        for info in fields {
+            let field_value = object
+                .get(&*info.name)
+                .or_else(|| {
+                    object
+                        .iter()
+                        .find(|(key, _)| names_match(key, &info.name))
+                        .map(|(_, value)| value)
+                })
+                .unwrap_or(&Value::Null);
+
+            let converted = if info.flags.is_binary() {
+                binary_from_json(field_value)
+            } else {
+                field_value_from_json(field_value, info.field_type)
+            };
+            match converted {
+                Some(converted) => {
+                    record.insert(info.name.to_string(), converted);
+                }
+                None => {
+                    return Err(Error {
+                        record_num: 0,
+                        field: Some(info.clone()),
+                        field_index: None,
+                        kind: ErrorKind::Message(format!(
+                            "The JSON value '{}' cannot be converted to a {:?} field",
+                            field_value, info.field_type
+                        )),
+                    })
+                }
+            }
+        }
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn iso_dates() {
+        assert_eq!(parse_iso_date("2021-03-04"), Some(Date::new(4, 3, 2021)));
+        assert_eq!(parse_iso_date("20210304"), Some(Date::new(4, 3, 2021)));
+        assert_eq!(parse_iso_date("2021-13-04"), None);
+        assert_eq!(parse_iso_date("2021-03"), None);
+
+        let datetime = DateTime::new(Date::new(4, 3, 2021), Time::new(10, 20, 30));
+        assert_eq!(datetime_to_iso(&datetime), "2021-03-04T10:20:30");
+        assert_eq!(parse_iso_datetime("2021-03-04T10:20:30"), Some(datetime));
+        assert_eq!(
+            parse_iso_datetime("2021-03-04 10:20:30.250Z"),
+            Some(datetime)
+        );
+    }
+
+    #[test]
+    fn coercion() {
+        assert_eq!(
+            field_value_from_json(&Value::from("12.5"), FieldType::Numeric),
+            Some(FieldValue::Numeric(Some(12.5)))
+        );
+        assert_eq!(
+            field_value_from_json(&Value::from(12), FieldType::Character),
+            Some(FieldValue::Character(Some("12".to_string())))
+        );
+        assert_eq!(
+            field_value_from_json(&Value::from("F"), FieldType::Logical),
+            Some(FieldValue::Logical(Some(false)))
+        );
+        assert_eq!(
+            field_value_from_json(&Value::Null, FieldType::Date),
+            Some(FieldValue::Date(None))
+        );
+        assert_eq!(
+            field_value_from_json(&Value::from(i64::MAX), FieldType::Integer),
+            None
+        );
+        assert_eq!(field_value_from_json(&Value::Null, FieldType::Double), None);
+        assert_eq!(
+            field_value_from_json(&Value::from(true), FieldType::Numeric),
+            None
+        );
+    }
+}
@@ -81,6 +81,13 @@
 //! replacement character. Alternatively [`Unicode`] is available, to return an [`Err`] when data
 //! can't be represented as Unicode.
 //!
+//! ## Untrusted files
+//!
+//! Reading (with [Reader] or [File]) is meant to be usable on files coming from untrusted
+//! sources: corrupted headers, fields or memos result in an [Error], not a panic.
+//! Memo lengths are checked against the size of the memo file before allocating.
+//! The `fuzz` directory contains a `cargo fuzz` target exercising this.
+//!
 //! ## Deserialisation
 //!
 //! If you know what kind of data to expect from a particular file you can use implement
@@ -175,11 +182,13 @@
 //!
 //! ```
 //! # fn main() -> Result<(), dbase::Error> {
+//! # let tmp_dir = tempfile::tempdir().unwrap();
+//! # let path = tmp_dir.path().join("stations.dbf");
 //! let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
 //! let mut stations = reader.read()?;
 //!
 //! let mut writer = dbase::TableWriterBuilder::from_reader(reader)
-//!     .build_with_file_dest("stations.dbf").unwrap();
+//!     .build_with_file_dest(&path).unwrap();
 //!
 //! stations[0].get_mut("line").and_then(|_old| Some("Red".to_string()));
 //! writer.write_records(&stations)?;
@@ -210,7 +219,7 @@
 //! let mut writer = TableWriterBuilder::new()
 //!     .add_character_field(FieldName::try_from("Nick Name").unwrap(), 50)
 //!     .add_numeric_field(FieldName::try_from("Age").unwrap(), 20, 10)
-//!     .build_with_dest(Cursor::new(Vec::<u8>::new()));
+//!     .build_with_dest(Cursor::new(Vec::<u8>::new())).unwrap();
 //!
 //!
 //! let records = User{
@@ -246,7 +255,7 @@
 //! let writer = TableWriterBuilder::new()
 //!     .add_character_field(FieldName::try_from("Nick Name").unwrap(), 50)
 //!     .add_numeric_field(FieldName::try_from("Age").unwrap(), 20, 10)
-//!     .build_with_dest(Cursor::new(Vec::<u8>::new()));
+//!     .build_with_dest(Cursor::new(Vec::<u8>::new())).unwrap();
 //!
 //!
 //! let records = vec![User{
@@ -260,6 +269,33 @@
 //! # fn main() {}
 //! ```
 //!
+//! With the `derive` optional feature, `#[derive(DbaseTable)]` implements
+//! [ReadableRecord], [WritableRecord] and [RecordCompare] for a struct, and generates
+//! a `table_builder()` function returning a [TableWriterBuilder] with its fields,
+//! so the fields are declared only once.
+//!
+//! # JSON
+//!
+//! With the `json` optional feature, a [Record] can be converted to a `serde_json::Value`
+//! (`serde_json::Value::from(&record)`) and created from one with `Record::from_json`,
+//! which converts the JSON values to the types of the fields.
+//!
+//! # Tracing
+//!
+//! With the `tracing` optional feature, opening tables, reading and writing records
+//! emit [tracing](https://docs.rs/tracing) spans and events with the `dbase` target:
+//! an `open_table` span with the version, code page, number of records and of fields,
+//! progress events every 1000 records, warnings when a fallback is used
+//! (no code page in the header, record size of the header that does not match the fields)
+//! and errors before they are returned.
+//!
+//! # Without the file system
+//!
+//! The `std-fs` feature, enabled by default, provides the functions opening tables
+//! and memo files by path (`read`, `Reader::from_path`, `File::open_read_only`, ...).
+//! Without it (`default-features = false`), tables are read and written with any
+//! `Read + Seek` and `Write + Seek` source, such as a `std::io::Cursor` over a buffer.
+//!
 //! # File
 //!
 //! This crate also has a third option to handle dbase files, the [File]
@@ -274,6 +310,9 @@ extern crate byteorder;
 extern crate serde;
 extern crate time;
 
+#[macro_use]
+mod trace;
+
 #[cfg(feature = "serde")]
 mod de;
 #[cfg(feature = "serde")]
@@ -282,36 +321,81 @@ mod ser;
 #[cfg(feature = "yore")]
 pub use yore;
 
+#[cfg(feature = "derive")]
+pub use dbase_derive::DbaseTable;
+
 #[cfg(feature = "datafusion")]
 mod datafusion;
 
+#[cfg(feature = "json")]
+mod json;
+
+#[cfg(feature = "std-fs")]
+mod convert;
 pub mod encoding;
 mod error;
 mod field;
 mod file;
 mod header;
+mod inner;
+pub mod limits;
 mod memo;
+mod preview;
+mod progress;
 mod reading;
 mod record;
+pub mod schema;
 mod writing;
 
-pub use file::{FieldIndex, FieldRef, File, RecordIndex, RecordRef};
+#[cfg(feature = "std-fs")]
+pub use crate::convert::{
+    convert_encoding, convert_encoding_with_policy, ConversionReport, OverflowPolicy,
+};
+#[cfg(feature = "std-fs")]
+pub use file::OpenOptions;
+pub use file::{
+    FieldIndex, FieldRef, File, RecordIndex, RecordRef, ReloadReport, Truncate, Warning,
+};
 
 #[cfg(feature = "datafusion")]
-pub use crate::datafusion::{DbaseTable, DbaseTableFactory};
+pub use crate::datafusion::{
+    arrow_to_field_info, field_info_to_arrow, field_type_to_arrow, DbaseTable, DbaseTableFactory,
+};
 pub use crate::encoding::{Encoding, Unicode, UnicodeLossy};
-pub use crate::error::{Error, ErrorKind, FieldIOError};
-pub use crate::field::types::{Date, DateTime, FieldType, FieldValue, Time, TrimOption};
-pub use crate::field::{FieldConversionError, FieldInfo, FieldName};
-pub use crate::header::CodePageMark;
+pub use crate::error::{DecodeError, EncodeError, Error, ErrorKind, FieldIOError};
+pub use crate::field::types::{
+    Date, DateFormat, DateTime, DisplayStyle, FieldType, FieldValue, Time, TrimOption,
+};
+pub use crate::field::{
+    DeletionFlag, FieldConversionError, FieldFlags, FieldInfo, FieldName, FromFieldValue,
+    SchemaWarning, ToFieldValue,
+};
+pub use crate::header::{CodePageMark, TableFlags, Version};
+pub use crate::memo::MemoFileType;
+pub use crate::preview::{TablePreview, DEFAULT_PREVIEW_COLUMN_WIDTH};
+pub use crate::progress::{Progress, ProgressCallback, DEFAULT_PROGRESS_INTERVAL};
+#[cfg(feature = "std-fs")]
+pub use crate::reading::{read, required_memo_path};
 pub use crate::reading::{
-    read, FieldIterator, NamedValue, ReadableRecord, Reader, ReaderBuilder, ReadingOptions,
-    RecordIterator, TableInfo,
+    read_table_info, DateRangeIterator, DecodeIssue, DecodeMode, FieldIterator,
+    FilteredRecordIterator, NamedValue, ReadableRecord, Reader, ReaderBuilder, ReadingOptions,
+    RecordCounts, RecordIterator, RecordWithDeletionIterator, TableInfo, UnknownFieldTypeHandling,
+    DEFAULT_MAX_MEMO_SIZE, ESTIMATED_MEMO_SIZE,
+};
+pub use crate::record::{CompareTolerance, Record, RecordCompare};
+pub use crate::schema::infer_schema;
+pub use crate::writing::{
+    AutoIncrementPolicy, CopyOptions, DeletedPolicy, DynWritableField, FieldWriter, TableWriter,
+    TableWriterBuilder, ValidationIssue, ValidationReport, WritableRecord,
+    DEFAULT_MAX_VALIDATION_ISSUES,
 };
-pub use crate::record::Record;
-pub use crate::writing::{FieldWriter, TableWriter, TableWriterBuilder, WritableRecord};
 
-/// macro to define a struct that implements the ReadableRecord and WritableRecord
+/// macro to define a struct that implements the ReadableRecord, WritableRecord
+/// and RecordCompare traits
+///
+/// The fields are read by position, with `#[dbase(check_names)]` the names
+/// of the struct fields are also checked against the names of the table fields
+/// (ignoring the case), see [FieldIterator::read_named_field_as].
 ///
 /// # Examples
 ///
@@ -326,15 +410,35 @@ pub use crate::writing::{FieldWriter, TableWriter, TableWriterBuilder, WritableR
 ///         age: f64
 ///     }
 /// );
+///
+/// dbase_record!(
+///     #[dbase(check_names)]
+///     #[derive(Debug)]
+///     struct CheckedUserRecord {
+///         first_name: String,
+///         last_name: String,
+///         age: f64
+///     }
+/// );
 /// # }
 /// ```
 #[macro_export]
 macro_rules! dbase_record {
+    (@read check_names $field_iterator:ident, $field_name:ident, $field_type:ty) => {
+        $field_iterator
+            .read_named_field_as::<$field_type>(stringify!($field_name))?
+            .value
+    };
+    (@read by_position $field_iterator:ident, $field_name:ident, $field_type:ty) => {
+        $field_iterator
+            .read_next_field_as::<$field_type>()?
+            .value
+    };
     (
+        @define $read_mode:ident
         $(#[derive($($derives:meta),*)])?
         $vis:vis struct $name:ident {
             $( $visf:vis $field_name:ident: $field_type:ty),+
-            $(,)?
         }
     ) => {
 
@@ -350,9 +454,9 @@ macro_rules! dbase_record {
                 {
                     Ok(Self {
                         $(
-                            $field_name: field_iterator
-                                .read_next_field_as::<$field_type>()?
-                                .value
+                            $field_name: dbase::dbase_record!(
+                                @read $read_mode field_iterator, $field_name, $field_type
+                            )
                         ),+
                     })
             }
@@ -368,5 +472,49 @@ macro_rules! dbase_record {
                 Ok(())
            }
         }
+
+        impl dbase::RecordCompare for $name {
+            fn to_record(&self) -> dbase::Record {
+                let mut record = dbase::Record::default();
+                $(
+                    record.insert(
+                        stringify!($field_name).to_owned(),
+                        dbase::FieldValue::from(self.$field_name.clone()),
+                    );
+                )+
+                record
+            }
+        }
+    };
+    (
+        #[dbase(check_names)]
+        $(#[derive($($derives:meta),*)])?
+        $vis:vis struct $name:ident {
+            $( $visf:vis $field_name:ident: $field_type:ty),+
+            $(,)?
+        }
+    ) => {
+        dbase::dbase_record!(
+            @define check_names
+            $(#[derive($($derives),*)])?
+            $vis struct $name {
+                $($visf $field_name: $field_type),+
+            }
+        );
+    };
+    (
+        $(#[derive($($derives:meta),*)])?
+        $vis:vis struct $name:ident {
+            $( $visf:vis $field_name:ident: $field_type:ty),+
+            $(,)?
+        }
+    ) => {
+        dbase::dbase_record!(
+            @define by_position
+            $(#[derive($($derives),*)])?
+            $vis struct $name {
+                $($visf $field_name: $field_type),+
+            }
+        );
     };
 }
@@ -0,0 +1,65 @@
+//! Limits of the dBase formats
+//!
+//! Some limits come from the layout of the header (sizes stored as `u16`),
+//! others from the programs that defined each version.
+use crate::Version;
+
+/// Maximum length of a field that is not a memo, the length is stored as a `u8`
+pub const MAX_FIELD_LENGTH: usize = u8::MAX as usize;
+
+/// Maximum length of a Character field, its length is stored across the length
+/// byte and the decimal count byte, see [FieldInfo::length](crate::FieldInfo::length)
+pub const MAX_CHARACTER_FIELD_LENGTH: usize = u16::MAX as usize;
+
+/// Maximum size of a record, the size is stored as a `u16` in the header
+pub const MAX_RECORD_SIZE: usize = u16::MAX as usize;
+
+/// Maximum offset to the first record, the offset is stored as a `u16` in the header
+pub const MAX_OFFSET_TO_FIRST_RECORD: usize = u16::MAX as usize;
+
+/// Maximum number of fields of a dBase II table
+pub const DBASE2_MAX_FIELDS: usize = 32;
+
+/// Maximum size of a dBase II record
+pub const DBASE2_MAX_RECORD_SIZE: usize = 1000;
+
+/// Maximum number of fields of a dBase III table
+pub const DBASE3_MAX_FIELDS: usize = 128;
+
+/// Maximum number of fields of dBase IV and FoxPro tables
+pub const DBASE4_MAX_FIELDS: usize = 255;
+
+/// Maximum number of fields of a Visual FoxPro table
+pub const VISUAL_FOXPRO_MAX_FIELDS: usize = 255;
+
+/// Maximum size of a Visual FoxPro record
+pub const VISUAL_FOXPRO_MAX_RECORD_SIZE: usize = 65_500;
+
+/// Returns the maximum number of fields a table of the `version` can have
+///
+/// # Example
+///
+/// ```
+/// use dbase::{limits, Version};
+///
+/// let version = Version::DBase3 { supports_memo: false };
+/// assert_eq!(limits::max_fields(version), limits::DBASE3_MAX_FIELDS);
+/// ```
+pub fn max_fields(version: Version) -> usize {
+    match version {
+        Version::DBase2 => DBASE2_MAX_FIELDS,
+        Version::FoxBase | Version::DBase3 { .. } => DBASE3_MAX_FIELDS,
+        Version::VisualFoxPro => VISUAL_FOXPRO_MAX_FIELDS,
+        Version::DBase4 { .. } | Version::FoxPro2 { .. } | Version::Unknown(_) => DBASE4_MAX_FIELDS,
+    }
+}
+
+/// Returns the maximum size of a record (including the deletion flag)
+/// of a table of the `version`
+pub fn max_record_size(version: Version) -> usize {
+    match version {
+        Version::DBase2 => DBASE2_MAX_RECORD_SIZE,
+        Version::VisualFoxPro => VISUAL_FOXPRO_MAX_RECORD_SIZE,
+        _ => MAX_RECORD_SIZE,
+    }
+}
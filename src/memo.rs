@@ -1,21 +1,107 @@
-use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
-use std::io::{Read, Seek, SeekFrom};
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Seek, SeekFrom, Write};
+#[cfg(feature = "std-fs")]
+use std::path::{Path, PathBuf};
+
+use crate::{Encoding, ErrorKind};
+
+/// Number of bytes copied at once when streaming a memo
+const STREAM_CHUNK_SIZE: usize = 8192;
+const ZEROS: [u8; 512] = [0; 512];
+/// Size of the header of the memo files, the memos start after it
+const MEMO_HEADER_SIZE: u32 = 512;
+/// Block size of the FoxPro memo files we create, the one FoxPro uses by default
+#[cfg(feature = "std-fs")]
+const FOXPRO_BLOCK_SIZE: u16 = 64;
+/// Ends the text of dBase III memos
+const DBASE3_MEMO_TERMINATOR: u8 = 0x1A;
+/// Ends the text of dBase IV memos
+const DBASE4_MEMO_TERMINATOR: u8 = 0x1F;
+/// Starts the blocks of dBase IV memos, followed by the length of the memo
+const DBASE4_BLOCK_SIGNATURE: u32 = 0x0008_FFFF;
+/// Type of the FoxPro memos holding text
+const FOXPRO_TEXT_MEMO: u32 = 1;
 
 /// The different types of Memo file structure there seem to exist
-#[derive(Debug, PartialEq, Copy, Clone)]
-pub(crate) enum MemoFileType {
+///
+/// The memo type of a table is given by its version,
+/// see [Version::supported_memo_type](crate::Version::supported_memo_type).
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum MemoFileType {
+    /// dBase III memo file (`.dbt`), with blocks of 512 bytes
     DbaseMemo,
+    /// dBase IV memo file (`.dbt`), with a block size set in its header
     DbaseMemo4,
+    /// FoxBase / FoxPro memo file (`.fpt`)
     FoxBaseMemo,
 }
 
 impl MemoFileType {
-    pub(crate) const fn extension(self) -> &'static str {
+    /// Returns the extension of the memo files of this type, in lowercase
+    pub const fn extension(self) -> &'static str {
         match self {
             MemoFileType::DbaseMemo | MemoFileType::DbaseMemo4 => "dbt",
             MemoFileType::FoxBaseMemo => "fpt",
         }
     }
+
+    /// Returns the paths where the memo file that goes along the dbf file
+    /// at `dbf_path` may be.
+    ///
+    /// The memo file is expected to have the same stem as the dbf file,
+    /// as file systems may be case-sensitive both the lowercase and the
+    /// uppercase extension are candidates (the one matching the case of the dbf
+    /// extension first).
+    #[cfg(feature = "std-fs")]
+    pub(crate) fn memo_path_candidates(self, dbf_path: &Path) -> [PathBuf; 2] {
+        let lowercase = self.extension();
+        let uppercase = lowercase.to_ascii_uppercase();
+        let dbf_extension_is_uppercase = dbf_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.chars().all(|c| c.is_ascii_uppercase()));
+
+        if dbf_extension_is_uppercase {
+            [
+                dbf_path.with_extension(&uppercase),
+                dbf_path.with_extension(lowercase),
+            ]
+        } else {
+            [
+                dbf_path.with_extension(lowercase),
+                dbf_path.with_extension(&uppercase),
+            ]
+        }
+    }
+
+    /// Opens the memo file that goes along the dbf file at `dbf_path`,
+    /// trying each of the [memo_path_candidates](Self::memo_path_candidates).
+    ///
+    /// With `write`, the memo file is opened to be read and written.
+    #[cfg(feature = "std-fs")]
+    pub(crate) fn open_memo_file(
+        self,
+        dbf_path: &Path,
+        write: bool,
+    ) -> Result<std::fs::File, ErrorKind> {
+        let mut first_error = None;
+        for path in self.memo_path_candidates(dbf_path) {
+            match std::fs::OpenOptions::new()
+                .read(true)
+                .write(write)
+                .open(&path)
+            {
+                Ok(file) => return Ok(file),
+                Err(error) => {
+                    if first_error.is_none() {
+                        first_error = Some((path, error));
+                    }
+                }
+            }
+        }
+        let (path, error) = first_error.expect("at least one memo path was tried");
+        Err(ErrorKind::ErrorOpeningMemoFile { path, error })
+    }
 }
 
 /// Although there are different memo file type with each a different
@@ -31,7 +117,11 @@ impl MemoHeader {
         src: &mut R,
         memo_type: MemoFileType,
     ) -> std::io::Result<Self> {
-        let next_available_block_index = src.read_u32::<LittleEndian>()?;
+        // FoxPro memo files store their header in big endian
+        let next_available_block_index = match memo_type {
+            MemoFileType::DbaseMemo | MemoFileType::DbaseMemo4 => src.read_u32::<LittleEndian>()?,
+            MemoFileType::FoxBaseMemo => src.read_u32::<BigEndian>()?,
+        };
         let block_size = match memo_type {
             MemoFileType::DbaseMemo | MemoFileType::DbaseMemo4 => {
                 match src.read_u16::<LittleEndian>()? {
@@ -50,6 +140,51 @@ impl MemoHeader {
             block_size,
         })
     }
+
+    /// Header of an empty memo file of the given type
+    #[cfg(feature = "std-fs")]
+    pub(crate) fn empty(memo_type: MemoFileType) -> Self {
+        let block_size = match memo_type {
+            MemoFileType::DbaseMemo | MemoFileType::DbaseMemo4 => MEMO_HEADER_SIZE,
+            MemoFileType::FoxBaseMemo => u32::from(FOXPRO_BLOCK_SIZE),
+        };
+        Self {
+            next_available_block_index: MEMO_HEADER_SIZE / block_size,
+            block_size,
+        }
+    }
+
+    /// Writes the header, padded to [MEMO_HEADER_SIZE]
+    #[cfg(feature = "std-fs")]
+    pub(crate) fn write_to<W: Write>(
+        &self,
+        dst: &mut W,
+        memo_type: MemoFileType,
+    ) -> std::io::Result<()> {
+        match memo_type {
+            MemoFileType::DbaseMemo | MemoFileType::DbaseMemo4 => {
+                dst.write_u32::<LittleEndian>(self.next_available_block_index)?;
+                // 0 is read as blocks of 512 bytes, dBase IV has the block size at byte 20
+                dst.write_all(&ZEROS[..16])?;
+                dst.write_u16::<LittleEndian>(self.block_size as u16)?;
+                dst.write_all(&ZEROS[..MEMO_HEADER_SIZE as usize - 22])
+            }
+            MemoFileType::FoxBaseMemo => {
+                dst.write_u32::<BigEndian>(self.next_available_block_index)?;
+                dst.write_u16::<BigEndian>(0)?;
+                dst.write_u16::<BigEndian>(self.block_size as u16)?;
+                dst.write_all(&ZEROS[..MEMO_HEADER_SIZE as usize - 8])
+            }
+        }
+    }
+}
+
+/// Writes memos at the end of a memo file, object safe so that the
+/// [FieldWriter](crate::FieldWriter) of a table does not depend on the type of the memo file
+pub(crate) trait MemoWriter {
+    /// Writes the memo in new blocks, the header is updated,
+    /// and returns the index of its first block
+    fn write_memo(&mut self, data: &[u8]) -> Result<u32, ErrorKind>;
 }
 
 /// Struct that reads knows how to read data from a memo source
@@ -58,33 +193,147 @@ pub(crate) struct MemoReader<T> {
     memo_file_type: MemoFileType,
     header: MemoHeader,
     source: T,
+    /// Size of the source, used to refuse lengths that
+    /// would make us allocate more than what the file holds
+    source_len: u64,
     internal_buffer: Vec<u8>,
 }
 
 impl<T: Read + Seek> MemoReader<T> {
     pub(crate) fn new(memo_type: MemoFileType, mut src: T) -> std::io::Result<Self> {
         let header = MemoHeader::read_from(&mut src, memo_type)?;
+        let source_len = src.seek(SeekFrom::End(0))?;
         let internal_buffer = vec![0u8; header.block_size as usize];
+        trace_event!(
+            debug,
+            memo_type = ?memo_type,
+            block_size = header.block_size,
+            "memo file opened"
+        );
         Ok(Self {
             memo_file_type: memo_type,
             header,
             source: src,
+            source_len,
             internal_buffer,
         })
     }
 
-    pub(crate) fn read_data_at(&mut self, index: u32) -> std::io::Result<&[u8]> {
-        let byte_offset = index * self.header.block_size;
-        self.source.seek(SeekFrom::Start(u64::from(byte_offset)))?;
+    /// Creates an empty memo file of the given type in `dst`
+    #[cfg(feature = "std-fs")]
+    pub(crate) fn create(memo_type: MemoFileType, mut dst: T) -> std::io::Result<Self>
+    where
+        T: Write,
+    {
+        let header = MemoHeader::empty(memo_type);
+        dst.seek(SeekFrom::Start(0))?;
+        header.write_to(&mut dst, memo_type)?;
+        dst.flush()?;
+        Ok(Self {
+            memo_file_type: memo_type,
+            header,
+            source: dst,
+            source_len: u64::from(MEMO_HEADER_SIZE),
+            internal_buffer: vec![0u8; header.block_size as usize],
+        })
+    }
+
+    /// Reads the header and the size of the source again,
+    /// as other handles may have written memos since they were read
+    pub(crate) fn reload(&mut self) -> std::io::Result<()> {
+        self.source.seek(SeekFrom::Start(0))?;
+        self.header = MemoHeader::read_from(&mut self.source, self.memo_file_type)?;
+        self.source_len = self.source.seek(SeekFrom::End(0))?;
+        self.internal_buffer
+            .resize(self.header.block_size as usize, 0);
+        Ok(())
+    }
+
+    /// Returns the index of the block after the last memo, as written in the header,
+    /// `None` when the header does not have it
+    pub(crate) fn next_available_block_index(&self) -> Option<u32> {
+        Some(self.header.next_available_block_index).filter(|index| *index != 0)
+    }
+
+    /// Checks that the block `index` is in the memo file and returns its position
+    fn block_position(&self, index: u32) -> Result<u64, ErrorKind> {
+        let position = u64::from(index) * u64::from(self.header.block_size);
+        let after_last_block = self
+            .next_available_block_index()
+            .is_some_and(|next| index >= next);
+        if after_last_block || position >= self.source_len {
+            Err(ErrorKind::InvalidMemoIndex {
+                index,
+                next_available_block_index: self.header.next_available_block_index,
+            })
+        } else {
+            Ok(position)
+        }
+    }
+
+    /// Returns the length of the text of the dBase IV memo at the block `index`,
+    /// `length` is the one of its block header, which counts the 8 bytes of the header
+    fn dbase4_text_length(&self, index: u32, position: u64, length: u32) -> Result<u32, ErrorKind> {
+        length.checked_sub(8).ok_or_else(|| ErrorKind::CorruptMemo {
+            index,
+            length: u64::from(length),
+            available: self.source_len.saturating_sub(position),
+        })
+    }
+
+    /// Checks that a memo of `length` bytes starting at `position`
+    /// fits in the memo file, and in `max_size` when it is given
+    fn check_length(
+        &self,
+        index: u32,
+        position: u64,
+        length: u32,
+        max_size: Option<usize>,
+    ) -> Result<usize, ErrorKind> {
+        let available = self.source_len.saturating_sub(position);
+        if u64::from(length) > available {
+            return Err(ErrorKind::CorruptMemo {
+                index,
+                length: u64::from(length),
+                available,
+            });
+        }
+        match max_size {
+            Some(max_size) if length as usize > max_size => Err(ErrorKind::MemoTooLarge {
+                index,
+                length: u64::from(length),
+                max_size,
+            }),
+            _ => Ok(length as usize),
+        }
+    }
+
+    /// Reads the memo stored at the block `index` and decodes it
+    pub(crate) fn read_text_at<E: Encoding>(
+        &mut self,
+        index: u32,
+        encoding: &E,
+        max_size: usize,
+    ) -> Result<String, ErrorKind> {
+        let data = self.read_data_at(index, max_size)?;
+        Ok(encoding.decode(data)?.into_owned())
+    }
+
+    /// Reads the memo stored at the block `index`,
+    /// memos longer than `max_size` are refused without being read
+    pub(crate) fn read_data_at(&mut self, index: u32, max_size: usize) -> Result<&[u8], ErrorKind> {
+        let byte_offset = self.block_position(index)?;
+        self.source.seek(SeekFrom::Start(byte_offset))?;
 
         match self.memo_file_type {
             MemoFileType::FoxBaseMemo => {
                 let _type = self.source.read_u32::<BigEndian>()?;
                 let length = self.source.read_u32::<BigEndian>()?;
-                if length as usize > self.internal_buffer.len() {
-                    self.internal_buffer.resize(length as usize, 0);
+                let length = self.check_length(index, byte_offset + 8, length, Some(max_size))?;
+                if length > self.internal_buffer.len() {
+                    self.internal_buffer.resize(length, 0);
                 }
-                let buf_slice = &mut self.internal_buffer[..length as usize];
+                let buf_slice = &mut self.internal_buffer[..length];
                 self.source.read_exact(buf_slice)?;
                 match buf_slice.iter().rposition(|b| *b != 0) {
                     Some(pos) => Ok(&buf_slice[..=pos]),
@@ -100,29 +349,200 @@ impl<T: Read + Seek> MemoReader<T> {
             MemoFileType::DbaseMemo4 => {
                 let _ = self.source.read_u32::<LittleEndian>()?;
                 let length = self.source.read_u32::<LittleEndian>()?;
-                self.source
-                    .read_exact(&mut self.internal_buffer[..length as usize])?;
-                match self.internal_buffer[..length as usize]
-                    .iter()
-                    .position(|b| *b == 0x1F)
-                {
-                    Some(pos) => Ok(&self.internal_buffer[..pos]),
-                    None => Ok(&self.internal_buffer),
+                let length = self.dbase4_text_length(index, byte_offset, length)?;
+                let length = self.check_length(index, byte_offset + 8, length, Some(max_size))?;
+                if length > self.internal_buffer.len() {
+                    self.internal_buffer.resize(length, 0);
+                }
+                let buf_slice = &mut self.internal_buffer[..length];
+                self.source.read_exact(buf_slice)?;
+                match buf_slice.iter().position(|b| *b == DBASE4_MEMO_TERMINATOR) {
+                    Some(pos) => Ok(&buf_slice[..pos]),
+                    None => Ok(buf_slice),
                 }
             }
             MemoFileType::DbaseMemo => {
-                if let Err(e) = self.source.read_exact(&mut self.internal_buffer) {
-                    if index != self.header.next_available_block_index - 1
-                        && e.kind() != std::io::ErrorKind::UnexpectedEof
-                    {
-                        return Err(e);
+                // The text ends at the terminator, it can span several blocks
+                let block_size = u64::from(self.header.block_size);
+                self.internal_buffer.clear();
+                loop {
+                    let start = self.internal_buffer.len();
+                    let num_read = (&mut self.source)
+                        .take(block_size)
+                        .read_to_end(&mut self.internal_buffer)?;
+                    let block = &self.internal_buffer[start..];
+                    if let Some(pos) = block.iter().position(|b| *b == DBASE3_MEMO_TERMINATOR) {
+                        self.internal_buffer.truncate(start + pos);
+                        break;
+                    }
+                    if (num_read as u64) < block_size {
+                        break;
+                    }
+                    if self.internal_buffer.len() > max_size {
+                        return Err(ErrorKind::MemoTooLarge {
+                            index,
+                            length: self.internal_buffer.len() as u64,
+                            max_size,
+                        });
                     }
                 }
-                match self.internal_buffer.iter().position(|b| *b == 0x1A) {
-                    Some(pos) => Ok(&self.internal_buffer[..pos]),
-                    None => Ok(&self.internal_buffer),
+                Ok(&self.internal_buffer)
+            }
+        }
+    }
+
+    /// Copies the memo stored at the block `index` to `dst`, one block at a time,
+    /// and returns the number of bytes written.
+    ///
+    /// The memo is only checked against the size of the memo file,
+    /// so that memos of any size can be read without holding them in memory.
+    pub(crate) fn read_data_at_into(
+        &mut self,
+        index: u32,
+        dst: &mut dyn Write,
+    ) -> Result<u64, ErrorKind> {
+        let byte_offset = self.block_position(index)?;
+        self.source.seek(SeekFrom::Start(byte_offset))?;
+
+        let (length, terminator) = match self.memo_file_type {
+            MemoFileType::FoxBaseMemo => {
+                let _type = self.source.read_u32::<BigEndian>()?;
+                let length = self.source.read_u32::<BigEndian>()?;
+                (
+                    self.check_length(index, byte_offset + 8, length, None)?,
+                    None,
+                )
+            }
+            MemoFileType::DbaseMemo4 => {
+                let _ = self.source.read_u32::<LittleEndian>()?;
+                let length = self.source.read_u32::<LittleEndian>()?;
+                let length = self.dbase4_text_length(index, byte_offset, length)?;
+                let length = self.check_length(index, byte_offset + 8, length, None)?;
+                (length, Some(DBASE4_MEMO_TERMINATOR))
+            }
+            MemoFileType::DbaseMemo => {
+                let data = self.read_data_at(index, usize::MAX)?;
+                dst.write_all(data)?;
+                return Ok(data.len() as u64);
+            }
+        };
+
+        if self.internal_buffer.len() < STREAM_CHUNK_SIZE {
+            self.internal_buffer.resize(STREAM_CHUNK_SIZE, 0);
+        }
+        let mut remaining = length;
+        let mut written = 0u64;
+        // FoxPro memos end with zeros that are not part of the text,
+        // zeros are only written once a non zero byte follows them
+        let mut pending_zeros = 0usize;
+        while remaining > 0 {
+            let chunk_len = remaining.min(self.internal_buffer.len());
+            let chunk = &mut self.internal_buffer[..chunk_len];
+            self.source.read_exact(chunk)?;
+            remaining -= chunk_len;
+
+            if let Some(terminator) = terminator {
+                let end = chunk.iter().position(|b| *b == terminator);
+                let data = &chunk[..end.unwrap_or(chunk_len)];
+                dst.write_all(data)?;
+                written += data.len() as u64;
+                if end.is_some() {
+                    break;
+                }
+            } else if let Some(pos) = chunk.iter().rposition(|b| *b != 0) {
+                while pending_zeros > 0 {
+                    let n = pending_zeros.min(ZEROS.len());
+                    dst.write_all(&ZEROS[..n])?;
+                    pending_zeros -= n;
+                    written += n as u64;
                 }
+                dst.write_all(&chunk[..=pos])?;
+                written += pos as u64 + 1;
+                pending_zeros = chunk_len - pos - 1;
+            } else {
+                pending_zeros += chunk_len;
+            }
+        }
+        Ok(written)
+    }
+}
+
+impl<T: Write + Seek> MemoWriter for MemoReader<T> {
+    fn write_memo(&mut self, data: &[u8]) -> Result<u32, ErrorKind> {
+        let block_size = u64::from(self.header.block_size);
+        let too_large = || ErrorKind::MemoDoesNotFit { length: data.len() };
+        let index = match self.header.next_available_block_index {
+            // The header does not have it, the memo goes after the end of the file
+            0 => {
+                let end = self.source_len.max(u64::from(MEMO_HEADER_SIZE));
+                u32::try_from((end + block_size - 1) / block_size).map_err(|_| too_large())?
+            }
+            index => index,
+        };
+        let position = u64::from(index) * block_size;
+        let length = u32::try_from(data.len()).map_err(|_| too_large())?;
+        let terminator = match self.memo_file_type {
+            MemoFileType::DbaseMemo => Some(DBASE3_MEMO_TERMINATOR),
+            MemoFileType::DbaseMemo4 => Some(DBASE4_MEMO_TERMINATOR),
+            MemoFileType::FoxBaseMemo => None,
+        };
+        if let Some(terminator) = terminator {
+            if let Some(position) = data.iter().position(|b| *b == terminator) {
+                return Err(ErrorKind::MemoContainsTerminator {
+                    terminator,
+                    position,
+                });
+            }
+        }
+
+        self.source.seek(SeekFrom::Start(position))?;
+        let written = match self.memo_file_type {
+            MemoFileType::DbaseMemo => {
+                self.source.write_all(data)?;
+                self.source
+                    .write_all(&[DBASE3_MEMO_TERMINATOR, DBASE3_MEMO_TERMINATOR])?;
+                u64::from(length) + 2
+            }
+            MemoFileType::DbaseMemo4 => {
+                // The length counts the 8 bytes of the block header
+                self.source
+                    .write_u32::<LittleEndian>(DBASE4_BLOCK_SIGNATURE)?;
+                self.source
+                    .write_u32::<LittleEndian>(length.checked_add(8).ok_or_else(too_large)?)?;
+                self.source.write_all(data)?;
+                self.source
+                    .write_all(&[DBASE4_MEMO_TERMINATOR, DBASE4_MEMO_TERMINATOR])?;
+                u64::from(length) + 10
+            }
+            MemoFileType::FoxBaseMemo => {
+                self.source.write_u32::<BigEndian>(FOXPRO_TEXT_MEMO)?;
+                self.source.write_u32::<BigEndian>(length)?;
+                self.source.write_all(data)?;
+                u64::from(length) + 8
+            }
+        };
+
+        // The last block is padded so that the next memo starts at a block boundary
+        let num_blocks = (written + block_size - 1) / block_size;
+        let end = position + num_blocks * block_size;
+        let mut padding = end - self.source.stream_position()?;
+        while padding > 0 {
+            let n = padding.min(ZEROS.len() as u64) as usize;
+            self.source.write_all(&ZEROS[..n])?;
+            padding -= n as u64;
+        }
+
+        let next_index = u32::try_from(u64::from(index) + num_blocks).map_err(|_| too_large())?;
+        self.header.next_available_block_index = next_index;
+        self.source.seek(SeekFrom::Start(0))?;
+        match self.memo_file_type {
+            MemoFileType::DbaseMemo | MemoFileType::DbaseMemo4 => {
+                self.source.write_u32::<LittleEndian>(next_index)?
             }
+            MemoFileType::FoxBaseMemo => self.source.write_u32::<BigEndian>(next_index)?,
         }
+        self.source.flush()?;
+        self.source_len = self.source_len.max(end);
+        Ok(index)
     }
 }
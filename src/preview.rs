@@ -0,0 +1,181 @@
+//! Rendering of the first records of a table as aligned text
+use std::fmt::{Display, Formatter};
+use std::io::{Read, Seek};
+
+use crate::{
+    DateFormat, DisplayStyle, FieldIOError, FieldInfo, FieldIterator, FieldValue, ReadableRecord,
+};
+
+/// Width of the columns, in characters, when none is set
+pub const DEFAULT_PREVIEW_COLUMN_WIDTH: usize = 20;
+
+/// First records of a table, rendered by its `Display` implementation
+/// as an aligned text table.
+///
+/// The header row has the name, type and length of the fields (`name C(50)`),
+/// values are rendered with [FieldValue::display_with] with ISO dates,
+/// `None` values are blank. Records marked as deleted start with `*`.
+///
+/// Created by [Reader::preview](crate::Reader::preview) and [File::preview](crate::File::preview).
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+/// let preview = reader.preview(3)?.max_column_width(12);
+/// println!("{}", preview);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TablePreview {
+    headers: Vec<String>,
+    rows: Vec<PreviewRow>,
+    max_column_width: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PreviewRow {
+    deleted: bool,
+    values: Vec<String>,
+}
+
+impl TablePreview {
+    pub(crate) fn new(fields: &[FieldInfo]) -> Self {
+        let headers = fields
+            .iter()
+            .map(|info| {
+                let type_code = char::from(u8::from(info.field_type));
+                if info.num_decimal_places > 0 {
+                    format!(
+                        "{} {}({},{})",
+                        info.name, type_code, info.field_length, info.num_decimal_places
+                    )
+                } else {
+                    format!("{} {}({})", info.name, type_code, info.field_length)
+                }
+            })
+            .collect();
+        Self {
+            headers,
+            rows: vec![],
+            max_column_width: DEFAULT_PREVIEW_COLUMN_WIDTH,
+        }
+    }
+
+    pub(crate) fn push(&mut self, fields: &[FieldInfo], values: PreviewValues, deleted: bool) {
+        let style = DisplayStyle::default().date_format(DateFormat::Iso);
+        let values = fields
+            .iter()
+            .zip(values.0.iter())
+            .map(|(info, value)| {
+                value
+                    .display_with(info, &style)
+                    .chars()
+                    // Memos may span several lines
+                    .map(|c| if c.is_control() { ' ' } else { c })
+                    .collect()
+            })
+            .collect();
+        self.rows.push(PreviewRow { deleted, values });
+    }
+
+    /// Sets the maximum width of the columns, in characters, longer texts are cut
+    /// and end with `…`. [DEFAULT_PREVIEW_COLUMN_WIDTH] by default.
+    pub fn max_column_width(mut self, width: usize) -> Self {
+        self.max_column_width = width.max(1);
+        self
+    }
+
+    /// Returns the number of records in the preview
+    pub fn num_records(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the text cut to the maximum width of the columns
+    fn fit<'a>(&self, text: &'a str) -> std::borrow::Cow<'a, str> {
+        if text.chars().count() <= self.max_column_width {
+            text.into()
+        } else {
+            let mut cut = text
+                .chars()
+                .take(self.max_column_width - 1)
+                .collect::<String>();
+            cut.push('…');
+            cut.into()
+        }
+    }
+}
+
+impl Display for TablePreview {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let headers = self
+            .headers
+            .iter()
+            .map(|header| self.fit(header))
+            .collect::<Vec<_>>();
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                let values = row
+                    .values
+                    .iter()
+                    .map(|value| self.fit(value))
+                    .collect::<Vec<_>>();
+                (row.deleted, values)
+            })
+            .collect::<Vec<_>>();
+        let widths = headers
+            .iter()
+            .enumerate()
+            .map(|(i, header)| {
+                rows.iter()
+                    .map(|(_, values)| values[i].chars().count())
+                    .fold(header.chars().count(), usize::max)
+            })
+            .collect::<Vec<_>>();
+
+        let write_line = |f: &mut Formatter<'_>, marker: &str, cells: &[std::borrow::Cow<str>]| {
+            let line = cells
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join(" | ");
+            writeln!(f, "{} {}", marker, line.trim_end())
+        };
+
+        write_line(f, " ", &headers)?;
+        let separator = widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-+-");
+        writeln!(f, "--{}", separator)?;
+        for (deleted, values) in &rows {
+            write_line(f, if *deleted { "*" } else { " " }, values)?;
+        }
+        Ok(())
+    }
+}
+
+/// Values of all the fields of a record, in the order of the fields
+pub(crate) struct PreviewValues(Vec<FieldValue>);
+
+impl ReadableRecord for PreviewValues {
+    fn read_using<Source, MemoSource>(
+        field_iterator: &mut FieldIterator<Source, MemoSource>,
+    ) -> Result<Self, FieldIOError>
+    where
+        Source: Read + Seek,
+        MemoSource: Read + Seek,
+    {
+        let mut values = vec![];
+        for result in field_iterator {
+            values.push(result?.value);
+        }
+        Ok(Self(values))
+    }
+}
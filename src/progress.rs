@@ -0,0 +1,75 @@
+//! Progress reporting of long reads and writes
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+/// Number of records between two calls of the progress callback, by default
+pub const DEFAULT_PROGRESS_INTERVAL: u32 = 1000;
+
+/// Advancement of a read or a write, given to progress callbacks
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Progress {
+    /// Number of records read or written so far, deleted records that are skipped count
+    pub records_done: u32,
+    /// Number of records that will be read or written, 0 when it is not known
+    pub records_total: u32,
+}
+
+/// Callback receiving the [Progress] of reads and writes
+pub type ProgressCallback = Box<dyn FnMut(Progress) + Send>;
+
+/// Calls an optional callback every `interval` records.
+///
+/// The callback is shared so that the types holding it stay `Clone`, `Send` and `Sync`.
+#[derive(Clone)]
+pub(crate) struct ProgressReporter {
+    callback: Option<Arc<Mutex<ProgressCallback>>>,
+    interval: u32,
+}
+
+impl Default for ProgressReporter {
+    fn default() -> Self {
+        Self {
+            callback: None,
+            interval: DEFAULT_PROGRESS_INTERVAL,
+        }
+    }
+}
+
+impl ProgressReporter {
+    pub(crate) fn set_callback(&mut self, callback: ProgressCallback) {
+        self.callback = Some(Arc::new(Mutex::new(callback)));
+    }
+
+    pub(crate) fn set_interval(&mut self, interval: u32) {
+        self.interval = interval.max(1);
+    }
+
+    /// Calls the callback when `records_done` is a multiple of the interval,
+    /// or is the last record
+    pub(crate) fn report(&self, records_done: u32, records_total: u32) {
+        let Some(callback) = &self.callback else {
+            return;
+        };
+        if records_done % self.interval == 0 || records_done == records_total {
+            // A callback that panicked is not called anymore
+            if let Ok(mut callback) = callback.lock() {
+                callback(Progress {
+                    records_done,
+                    records_total,
+                });
+            }
+        }
+    }
+}
+
+impl Debug for ProgressReporter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressReporter")
+            .field(
+                "callback",
+                &self.callback.as_ref().map(|_| "FnMut(Progress)"),
+            )
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
@@ -1,24 +1,55 @@
 //! Module with the definition of fn's and struct's to read .dbf files
 
+use byteorder::ReadBytesExt;
+use std::borrow::Cow;
 use std::convert::TryFrom;
+#[cfg(feature = "std-fs")]
 use std::fs::File;
-use std::io::{BufReader, Read, Seek, SeekFrom};
+#[cfg(feature = "std-fs")]
+use std::io::BufReader;
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 use std::iter::FusedIterator;
-use std::path::Path;
+#[cfg(feature = "std-fs")]
+use std::path::{Path, PathBuf};
 
 use crate::encoding::DynEncoding;
 use crate::error::{Error, ErrorKind, FieldIOError};
-use crate::field::types::{FieldType, FieldValue, TrimOption};
-use crate::field::{DeletionFlag, FieldInfo};
-use crate::header::Header;
-use crate::memo::{MemoFileType, MemoReader};
-use crate::{Encoding, FieldConversionError, Record};
+use crate::field::types::{
+    read_character_str, read_memo_index, Date, FieldType, FieldValue, TrimOption,
+};
+use crate::field::{
+    field_data_buffer, names_match, DeletionFlag, FieldInfo, SchemaWarning, DELETION_FLAG_SIZE,
+    FILE_TERMINATOR,
+};
+use crate::file::{add_unexpected_deletion_flag_warning, TableDefinition, Warning};
+use crate::header::{CodePageMark, Header, TableFlags, Version};
+use crate::memo::MemoReader;
+use crate::preview::{PreviewValues, TablePreview};
+use crate::progress::{ProgressCallback, ProgressReporter, DEFAULT_PROGRESS_INTERVAL};
+use crate::{Encoding, FromFieldValue, Record};
+
+/// Maximum size of the memos read, by default, see [ReadingOptions::max_memo_size]
+pub const DEFAULT_MAX_MEMO_SIZE: usize = 64 * 1024 * 1024;
+
+/// Length of the memos, in bytes, assumed by [Reader::estimated_memory_usage]
+pub const ESTIMATED_MEMO_SIZE: usize = 256;
 
 /// Value of the byte between the last RecordFieldInfo and the first record
 pub(crate) const TERMINATOR_VALUE: u8 = 0x0D;
 
 pub(crate) const BACKLINK_SIZE: u16 = 263;
 
+/// Returns the offset to the first record of a table with `num_fields` fields:
+/// the header, the field descriptors, their terminator and the backlink of Visual FoxPro files
+pub(crate) fn offset_to_first_record(version: Version, num_fields: usize) -> usize {
+    let offset = Header::SIZE + (num_fields * FieldInfo::SIZE) + std::mem::size_of::<u8>();
+    if version.is_visual_fox_pro() {
+        offset + BACKLINK_SIZE as usize
+    } else {
+        offset
+    }
+}
+
 /// Trait to be implemented by structs that represent records read from a
 /// dBase file.
 ///
@@ -26,6 +57,48 @@ pub(crate) const BACKLINK_SIZE: u16 = 263;
 /// or skip them.
 /// It is not required that the user reads / skips all the fields in a record,
 /// in other words: it is not required to consume the iterator.
+///
+/// The iterator also tells where it is in the record: [FieldIterator::current_index],
+/// [FieldIterator::remaining] and [FieldIterator::peek_info] give the index of the next
+/// field, the number of fields left and the info of the next field, which helps
+/// when the fields to read depend on the values of the previous ones.
+///
+/// # Example
+///
+/// ```
+/// use dbase::{FieldIOError, FieldIterator, ReadableRecord};
+/// use std::io::{Read, Seek};
+///
+/// struct Station {
+///     name: String,
+///     line: String,
+/// }
+///
+/// impl ReadableRecord for Station {
+///     fn read_using<T, R>(iter: &mut FieldIterator<T, R>) -> Result<Self, FieldIOError>
+///     where
+///         T: Read + Seek,
+///         R: Read + Seek,
+///     {
+///         let name = iter.read_next_field_as::<String>()?.value;
+///         // The marker columns are not in every version of the table
+///         if iter.peek_info().is_some_and(|info| info.name() == "marker-col") {
+///             iter.skip_fields(2)?;
+///         }
+///         assert_eq!(iter.current_index(), 3);
+///         let line = iter.read_next_field_as::<String>()?.value;
+///         Ok(Self { name, line })
+///     }
+/// }
+///
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+/// let stations = reader.read_as::<Station>()?;
+/// assert_eq!(stations[0].name, "Van Dorn Street");
+/// assert_eq!(stations[0].line, "blue");
+/// # Ok(())
+/// # }
+/// ```
 pub trait ReadableRecord: Sized {
     /// function to be implemented that returns a new instance of your type
     /// using values read from the `FieldIterator'
@@ -37,28 +110,253 @@ pub trait ReadableRecord: Sized {
         MemoSource: Read + Seek;
 }
 
+/// Reads the values of all the fields, in the order of the fields
+///
+/// Not available with the `serde` feature, as it conflicts with
+/// the implementation for `Deserialize` types.
+#[cfg(not(feature = "serde"))]
+impl ReadableRecord for Vec<FieldValue> {
+    fn read_using<Source, MemoSource>(
+        field_iterator: &mut FieldIterator<Source, MemoSource>,
+    ) -> Result<Self, FieldIOError>
+    where
+        Source: Read + Seek,
+        MemoSource: Read + Seek,
+    {
+        let mut values = Vec::with_capacity(field_iterator.fields_info.len());
+        loop {
+            let index = field_iterator.next_field_index();
+            let Some(field_info) = field_iterator.fields_info.next() else {
+                break;
+            };
+            let value = field_iterator
+                .read_field(field_info, index)
+                .map_err(|error| error.at_field_index(index))?;
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
 /// Structs containing the information allowing to
 /// create a new TableWriter which would write file
 /// with the same record structure as another dbase file.
 ///
-/// You can get this by using [Reader::into_table_info].
+/// You can get this by using [Reader::into_table_info], [Reader::table_info]
+/// or [File::table_info](crate::File::table_info).
 #[derive(Clone)]
 pub struct TableInfo {
     pub(crate) header: Header,
     pub(crate) fields_info: Vec<FieldInfo>,
     pub(crate) encoding: DynEncoding,
+    pub(crate) backlink: Option<String>,
+    /// Code page mark written instead of the one of the encoding
+    pub(crate) code_page_mark: Option<CodePageMark>,
+}
+
+impl TableInfo {
+    /// Sets the code page mark written in the header of the tables created
+    /// from this info, instead of the one of the encoding,
+    /// see [TableWriterBuilder::set_code_page_mark](crate::TableWriterBuilder::set_code_page_mark).
+    pub fn set_code_page_mark(&mut self, mark: CodePageMark) {
+        self.header.code_page_mark = mark;
+        self.code_page_mark = Some(mark);
+    }
+
+    /// Returns the path to the database (.dbc) the table belongs to
+    ///
+    /// Only Visual FoxPro tables can have one.
+    pub fn backlink(&self) -> Option<&str> {
+        self.backlink.as_deref()
+    }
+
+    /// Returns the header of the table
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns the information about the fields of the table
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.fields_info
+    }
+
+    /// Returns the size in bytes of a record, deletion flag included.
+    ///
+    /// Like the other sizes and offsets of the table info, this is the size
+    /// of the records written by a [TableWriter](crate::TableWriter) created from it.
+    pub fn record_size(&self) -> u16 {
+        let size = self
+            .fields_info
+            .iter()
+            .fold(DELETION_FLAG_SIZE, |size, info| {
+                size + info.field_length as usize
+            });
+        u16::try_from(size).unwrap_or(u16::MAX)
+    }
+
+    /// Returns the offset of each field from the start of the record,
+    /// in the order of the fields, the first one is after the deletion flag.
+    pub fn field_offsets(&self) -> Vec<usize> {
+        self.fields_info
+            .iter()
+            .scan(DELETION_FLAG_SIZE, |offset, info| {
+                let field_offset = *offset;
+                *offset += info.field_length as usize;
+                Some(field_offset)
+            })
+            .collect()
+    }
+
+    /// Returns the offset of the first record from the start of the file
+    pub fn offset_to_first_record(&self) -> u16 {
+        let offset = offset_to_first_record(self.header.file_type, self.fields_info.len());
+        u16::try_from(offset).unwrap_or(u16::MAX)
+    }
+
+    /// Returns the size in bytes of a file holding `num_records` records:
+    /// header, field descriptors, backlink, records and end of file marker.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let info = dbase::Reader::from_path("tests/data/line.dbf")?.into_table_info();
+    /// let expected_size = info.expected_file_size(100);
+    /// # assert!(expected_size > 100 * u64::from(info.record_size()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn expected_file_size(&self, num_records: u32) -> u64 {
+        u64::from(self.offset_to_first_record())
+            + u64::from(num_records) * u64::from(self.record_size())
+            + std::mem::size_of_val(&FILE_TERMINATOR) as u64
+    }
+}
+
+/// What to do with fields whose type byte is not a known [FieldType]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum UnknownFieldTypeHandling {
+    /// Opening the file fails with [ErrorKind::InvalidFieldType]
+    #[default]
+    Error,
+    /// The field is read as a [FieldType::Character] field of the declared length
+    TreatAsCharacter,
+    /// The field is not part of the fields of the file,
+    /// its bytes are skipped when reading records
+    Skip,
+}
+
+/// How the bytes of Character fields that are invalid in the encoding are handled
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// Reading the field fails with [ErrorKind::StringDecodeError],
+    /// giving the offset of the invalid byte
+    #[default]
+    Strict,
+    /// The invalid bytes are replaced by `U+FFFD`
+    Lossy,
+    /// The invalid bytes are replaced by `U+FFFD`
+    /// and listed by [Reader::decode_issues]
+    LossyWithReport,
+}
+
+/// A byte of a Character field that could not be decoded,
+/// listed by [Reader::decode_issues]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DecodeIssue {
+    /// Index of the record
+    pub record_index: usize,
+    /// Index of the field in the record
+    pub field_index: usize,
+    /// Offset of the byte within the field
+    pub byte_offset: usize,
+}
+
+/// Number of records of a table, according to its header and to its content,
+/// returned by [Reader::count_records] and [File::count_records](crate::File::count_records)
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RecordCounts {
+    /// Number of records written in the header
+    pub header_count: usize,
+    /// Number of complete records in the file, before the end of file marker
+    pub physical_count: usize,
+    /// Number of those records that are marked as deleted
+    pub deleted_count: usize,
+}
+
+impl RecordCounts {
+    /// Counts the records by reading only their deletion flag,
+    /// the position of the source is restored afterwards
+    pub(crate) fn scan<S: Read + Seek>(source: &mut S, header: &Header) -> std::io::Result<Self> {
+        let start_position = source.stream_position()?;
+        let end = source.seek(SeekFrom::End(0))?;
+        let record_size = u64::from(header.size_of_record);
+        let mut counts = Self {
+            header_count: header.num_records as usize,
+            physical_count: 0,
+            deleted_count: 0,
+        };
+
+        // The records are read whole, one after the other, so that a
+        // buffered source is not emptied by a seek for each deletion flag
+        let mut position = u64::from(header.offset_to_first_record);
+        let mut record = vec![0u8; record_size as usize];
+        source.seek(SeekFrom::Start(position))?;
+        while record_size != 0 && position + record_size <= end {
+            source.read_exact(&mut record)?;
+            let flag = record[0];
+            if flag == FILE_TERMINATOR {
+                break;
+            }
+            if matches!(
+                DeletionFlag::try_from_byte(flag),
+                Some(DeletionFlag::Deleted)
+            ) {
+                counts.deleted_count += 1;
+            }
+            counts.physical_count += 1;
+            position += record_size;
+        }
+
+        source.seek(SeekFrom::Start(start_position))?;
+        Ok(counts)
+    }
 }
 
 /// Options related to reading
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct ReadingOptions {
     pub(crate) character_trim: TrimOption,
+    pub(crate) on_unknown_field_type: UnknownFieldTypeHandling,
+    pub(crate) check_field_names: bool,
+    pub(crate) progress: ProgressReporter,
+    pub(crate) two_digit_year_pivot: Option<u32>,
+    pub(crate) lenient_memo: bool,
+    pub(crate) case_insensitive_names: bool,
+    pub(crate) strict_deletion_flag: bool,
+    pub(crate) include_system_columns: bool,
+    pub(crate) character_decode: DecodeMode,
+    pub(crate) max_memo_size: usize,
+    pub(crate) numeric_as_string: bool,
+    pub(crate) max_materialized_records: Option<u32>,
 }
 
 impl Default for ReadingOptions {
     fn default() -> Self {
         Self {
             character_trim: TrimOption::BeginEnd,
+            on_unknown_field_type: UnknownFieldTypeHandling::Error,
+            check_field_names: false,
+            progress: ProgressReporter::default(),
+            two_digit_year_pivot: None,
+            lenient_memo: false,
+            case_insensitive_names: false,
+            strict_deletion_flag: false,
+            include_system_columns: false,
+            character_decode: DecodeMode::Strict,
+            max_memo_size: DEFAULT_MAX_MEMO_SIZE,
+            numeric_as_string: false,
+            max_materialized_records: None,
         }
     }
 }
@@ -71,6 +369,181 @@ impl ReadingOptions {
         self.character_trim = trim_option;
         self
     }
+
+    /// Customize what is done with fields of unknown type when
+    /// the fields definition is read.
+    ///
+    /// By default opening the file fails.
+    /// As the fields are read when the reader is created,
+    /// this option has to be given to [ReaderBuilder::with_options].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{ReaderBuilder, ReadingOptions, UnknownFieldTypeHandling};
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let options = ReadingOptions::default()
+    ///     .on_unknown_field_type(UnknownFieldTypeHandling::TreatAsCharacter);
+    /// let mut reader = ReaderBuilder::new(std::fs::File::open("tests/data/line.dbf").unwrap())
+    ///     .with_options(options)
+    ///     .with_encoding(dbase::encoding::UnicodeLossy)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_unknown_field_type(mut self, handling: UnknownFieldTypeHandling) -> Self {
+        self.on_unknown_field_type = handling;
+        self
+    }
+
+    /// When enabled, structs deserialized with serde must have their fields
+    /// named like the fields of the table (ignoring the case, see [Record::get_ignoring_case])
+    /// and in the same order,
+    /// otherwise reading fails with [ErrorKind::FieldNameMismatch].
+    ///
+    /// By default the fields are only matched by position.
+    pub fn check_field_names(mut self, enabled: bool) -> Self {
+        self.check_field_names = enabled;
+        self
+    }
+
+    /// Sets how two digits years are interpreted, years below the pivot
+    /// are in the 2000s, the others in the 1900s: with a pivot of 50,
+    /// `49` is 2049 and `51` is 1951.
+    ///
+    /// It applies to Date fields whose value has 6 digits (`YYMMDD`)
+    /// instead of 8 (`YYYYMMDD`), and to the last update date of the header
+    /// when its year byte is below 100 (some programs store the last two digits
+    /// of the year instead of the number of years since 1900).
+    ///
+    /// By default (`None`), 6 digits dates fail to parse
+    /// and header years are read as years since 1900.
+    pub fn two_digit_year_pivot(mut self, pivot: Option<u32>) -> Self {
+        self.two_digit_year_pivot = pivot;
+        self
+    }
+
+    /// When enabled, Memo fields whose content is not a block index
+    /// (some programs store short texts directly in the field) are read
+    /// as the trimmed text of the field, without using the memo file.
+    ///
+    /// By default reading them fails with [ErrorKind::InvalidFieldContent].
+    pub fn lenient_memo(mut self, enabled: bool) -> Self {
+        self.lenient_memo = enabled;
+        self
+    }
+
+    /// When enabled, [Record::get] on the records read finds the values
+    /// of names that differ only by the case, see [Record::set_case_insensitive_get]
+    /// and [Record::get_ignoring_case].
+    ///
+    /// Disabled by default.
+    pub fn case_insensitive_names(mut self, enabled: bool) -> Self {
+        self.case_insensitive_names = enabled;
+        self
+    }
+
+    /// When enabled, records whose deletion flag is neither a space (0x20)
+    /// nor `*` (0x2A) return [ErrorKind::InvalidDeletionFlag], which usually means
+    /// that the records are shifted in the file.
+    ///
+    /// By default they are read as not deleted,
+    /// and counted by [Reader::unexpected_deletion_flag_count].
+    pub fn strict_deletion_flag(mut self, enabled: bool) -> Self {
+        self.strict_deletion_flag = enabled;
+        self
+    }
+
+    /// When enabled, the system columns of Visual FoxPro tables (fields flagged
+    /// with [FieldFlags::is_system_column](crate::FieldFlags::is_system_column),
+    /// like `_NullFlags`) are fields of the table like the others.
+    ///
+    /// By default they are skipped: they are not in the fields of the table
+    /// nor in the records read.
+    /// As the fields are read when the reader is created,
+    /// this option has to be given to [ReaderBuilder::with_options].
+    pub fn include_system_columns(mut self, enabled: bool) -> Self {
+        self.include_system_columns = enabled;
+        self
+    }
+
+    /// Customize what is done with the bytes of Character fields
+    /// that cannot be decoded with the encoding of the file.
+    ///
+    /// By default reading the field fails, see [DecodeMode::Strict].
+    pub fn character_decode(mut self, mode: DecodeMode) -> Self {
+        self.character_decode = mode;
+        self
+    }
+
+    /// When enabled, the values of Numeric fields are read as
+    /// [FieldValue::Character] holding their trimmed text, `None` when blank,
+    /// to be parsed by the caller (e.g. with a decimal type for amounts,
+    /// or as integers for identifiers longer than what a `f64` holds exactly).
+    ///
+    /// By default they are parsed to [FieldValue::Numeric].
+    pub fn numeric_as_string(mut self, enabled: bool) -> Self {
+        self.numeric_as_string = enabled;
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of the memos read,
+    /// by default [DEFAULT_MAX_MEMO_SIZE].
+    ///
+    /// Reading a longer memo fails with [ErrorKind::MemoTooLarge] before
+    /// any memory is allocated for it, memos whose length goes past the end
+    /// of the memo file fail with [ErrorKind::CorruptMemo].
+    /// [Reader::read_memo_into] can read memos of any size.
+    pub fn max_memo_size(mut self, max_size: usize) -> Self {
+        self.max_memo_size = max_size;
+        self
+    }
+
+    /// Sets the maximum number of records [Reader::read] and [Reader::read_as]
+    /// put in a `Vec`, no limit by default.
+    ///
+    /// When the header announces more records, they fail with
+    /// [ErrorKind::TooManyRecords] before reading any record, the records can still
+    /// be read one at a time with [Reader::iter_records] or [Reader::for_each_record].
+    /// See [Reader::estimated_memory_usage].
+    pub fn max_materialized_records(mut self, max: Option<u32>) -> Self {
+        self.max_materialized_records = max;
+        self
+    }
+
+    /// Sets a callback called with the [Progress](crate::Progress) of the reading,
+    /// every [Self::progress_interval] records and after the last one.
+    ///
+    /// It is called by [Reader::read], [Reader::read_as], [Reader::for_each_record]
+    /// and the record iterators of the [Reader].
+    /// [File::append_records](crate::File::append_records) also calls it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let options = dbase::ReadingOptions::default()
+    ///     .with_progress(Box::new(|progress: dbase::Progress| {
+    ///         println!("{}/{}", progress.records_done, progress.records_total);
+    ///     }))
+    ///     .progress_interval(10);
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// reader.set_options(options);
+    /// let records = reader.read()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_progress(mut self, callback: ProgressCallback) -> Self {
+        self.progress.set_callback(callback);
+        self
+    }
+
+    /// Sets the number of records between two calls of the progress callback,
+    /// by default [DEFAULT_PROGRESS_INTERVAL](crate::DEFAULT_PROGRESS_INTERVAL)
+    pub fn progress_interval(mut self, interval: u32) -> Self {
+        self.progress.set_interval(interval);
+        self
+    }
 }
 
 /// Convenience builder to create a reader directly from file sources
@@ -131,11 +604,16 @@ impl<T: Read + Seek, E: Encoding + 'static> ReaderBuilder<T, E> {
         self
     }
 
-    pub fn build(self) -> Result<Reader<T>, Error> {
-        let file = crate::File::open(self.source)?;
+    pub fn build(mut self) -> Result<Reader<T>, Error> {
+        let options = self.options.unwrap_or_default();
+        let definition = TableDefinition::read_from(
+            &mut self.source,
+            &options,
+            self.encoding.map(DynEncoding::new),
+        )?;
 
         let memo_reader = if let Some(memo_source) = self.memo_source {
-            let memo_type = file.header.file_type.supported_memo_type();
+            let memo_type = definition.header.file_type.supported_memo_type();
             if let Some(mt) = memo_type {
                 let memo_reader =
                     MemoReader::new(mt, memo_source).map_err(|error| Error::io_error(error, 0))?;
@@ -149,14 +627,20 @@ impl<T: Read + Seek, E: Encoding + 'static> ReaderBuilder<T, E> {
         };
 
         Ok(Reader {
-            source: file.inner,
+            source: self.source,
             memo_reader,
-            header: file.header,
-            fields_info: file.fields_info.inner,
-            encoding: self
-                .encoding
-                .map_or_else(|| file.encoding, DynEncoding::new),
-            options: self.options.unwrap_or_default(),
+            header: definition.header,
+            fields_info: definition.fields_info.inner,
+            schema_warnings: definition.fields_info.warnings,
+            warnings: definition.warnings,
+            backlink: definition.backlink,
+            selected_fields: None,
+            encoding: definition.encoding,
+            options,
+            actual_record_count: None,
+            unexpected_deletion_flag_count: 0,
+            decode_issues: vec![],
+            start_record: 0,
         })
     }
 }
@@ -164,15 +648,58 @@ impl<T: Read + Seek, E: Encoding + 'static> ReaderBuilder<T, E> {
 /// Struct with the handle to the source .dbf file
 /// Responsible for reading the content
 // TODO Debug impl
-#[derive(Clone)]
 pub struct Reader<T: Read + Seek> {
     /// Where the data is read from
     source: T,
     memo_reader: Option<MemoReader<T>>,
     header: Header,
     fields_info: Vec<FieldInfo>,
+    schema_warnings: Vec<SchemaWarning>,
+    /// Fallbacks used since the last call to [Reader::take_warnings]
+    warnings: Vec<Warning>,
+    backlink: Option<String>,
+    /// For each field, whether it is read when reading [Record]s
+    selected_fields: Option<Vec<bool>>,
     encoding: DynEncoding,
     options: ReadingOptions,
+    /// Number of records found the last time the records were iterated to the end
+    actual_record_count: Option<usize>,
+    /// Number of records with an unexpected deletion flag in the last iteration
+    unexpected_deletion_flag_count: usize,
+    /// Bytes that could not be decoded in the last iteration,
+    /// with [DecodeMode::LossyWithReport]
+    decode_issues: Vec<DecodeIssue>,
+    /// Index of the record the next iteration starts at, set by [Reader::seek]
+    start_record: usize,
+}
+
+/// The clone starts reading at the first record,
+/// whatever the position of the reader it is cloned from
+impl<T: Read + Seek + Clone> Clone for Reader<T> {
+    fn clone(&self) -> Self {
+        let mut source = self.source.clone();
+        // The iterations seek to their first record,
+        // a source that cannot seek fails there
+        let _ = source.seek(SeekFrom::Start(u64::from(
+            self.header.offset_to_first_record,
+        )));
+        Self {
+            source,
+            memo_reader: self.memo_reader.clone(),
+            header: self.header,
+            fields_info: self.fields_info.clone(),
+            schema_warnings: self.schema_warnings.clone(),
+            warnings: self.warnings.clone(),
+            backlink: self.backlink.clone(),
+            selected_fields: self.selected_fields.clone(),
+            encoding: self.encoding.clone(),
+            options: self.options.clone(),
+            actual_record_count: self.actual_record_count,
+            unexpected_deletion_flag_count: self.unexpected_deletion_flag_count,
+            decode_issues: self.decode_issues.clone(),
+            start_record: 0,
+        }
+    }
 }
 
 impl<T: Read + Seek> Reader<T> {
@@ -203,24 +730,38 @@ impl<T: Read + Seek> Reader<T> {
     /// # }
     /// ```
     pub fn new(source: T) -> Result<Self, Error> {
-        let file = crate::File::open(source)?;
-        Ok(Self {
-            source: file.inner,
-            memo_reader: None,
-            header: file.header,
-            fields_info: file.fields_info.inner,
-            encoding: file.encoding,
-            options: ReadingOptions::default(),
-        })
+        Self::with_optional_encoding(source, None)
     }
 
     /// Creates a new reader from the source and reads strings using the encoding provided.
     ///
+    /// The encoding is also used to decode the field names.
+    ///
     /// See [`Self::new`] for more information.
     pub fn new_with_encoding<E: Encoding + 'static>(source: T, encoding: E) -> Result<Self, Error> {
-        let mut reader = Self::new(source)?;
-        reader.set_encoding(encoding);
-        Ok(reader)
+        Self::with_optional_encoding(source, Some(DynEncoding::new(encoding)))
+    }
+
+    /// Reads the table definition, using the encoding of the header if none is given
+    fn with_optional_encoding(mut source: T, encoding: Option<DynEncoding>) -> Result<Self, Error> {
+        let definition =
+            TableDefinition::read_from(&mut source, &ReadingOptions::default(), encoding)?;
+        Ok(Self {
+            source,
+            memo_reader: None,
+            header: definition.header,
+            fields_info: definition.fields_info.inner,
+            schema_warnings: definition.fields_info.warnings,
+            warnings: definition.warnings,
+            backlink: definition.backlink,
+            selected_fields: None,
+            encoding: definition.encoding,
+            options: ReadingOptions::default(),
+            actual_record_count: None,
+            unexpected_deletion_flag_count: 0,
+            decode_issues: vec![],
+            start_record: 0,
+        })
     }
 
     pub fn set_encoding<E: Encoding + 'static>(&mut self, encoding: E) {
@@ -236,34 +777,166 @@ impl<T: Read + Seek> Reader<T> {
         &self.header
     }
 
+    /// Returns the flags of the header, telling for example whether
+    /// the table has a structural compound index
+    pub fn table_flags(&self) -> TableFlags {
+        self.header.table_flags
+    }
+
     /// Returns the fields contained in the opened file
     pub fn fields(&self) -> &[FieldInfo] {
         &self.fields_info
     }
 
+    /// Returns the oddities (zero length fields, duplicated names)
+    /// found in the fields definition of the file
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// assert!(reader.schema_warnings().is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn schema_warnings(&self) -> &[SchemaWarning] {
+        &self.schema_warnings
+    }
+
+    /// Returns the warnings collected since the last call, see [Warning]
+    ///
+    /// Opening the table reports an unknown code page or a record size that does not
+    /// match the fields, iterating over the records reports unexpected deletion flags
+    /// and a file that ends before the number of records of the header.
+    /// No warnings means that the table was read as it is written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{CodePageMark, Warning};
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// // The header of this table has no code page
+    /// assert_eq!(
+    ///     reader.take_warnings(),
+    ///     vec![Warning::UnknownCodePage(CodePageMark::Undefined)]
+    /// );
+    /// let records = reader.read()?;
+    /// assert!(reader.take_warnings().is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Selects the fields to read when reading [Record]s,
+    /// the other fields are skipped without being decoded.
+    ///
+    /// Names are compared ignoring the case, see [Record::get_ignoring_case].
+    /// Reading other record types (e.g. with [Self::read_as]) is not affected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// reader.select_fields(&["name", "line"])?;
+    /// let records = reader.read()?;
+    /// assert_eq!(records[0].len(), 2);
+    /// assert!(records[0].get("marker-col").is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if one of the names is not a field of the file,
+    /// the selection is then left unchanged.
+    pub fn select_fields(&mut self, names: &[&str]) -> Result<(), Error> {
+        let mut selection = vec![false; self.fields_info.len()];
+        for name in names {
+            let mut found = false;
+            for (selected, info) in selection.iter_mut().zip(&self.fields_info) {
+                if names_match(&info.name, name) {
+                    *selected = true;
+                    found = true;
+                }
+            }
+            if !found {
+                return Err(Error {
+                    record_num: 0,
+                    field: None,
+                    field_index: None,
+                    kind: ErrorKind::Message(format!("The file has no field named '{}'", name)),
+                });
+            }
+        }
+        self.selected_fields = Some(selection);
+        Ok(())
+    }
+
+    /// Removes the selection made with [Self::select_fields],
+    /// all fields are read again.
+    pub fn clear_selected_fields(&mut self) {
+        self.selected_fields = None;
+    }
+
     /// Creates an iterator of records of the type you want
+    ///
+    /// The iteration starts at the first record, or at the record given to [Self::seek]
+    /// before, so that the records can be iterated several times.
     pub fn iter_records_as<R: ReadableRecord>(&mut self) -> RecordIterator<T, R> {
-        let record_size: usize = self
-            .fields_info
-            .iter()
-            .map(|i| i.field_length as usize)
-            .sum();
+        let record_size = usize::from(self.header.size_of_record) - DELETION_FLAG_SIZE;
+        self.unexpected_deletion_flag_count = 0;
+        self.decode_issues.clear();
+        let start_record = std::mem::take(&mut self.start_record);
+        let field_data_buffer = field_data_buffer(&self.fields_info);
         RecordIterator {
             reader: self,
             record_type: std::marker::PhantomData,
-            current_record: 0,
+            current_record: u32::try_from(start_record).unwrap_or(u32::MAX),
+            positioned: false,
+            include_deleted: false,
+            last_deletion_flag: DeletionFlag::NotDeleted,
+            num_deleted: 0,
+            reached_terminator: false,
             record_data_buffer: std::io::Cursor::new(vec![0u8; record_size]),
-            field_data_buffer: [0u8; 255],
+            field_data_buffer,
         }
     }
 
     /// Shortcut function to get an iterator over the [Records](struct.Record.html) in the file
+    ///
+    /// See [Self::iter_records_as] for where the iteration starts.
     pub fn iter_records(&mut self) -> RecordIterator<T, Record> {
         self.iter_records_as::<Record>()
     }
 
+    /// Creates an iterator over all the records, the ones marked as deleted included,
+    /// each record comes with its [DeletionFlag]
+    ///
+    /// See [Self::iter_records_as] for where the iteration starts.
+    pub fn iter_records_with_deletion_as<R: ReadableRecord>(
+        &mut self,
+    ) -> RecordWithDeletionIterator<'_, T, R> {
+        let mut records = self.iter_records_as::<R>();
+        records.include_deleted = true;
+        RecordWithDeletionIterator { records }
+    }
+
+    /// Shortcut function to get an iterator over all the [Records](struct.Record.html),
+    /// with their [DeletionFlag], see [Self::iter_records_with_deletion_as]
+    pub fn iter_records_with_deletion(&mut self) -> RecordWithDeletionIterator<'_, T, Record> {
+        self.iter_records_with_deletion_as::<Record>()
+    }
+
     /// Reads all the records of the file inside a `Vec`
+    ///
+    /// See [ReadingOptions::max_materialized_records] to limit the number of records.
     pub fn read_as<R: ReadableRecord>(&mut self) -> Result<Vec<R>, Error> {
+        self.check_materialized_records()?;
         // We don't read the file terminator
         self.iter_records_as::<R>()
             .collect::<Result<Vec<R>, Error>>()
@@ -283,20 +956,419 @@ impl<T: Read + Seek> Reader<T> {
     /// # }
     /// ```
     pub fn read(&mut self) -> Result<Vec<Record>, Error> {
+        self.check_materialized_records()?;
         // We don't read the file terminator
         self.iter_records().collect::<Result<Vec<Record>, Error>>()
     }
 
-    /// Seek to the start of the record at `index`
+    /// Fails when the records left to read are more than
+    /// [ReadingOptions::max_materialized_records]
+    fn check_materialized_records(&self) -> Result<(), Error> {
+        let Some(max) = self.options.max_materialized_records else {
+            return Ok(());
+        };
+        let start_record = u32::try_from(self.start_record).unwrap_or(u32::MAX);
+        let num_records = self.header.num_records.saturating_sub(start_record);
+        if num_records > max {
+            return Err(Error {
+                record_num: self.start_record,
+                field: None,
+                field_index: None,
+                kind: ErrorKind::TooManyRecords { num_records, max },
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns a rough estimate, in bytes, of the memory [Self::read] needs
+    /// to hold all the records of the table.
+    ///
+    /// The estimate assumes that:
+    ///
+    /// - the file has the number of records announced by the header,
+    ///   deleted ones included,
+    /// - Character values are as long as their field, and memos are
+    ///   [ESTIMATED_MEMO_SIZE] bytes long,
+    /// - the other values are stored in the [FieldValue] without any allocation,
+    /// - each field of a [Record] costs a slot of its hash table, holding the value
+    ///   and the name shared by all the records, with the load factor of a full table.
+    ///
+    /// Only the selected fields are counted, see [Self::select_fields].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// if reader.estimated_memory_usage() < 512 * 1024 * 1024 {
+    ///     // read() is fine, otherwise iterate over the records
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn estimated_memory_usage(&self) -> usize {
+        let slot_size = std::mem::size_of::<std::sync::Arc<str>>()
+            + std::mem::size_of::<FieldValue>()
+            // Control byte of the hash table
+            + 1;
+        let record_size = self
+            .fields_info
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| {
+                self.selected_fields
+                    .as_ref()
+                    .map_or(true, |selected| selected[*index])
+            })
+            .map(|(_, info)| {
+                let value_size = match info.field_type {
+                    FieldType::Character => usize::from(info.field_length),
+                    FieldType::Memo => ESTIMATED_MEMO_SIZE,
+                    _ => 0,
+                };
+                // A table is at most 7/8 full
+                slot_size * 8 / 7 + value_size
+            })
+            .sum::<usize>()
+            + std::mem::size_of::<Record>();
+        (self.header.num_records as usize).saturating_mul(record_size)
+    }
+
+    /// Reads the next `n` records into a [TablePreview] to print them as a text table,
+    /// the ones marked as deleted are included and marked with an asterisk.
+    ///
+    /// Like the other reading methods, it starts at the first record,
+    /// or at the record given to [Self::seek].
+    pub fn preview(&mut self, n: usize) -> Result<TablePreview, Error> {
+        let records = self
+            .iter_records_with_deletion_as::<PreviewValues>()
+            .take(n)
+            .collect::<Result<Vec<_>, Error>>()?;
+        let mut preview = TablePreview::new(self.fields());
+        for (values, deletion_flag) in records {
+            preview.push(
+                self.fields(),
+                values,
+                deletion_flag == DeletionFlag::Deleted,
+            );
+        }
+        Ok(preview)
+    }
+
+    /// Returns the number of records the file actually contains,
+    /// known once the records have been iterated to the end.
+    ///
+    /// Some writers put in the header a number of records larger
+    /// than the number of records written, the records then end early
+    /// with the file terminator byte (0x1A), which stops the iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// assert_eq!(reader.actual_record_count(), None);
+    /// let records = reader.read()?;
+    /// assert_eq!(reader.actual_record_count(), Some(records.len()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn actual_record_count(&self) -> Option<usize> {
+        self.actual_record_count
+    }
+
+    /// Counts the records of the file, to check the number of records of the header,
+    /// without iterating over them: only the deletion flag of each record is read.
+    ///
+    /// The count stops at the end of the file or at the end of file marker (0x1A),
+    /// the position of the reader is not changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// let counts = reader.count_records()?;
+    /// assert_eq!(counts.physical_count, counts.header_count);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn count_records(&mut self) -> Result<RecordCounts, Error> {
+        RecordCounts::scan(&mut self.source, &self.header).map_err(|err| Error::io_error(err, 0))
+    }
+
+    /// Returns the number of records whose deletion flag was neither a space nor `*`
+    /// during the last iteration over the records.
+    ///
+    /// These records are read as not deleted, unless
+    /// [ReadingOptions::strict_deletion_flag] is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// let records = reader.read()?;
+    /// assert_eq!(reader.unexpected_deletion_flag_count(), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn unexpected_deletion_flag_count(&self) -> usize {
+        self.unexpected_deletion_flag_count
+    }
+
+    /// Returns the bytes of Character fields that could not be decoded
+    /// during the last iteration over the records, when
+    /// [ReadingOptions::character_decode] is [DecodeMode::LossyWithReport].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let options = dbase::ReadingOptions::default()
+    ///     .character_decode(dbase::DecodeMode::LossyWithReport);
+    /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// reader.set_options(options);
+    /// let records = reader.read()?;
+    /// assert!(reader.decode_issues().is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn decode_issues(&self) -> &[DecodeIssue] {
+        &self.decode_issues
+    }
+
+    /// Calls `f` with each record of the file, reusing the same [Record]
+    /// instead of allocating a new one for each record.
+    ///
+    /// Stops at the first error, returned by `f` or when reading a record.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let mut num_blue_stations = 0;
+    /// reader.for_each_record(|record| {
+    ///     let line = record.get("line");
+    ///     if line == Some(&dbase::FieldValue::Character(Some("blue".to_string()))) {
+    ///         num_blue_stations += 1;
+    ///     }
+    ///     Ok::<(), dbase::Error>(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn for_each_record<E, F>(&mut self, mut f: F) -> Result<(), E>
+    where
+        E: From<Error>,
+        F: FnMut(&Record) -> Result<(), E>,
+    {
+        let mut record = Record::default();
+        let mut records = self.iter_records();
+        while let Some(result) = records.read_next_with(|iter| record.read_into(iter)) {
+            result?;
+            f(&record)?;
+        }
+        Ok(())
+    }
+
+    /// Returns an iterator over the records for which `predicate` returns true,
+    /// the errors are always returned.
+    ///
+    /// See [Self::iter_records_as] for where the iteration starts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let blue = dbase::FieldValue::Character(Some("blue".to_string()));
+    /// let blue_stations = reader
+    ///     .filter_records(|record| record.get("line") == Some(&blue))
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn filter_records<F>(&mut self, predicate: F) -> FilteredRecordIterator<'_, T, F>
+    where
+        F: FnMut(&Record) -> bool,
+    {
+        FilteredRecordIterator {
+            records: self.iter_records(),
+            predicate,
+            date_range: None,
+        }
+    }
+
+    /// Returns an iterator over the records whose Date field `field_name`
+    /// is between `from` and `to`, both included, a bound that is `None` is not checked.
+    ///
+    /// The date is read before the other fields, which are not read when it
+    /// is not in the range. Records with an empty date are only returned
+    /// when both bounds are `None`.
+    ///
+    /// Fails if the table has no field named `field_name`
+    /// (ignoring the case, see [Record::get_ignoring_case])
+    /// or if it is not a Date field.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// # let mut dst = std::io::Cursor::new(Vec::<u8>::new());
+    /// # let mut writer = dbase::TableWriterBuilder::new()
+    /// #     .add_date_field("DAY".try_into().unwrap())
+    /// #     .build_with_dest(&mut dst)?;
+    /// # writer.write_record(&[dbase::FieldValue::Date(Some(dbase::Date::new(3, 1, 2024)))][..])?;
+    /// # drop(writer);
+    /// # dst.set_position(0);
+    /// let mut reader = dbase::Reader::new(dst)?;
+    /// let january = reader
+    ///     .filter_by_date_range(
+    ///         "DAY",
+    ///         Some(dbase::Date::new(1, 1, 2024)),
+    ///         Some(dbase::Date::new(31, 1, 2024)),
+    ///     )?
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(january.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn filter_by_date_range(
+        &mut self,
+        field_name: &str,
+        from: Option<Date>,
+        to: Option<Date>,
+    ) -> Result<DateRangeIterator<'_, T>, Error> {
+        let Some(index) = self
+            .fields_info
+            .iter()
+            .position(|info| names_match(&info.name, field_name))
+        else {
+            return Err(Error {
+                record_num: 0,
+                field: None,
+                field_index: None,
+                kind: ErrorKind::Message(format!("The file has no field named '{}'", field_name)),
+            });
+        };
+        let field_info = &self.fields_info[index];
+        if field_info.field_type != FieldType::Date {
+            return Err(Error::new(
+                FieldIOError::new(ErrorKind::IncompatibleType, Some(field_info.clone()))
+                    .at_field_index(index),
+                0,
+            ));
+        }
+        let offset = self.fields_info[..index]
+            .iter()
+            .map(|info| info.hidden_bytes_before + usize::from(info.field_length))
+            .sum::<usize>()
+            + field_info.hidden_bytes_before;
+        let date_range = DateRange {
+            field_info: field_info.clone(),
+            index,
+            offset,
+            from,
+            to,
+        };
+        Ok(FilteredRecordIterator {
+            records: self.iter_records(),
+            predicate: |_| true,
+            date_range: Some(date_range),
+        })
+    }
+
+    /// Reads the text of the memo stored at the block `index`
+    /// of the memo file, see [FieldIterator::read_next_memo_index].
+    pub fn read_memo_text(&mut self, index: u32) -> Result<String, Error> {
+        let memo_reader = self.memo_reader.as_mut().ok_or(Error {
+            record_num: 0,
+            field: None,
+            field_index: None,
+            kind: ErrorKind::MissingMemoFile,
+        })?;
+        memo_reader
+            .read_text_at(index, &self.encoding, self.options.max_memo_size)
+            .map_err(|kind| Error {
+                record_num: 0,
+                field: None,
+                field_index: None,
+                kind,
+            })
+    }
+
+    /// Copies the bytes of the memo stored at the block `index` to `dst`,
+    /// without decoding them, and returns the number of bytes written.
+    ///
+    /// Unlike [Self::read_memo_text], the memo is not held in memory
+    /// and [ReadingOptions::max_memo_size] does not apply.
+    pub fn read_memo_into(&mut self, index: u32, dst: &mut dyn Write) -> Result<u64, Error> {
+        let memo_reader = self.memo_reader.as_mut().ok_or(Error {
+            record_num: 0,
+            field: None,
+            field_index: None,
+            kind: ErrorKind::MissingMemoFile,
+        })?;
+        memo_reader
+            .read_data_at_into(index, dst)
+            .map_err(|kind| Error {
+                record_num: 0,
+                field: None,
+                field_index: None,
+                kind,
+            })
+    }
+
+    /// Seek to the start of the record at `index`,
+    /// the next iteration over the records starts there
     pub fn seek(&mut self, index: usize) -> Result<(), Error> {
         let offset = self.header.offset_to_first_record as usize
             + (index * self.header.size_of_record as usize);
         self.source
             .seek(SeekFrom::Start(offset as u64))
             .map_err(|err| Error::io_error(err, 0))?;
+        self.start_record = index;
         Ok(())
     }
 
+    /// Seeks back to the first record.
+    ///
+    /// The iterations over the records already start at the first record,
+    /// this undoes a [Self::seek] and puts the source back where the records start.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// reader.seek(10)?;
+    /// reader.rewind()?;
+    /// let records = reader.read()?;
+    /// assert_eq!(records.len(), 86);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rewind(&mut self) -> Result<(), Error> {
+        self.seek(0)
+    }
+
+    /// Returns the info that allow to create a writer that would write a file
+    /// with the same structure, without consuming the reader.
+    ///
+    /// Unlike [Self::into_table_info], this is usable with a reader
+    /// that borrows its source (see [Reader::from_mut]).
+    pub fn table_info(&self) -> TableInfo {
+        TableInfo {
+            header: self.header,
+            fields_info: self.fields_info.clone(),
+            encoding: self.encoding.clone(),
+            backlink: self.backlink.clone(),
+            code_page_mark: None,
+        }
+    }
+
     /// Consumes the reader, and returns the info that
     /// allow to create a writer that would write a file
     /// with the same structure.
@@ -320,10 +1392,51 @@ impl<T: Read + Seek> Reader<T> {
             header: self.header,
             fields_info: self.fields_info,
             encoding: self.encoding,
+            backlink: self.backlink,
+            code_page_mark: None,
         }
     }
 }
 
+impl<'a, T: Read + Seek> Reader<&'a mut T> {
+    /// Creates a new reader that borrows the source instead of owning it,
+    /// the source can be used again once the reader is dropped.
+    ///
+    /// A reader over `&mut T` can do everything an owning reader does,
+    /// use [Reader::table_info] rather than [Reader::into_table_info]
+    /// to keep the structure of the table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut source = std::io::Cursor::new(std::fs::read("tests/data/line.dbf").unwrap());
+    /// let table_info = {
+    ///     let mut reader = dbase::Reader::from_mut(&mut source)?;
+    ///     let records = reader.read()?;
+    ///     reader.table_info()
+    /// };
+    /// // The source is ours again
+    /// source.set_position(0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_mut(source: &'a mut T) -> Result<Self, Error> {
+        Self::new(source)
+    }
+
+    /// Creates a new reader that borrows the source, and reads strings using the encoding provided.
+    ///
+    /// See [Reader::from_mut] and [Reader::new_with_encoding].
+    pub fn from_mut_with_encoding<E: Encoding + 'static>(
+        source: &'a mut T,
+        encoding: E,
+    ) -> Result<Self, Error> {
+        Self::new_with_encoding(source, encoding)
+    }
+}
+
+#[cfg(feature = "std-fs")]
 impl Reader<BufReader<File>> {
     /// Creates a new dbase Reader from a path
     ///
@@ -336,10 +1449,27 @@ impl Reader<BufReader<File>> {
     /// # }
     /// ```
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::open_path(path, None)
+    }
+
+    /// Creates a new dbase Reader from a path and reads string using the encoding provided.
+    ///
+    /// The encoding is also used to decode the field names.
+    pub fn from_path_with_encoding<P: AsRef<Path>, E: Encoding + 'static>(
+        path: P,
+        encoding: E,
+    ) -> Result<Self, Error> {
+        Self::open_path(path, Some(DynEncoding::new(encoding)))
+    }
+
+    pub(crate) fn open_path<P: AsRef<Path>>(
+        path: P,
+        encoding: Option<DynEncoding>,
+    ) -> Result<Self, Error> {
         let p = path.as_ref().to_owned();
         let bufreader =
             BufReader::new(File::open(path).map_err(|error| Error::io_error(error, 0))?);
-        let mut reader = Reader::new(bufreader)?;
+        let mut reader = Reader::with_optional_encoding(bufreader, encoding)?;
         let at_least_one_field_is_memo = reader
             .fields_info
             .iter()
@@ -348,15 +1478,11 @@ impl Reader<BufReader<File>> {
         if at_least_one_field_is_memo {
             let memo_type = reader.header.file_type.supported_memo_type();
             if let Some(mt) = memo_type {
-                let memo_path = match mt {
-                    MemoFileType::DbaseMemo | MemoFileType::DbaseMemo4 => p.with_extension("dbt"),
-                    MemoFileType::FoxBaseMemo => p.with_extension("fpt"),
-                };
-
-                let memo_file = File::open(memo_path).map_err(|error| Error {
+                let memo_file = mt.open_memo_file(&p, false).map_err(|kind| Error {
                     record_num: 0,
                     field: None,
-                    kind: ErrorKind::ErrorOpeningMemoFile(error),
+                    field_index: None,
+                    kind,
                 })?;
 
                 let memo_reader = MemoReader::new(mt, BufReader::new(memo_file))
@@ -366,16 +1492,6 @@ impl Reader<BufReader<File>> {
         }
         Ok(reader)
     }
-
-    /// Creates a new dbase Reader from a path and reads string using the encoding provided.
-    pub fn from_path_with_encoding<P: AsRef<Path>, E: Encoding + 'static>(
-        path: P,
-        encoding: E,
-    ) -> Result<Self, Error> {
-        let mut reader = Self::from_path(path)?;
-        reader.encoding = DynEncoding::new(encoding);
-        Ok(reader)
-    }
 }
 
 /// Simple struct to wrap together the value with the name
@@ -401,20 +1517,67 @@ pub struct FieldIterator<'a, Source: Read + Seek, MemoSource: Read + Seek> {
     /// The source where the Memo field data is read
     pub(crate) memo_reader: &'a mut Option<MemoReader<MemoSource>>,
     /// Buffer where field data is stored
-    pub(crate) field_data_buffer: &'a mut [u8; 255],
+    pub(crate) field_data_buffer: &'a mut [u8],
     /// The string encoding
     pub(crate) encoding: &'a DynEncoding,
-    pub(crate) options: ReadingOptions,
+    pub(crate) options: &'a ReadingOptions,
+    /// Which fields are selected, by index, when reading into a [Record]
+    pub(crate) selection: Option<&'a [bool]>,
+    /// Number of fields of the record, to know the index of the next one
+    pub(crate) num_fields: usize,
+    /// Index of the field and offset within the field of the bytes
+    /// that could not be decoded, with a lossy [DecodeMode]
+    pub(crate) invalid_bytes: Vec<(usize, usize)>,
 }
 
 impl<'a, Source: Read + Seek, MemoSource: Read + Seek> FieldIterator<'a, Source, MemoSource> {
+    /// Index of the next field that will be read
+    pub(crate) fn next_field_index(&self) -> usize {
+        self.num_fields - self.fields_info.len()
+    }
+
+    /// Returns the index of the next field that will be read or skipped
+    pub fn current_index(&self) -> usize {
+        self.next_field_index()
+    }
+
+    /// Returns the number of fields of the record not read nor skipped yet
+    pub fn remaining(&self) -> usize {
+        self.fields_info.len()
+    }
+
+    /// Returns the info of the next field, without reading it
+    ///
+    /// Returns `None` when all the fields of the record were read or skipped.
+    pub fn peek_info(&self) -> Option<&'a FieldInfo> {
+        self.fields_info.clone().next()
+    }
+
+    /// Skips the next `n` fields of the record.
+    ///
+    /// Returns an [ErrorKind::EndOfRecord] error, without skipping any field,
+    /// when less than `n` fields remain.
+    pub fn skip_fields(&mut self, n: usize) -> Result<(), FieldIOError> {
+        if n > self.remaining() {
+            return Err(FieldIOError::end_of_record());
+        }
+        for _ in 0..n {
+            self.skip_next_field()?;
+        }
+        Ok(())
+    }
+
     /// Reads the next field and returns its name and value
     pub fn read_next_field_impl(&mut self) -> Result<(&'a FieldInfo, FieldValue), FieldIOError> {
+        let index = self.next_field_index();
         let field_info = self
             .fields_info
             .next()
             .ok_or_else(FieldIOError::end_of_record)?;
-        Ok((field_info, self.read_field(field_info)?))
+        let value = self
+            .read_field(field_info, index)
+            .map_err(|error| error.at_field_index(index))?;
+        Ok((field_info, value))
     }
 
     /// Reads the next field and returns its name and value
@@ -427,19 +1590,168 @@ impl<'a, Source: Read + Seek, MemoSource: Read + Seek> FieldIterator<'a, Source,
     }
 
     /// Reads the next field and tries to convert into the requested type
-    /// using [TryFrom]
+    /// using [FromFieldValue]
     pub fn read_next_field_as<F>(&mut self) -> Result<NamedValue<'a, F>, FieldIOError>
     where
-        F: TryFrom<FieldValue, Error = FieldConversionError>,
+        F: FromFieldValue,
     {
+        let index = self.next_field_index();
         self.read_next_field_impl()
-            .and_then(|(field_info, field_value)| match F::try_from(field_value) {
-                Ok(v) => Ok(NamedValue {
-                    name: field_info.name(),
-                    value: v,
-                }),
-                Err(e) => Err(FieldIOError::new(e.into(), Some(field_info.to_owned()))),
-            })
+            .and_then(
+                |(field_info, field_value)| match F::from_field(field_value, field_info) {
+                    Ok(v) => Ok(NamedValue {
+                        name: field_info.name(),
+                        value: v,
+                    }),
+                    Err(e) => Err(FieldIOError::new(e.into(), Some(field_info.to_owned()))
+                        .at_field_index(index)),
+                },
+            )
+    }
+
+    /// Same as [Self::read_next_field_as], but first checks that the next field
+    /// is named `expected_name` (ignoring the case, see [Record::get_ignoring_case]).
+    ///
+    /// This catches structs whose fields are not in the order of the table,
+    /// which would otherwise read the wrong fields when their types match.
+    /// The field is not consumed if its name does not match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldIOError, FieldIterator, ReadableRecord};
+    /// use std::io::{Read, Seek};
+    ///
+    /// struct Station {
+    ///     name: String,
+    ///     marker_col: String,
+    /// }
+    ///
+    /// impl ReadableRecord for Station {
+    ///     fn read_using<T, R>(iter: &mut FieldIterator<T, R>) -> Result<Self, FieldIOError>
+    ///     where
+    ///         T: Read + Seek,
+    ///         R: Read + Seek,
+    ///     {
+    ///         Ok(Self {
+    ///             name: iter.read_named_field_as::<String>("name")?.value,
+    ///             marker_col: iter.read_named_field_as::<String>("marker-col")?.value,
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let stations = reader.read_as::<Station>()?;
+    /// assert_eq!(stations[0].name, "Van Dorn Street");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_named_field_as<F>(
+        &mut self,
+        expected_name: &str,
+    ) -> Result<NamedValue<'a, F>, FieldIOError>
+    where
+        F: FromFieldValue,
+    {
+        self.check_next_field_names(std::iter::once(expected_name))?;
+        self.read_next_field_as()
+    }
+
+    /// Checks that the next fields are named like `expected_names`, in that order
+    pub(crate) fn check_next_field_names<'n, I>(
+        &mut self,
+        expected_names: I,
+    ) -> Result<(), FieldIOError>
+    where
+        I: IntoIterator<Item = &'n str>,
+    {
+        let mut upcoming = self.fields_info.clone();
+        for (index, expected) in (self.next_field_index()..).zip(expected_names) {
+            let field_info = upcoming.next().ok_or_else(FieldIOError::end_of_record)?;
+            if !names_match(&field_info.name, expected) {
+                return Err(FieldIOError::new(
+                    ErrorKind::FieldNameMismatch {
+                        expected: expected.to_string(),
+                        actual: field_info.name.to_string(),
+                    },
+                    Some(field_info.clone()),
+                )
+                .at_field_index(index));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the next field, which must be a Character field, without copying its text
+    /// when the encoding allows it.
+    ///
+    /// The returned string borrows the buffer of the iterator, so it is valid
+    /// until the next field is read. Returns `None` as value when the field is empty.
+    pub fn read_next_field_str(
+        &mut self,
+    ) -> Result<NamedValue<'a, Option<Cow<'_, str>>>, FieldIOError> {
+        let index = self.next_field_index();
+        let field_info = self
+            .fields_info
+            .next()
+            .ok_or_else(FieldIOError::end_of_record)?;
+        self.skip_hidden_bytes(field_info)
+            .map_err(|error| error.at_field_index(index))?;
+        let field_data_buffer = &mut self.field_data_buffer[..field_info.length() as usize];
+        self.source.read_exact(field_data_buffer).map_err(|error| {
+            FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
+                .at_field_index(index)
+        })?;
+        let mut invalid_offsets = vec![];
+        let value = read_character_str(
+            field_data_buffer,
+            field_info,
+            self.encoding,
+            self.options,
+            &mut invalid_offsets,
+        )
+        .map_err(|kind| FieldIOError::new(kind, Some(field_info.clone())).at_field_index(index))?;
+        self.invalid_bytes
+            .extend(invalid_offsets.into_iter().map(|offset| (index, offset)));
+        Ok(NamedValue {
+            name: field_info.name(),
+            value,
+        })
+    }
+
+    /// Reads the index of the memo block stored in the next field, which must be a Memo field,
+    /// without reading the memo file.
+    ///
+    /// Returns `None` when the field is empty.
+    /// The text can then be read with [Reader::read_memo_text].
+    pub fn read_next_memo_index(&mut self) -> Result<Option<u32>, FieldIOError> {
+        let index = self.next_field_index();
+        let field_info = self
+            .fields_info
+            .next()
+            .ok_or_else(FieldIOError::end_of_record)?;
+        let to_field_error = |kind| FieldIOError::new(kind, Some(field_info.clone()));
+        self.skip_hidden_bytes(field_info)
+            .map_err(|error| error.at_field_index(index))?;
+        let field_data_buffer = &mut self.field_data_buffer[..field_info.length() as usize];
+        self.source
+            .read_exact(field_data_buffer)
+            .map_err(|error| to_field_error(ErrorKind::IoError(error)).at_field_index(index))?;
+        if field_info.field_type != FieldType::Memo {
+            return Err(to_field_error(ErrorKind::IncompatibleType).at_field_index(index));
+        }
+        let next_available_block_index = self
+            .memo_reader
+            .as_ref()
+            .and_then(MemoReader::next_available_block_index);
+        read_memo_index(
+            field_data_buffer,
+            field_info,
+            self.encoding,
+            next_available_block_index,
+        )
+        .map_err(|kind| to_field_error(kind).at_field_index(index))
     }
 
     /// Skips the next field of the record, useful if the field does not interest you
@@ -447,9 +1759,37 @@ impl<'a, Source: Read + Seek, MemoSource: Read + Seek> FieldIterator<'a, Source,
     ///
     /// Does nothing if the last field of the record was already skipped or read.
     pub fn skip_next_field(&mut self) -> Result<(), FieldIOError> {
+        let index = self.next_field_index();
         match self.fields_info.next() {
             None => Ok(()),
-            Some(field_info) => self.skip_field(field_info),
+            Some(field_info) => self
+                .skip_field(field_info)
+                .map_err(|error| error.at_field_index(index)),
+        }
+    }
+
+    /// Reads the next selected field, skipping the ones that are not selected
+    ///
+    /// Returns None when there are no more fields
+    pub(crate) fn read_next_selected_field(
+        &mut self,
+    ) -> Option<Result<(&'a FieldInfo, FieldValue), FieldIOError>> {
+        if let Some(selection) = self.selection {
+            loop {
+                let index = selection.len() - self.fields_info.len();
+                match selection.get(index) {
+                    Some(false) => {
+                        if let Err(error) = self.skip_next_field() {
+                            return Some(Err(error));
+                        }
+                    }
+                    _ => break,
+                }
+            }
+        }
+        match self.read_next_field_impl() {
+            Err(error) if matches!(error.kind(), ErrorKind::EndOfRecord) => None,
+            result => Some(result),
         }
     }
 
@@ -469,26 +1809,37 @@ impl<'a, Source: Read + Seek, MemoSource: Read + Seek> FieldIterator<'a, Source,
     /// Reads the raw bytes of the next field without doing any filtering or trimming
     #[cfg(feature = "serde")]
     pub(crate) fn read_next_field_raw(&mut self) -> Result<Vec<u8>, FieldIOError> {
+        let index = self.next_field_index();
         let field_info = self
             .fields_info
             .next()
             .ok_or(FieldIOError::end_of_record())?;
+        self.skip_hidden_bytes(field_info)
+            .map_err(|error| error.at_field_index(index))?;
         let mut buf = vec![0u8; field_info.field_length as usize];
         self.source.read_exact(&mut buf).map_err(|error| {
             FieldIOError::new(ErrorKind::IoError(error), Some(field_info.to_owned()))
+                .at_field_index(index)
         })?;
         Ok(buf)
     }
 
     #[cfg(feature = "serde")]
     pub(crate) fn peek_next_field(&mut self) -> Result<NamedValue<'a, FieldValue>, FieldIOError> {
-        let field_info = *self.fields_info.peek().ok_or(FieldIOError {
-            field: None,
-            kind: ErrorKind::EndOfRecord,
-        })?;
-        let value = self.read_field(field_info)?;
+        let index = self.next_field_index();
+        let field_info = *self
+            .fields_info
+            .peek()
+            .ok_or(FieldIOError::end_of_record())?;
+        let num_invalid_bytes = self.invalid_bytes.len();
+        let value = self
+            .read_field(field_info, index)
+            .map_err(|error| error.at_field_index(index))?;
+        // The field is read again after
+        self.invalid_bytes.truncate(num_invalid_bytes);
+        let read_len = i64::from(field_info.field_length) + field_info.hidden_bytes_before as i64;
         self.source
-            .seek(SeekFrom::Current(-i64::from(field_info.field_length)))
+            .seek(SeekFrom::Current(-read_len))
             .map_err(|error| {
                 FieldIOError::new(ErrorKind::IoError(error), Some(field_info.to_owned()))
             })?;
@@ -501,6 +1852,7 @@ impl<'a, Source: Read + Seek, MemoSource: Read + Seek> FieldIterator<'a, Source,
 
     /// Advance the source to skip the field
     fn skip_field(&mut self, field_info: &FieldInfo) -> Result<(), FieldIOError> {
+        self.skip_hidden_bytes(field_info)?;
         self.source
             .seek(SeekFrom::Current(i64::from(field_info.field_length)))
             .map_err(|error| {
@@ -509,23 +1861,43 @@ impl<'a, Source: Read + Seek, MemoSource: Read + Seek> FieldIterator<'a, Source,
         Ok(())
     }
 
-    /// read the next field using the given info
-    fn read_field(&mut self, field_info: &'a FieldInfo) -> Result<FieldValue, FieldIOError> {
+    /// Advance the source past the bytes of the skipped fields
+    /// that are before the field
+    fn skip_hidden_bytes(&mut self, field_info: &FieldInfo) -> Result<(), FieldIOError> {
+        if field_info.hidden_bytes_before != 0 {
+            self.source
+                .seek(SeekFrom::Current(field_info.hidden_bytes_before as i64))
+                .map_err(|error| {
+                    FieldIOError::new(ErrorKind::IoError(error), Some(field_info.to_owned()))
+                })?;
+        }
+        Ok(())
+    }
+
+    /// read the next field, at `index`, using the given info
+    fn read_field(
+        &mut self,
+        field_info: &'a FieldInfo,
+        index: usize,
+    ) -> Result<FieldValue, FieldIOError> {
+        self.skip_hidden_bytes(field_info)?;
         let field_data_buffer = &mut self.field_data_buffer[..field_info.length() as usize];
-        self.source.read_exact(field_data_buffer).unwrap();
-        match FieldValue::read_from(
+        self.source.read_exact(field_data_buffer).map_err(|error| {
+            FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
+        })?;
+        let mut invalid_offsets = vec![];
+        let value = FieldValue::read_from(
             field_data_buffer,
             self.memo_reader,
             field_info,
             &*self.encoding,
-            self.options.character_trim,
-        ) {
-            Ok(value) => Ok(value),
-            Err(kind) => Err(FieldIOError {
-                field: Some(field_info.clone()),
-                kind,
-            }),
-        }
+            self.options,
+            &mut invalid_offsets,
+        )
+        .map_err(|kind| FieldIOError::new(kind, Some(field_info.clone())))?;
+        self.invalid_bytes
+            .extend(invalid_offsets.into_iter().map(|offset| (index, offset)));
+        Ok(value)
     }
 }
 
@@ -550,62 +1922,370 @@ impl<'a, Source: Read + Seek, MemoSource: Read + Seek> FusedIterator
 {
 }
 
+/// How the bytes of the records of a table are read,
+/// the one implementation used by [Reader] and [File](crate::File)
+pub(crate) struct RecordParser<'a> {
+    pub(crate) fields_info: &'a [FieldInfo],
+    pub(crate) encoding: &'a DynEncoding,
+    pub(crate) options: &'a ReadingOptions,
+    /// Which fields are selected, by index, when reading into a [Record]
+    pub(crate) selection: Option<&'a [bool]>,
+}
+
+impl RecordParser<'_> {
+    /// Returns the deletion flag of a record, a byte that is neither a space nor `*`
+    /// is a record not marked as deleted, unless [ReadingOptions::strict_deletion_flag] is enabled
+    pub(crate) fn deletion_flag(&self, flag_byte: u8) -> Result<DeletionFlag, ErrorKind> {
+        match DeletionFlag::try_from_byte(flag_byte) {
+            Some(flag) => Ok(flag),
+            None if self.options.strict_deletion_flag => {
+                Err(ErrorKind::InvalidDeletionFlag(flag_byte))
+            }
+            None => Ok(DeletionFlag::NotDeleted),
+        }
+    }
+
+    /// Reads the fields of a record with `read`, the `source` must be positioned
+    /// after the deletion flag and is left at the end of the record.
+    ///
+    /// Also returns the field index and offset of the bytes that could not be decoded.
+    pub(crate) fn read_fields<Source, MemoSource, X>(
+        &self,
+        source: &mut Source,
+        memo_reader: &mut Option<MemoReader<MemoSource>>,
+        field_data_buffer: &mut [u8],
+        read: impl FnOnce(&mut FieldIterator<Source, MemoSource>) -> Result<X, FieldIOError>,
+    ) -> (Result<X, FieldIOError>, Vec<(usize, usize)>)
+    where
+        Source: Read + Seek,
+        MemoSource: Read + Seek,
+    {
+        let mut iter = FieldIterator {
+            source,
+            fields_info: self.fields_info.iter().peekable(),
+            memo_reader,
+            field_data_buffer,
+            encoding: self.encoding,
+            options: self.options,
+            selection: self.selection,
+            num_fields: self.fields_info.len(),
+            invalid_bytes: vec![],
+        };
+        let record =
+            read(&mut iter).and_then(|record| iter.skip_remaining_fields().and(Ok(record)));
+        (record, iter.invalid_bytes)
+    }
+}
+
 /// Iterator over records contained in the dBase
 pub struct RecordIterator<'a, T: Read + Seek, R: ReadableRecord> {
     reader: &'a mut Reader<T>,
     record_type: std::marker::PhantomData<R>,
     current_record: u32,
+    /// Whether the source was moved to the first record of the iteration
+    positioned: bool,
+    /// Whether the records marked as deleted are read instead of skipped
+    include_deleted: bool,
+    /// Deletion flag of the last record read
+    last_deletion_flag: DeletionFlag,
+    /// Number of records marked as deleted that were skipped
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    num_deleted: u32,
+    /// Whether the file terminator, the end of the source or an error
+    /// was found where a record was expected, ending the iteration
+    reached_terminator: bool,
     record_data_buffer: std::io::Cursor<Vec<u8>>,
-    /// Non-Memo field length is stored on a u8,
-    /// so fields cannot exceed 255 bytes
-    field_data_buffer: [u8; 255],
+    /// Large enough for the longest field, see [field_data_buffer]
+    field_data_buffer: Vec<u8>,
 }
 
-impl<'a, T: Read + Seek, R: ReadableRecord> Iterator for RecordIterator<'a, T, R> {
-    type Item = Result<R, Error>;
+impl<'a, T: Read + Seek, R: ReadableRecord> RecordIterator<'a, T, R> {
+    fn report_progress(&self) {
+        self.reader
+            .options
+            .progress
+            .report(self.current_record, self.reader.header.num_records);
+        if self.current_record % DEFAULT_PROGRESS_INTERVAL == 0 {
+            trace_event!(
+                debug,
+                records_read = self.current_record,
+                num_records = self.reader.header.num_records,
+                "reading records"
+            );
+        }
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
+    fn trace_end(&self) {
+        trace_event!(
+            debug,
+            records_read = self.current_record,
+            deleted_records_skipped = self.num_deleted,
+            "end of the records"
+        );
+    }
+
+    /// Ends the iteration on a table that has only `records_found` records
+    /// of the ones its header announces
+    fn end_with_missing_records(&mut self, records_found: usize) {
+        self.reached_terminator = true;
+        self.reader.actual_record_count = Some(records_found);
+        self.reader.warnings.push(Warning::MissingRecords {
+            num_records: self.reader.header.num_records as usize,
+            records_found,
+        });
+        trace_event!(
+            warn,
+            num_records = self.reader.header.num_records,
+            records_found,
+            "the file ends before the number of records of the header"
+        );
+        self.trace_end();
+    }
+
+    /// Ends the iteration on an error of the source, after which its position is unknown.
+    ///
+    /// A source ending in the middle of a record has missing records instead.
+    fn end_with_io_error<X>(&mut self, error: std::io::Error) -> Option<Result<X, Error>> {
+        if error.kind() == std::io::ErrorKind::UnexpectedEof {
+            self.end_with_missing_records(self.current_record as usize);
+            return None;
+        }
+        self.reached_terminator = true;
+        let error = Error::io_error(error, self.current_record as usize);
+        trace_event!(error, %error, "cannot read a record");
+        Some(Err(error))
+    }
+
+    /// Reads the next record that is not marked as deleted using `read`,
+    /// or the next record when deleted records are included
+    fn read_next_with<X>(
+        &mut self,
+        read: impl FnOnce(&mut FieldIterator<std::io::Cursor<Vec<u8>>, T>) -> Result<X, FieldIOError>,
+    ) -> Option<Result<X, Error>> {
+        if !self.positioned {
+            self.positioned = true;
+            let position = u64::from(self.reader.header.offset_to_first_record)
+                + u64::from(self.current_record) * u64::from(self.reader.header.size_of_record);
+            if let Err(error) = self.reader.source.seek(SeekFrom::Start(position)) {
+                self.reached_terminator = true;
+                return Some(Err(Error::io_error(error, self.current_record as usize)));
+            }
+        }
         loop {
-            if self.current_record >= self.reader.header.num_records {
+            if self.reached_terminator {
+                return None;
+            } else if self.current_record >= self.reader.header.num_records {
+                if self.reader.actual_record_count.is_none() {
+                    self.trace_end();
+                }
+                self.reader.actual_record_count = Some(self.reader.header.num_records as usize);
                 return None;
             } else {
-                let deletion_flag = DeletionFlag::read_from(&mut self.reader.source).ok()?;
+                let flag_byte = match self.reader.source.read_u8() {
+                    Ok(flag_byte) => flag_byte,
+                    Err(error) => return self.end_with_io_error(error),
+                };
+                if flag_byte == FILE_TERMINATOR {
+                    // The header announces more records than there are
+                    let position = match self.reader.source.stream_position() {
+                        Ok(position) => position - 1,
+                        Err(error) => return self.end_with_io_error(error),
+                    };
+                    let offset = u64::from(self.reader.header.offset_to_first_record);
+                    let record_size = u64::from(self.reader.header.size_of_record);
+                    let records_found = (position.saturating_sub(offset) / record_size) as usize;
+                    self.end_with_missing_records(records_found);
+                    return None;
+                }
 
-                if deletion_flag == DeletionFlag::Deleted {
-                    self.reader
-                        .source
-                        .seek(SeekFrom::Current(
-                            self.record_data_buffer.get_ref().len() as i64
-                        ))
-                        .ok()?;
+                let parser = RecordParser {
+                    fields_info: &self.reader.fields_info,
+                    encoding: &self.reader.encoding,
+                    options: &self.reader.options,
+                    selection: self.reader.selected_fields.as_deref(),
+                };
+                if DeletionFlag::try_from_byte(flag_byte).is_none() {
+                    self.reader.unexpected_deletion_flag_count += 1;
+                    if !self.reader.options.strict_deletion_flag {
+                        add_unexpected_deletion_flag_warning(
+                            &mut self.reader.warnings,
+                            self.current_record as usize,
+                            flag_byte,
+                        );
+                    }
+                }
+                let deletion_flag = match parser.deletion_flag(flag_byte) {
+                    Ok(flag) => flag,
+                    Err(kind) => {
+                        let error = Error {
+                            record_num: self.current_record as usize,
+                            field: None,
+                            field_index: None,
+                            kind,
+                        };
+                        trace_event!(error, %error, "cannot read a record");
+                        // The next records can still be read
+                        if let Err(error) = self.reader.source.seek(SeekFrom::Current(
+                            self.record_data_buffer.get_ref().len() as i64,
+                        )) {
+                            return self.end_with_io_error(error);
+                        }
+                        self.current_record += 1;
+                        self.report_progress();
+                        return Some(Err(error));
+                    }
+                };
+
+                if deletion_flag == DeletionFlag::Deleted && !self.include_deleted {
+                    if let Err(error) = self.reader.source.seek(SeekFrom::Current(
+                        self.record_data_buffer.get_ref().len() as i64,
+                    )) {
+                        return self.end_with_io_error(error);
+                    }
+                    self.current_record += 1;
+                    self.num_deleted += 1;
+                    self.report_progress();
                     continue;
                 }
+                self.last_deletion_flag = deletion_flag;
 
-                self.reader
+                if let Err(error) = self
+                    .reader
                     .source
                     .read_exact(self.record_data_buffer.get_mut())
-                    .ok()?;
+                {
+                    return self.end_with_io_error(error);
+                }
                 self.record_data_buffer.set_position(0);
 
-                let mut iter = FieldIterator {
-                    source: &mut self.record_data_buffer,
-                    fields_info: self.reader.fields_info.iter().peekable(),
-                    memo_reader: &mut self.reader.memo_reader,
-                    field_data_buffer: &mut self.field_data_buffer,
-                    encoding: &self.reader.encoding,
-                    options: self.reader.options,
-                };
-
-                let record = R::read_using(&mut iter)
-                    .and_then(|record| iter.skip_remaining_fields().and(Ok(record)))
-                    .map_err(|error| Error::new(error, self.current_record as usize));
+                let (record, invalid_bytes) = parser.read_fields(
+                    &mut self.record_data_buffer,
+                    &mut self.reader.memo_reader,
+                    &mut self.field_data_buffer,
+                    read,
+                );
+                if self.reader.options.character_decode == DecodeMode::LossyWithReport {
+                    let record_index = self.current_record as usize;
+                    self.reader
+                        .decode_issues
+                        .extend(invalid_bytes.into_iter().map(|(field_index, byte_offset)| {
+                            DecodeIssue {
+                                record_index,
+                                field_index,
+                                byte_offset,
+                            }
+                        }));
+                }
+                let record = record.map_err(|error| {
+                    let error = Error::new(error, self.current_record as usize);
+                    trace_event!(error, %error, "cannot read a record");
+                    error
+                });
                 self.current_record += 1;
+                self.report_progress();
                 return Some(record);
             }
         }
     }
 }
 
+impl<'a, T: Read + Seek, R: ReadableRecord> Iterator for RecordIterator<'a, T, R> {
+    type Item = Result<R, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_next_with(R::read_using)
+    }
+}
+
+/// Iterator over all the records of a [Reader], the ones marked as deleted included,
+/// created by [Reader::iter_records_with_deletion_as]
+pub struct RecordWithDeletionIterator<'a, T: Read + Seek, R: ReadableRecord> {
+    records: RecordIterator<'a, T, R>,
+}
+
+impl<T: Read + Seek, R: ReadableRecord> Iterator for RecordWithDeletionIterator<'_, T, R> {
+    type Item = Result<(R, DeletionFlag), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = self.records.read_next_with(R::read_using)?;
+        Some(record.map(|record| (record, self.records.last_deletion_flag)))
+    }
+}
+
+/// The Date field, and its bounds, checked by [Reader::filter_by_date_range]
+struct DateRange {
+    field_info: FieldInfo,
+    index: usize,
+    /// Offset of the field in the record, after the deletion flag
+    offset: usize,
+    from: Option<Date>,
+    to: Option<Date>,
+}
+
+impl DateRange {
+    fn contains(&self, date: Option<Date>) -> bool {
+        match date {
+            Some(date) => {
+                self.from.map_or(true, |from| from <= date) && self.to.map_or(true, |to| date <= to)
+            }
+            None => self.from.is_none() && self.to.is_none(),
+        }
+    }
+}
+
+/// Iterator over the records of a [Reader] that match a predicate,
+/// created by [Reader::filter_records] and [Reader::filter_by_date_range]
+pub struct FilteredRecordIterator<'a, T: Read + Seek, F> {
+    records: RecordIterator<'a, T, Record>,
+    predicate: F,
+    date_range: Option<DateRange>,
+}
+
+/// Iterator returned by [Reader::filter_by_date_range]
+pub type DateRangeIterator<'a, T> = FilteredRecordIterator<'a, T, fn(&Record) -> bool>;
+
+impl<T: Read + Seek, F: FnMut(&Record) -> bool> Iterator for FilteredRecordIterator<'_, T, F> {
+    type Item = Result<Record, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let date_range = self.date_range.as_ref();
+            let result = self.records.read_next_with(|iter| {
+                if let Some(range) = date_range {
+                    let field_info = &range.field_info;
+                    let length = usize::from(field_info.field_length);
+                    let bytes = &iter.source.get_ref()[range.offset..range.offset + length];
+                    let date = FieldValue::read_from(
+                        bytes,
+                        iter.memo_reader,
+                        field_info,
+                        iter.encoding,
+                        iter.options,
+                        &mut vec![],
+                    )
+                    .map_err(|kind| {
+                        FieldIOError::new(kind, Some(field_info.clone()))
+                            .at_field_index(range.index)
+                    })?;
+                    let FieldValue::Date(date) = date else {
+                        unreachable!("the field is a Date field");
+                    };
+                    if !range.contains(date) {
+                        return Ok(None);
+                    }
+                }
+                Record::read_using(iter).map(Some)
+            })?;
+            match result {
+                Ok(Some(record)) if !(self.predicate)(&record) => continue,
+                Ok(Some(record)) => return Some(Ok(record)),
+                Ok(None) => continue,
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
 /// One liner to read the content of a .dbf file
 ///
 /// # Example
@@ -614,11 +2294,136 @@ impl<'a, T: Read + Seek, R: ReadableRecord> Iterator for RecordIterator<'a, T, R
 /// let records = dbase::read("tests/data/line.dbf").unwrap();
 /// assert_eq!(records.len(), 1);
 /// ```
+#[cfg(feature = "std-fs")]
 pub fn read<P: AsRef<Path>>(path: P) -> Result<Vec<Record>, Error> {
-    let mut reader = Reader::from_path(path).unwrap();
+    let mut reader = Reader::from_path(path)?;
     reader.read()
 }
 
+/// Reads the header and the fields definition of a table, without its records.
+///
+/// Exactly the bytes before the first record are read, so the source does not
+/// need to be seekable, nor to hold the records (e.g. the start of a file fetched
+/// with an HTTP range request). The terminator that ends the field descriptors
+/// is checked, a source that is too short gives an [ErrorKind::IoError].
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// let bytes = std::fs::read("tests/data/stations.dbf").unwrap();
+/// // The first 161 bytes are the header and the 4 field descriptors
+/// let table_info = dbase::read_table_info(&bytes[..161])?;
+/// assert_eq!(table_info.offset_to_first_record(), 161);
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_table_info<R: Read>(mut source: R) -> Result<TableInfo, Error> {
+    let mut header_bytes = [0u8; Header::SIZE];
+    source
+        .read_exact(&mut header_bytes)
+        .map_err(|error| Error::io_error(error, 0))?;
+    let is_dbase2 = Header::is_dbase2(&header_bytes);
+    let header = if is_dbase2 {
+        Header::read_dbase2_from(&mut &header_bytes[..])
+    } else {
+        Header::read_from(&mut &header_bytes[..])
+    }
+    .map_err(|error| Error::io_error(error, 0))?;
+
+    let invalid_header = |message: String| Error {
+        record_num: 0,
+        field: None,
+        field_index: None,
+        kind: ErrorKind::InvalidHeader(message),
+    };
+    let offset = usize::from(header.offset_to_first_record);
+    if offset <= Header::SIZE {
+        return Err(invalid_header(format!(
+            "offset to first record ({}) is too small",
+            offset
+        )));
+    }
+    let mut bytes = header_bytes.to_vec();
+    bytes.resize(offset, 0);
+    source
+        .read_exact(&mut bytes[Header::SIZE..])
+        .map_err(|error| Error::io_error(error, 0))?;
+
+    let definition =
+        TableDefinition::read_from(&mut Cursor::new(&bytes), &ReadingOptions::default(), None)?;
+
+    // dBase II descriptors are checked when read
+    if !is_dbase2 {
+        let descriptors_end = if header.file_type.is_visual_fox_pro() {
+            offset.saturating_sub(usize::from(BACKLINK_SIZE))
+        } else {
+            offset
+        };
+        let num_descriptors = descriptors_end.saturating_sub(Header::SIZE + 1) / FieldInfo::SIZE;
+        let position = Header::SIZE + num_descriptors * FieldInfo::SIZE;
+        if bytes.get(position) != Some(&TERMINATOR_VALUE) {
+            return Err(invalid_header(format!(
+                "expected the terminator 0x{:02X} after the field descriptors, at offset {}",
+                TERMINATOR_VALUE, position
+            )));
+        }
+    }
+
+    Ok(TableInfo {
+        header: definition.header,
+        fields_info: definition.fields_info.inner,
+        encoding: definition.encoding,
+        backlink: definition.backlink,
+        code_page_mark: None,
+    })
+}
+
+/// Returns the path of the memo file needed to read the table at `dbf_path`,
+/// `None` when the table has no memo field.
+///
+/// Only the header and the fields definition are read, see [read_table_info].
+/// The memo file has the same stem as the table, and the extension of the
+/// [MemoFileType] of the table version. When a memo file with the extension
+/// in lowercase or uppercase exists, its path is returned, otherwise the returned
+/// path has the extension in the case of the table extension.
+///
+/// Like [Reader::from_path], `None` is returned for a table with memo fields
+/// whose version does not support memo files.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// let memo_path = dbase::required_memo_path("tests/data/memo.dbf")?;
+/// assert_eq!(memo_path, Some("tests/data/memo.fpt".into()));
+/// assert_eq!(dbase::required_memo_path("tests/data/stations.dbf")?, None);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "std-fs")]
+pub fn required_memo_path<P: AsRef<Path>>(dbf_path: P) -> Result<Option<PathBuf>, Error> {
+    let dbf_path = dbf_path.as_ref();
+    let file = File::open(dbf_path).map_err(|error| Error::io_error(error, 0))?;
+    let table_info = read_table_info(BufReader::new(file))?;
+    let has_memo_field = table_info
+        .fields_info
+        .iter()
+        .any(|info| info.field_type == FieldType::Memo);
+    if !has_memo_field {
+        return Ok(None);
+    }
+    let Some(memo_type) = table_info.header.file_type.supported_memo_type() else {
+        return Ok(None);
+    };
+    let [expected, other] = memo_type.memo_path_candidates(dbf_path);
+    if !expected.exists() && other.exists() {
+        Ok(Some(other))
+    } else {
+        Ok(Some(expected))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::fs::File;
@@ -638,4 +2443,53 @@ mod test {
 
         assert_eq!(pos_after_reading, expected_pos as u64);
     }
+
+    /// Reads the first record of stations.dbf with `read`
+    fn read_first_station<X>(
+        read: impl FnOnce(&mut FieldIterator<std::io::Cursor<Vec<u8>>, File>) -> Result<X, FieldIOError>,
+    ) -> Result<X, Error> {
+        let file = File::open("tests/data/stations.dbf").unwrap();
+        let mut reader = Reader::new(file).unwrap();
+        let mut records = reader.iter_records_as::<Record>();
+        records.read_next_with(read).unwrap()
+    }
+
+    #[test]
+    fn skip_fields_keeps_the_fields_aligned() {
+        let line = read_first_station(|iter| {
+            assert_eq!((iter.current_index(), iter.remaining()), (0, 4));
+            assert_eq!(
+                iter.read_next_field_as::<String>()?.value,
+                "Van Dorn Street"
+            );
+            iter.skip_fields(2)?;
+            assert_eq!((iter.current_index(), iter.remaining()), (3, 1));
+            assert_eq!(iter.peek_info().map(FieldInfo::name), Some("line"));
+            let line = iter.read_next_field_as::<String>()?;
+            assert_eq!(line.name, "line");
+            assert_eq!((iter.current_index(), iter.remaining()), (4, 0));
+            assert!(iter.peek_info().is_none());
+            Ok(line.value)
+        })
+        .unwrap();
+        assert_eq!(line, "blue");
+    }
+
+    #[test]
+    fn skip_fields_past_the_end_skips_nothing() {
+        let marker_col = read_first_station(|iter| {
+            iter.skip_fields(1)?;
+            let error = iter.skip_fields(4).unwrap_err();
+            assert!(matches!(error.kind(), ErrorKind::EndOfRecord));
+            assert_eq!(iter.current_index(), 1);
+            iter.skip_fields(0)?;
+            let marker_col = iter.read_next_field_as::<String>()?;
+            assert_eq!(marker_col.name, "marker-col");
+            iter.skip_fields(2)?;
+            assert_eq!(iter.remaining(), 0);
+            Ok(marker_col.value)
+        })
+        .unwrap();
+        assert!(!marker_col.is_empty());
+    }
 }
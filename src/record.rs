@@ -1,13 +1,36 @@
-use crate::{FieldIOError, FieldIterator, FieldValue, NamedValue, ReadableRecord};
-use std::collections::hash_map::RandomState;
+use crate::field::names_match;
+use crate::{FieldIOError, FieldIterator, FieldValue, ReadableRecord, TrimOption};
+#[cfg(not(feature = "serde"))]
+use std::collections::BTreeMap;
 use std::collections::HashMap;
+#[cfg(not(feature = "serde"))]
+use std::hash::BuildHasher;
 use std::io::{Read, Seek};
+use std::sync::Arc;
 
 /// Type definition of a generic record.
 /// A .dbf file is composed of many records
-#[derive(Debug, PartialEq, Clone, Default)]
+///
+/// When a file has several fields with the same name, the values
+/// of the duplicates are stored under the name suffixed by `_2`, `_3`, etc.,
+/// skipping the suffixed names that are already the names of other fields.
+///
+/// The names of the records read from the same file are shared,
+/// reading a record does not allocate its names.
+///
+/// Records are equal when they have the same names and values,
+/// whether [Record::get] ignores the case or not.
+#[derive(Debug, Clone, Default)]
 pub struct Record {
-    map: HashMap<String, FieldValue>,
+    map: HashMap<Arc<str>, FieldValue>,
+    /// [Record::get] falls back to a lookup ignoring the case
+    case_insensitive_get: bool,
+}
+
+impl PartialEq for Record {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
 }
 
 impl ReadableRecord for Record {
@@ -18,16 +41,93 @@ impl ReadableRecord for Record {
         Source: Read + Seek,
         MemoSource: Read + Seek,
     {
-        let mut map = HashMap::<String, FieldValue>::new();
-        for result in field_iterator {
-            let NamedValue { name, value } = result?;
-            map.insert(name.to_owned(), value);
-        }
-        Ok(Self { map })
+        let mut record = Self::default();
+        record.read_into(field_iterator)?;
+        Ok(record)
+    }
+}
+
+/// Reads the values like [Record] does, duplicated names included
+///
+/// Not available with the `serde` feature, as it conflicts with
+/// the implementation for `Deserialize` types,
+/// convert the `Record` read instead.
+#[cfg(not(feature = "serde"))]
+impl<S: BuildHasher + Default> ReadableRecord for HashMap<String, FieldValue, S> {
+    fn read_using<Source, MemoSource>(
+        field_iterator: &mut FieldIterator<Source, MemoSource>,
+    ) -> Result<Self, FieldIOError>
+    where
+        Source: Read + Seek,
+        MemoSource: Read + Seek,
+    {
+        let record = Record::read_using(field_iterator)?;
+        Ok(record
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect())
+    }
+}
+
+/// Reads the values like [Record] does, duplicated names included
+///
+/// Not available with the `serde` feature, as it conflicts with
+/// the implementation for `Deserialize` types,
+/// convert the `Record` read instead.
+#[cfg(not(feature = "serde"))]
+impl ReadableRecord for BTreeMap<String, FieldValue> {
+    fn read_using<Source, MemoSource>(
+        field_iterator: &mut FieldIterator<Source, MemoSource>,
+    ) -> Result<Self, FieldIOError>
+    where
+        Source: Read + Seek,
+        MemoSource: Read + Seek,
+    {
+        let record = Record::read_using(field_iterator)?;
+        Ok(record
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect())
     }
 }
 
 impl Record {
+    /// Replaces the content of the record with the fields read,
+    /// the memory of the map is reused.
+    pub(crate) fn read_into<Source, MemoSource>(
+        &mut self,
+        field_iterator: &mut FieldIterator<Source, MemoSource>,
+    ) -> Result<(), FieldIOError>
+    where
+        Source: Read + Seek,
+        MemoSource: Read + Seek,
+    {
+        self.case_insensitive_get = field_iterator.options.case_insensitive_names;
+        let map = &mut self.map;
+        map.clear();
+        while let Some(result) = field_iterator.read_next_selected_field() {
+            let (field_info, value) = result?;
+            // Files may contain several fields with the same name,
+            // suffix the duplicates so that none of them is lost,
+            // without taking the name of a field that comes after
+            let mut key = Arc::clone(&field_info.name);
+            let mut n = 2;
+            while map.contains_key(&key) {
+                key = format!("{}_{}", field_info.name, n).into();
+                n += 1;
+                if field_iterator
+                    .fields_info
+                    .clone()
+                    .any(|info| info.name == key)
+                {
+                    key = Arc::clone(&field_info.name);
+                }
+            }
+            map.insert(key, value);
+        }
+        Ok(())
+    }
+
     /// Inserts a new value in the record, returning the old one if there was any
     ///
     /// # Example
@@ -37,12 +137,56 @@ impl Record {
     /// record.insert("FirstName".to_owned(), dbase::FieldValue::Character(Some("Yoshi".to_owned())));
     /// ```
     pub fn insert(&mut self, field_name: String, value: FieldValue) -> Option<FieldValue> {
-        self.map.insert(field_name, value)
+        self.map.insert(field_name.into(), value)
+    }
+
+    /// Removes all the values, keeping the allocated memory
+    pub fn clear(&mut self) {
+        self.map.clear();
     }
 
     /// Returns the [FieldValue](enum.FieldValue.html) for the given field name
+    ///
+    /// When enabled with [Record::set_case_insensitive_get]
+    /// (or [ReadingOptions::case_insensitive_names](crate::ReadingOptions::case_insensitive_names)
+    /// for the records read), a name that is not found is looked up again ignoring the case.
     pub fn get(&self, field_name: &str) -> Option<&FieldValue> {
-        self.map.get(field_name)
+        if self.case_insensitive_get {
+            self.get_ignoring_case(field_name)
+        } else {
+            self.map.get(field_name)
+        }
+    }
+
+    /// Returns the value for the given field name, or if there is none,
+    /// for a name that is the same ignoring the case.
+    ///
+    /// ASCII names are compared ignoring their ASCII case, other names are compared
+    /// lowercased (with `ß` as `ss`). The names are matched this way wherever
+    /// the crate looks up a field by name.
+    ///
+    /// This is how the values are found when writing a record.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut record = dbase::Record::default();
+    /// record.insert("straße".to_owned(), dbase::FieldValue::Numeric(Some(1.0)));
+    /// assert!(record.get("STRASSE").is_none());
+    /// assert!(record.get_ignoring_case("STRASSE").is_some());
+    /// ```
+    pub fn get_ignoring_case(&self, field_name: &str) -> Option<&FieldValue> {
+        self.map.get(field_name).or_else(|| {
+            self.map
+                .iter()
+                .find(|(name, _)| names_match(name, field_name))
+                .map(|(_, value)| value)
+        })
+    }
+
+    /// Sets whether [Record::get] falls back to a lookup ignoring the case
+    pub fn set_case_insensitive_get(&mut self, enabled: bool) {
+        self.case_insensitive_get = enabled;
     }
 
     /// Returns the mutable [FieldValue](enum.FieldValue.html) for the given field name
@@ -54,37 +198,234 @@ impl Record {
     pub fn remove(&mut self, field_name: &str) -> Option<FieldValue> {
         self.map.remove(field_name)
     }
+
+    /// Renames the field `old_name` to `new_name`, keeping its value.
+    ///
+    /// If a field named `new_name` already existed, its value is replaced.
+    ///
+    /// Returns false if there were no field named `old_name`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut record = dbase::Record::default();
+    /// record.insert("NAME".to_owned(), dbase::FieldValue::Character(Some("Yoshi".to_owned())));
+    /// assert!(record.rename_field("NAME", "FIRST_NAME".to_owned()));
+    /// assert!(!record.contains_key("NAME"));
+    /// assert!(record.contains_key("FIRST_NAME"));
+    /// ```
+    pub fn rename_field(&mut self, old_name: &str, new_name: String) -> bool {
+        match self.map.remove(old_name) {
+            Some(value) => {
+                self.map.insert(new_name.into(), value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns true if the record contains a value for the given field name
+    pub fn contains_key(&self, field_name: &str) -> bool {
+        self.map.contains_key(field_name)
+    }
+
+    /// Returns the number of fields in the record
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the record does not contain any field
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns an iterator over the field names
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.map.keys().map(|name| &**name)
+    }
+
+    /// Returns an iterator over the field values
+    pub fn values(&self) -> impl Iterator<Item = &FieldValue> {
+        self.map.values()
+    }
+}
+
+/// How loosely [RecordCompare::matches_record] compares the values
+#[derive(Debug, Copy, Clone)]
+pub struct CompareTolerance {
+    /// The spaces trimmed from Character and Memo values before comparing them,
+    /// an empty trimmed string is then equal to `None`.
+    ///
+    /// With `None`, the values must be exactly equal.
+    pub trim: Option<TrimOption>,
+    /// Maximum difference between two numbers for them to be equal,
+    /// Float values are compared as `f64`
+    pub max_float_difference: f64,
+}
+
+impl CompareTolerance {
+    /// Values must be exactly equal
+    pub fn exact() -> Self {
+        Self {
+            trim: None,
+            max_float_difference: 0.0,
+        }
+    }
+}
+
+/// Trims the spaces at both ends of the strings
+impl Default for CompareTolerance {
+    fn default() -> Self {
+        Self {
+            trim: Some(TrimOption::BeginEnd),
+            max_float_difference: 0.0,
+        }
+    }
+}
+
+/// Trait to compare a typed record against a dynamically read [Record]
+///
+/// It is implemented by the structs generated with the
+/// [dbase_record](macro.dbase_record.html) macro, and by `#[derive(DbaseTable)]`
+/// (`derive` feature).
+pub trait RecordCompare {
+    /// Converts the typed record into a [Record]
+    ///
+    /// The keys of the record are the struct's field names
+    fn to_record(&self) -> Record;
+
+    /// Returns true if every field of `self` has a matching value in the `record`.
+    ///
+    /// The `record` may contain more fields than `self`, these are ignored.
+    ///
+    /// Values are compared loosely to account for how they are stored in the file:
+    ///
+    /// - Character and Memo values are trimmed as set by the `tolerance`
+    /// - Numbers match any of the Numeric, Float, Currency, Double and Integer fields,
+    ///   with a difference up to the `tolerance`
+    fn matches_record(
+        &self,
+        record: &Record,
+        case_insensitive_names: bool,
+        tolerance: CompareTolerance,
+    ) -> bool {
+        self.to_record().map.iter().all(|(name, expected)| {
+            let actual = if case_insensitive_names {
+                record
+                    .map
+                    .iter()
+                    .find(|(key, _)| names_match(key, name))
+                    .map(|(_, value)| value)
+            } else {
+                record.get(name)
+            };
+            actual.is_some_and(|actual| values_match(expected, actual, tolerance))
+        })
+    }
+}
+
+fn values_match(expected: &FieldValue, actual: &FieldValue, tolerance: CompareTolerance) -> bool {
+    let trimmed = |value: &str| match tolerance.trim {
+        Some(TrimOption::Begin) => value.trim_start().to_string(),
+        Some(TrimOption::End) => value.trim_end().to_string(),
+        Some(TrimOption::BeginEnd) => value.trim().to_string(),
+        None => value.to_string(),
+    };
+    let optional_trimmed = |value: &Option<String>| match (value, tolerance.trim) {
+        (None, Some(_)) => Some(String::new()),
+        (value, _) => value.as_deref().map(trimmed),
+    };
+
+    fn as_f64(value: &FieldValue) -> Option<f64> {
+        match value {
+            FieldValue::Numeric(Some(v)) | FieldValue::Currency(v) | FieldValue::Double(v) => {
+                Some(*v)
+            }
+            FieldValue::Float(Some(v)) => Some(f64::from(*v)),
+            FieldValue::Integer(v) => Some(f64::from(*v)),
+            _ => None,
+        }
+    }
+
+    match (expected, actual) {
+        (FieldValue::Character(e), FieldValue::Character(a)) => {
+            optional_trimmed(e) == optional_trimmed(a)
+        }
+        (FieldValue::Memo(e), FieldValue::Memo(a)) => trimmed(e) == trimmed(a),
+        _ => match (as_f64(expected), as_f64(actual)) {
+            (Some(e), Some(a)) => e == a || (e - a).abs() <= tolerance.max_float_difference,
+            _ => expected == actual,
+        },
+    }
 }
 
 impl IntoIterator for Record {
-    type Item = (String, FieldValue);
-    type IntoIter = std::collections::hash_map::IntoIter<String, FieldValue>;
+    type Item = (Arc<str>, FieldValue);
+    type IntoIter = std::collections::hash_map::IntoIter<Arc<str>, FieldValue>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.map.into_iter()
     }
 }
 
+impl<K: Into<Arc<str>>> FromIterator<(K, FieldValue)> for Record {
+    fn from_iter<I: IntoIterator<Item = (K, FieldValue)>>(iter: I) -> Self {
+        Self {
+            map: iter
+                .into_iter()
+                .map(|(name, value)| (name.into(), value))
+                .collect(),
+            case_insensitive_get: false,
+        }
+    }
+}
+
+impl<K: Into<Arc<str>>> Extend<(K, FieldValue)> for Record {
+    fn extend<I: IntoIterator<Item = (K, FieldValue)>>(&mut self, iter: I) {
+        self.map
+            .extend(iter.into_iter().map(|(name, value)| (name.into(), value)))
+    }
+}
+
 impl From<HashMap<String, FieldValue>> for Record {
-    fn from(map: HashMap<String, FieldValue, RandomState>) -> Self {
-        Self { map }
+    fn from(map: HashMap<String, FieldValue>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl From<HashMap<Arc<str>, FieldValue>> for Record {
+    fn from(map: HashMap<Arc<str>, FieldValue>) -> Self {
+        Self {
+            map,
+            case_insensitive_get: false,
+        }
     }
 }
 
 impl From<Record> for HashMap<String, FieldValue> {
     fn from(record: Record) -> HashMap<String, FieldValue> {
+        record
+            .map
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect()
+    }
+}
+
+impl From<Record> for HashMap<Arc<str>, FieldValue> {
+    fn from(record: Record) -> HashMap<Arc<str>, FieldValue> {
         record.map
     }
 }
 
-impl AsRef<HashMap<String, FieldValue>> for Record {
-    fn as_ref(&self) -> &HashMap<String, FieldValue> {
+impl AsRef<HashMap<Arc<str>, FieldValue>> for Record {
+    fn as_ref(&self) -> &HashMap<Arc<str>, FieldValue> {
         &self.map
     }
 }
 
-impl AsMut<HashMap<String, FieldValue>> for Record {
-    fn as_mut(&mut self) -> &mut HashMap<String, FieldValue> {
+impl AsMut<HashMap<Arc<str>, FieldValue>> for Record {
+    fn as_mut(&mut self) -> &mut HashMap<Arc<str>, FieldValue> {
         &mut self.map
     }
 }
@@ -0,0 +1,566 @@
+//! Comparison of a [Record] with the fields of a table,
+//! and inference of the fields of a table from [Record]s
+use std::borrow::Borrow;
+use std::convert::TryFrom;
+
+use crate::encoding::DynEncoding;
+use crate::field::conversion::can_coerce;
+use crate::field::names_match;
+use crate::field::types::FieldType;
+use crate::{
+    Encoding, Error, ErrorKind, FieldIOError, FieldInfo, FieldName, FieldValue, Record,
+    TableWriterBuilder, UnicodeLossy,
+};
+
+/// Longest Character field [SchemaInferer] creates
+pub const MAX_INFERRED_CHARACTER_LENGTH: u8 = 254;
+/// Most decimals [SchemaInferer] gives to a Numeric or Float field
+pub const MAX_INFERRED_DECIMALS: u8 = 15;
+
+/// Reason why a [Record] cannot be written as-is to a table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Incompatibility {
+    /// The table has a field that the record does not have
+    MissingField(String),
+    /// The record has a field that the table does not have
+    ExtraField(String),
+    /// The type of the value in the record is not the type of the field
+    TypeMismatch {
+        name: String,
+        expected: FieldType,
+        actual: FieldType,
+    },
+    /// The value needs more bytes than the field length,
+    /// it would be truncated
+    ValueTooLong {
+        name: String,
+        max: usize,
+        actual: usize,
+    },
+}
+
+impl std::fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Incompatibility::MissingField(name) => {
+                write!(f, "The record has no value for the field '{}'", name)
+            }
+            Incompatibility::ExtraField(name) => {
+                write!(f, "The table has no field named '{}'", name)
+            }
+            Incompatibility::TypeMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "The field '{}' is a {} field, the record has a {} value",
+                name, expected, actual
+            ),
+            Incompatibility::ValueTooLong { name, max, actual } => write!(
+                f,
+                "The value of the field '{}' is {} bytes long, the field can only hold {} bytes",
+                name, actual, max
+            ),
+        }
+    }
+}
+
+/// Returns all the reasons why the `record` cannot be written
+/// to a table with the given `fields`.
+///
+/// Lengths are computed with the UTF-8 encoding, see [check_compatibility_with_encoding]
+/// to use the encoding of the table.
+///
+/// # Example
+///
+/// ```
+/// use dbase::schema::{check_compatibility, Incompatibility};
+/// use dbase::{FieldValue, Record};
+///
+/// # fn main() -> Result<(), dbase::Error> {
+/// let reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+/// let mut record = Record::default();
+/// record.insert("name".to_string(), FieldValue::Numeric(Some(1.0)));
+///
+/// let incompatibilities = check_compatibility(reader.fields(), &record);
+/// assert_eq!(
+///     incompatibilities,
+///     vec![Incompatibility::TypeMismatch {
+///         name: "name".to_string(),
+///         expected: dbase::FieldType::Character,
+///         actual: dbase::FieldType::Numeric,
+///     }]
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn check_compatibility(fields: &[FieldInfo], record: &Record) -> Vec<Incompatibility> {
+    check_compatibility_with_encoding(fields, record, &UnicodeLossy)
+}
+
+/// Same as [check_compatibility], but the lengths of values are computed
+/// with the given encoding.
+pub fn check_compatibility_with_encoding<E: Encoding>(
+    fields: &[FieldInfo],
+    record: &Record,
+    encoding: &E,
+) -> Vec<Incompatibility> {
+    incompatibilities(
+        fields.iter(),
+        |name| record.get_ignoring_case(name),
+        record.keys(),
+        encoding,
+    )
+}
+
+/// Compares the `fields` with values found by name with `get`,
+/// `names` are the names of all the values
+pub(crate) fn incompatibilities<'a, 'v, I, E>(
+    fields: I,
+    get: impl Fn(&str) -> Option<&'v FieldValue>,
+    names: impl Iterator<Item = &'v str>,
+    encoding: &E,
+) -> Vec<Incompatibility>
+where
+    I: Iterator<Item = &'a FieldInfo> + Clone,
+    E: Encoding,
+{
+    let mut incompatibilities = vec![];
+    let mut buffer = Vec::<u8>::new();
+
+    for info in fields.clone() {
+        let Some(value) = get(&info.name) else {
+            incompatibilities.push(Incompatibility::MissingField(info.name.to_string()));
+            continue;
+        };
+
+        if value.field_type() != info.field_type {
+            incompatibilities.push(Incompatibility::TypeMismatch {
+                name: info.name.to_string(),
+                expected: info.field_type,
+                actual: value.field_type(),
+            });
+            continue;
+        }
+
+        // Encoding errors are reported when writing
+        let Ok(actual) = value.encoded_size_with(info, encoding, &mut buffer) else {
+            continue;
+        };
+        if actual > info.field_length as usize {
+            incompatibilities.push(Incompatibility::ValueTooLong {
+                name: info.name.to_string(),
+                max: info.field_length as usize,
+                actual,
+            });
+        }
+    }
+
+    let mut extra_fields = names
+        .filter(|name| !fields.clone().any(|info| names_match(&info.name, name)))
+        .collect::<Vec<_>>();
+    // The values are in a map, sort to have a stable output
+    extra_fields.sort_unstable();
+    let extra_fields = extra_fields
+        .into_iter()
+        .map(|name| Incompatibility::ExtraField(name.to_string()));
+    incompatibilities.extend(extra_fields);
+
+    incompatibilities
+}
+
+/// Describes the differences between the `expected` fields and the `actual` ones:
+/// names, types, lengths and decimals, and the fields that only one of them has
+pub(crate) fn field_differences(expected: &[FieldInfo], actual: &[FieldInfo]) -> Vec<String> {
+    let mut differences = vec![];
+    for (index, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+        if !names_match(&expected.name, &actual.name) {
+            differences.push(format!(
+                "field {}: expected name '{}', found '{}'",
+                index, expected.name, actual.name
+            ));
+        }
+        let mut compare = |what: &str, expected_value: String, actual_value: String| {
+            if expected_value != actual_value {
+                differences.push(format!(
+                    "field {} '{}': expected {} {}, found {}",
+                    index, expected.name, what, expected_value, actual_value
+                ));
+            }
+        };
+        compare(
+            "type",
+            format!("{:?}", expected.field_type),
+            format!("{:?}", actual.field_type),
+        );
+        compare(
+            "length",
+            expected.field_length.to_string(),
+            actual.field_length.to_string(),
+        );
+        compare(
+            "decimals",
+            expected.num_decimal_places.to_string(),
+            actual.num_decimal_places.to_string(),
+        );
+    }
+    for (index, missing) in expected.iter().enumerate().skip(actual.len()) {
+        differences.push(format!("field {} '{}' is missing", index, missing.name));
+    }
+    for (index, extra) in actual.iter().enumerate().skip(expected.len()) {
+        differences.push(format!("field {} '{}' is not expected", index, extra.name));
+    }
+    differences
+}
+
+/// Returns the first incompatibility, with the field it concerns
+pub(crate) fn first_incompatibility<'a, 'v, I>(
+    fields: I,
+    get: impl Fn(&str) -> Option<&'v FieldValue>,
+    names: impl Iterator<Item = &'v str>,
+    encoding: &DynEncoding,
+    generates_autoincrement: bool,
+    coerce_types: bool,
+) -> Option<(Incompatibility, Option<FieldInfo>)>
+where
+    I: Iterator<Item = &'a FieldInfo> + Clone,
+{
+    let incompatibility = incompatibilities(fields.clone(), get, names, encoding)
+        .into_iter()
+        .find(|incompatibility| match incompatibility {
+            // The value of an autoincrement field is generated when missing
+            Incompatibility::MissingField(name) if generates_autoincrement => !fields
+                .clone()
+                .any(|info| *info.name == **name && info.autoincrement().is_some()),
+            // The value is converted when written
+            Incompatibility::TypeMismatch {
+                expected, actual, ..
+            } if coerce_types => !can_coerce(*actual, *expected),
+            _ => true,
+        })?;
+    let name = match &incompatibility {
+        Incompatibility::MissingField(name)
+        | Incompatibility::TypeMismatch { name, .. }
+        | Incompatibility::ValueTooLong { name, .. } => Some(name),
+        Incompatibility::ExtraField(_) => None,
+    };
+    let field = name.and_then(|name| {
+        fields
+            .into_iter()
+            .find(|info| *info.name == **name)
+            .cloned()
+    });
+    Some((incompatibility, field))
+}
+
+/// Field whose size was inferred by a [SchemaInferer]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InferredField {
+    name: String,
+    field_type: FieldType,
+    binary: bool,
+    /// Longest Character value, or number of digits before the decimal point
+    /// (sign included) of Numeric and Float values
+    max_length: usize,
+    max_decimals: u8,
+    has_nulls: bool,
+}
+
+impl InferredField {
+    /// Name of the field
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Type of the values seen
+    pub fn field_type(&self) -> FieldType {
+        self.field_type
+    }
+
+    /// Number of bytes of the longest value seen, once encoded
+    ///
+    /// For Numeric and Float values, this is the number of characters
+    /// before the decimal point, sign included.
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Most decimals used by the Numeric and Float values seen
+    pub fn max_decimals(&self) -> u8 {
+        self.max_decimals
+    }
+
+    /// Whether at least one value was `None` (see [FieldValue::is_none])
+    pub fn has_nulls(&self) -> bool {
+        self.has_nulls
+    }
+
+    fn observe(&mut self, value: &FieldValue, encoding: &DynEncoding) -> Result<(), ErrorKind> {
+        if value.field_type() != self.field_type
+            || matches!(value, FieldValue::CharacterBinary(_)) != self.binary
+        {
+            return Err(ErrorKind::IncompatibleRecord(
+                Incompatibility::TypeMismatch {
+                    name: self.name.clone(),
+                    expected: self.field_type,
+                    actual: value.field_type(),
+                },
+            ));
+        }
+        self.has_nulls |= value.is_none();
+        let (length, decimals) = match value {
+            FieldValue::Character(Some(text)) => (encoding.encode(text)?.len(), 0),
+            FieldValue::CharacterBinary(bytes) => (bytes.len(), 0),
+            FieldValue::Numeric(Some(number)) => number_size(number.to_string()),
+            FieldValue::Float(Some(number)) => number_size(number.to_string()),
+            FieldValue::Memo(_) => {
+                return Err(ErrorKind::Message(format!(
+                    "The field '{}' has Memo values, they cannot be written",
+                    self.name
+                )))
+            }
+            _ => (0, 0),
+        };
+        if self.field_type == FieldType::Character
+            && length > MAX_INFERRED_CHARACTER_LENGTH as usize
+        {
+            return Err(ErrorKind::IncompatibleRecord(
+                Incompatibility::ValueTooLong {
+                    name: self.name.clone(),
+                    max: MAX_INFERRED_CHARACTER_LENGTH as usize,
+                    actual: length,
+                },
+            ));
+        }
+        self.max_length = self.max_length.max(length);
+        self.max_decimals = self.max_decimals.max(decimals);
+        Ok(())
+    }
+
+    /// Adds the field to the builder, lengths are increased by `margin`
+    fn add_to(&self, builder: TableWriterBuilder, margin: u8) -> TableWriterBuilder {
+        let name = FieldName::try_from(self.name.as_str()).expect("names are checked when seen");
+        let with_margin = |length: usize| (length + margin as usize).max(1);
+        match self.field_type {
+            FieldType::Character => {
+                let length =
+                    with_margin(self.max_length).min(MAX_INFERRED_CHARACTER_LENGTH as usize) as u8;
+                if self.binary {
+                    builder.add_binary_character_field(name, length)
+                } else {
+                    builder.add_character_field(name, length)
+                }
+            }
+            FieldType::Numeric | FieldType::Float => {
+                let decimals_length = match self.max_decimals {
+                    0 => 0,
+                    decimals => decimals as usize + 1,
+                };
+                // Too wide numbers get a length of 0, rejected by the builder
+                let length =
+                    u8::try_from(with_margin(self.max_length) + decimals_length).unwrap_or(0);
+                if self.field_type == FieldType::Numeric {
+                    builder.add_numeric_field(name, length, self.max_decimals)
+                } else {
+                    builder.add_float_field(name, length, self.max_decimals)
+                }
+            }
+            FieldType::Logical => builder.add_logical_field(name),
+            FieldType::Date => builder.add_date_field(name),
+            FieldType::Integer => builder.add_integer_field(name),
+            FieldType::DateTime => builder.add_datetime_field(name),
+            FieldType::Double => builder.add_double_field(name),
+            FieldType::Currency => builder.add_currency_field(name),
+            FieldType::Memo => unreachable!("Memo values are rejected when seen"),
+            FieldType::SystemNullFlags | FieldType::Timestamp => {
+                unreachable!("values are never of these types")
+            }
+        }
+    }
+}
+
+/// Returns the number of characters before the decimal point and the number of decimals
+fn number_size(text: String) -> (usize, u8) {
+    match text.split_once('.') {
+        Some((integer, decimals)) => (
+            integer.len(),
+            decimals.len().min(MAX_INFERRED_DECIMALS as usize) as u8,
+        ),
+        None => (text.len(), 0),
+    }
+}
+
+/// Infers the fields of a table from the [Record]s that will be written to it.
+///
+/// Each record is given to [SchemaInferer::observe], which keeps, for each field name,
+/// the type of the values, the length of the longest Character value once encoded,
+/// and the number of digits and decimals of Numeric and Float values.
+/// [SchemaInferer::finish] then creates a [TableWriterBuilder] with fields just large
+/// enough for the values seen, plus an optional [margin](SchemaInferer::margin).
+///
+/// Fields are in the order in which their names were first seen,
+/// names that appear in the same record are sorted.
+///
+/// # Example
+///
+/// ```
+/// use dbase::schema::SchemaInferer;
+///
+/// # fn main() -> Result<(), dbase::Error> {
+/// let records = dbase::read("tests/data/stations.dbf")?;
+/// let mut inferer = SchemaInferer::new().margin(2);
+/// for record in &records {
+///     inferer.observe(record)?;
+/// }
+/// let name = inferer.fields().iter().find(|field| field.name() == "name").unwrap();
+/// assert_eq!(name.max_length(), 44);
+///
+/// let writer = inferer
+///     .finish()
+///     .build_with_dest(std::io::Cursor::new(Vec::<u8>::new()))?;
+/// writer.write_records(&records)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct SchemaInferer {
+    fields: Vec<InferredField>,
+    encoding: DynEncoding,
+    margin: u8,
+    num_records: usize,
+}
+
+impl Default for SchemaInferer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for SchemaInferer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SchemaInferer")
+            .field("fields", &self.fields)
+            .field("margin", &self.margin)
+            .field("num_records", &self.num_records)
+            .finish()
+    }
+}
+
+impl SchemaInferer {
+    /// Creates an inferer measuring Character values encoded in UTF-8,
+    /// the builder created uses [UnicodeLossy]
+    pub fn new() -> Self {
+        Self::with_encoding(UnicodeLossy)
+    }
+
+    /// Creates an inferer measuring Character values with the given encoding,
+    /// the builder created uses the same encoding
+    pub fn with_encoding<E: Encoding + 'static>(encoding: E) -> Self {
+        Self {
+            fields: vec![],
+            encoding: DynEncoding::new(encoding),
+            margin: 0,
+            num_records: 0,
+        }
+    }
+
+    /// Sets the number of bytes added to the length of Character fields
+    /// and to the number of digits of Numeric and Float fields, 0 by default
+    pub fn margin(mut self, margin: u8) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Takes the values of the record into account
+    ///
+    /// # Errors
+    ///
+    /// - [ErrorKind::IncompatibleRecord] with [Incompatibility::TypeMismatch] when a value
+    ///   does not have the type of the values previously seen for the same name,
+    /// - [ErrorKind::IncompatibleRecord] with [Incompatibility::ValueTooLong] when a Character
+    ///   value is longer than [MAX_INFERRED_CHARACTER_LENGTH],
+    /// - [ErrorKind::Message] when a name is too long for a field or a value is a Memo.
+    ///
+    /// The error's [record_num](Error::record_num) is the number of records observed before.
+    pub fn observe(&mut self, record: &Record) -> Result<(), Error> {
+        let mut new_names = vec![];
+        for name in record.keys() {
+            let value = record.get(name).expect("the name comes from the record");
+            let result = match self.fields.iter_mut().find(|field| field.name == name) {
+                Some(field) => field.observe(value, &self.encoding),
+                None => {
+                    new_names.push(name);
+                    Ok(())
+                }
+            };
+            result.map_err(|kind| Error::new(FieldIOError::new(kind, None), self.num_records))?;
+        }
+
+        new_names.sort_unstable();
+        for name in new_names {
+            if let Err(message) = FieldName::try_from(name) {
+                let kind =
+                    ErrorKind::Message(format!("Invalid field name '{}': {}", name, message));
+                return Err(Error::new(FieldIOError::new(kind, None), self.num_records));
+            }
+            let value = record.get(name).expect("the name comes from the record");
+            let mut field = InferredField {
+                name: name.to_string(),
+                field_type: value.field_type(),
+                binary: matches!(value, FieldValue::CharacterBinary(_)),
+                max_length: 0,
+                max_decimals: 0,
+                has_nulls: false,
+            };
+            field
+                .observe(value, &self.encoding)
+                .map_err(|kind| Error::new(FieldIOError::new(kind, None), self.num_records))?;
+            self.fields.push(field);
+        }
+        self.num_records += 1;
+        Ok(())
+    }
+
+    /// Returns the fields seen so far
+    pub fn fields(&self) -> &[InferredField] {
+        &self.fields
+    }
+
+    /// Creates a builder with the fields seen
+    ///
+    /// Fields whose values were all `None` get a length of 1 (plus the margin),
+    /// Numeric and Float fields too wide to be stored make the builder return
+    /// [ErrorKind::ZeroLengthField] when it is built.
+    pub fn finish(self) -> TableWriterBuilder {
+        let builder = TableWriterBuilder::with_encoding(self.encoding);
+        self.fields
+            .iter()
+            .fold(builder, |builder, field| field.add_to(builder, self.margin))
+    }
+}
+
+/// Infers the fields of a table from the records with a default [SchemaInferer]
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// let records = dbase::read("tests/data/stations.dbf")?;
+/// let writer = dbase::infer_schema(&records)?
+///     .build_with_dest(std::io::Cursor::new(Vec::<u8>::new()))?;
+/// writer.write_records(&records)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn infer_schema<I, R>(records: I) -> Result<TableWriterBuilder, Error>
+where
+    I: IntoIterator<Item = R>,
+    R: Borrow<Record>,
+{
+    let mut inferer = SchemaInferer::new();
+    for record in records {
+        inferer.observe(record.borrow())?;
+    }
+    Ok(inferer.finish())
+}
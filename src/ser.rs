@@ -1,9 +1,9 @@
 use serde::{Serialize, Serializer};
 use std::io::Write;
 
-use crate::field::types::FieldType;
+use crate::field::types::{FieldType, DATETIME_STRUCT_NAME, DATE_STRUCT_NAME};
 use crate::writing::FieldWriter;
-use crate::{Date, FieldIOError};
+use crate::{Date, DateTime, FieldIOError};
 use crate::{ErrorKind, WritableRecord};
 
 impl<T> WritableRecord for T
@@ -98,6 +98,8 @@ impl<'a, W: Write> Serializer for &mut FieldWriter<'a, W> {
                 FieldType::Float => self.write_next_field_value::<Option<f32>>(&None),
                 FieldType::Date => self.write_next_field_value::<Option<Date>>(&None),
                 FieldType::Logical => self.write_next_field_value::<Option<bool>>(&None),
+                FieldType::Integer => self.write_next_field_value::<Option<i32>>(&None),
+                FieldType::DateTime => self.write_next_field_value::<Option<DateTime>>(&None),
                 _ => Err(FieldIOError::new(
                     ErrorKind::Message(format!("This field cannot store None values")),
                     Some((*field_info).to_owned()),
@@ -134,12 +136,27 @@ impl<'a, W: Write> Serializer for &mut FieldWriter<'a, W> {
 
     fn serialize_newtype_struct<T: ?Sized>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
         T: Serialize,
     {
+        // The bytes of dates are written as they are, they must go in a field of their type
+        let expected_type = match name {
+            DATE_STRUCT_NAME => Some(FieldType::Date),
+            DATETIME_STRUCT_NAME => Some(FieldType::DateTime),
+            _ => None,
+        };
+        if let (Some(expected_type), Some(field_info)) = (expected_type, self.fields_info.peek()) {
+            if field_info.field_type != expected_type {
+                return Err(FieldIOError::new(
+                    ErrorKind::IncompatibleType,
+                    Some((*field_info).to_owned()),
+                )
+                .at_field_index(self.next_field_index()));
+            }
+        }
         value.serialize(self)
     }
 
@@ -343,6 +360,7 @@ impl serde::ser::Error for FieldIOError {
     fn custom<T: std::fmt::Display>(msg: T) -> Self {
         Self {
             field: None,
+            field_index: None,
             kind: ErrorKind::Message(msg.to_string()),
         }
     }
@@ -0,0 +1,47 @@
+//! Diagnostics emitted with the `tracing` crate when the `tracing` feature is enabled.
+//!
+//! Without the feature, the macros expand to nothing (or to `()` for spans).
+//! The events have the `dbase` target.
+
+/// Emits an event: `trace_event!(debug, records_read = 1000, "reading records")`
+#[cfg(feature = "tracing")]
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {
+        ::tracing::$level!(target: "dbase", $($arg)+)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {};
+}
+
+/// Creates a debug span and enters it, the span is exited when the returned guard is dropped
+#[cfg(feature = "tracing")]
+macro_rules! trace_span {
+    ($name:literal $(, $($field:tt)+)?) => {
+        ::tracing::debug_span!(target: "dbase", $name $(, $($field)+)?).entered()
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_span {
+    ($name:literal $(, $($field:tt)+)?) => {
+        ()
+    };
+}
+
+/// Records the value of a field declared as `tracing::field::Empty` in a span
+#[cfg(feature = "tracing")]
+macro_rules! trace_record {
+    ($span:expr, $field:literal, $value:expr) => {
+        $span.record($field, $value)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_record {
+    ($span:expr, $field:literal, $value:expr) => {
+        let _ = &$span;
+    };
+}
@@ -1,24 +1,95 @@
 //! Module with all structs & functions charged of writing .dbf file content
+#[cfg(not(feature = "serde"))]
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "std-fs")]
 use std::fs::File;
-use std::io::{BufWriter, Cursor, Seek, SeekFrom, Write};
+#[cfg(not(feature = "serde"))]
+use std::hash::BuildHasher;
+#[cfg(feature = "std-fs")]
+use std::io::BufWriter;
+use std::io::{Cursor, Seek, SeekFrom, Write};
+#[cfg(feature = "std-fs")]
 use std::path::Path;
 
-use byteorder::WriteBytesExt;
+use byteorder::{LittleEndian, WriteBytesExt};
 
 use crate::encoding::{AsCodePageMark, DynEncoding};
-use crate::field::{types::FieldType, DeletionFlag, FieldInfo, FieldName};
-use crate::header::Header;
+use crate::field::{
+    field_data_buffer, names_match, types::FieldType, DeletionFlag, FieldInfo, FieldName,
+    FILE_TERMINATOR,
+};
+use crate::header::{CodePageMark, Header, TableFlags};
+use crate::inner::Inner;
+use crate::limits;
+use crate::memo::MemoWriter;
+use crate::progress::{ProgressCallback, ProgressReporter, DEFAULT_PROGRESS_INTERVAL};
 use crate::reading::TERMINATOR_VALUE;
-use crate::reading::{TableInfo, BACKLINK_SIZE};
-use crate::{Encoding, Error, ErrorKind, FieldIOError, Record, UnicodeLossy};
+use crate::reading::{offset_to_first_record, TableInfo, BACKLINK_SIZE};
+use crate::{
+    Date, DateTime, Encoding, Error, ErrorKind, FieldIOError, FieldValue, Record, Time,
+    ToFieldValue, UnicodeLossy,
+};
 
-/// A dbase file ends with this byte
-const FILE_TERMINATOR: u8 = 0x1A;
+/// Spaces used to pad the values shorter than their field
+const PAD_BYTES: [u8; 255] = [b' '; 255];
+
+/// Zeros used to pad the values of binary Character fields, which are raw bytes,
+/// and to fill the hidden fields of new records
+const ZERO_BYTES: [u8; 255] = [0u8; 255];
+
+/// What to do with the values given for autoincrement fields
+/// (see [FieldInfo::autoincrement]) when appending records.
+///
+/// When a [Record] does not have the field, the value is always generated.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum AutoIncrementPolicy {
+    /// The given value is replaced by the next value of the field
+    #[default]
+    Generate,
+    /// Giving a value fails with [ErrorKind::AutoIncrementValueGiven]
+    Error,
+}
+
+/// What [TableWriter::copy_records_from] does with the records marked as deleted
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DeletedPolicy {
+    /// The deleted records are not copied, the copy is packed like dBase's `PACK` does
+    #[default]
+    Drop,
+    /// The deleted records are copied with their deletion flag,
+    /// see [TableWriter::write_deleted_record]
+    Preserve,
+}
+
+/// Options of [TableWriter::copy_records_from]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CopyOptions {
+    /// What to do with the records marked as deleted, [DeletedPolicy::Drop] by default
+    pub deleted: DeletedPolicy,
+}
+
+/// Encodes the path to the database container (.dbc)
+/// that is written in the backlink block of Visual FoxPro files.
+///
+/// The block must keep room for the terminating NUL byte.
+pub(crate) fn encode_backlink<E: Encoding>(path: &str, encoding: &E) -> Result<Vec<u8>, ErrorKind> {
+    let bytes = encoding.encode(path)?;
+    if bytes.len() >= BACKLINK_SIZE as usize {
+        return Err(ErrorKind::Message(format!(
+            "The database backlink '{}' is {} bytes long, it cannot exceed {} bytes",
+            path,
+            bytes.len(),
+            BACKLINK_SIZE - 1
+        )));
+    }
+    Ok(bytes.into_owned())
+}
 
 pub(crate) fn write_header_parts<W>(
     dst: &mut W,
     header: &Header,
     fields_info: &[FieldInfo],
+    backlink: &[u8],
 ) -> Result<(), Error>
 where
     W: Write,
@@ -35,12 +106,14 @@ where
     dst.write_u8(TERMINATOR_VALUE)
         .map_err(|error| Error::io_error(error, 0))?;
 
-    // TODO foxpro adds this backlink thing
-    //  Since we don't have a spec for we just write zeros
+    // Visual FoxPro tables have a block holding the path to the database (.dbc)
+    // they belong to, zeros means it's a free table
     if header.file_type.is_visual_fox_pro() {
-        for _ in 0..BACKLINK_SIZE {
-            dst.write_u8(0).map_err(|error| Error::io_error(error, 0))?;
-        }
+        debug_assert!(backlink.len() < BACKLINK_SIZE as usize);
+        let mut block = [0u8; BACKLINK_SIZE as usize];
+        block[..backlink.len()].copy_from_slice(backlink);
+        dst.write_all(&block)
+            .map_err(|error| Error::io_error(error, 0))?;
     }
 
     Ok(())
@@ -65,12 +138,30 @@ where
 /// let writer = TableWriterBuilder::new()
 ///     .add_character_field(FieldName::try_from("First Name").unwrap(), 50)
 ///     .add_character_field(FieldName::try_from("Last Name").unwrap(), 50)
-///     .build_with_dest(Cursor::new(Vec::<u8>::new()));
+///     .build_with_dest(Cursor::new(Vec::<u8>::new())).unwrap();
 /// ```
 pub struct TableWriterBuilder {
     v: Vec<FieldInfo>,
     hdr: Header,
     encoding: DynEncoding,
+    /// Path to the database container, written in the Visual FoxPro backlink
+    backlink: Option<String>,
+    /// First error that happened when adding fields
+    error: Option<Error>,
+    /// Chose the code page mark from the written data
+    auto_code_page: bool,
+    /// Code page mark written instead of the one of the encoding
+    code_page_mark: Option<CodePageMark>,
+    /// Compare records with the fields before writing them
+    precheck: bool,
+    /// Convert the values of records to the type of their field
+    coerce_types: bool,
+    autoincrement_policy: AutoIncrementPolicy,
+    progress: ProgressReporter,
+    /// Last update date of the table the builder was created from
+    source_last_update: Option<Date>,
+    /// Flags requested with [Self::set_table_flags]
+    table_flags: TableFlags,
 }
 
 impl TableWriterBuilder {
@@ -82,6 +173,16 @@ impl TableWriterBuilder {
             v: vec![],
             hdr: Header::new(0, 0, 0),
             encoding: DynEncoding::new(UnicodeLossy),
+            backlink: None,
+            error: None,
+            auto_code_page: false,
+            code_page_mark: None,
+            precheck: false,
+            coerce_types: false,
+            autoincrement_policy: AutoIncrementPolicy::default(),
+            progress: ProgressReporter::default(),
+            source_last_update: None,
+            table_flags: TableFlags::default(),
         }
     }
 
@@ -91,6 +192,16 @@ impl TableWriterBuilder {
             v: vec![],
             hdr: Header::new(0, 0, 0),
             encoding: DynEncoding::new(encoding),
+            backlink: None,
+            error: None,
+            auto_code_page: false,
+            code_page_mark: None,
+            precheck: false,
+            coerce_types: false,
+            autoincrement_policy: AutoIncrementPolicy::default(),
+            progress: ProgressReporter::default(),
+            source_last_update: None,
+            table_flags: TableFlags::default(),
         }
     }
 
@@ -106,7 +217,7 @@ impl TableWriterBuilder {
     /// assert_eq!(old_name, Some(FieldValue::Character(Some("Van Dorn Street".parse().unwrap()))));
     ///
     /// let mut writer = TableWriterBuilder::from_reader(reader)
-    ///     .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new())).unwrap();
     ///
     /// // from_reader picked up the record definition,
     /// // so writing will work
@@ -118,15 +229,75 @@ impl TableWriterBuilder {
     }
 
     pub fn from_table_info(table_info: TableInfo) -> Self {
-        let fields_info = table_info.fields_info;
+        let mut fields_info = table_info.fields_info;
+        // The hidden fields of the source, like system columns, are not copied
+        for info in &mut fields_info {
+            info.hidden_bytes_before = 0;
+        }
         let mut hdr = table_info.header;
+        let source_last_update = hdr.last_update;
         hdr.update_date();
         hdr.num_records = 0;
+        // dBase II files cannot be written, the closest version is used instead
+        if matches!(hdr.file_type, crate::header::Version::DBase2) {
+            hdr.file_type = crate::header::Version::DBase3 {
+                supports_memo: false,
+            };
+        }
         Self {
             v: fields_info,
             hdr,
             encoding: table_info.encoding,
+            backlink: table_info.backlink,
+            error: None,
+            auto_code_page: false,
+            code_page_mark: table_info.code_page_mark,
+            precheck: false,
+            coerce_types: false,
+            autoincrement_policy: AutoIncrementPolicy::default(),
+            progress: ProgressReporter::default(),
+            source_last_update: Some(source_last_update),
+            table_flags: TableFlags::default(),
+        }
+    }
+
+    /// Sets the last update date written in the header, today by default.
+    ///
+    /// The date is the only part of the table that depends on when it is written,
+    /// with it fixed, writing the same fields and records gives the same bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{Date, TableWriterBuilder};
+    /// use std::convert::TryInto;
+    /// use std::io::Cursor;
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let writer = TableWriterBuilder::new()
+    ///     .set_last_update(Date::new(1, 1, 2000))
+    ///     .add_character_field("NAME".try_into().unwrap(), 20)
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new()))?;
+    /// let bytes = writer.into_inner()?.into_inner();
+    /// // The year is stored as the number of years since 1900
+    /// assert_eq!(bytes[1..4], [100, 1, 1]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_last_update(mut self, date: Date) -> Self {
+        self.hdr.last_update = date;
+        self
+    }
+
+    /// Keeps the last update date of the table the builder was created from
+    /// (see [Self::from_table_info] and [Self::from_reader]) instead of using today.
+    ///
+    /// It has no effect on builders that were not created from a table.
+    pub fn preserve_last_update(mut self) -> Self {
+        if let Some(date) = self.source_last_update {
+            self.hdr.last_update = date;
         }
+        self
     }
 
     /// Changes the encoding of the writer.
@@ -135,17 +306,169 @@ impl TableWriterBuilder {
         self
     }
 
+    /// When enabled, the code page mark written in the header is chosen
+    /// from the records that were written instead of always being the
+    /// one of the encoding.
+    ///
+    /// If all the Character values written are ASCII, the mark is
+    /// [CodePageMark::Undefined](crate::CodePageMark::Undefined),
+    /// otherwise it is the mark of the encoding (e.g. UTF-8 for [UnicodeLossy]).
+    ///
+    /// The mark is decided when the writer is closed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{CodePageMark, FieldName, Record, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    /// use std::io::Cursor;
+    ///
+    /// let mut cursor = Cursor::new(Vec::<u8>::new());
+    /// let mut writer = TableWriterBuilder::new()
+    ///     .auto_code_page(true)
+    ///     .add_character_field(FieldName::try_from("Name").unwrap(), 50)
+    ///     .build_with_dest(&mut cursor).unwrap();
+    ///
+    /// let mut record = Record::default();
+    /// record.insert("Name".to_string(), "Yoshi".to_string().into());
+    /// writer.write_record(&record).unwrap();
+    /// writer.close().unwrap();
+    /// drop(writer);
+    ///
+    /// cursor.set_position(0);
+    /// let reader = dbase::Reader::new(cursor).unwrap();
+    /// assert_eq!(reader.header().code_page_mark, CodePageMark::Undefined);
+    /// ```
+    pub fn auto_code_page(mut self, enabled: bool) -> Self {
+        self.auto_code_page = enabled;
+        self
+    }
+
+    /// Sets the code page mark written in the header, instead of the one
+    /// of the encoding. It takes precedence over [Self::auto_code_page].
+    ///
+    /// The records are still encoded with the encoding of the builder,
+    /// this is for tables that must have a given mark, e.g. tables
+    /// whose text is ASCII, which is the same in most code pages.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{CodePageMark, TableWriterBuilder};
+    /// use std::convert::TryInto;
+    /// use std::io::Cursor;
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut cursor = Cursor::new(Vec::<u8>::new());
+    /// let writer = TableWriterBuilder::with_encoding(dbase::encoding::Ascii)
+    ///     .set_code_page_mark(CodePageMark::CP1252)
+    ///     .add_character_field("Name".try_into().unwrap(), 50)
+    ///     .build_with_dest(&mut cursor)?;
+    /// drop(writer);
+    /// assert_eq!(cursor.get_ref()[29], CodePageMark::CP1252.as_byte());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_code_page_mark(mut self, mark: CodePageMark) -> Self {
+        self.code_page_mark = Some(mark);
+        self
+    }
+
+    /// When enabled, [Record]s are compared with the fields before being written,
+    /// the error returned by [TableWriter::write_record] is then an
+    /// [ErrorKind::IncompatibleRecord] that tells what does not match
+    /// (missing or extra field, wrong type, value too long).
+    ///
+    /// Without it, extra fields are ignored and values too long are truncated.
+    ///
+    /// See [check_compatibility](crate::schema::check_compatibility).
+    pub fn precheck_records(mut self, enabled: bool) -> Self {
+        self.precheck = enabled;
+        self
+    }
+
+    /// When enabled, the values of [Record]s and `[FieldValue]` slices whose type is not
+    /// the one of their field are converted with [FieldValue::coerce_to], instead of failing
+    /// with [ErrorKind::IncompatibleType], e.g. a Float value is written in a Numeric field.
+    ///
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// use dbase::FieldValue;
+    ///
+    /// let mut writer = dbase::TableWriterBuilder::new()
+    ///     .add_numeric_field("AMOUNT".try_into().unwrap(), 10, 2)
+    ///     .coerce_types(true)
+    ///     .build_with_dest(std::io::Cursor::new(Vec::<u8>::new()))?;
+    /// writer.write_record(&[FieldValue::Integer(12)][..])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn coerce_types(mut self, enabled: bool) -> Self {
+        self.coerce_types = enabled;
+        self
+    }
+
+    /// Sets how the values given for autoincrement fields are handled, see [AutoIncrementPolicy]
+    pub fn autoincrement_policy(mut self, policy: AutoIncrementPolicy) -> Self {
+        self.autoincrement_policy = policy;
+        self
+    }
+
+    /// Sets a callback called by [TableWriter::write_records] with the
+    /// [Progress](crate::Progress) of the writing, every [Self::progress_interval]
+    /// records and after the last one.
+    pub fn with_progress(mut self, callback: ProgressCallback) -> Self {
+        self.progress.set_callback(callback);
+        self
+    }
+
+    /// Sets the number of records between two calls of the progress callback,
+    /// by default [DEFAULT_PROGRESS_INTERVAL](crate::DEFAULT_PROGRESS_INTERVAL)
+    pub fn progress_interval(mut self, interval: u32) -> Self {
+        self.progress.set_interval(interval);
+        self
+    }
+
     /// Adds a Character field to the record definition,
     /// the length is the maximum number of bytes (not chars) that fields can hold
     pub fn add_character_field(mut self, name: FieldName, length: u8) -> Self {
-        self.v
-            .push(FieldInfo::new(name, FieldType::Character, length));
+        self.push_field(FieldInfo::new(name, FieldType::Character, length));
+        self
+    }
+
+    /// Adds a Character field that can be longer than 255 bytes, up to
+    /// [MAX_CHARACTER_FIELD_LENGTH](limits::MAX_CHARACTER_FIELD_LENGTH).
+    ///
+    /// Like Clipper and FoxPro, the length is stored across the length byte and
+    /// the decimal count byte of the field descriptor, see [FieldInfo::length].
+    /// Other programs may not read such fields.
+    pub fn add_character_field_long(mut self, name: FieldName, length: u16) -> Self {
+        let mut info = FieldInfo::new(name, FieldType::Character, 0);
+        info.field_length = length;
+        self.push_field(info);
+        self
+    }
+
+    /// Adds a Character field flagged as binary, its values are raw bytes
+    /// that are not encoded, see [FieldValue::CharacterBinary].
+    ///
+    /// Field flags only exist in Visual FoxPro files,
+    /// so the table becomes a Visual FoxPro table.
+    pub fn add_binary_character_field(mut self, name: FieldName, length: u8) -> Self {
+        let mut info = FieldInfo::new(name, FieldType::Character, length);
+        info.flags.0 |= crate::field::FieldFlags::BINARY;
+        self.push_field(info);
+        self.hdr.file_type = crate::header::Version::VisualFoxPro;
         self
     }
 
     /// Adds a [Date](struct.Date.html) field
     pub fn add_date_field(mut self, name: FieldName) -> Self {
-        self.v.push(FieldInfo::new(
+        self.push_field(FieldInfo::new(
             name,
             FieldType::Date,
             FieldType::Date.size().unwrap(),
@@ -157,7 +480,7 @@ impl TableWriterBuilder {
     pub fn add_numeric_field(mut self, name: FieldName, length: u8, num_decimals: u8) -> Self {
         let mut info = FieldInfo::new(name, FieldType::Numeric, length);
         info.num_decimal_places = num_decimals;
-        self.v.push(info);
+        self.push_field(info);
         self
     }
 
@@ -165,13 +488,13 @@ impl TableWriterBuilder {
     pub fn add_float_field(mut self, name: FieldName, length: u8, num_decimals: u8) -> Self {
         let mut info = FieldInfo::new(name, FieldType::Float, length);
         info.num_decimal_places = num_decimals;
-        self.v.push(info);
+        self.push_field(info);
         self
     }
 
     /// Adds a [Logical](enum.FieldValue.html#variant.Logical)
     pub fn add_logical_field(mut self, name: FieldName) -> Self {
-        self.v.push(FieldInfo::new(
+        self.push_field(FieldInfo::new(
             name,
             FieldType::Logical,
             FieldType::Logical
@@ -183,108 +506,413 @@ impl TableWriterBuilder {
 
     /// Adds a [Integer](enum.FieldValue.html#variant.Integer)
     pub fn add_integer_field(mut self, name: FieldName) -> Self {
-        self.v.push(FieldInfo::new(
+        self.push_field(FieldInfo::new(
             name,
             FieldType::Integer,
             FieldType::Integer
                 .size()
                 .expect("Internal error Integer field date should be known"),
         ));
-        self.hdr.file_type = crate::header::Version::FoxPro2 {
-            supports_memo: false,
-        };
+        self.require_foxpro();
+        self
+    }
+
+    /// Adds an autoincrement [Integer](enum.FieldValue.html#variant.Integer) field,
+    /// the first record appended gets `first_value`, the next ones are incremented by `step`.
+    ///
+    /// Autoincrement fields only exist in Visual FoxPro files,
+    /// so the table becomes a Visual FoxPro table.
+    ///
+    /// See [AutoIncrementPolicy].
+    pub fn add_autoincrement_field(mut self, name: FieldName, first_value: u32, step: u8) -> Self {
+        let mut info = FieldInfo::new(
+            name,
+            FieldType::Integer,
+            FieldType::Integer
+                .size()
+                .expect("Internal error Integer field date should be known"),
+        );
+        info.flags.0 |= crate::field::FieldFlags::AUTOINCREMENT;
+        info.autoincrement_next_val = first_value;
+        info.autoincrement_step = step;
+        self.push_field(info);
+        self.hdr.file_type = crate::header::Version::VisualFoxPro;
         self
     }
 
     /// Adds a [DateTime](enum.FieldValue.html#variant.DateTime)
     pub fn add_datetime_field(mut self, name: FieldName) -> Self {
-        self.v.push(FieldInfo::new(
+        self.push_field(FieldInfo::new(
             name,
             FieldType::DateTime,
             FieldType::DateTime
                 .size()
                 .expect("Internal error datetime field date should be known"),
         ));
-        self.hdr.file_type = crate::header::Version::FoxPro2 {
-            supports_memo: false,
-        };
+        self.require_foxpro();
         self
     }
 
     /// Adds a [Double](enum.FieldValue.html#variant.Double)
+    ///
+    /// The field is 8 bytes long and its values are written as binary `f64`,
+    /// as FoxPro does, so the file is written as a FoxPro file.
+    /// Double fields that store their values as text (Clipper, dBase III / IV,
+    /// see [FieldInfo::is_text_double](crate::FieldInfo::is_text_double)) can be read but not written.
     pub fn add_double_field(mut self, name: FieldName) -> Self {
-        self.v.push(FieldInfo::new(
+        self.push_field(FieldInfo::new(
             name,
             FieldType::Double,
             FieldType::Double
                 .size()
                 .expect("Internal error Double field date should be known"),
         ));
-        self.hdr.file_type = crate::header::Version::FoxPro2 {
-            supports_memo: false,
-        };
+        self.require_foxpro();
         self
     }
 
     /// Adds a [Currency](enum.FieldValue.html#variant.Currency)
     pub fn add_currency_field(mut self, name: FieldName) -> Self {
-        self.v.push(FieldInfo::new(
+        self.push_field(FieldInfo::new(
             name,
             FieldType::Currency,
             FieldType::Currency
                 .size()
                 .expect("Internal error Currency field date should be known"),
         ));
-        self.hdr.file_type = crate::header::Version::FoxPro2 {
-            supports_memo: false,
+        self.require_foxpro();
+        self
+    }
+
+    /// Adds a [Memo](enum.FieldValue.html#variant.Memo) field
+    ///
+    /// The texts are stored in a memo file, the field holds the index of their first block:
+    /// as text on 10 bytes, or as a binary integer on 4 bytes in Visual FoxPro files.
+    /// The version of the file is changed to the one with a memo file, if needed.
+    ///
+    /// Memo values are written by [File](crate::File)s created with [File::create](crate::File::create),
+    /// which creates the memo file, they cannot be written by a [TableWriter].
+    pub fn add_memo_field(mut self, name: FieldName) -> Self {
+        let length = if self.hdr.file_type.is_visual_fox_pro() {
+            4
+        } else {
+            10
         };
+        self.push_field(FieldInfo::new(name, FieldType::Memo, length));
+        self
+    }
+
+    /// Adds the field, switching to a FoxPro file if the field type requires it
+    #[cfg(feature = "datafusion")]
+    pub(crate) fn add_field_info(mut self, info: FieldInfo) -> Self {
+        if matches!(
+            info.field_type,
+            FieldType::Integer | FieldType::DateTime | FieldType::Double | FieldType::Currency
+        ) {
+            self.require_foxpro();
+        }
+        self.push_field(info);
+        self
+    }
+
+    /// Sets the database (.dbc) the table belongs to,
+    /// the path is usually relative to the table.
+    ///
+    /// The path is written in the backlink block, which only exists
+    /// in Visual FoxPro files, so the table becomes a Visual FoxPro table.
+    ///
+    /// The encoded path cannot exceed 262 bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    /// use std::io::Cursor;
+    ///
+    /// let writer = TableWriterBuilder::new()
+    ///     .set_database_backlink("sales.dbc")
+    ///     .add_character_field(FieldName::try_from("Name").unwrap(), 50)
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    /// assert!(writer.is_ok());
+    /// ```
+    pub fn set_database_backlink(mut self, path: &str) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        match encode_backlink(path, &self.encoding) {
+            Ok(_) => {
+                self.hdr.file_type = crate::header::Version::VisualFoxPro;
+                self.backlink = Some(path.to_string());
+            }
+            Err(kind) => {
+                self.error = Some(Error {
+                    record_num: 0,
+                    field: None,
+                    field_index: None,
+                    kind,
+                })
+            }
+        }
+        self
+    }
+
+    /// Sets the flags of the header, used by Visual FoxPro tables.
+    ///
+    /// Only the structural index and database flags are taken from `flags`,
+    /// the memo flag is set when a Visual FoxPro table has a Memo field.
+    /// The flags of the table a builder was created from are not kept,
+    /// they may not match the new table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, TableFlags, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    /// use std::io::Cursor;
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let writer = TableWriterBuilder::new()
+    ///     .set_database_backlink("sales.dbc")
+    ///     .set_table_flags(TableFlags::new(false, true, true))
+    ///     .add_integer_field(FieldName::try_from("ID").unwrap())
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new()))?;
+    /// let bytes = writer.into_inner()?.into_inner();
+    /// // There is no Memo field
+    /// assert_eq!(TableFlags::from_bits(bytes[28]), TableFlags::new(false, false, true));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_table_flags(mut self, flags: TableFlags) -> Self {
+        self.table_flags = flags;
         self
     }
 
-    fn sync_header(&mut self) {
-        let mut offset_to_first_record =
-            Header::SIZE + (self.v.len() * FieldInfo::SIZE) + std::mem::size_of::<u8>();
+    /// Field types like Integer or Double need a FoxPro file,
+    /// Visual FoxPro files support them too
+    fn require_foxpro(&mut self) {
+        if !self.hdr.file_type.is_visual_fox_pro() {
+            self.hdr.file_type = crate::header::Version::FoxPro2 {
+                supports_memo: self.hdr.file_type.supported_memo_type().is_some(),
+            };
+        }
+    }
 
-        if self.hdr.file_type.is_visual_fox_pro() {
-            offset_to_first_record += BACKLINK_SIZE as usize;
+    /// Adds the field to the definition if it is valid,
+    /// otherwise keeps the (first) error so that it is returned when building
+    fn push_field(&mut self, info: FieldInfo) {
+        if self.error.is_some() {
+            return;
         }
 
-        let size_of_record = self
+        let kind = if info.field_length == 0 {
+            Some(ErrorKind::ZeroLengthField)
+        } else if self
             .v
             .iter()
-            .fold(1u16, |s, info| s + info.field_length as u16);
+            .any(|other| names_match(&other.name, &info.name))
+        {
+            Some(ErrorKind::DuplicateFieldName)
+        } else if self.record_size() + info.field_length as usize > limits::MAX_RECORD_SIZE {
+            Some(ErrorKind::RecordTooLarge)
+        } else {
+            None
+        };
+
+        match kind {
+            Some(kind) => {
+                self.error = Some(Error {
+                    record_num: 0,
+                    field: Some(info),
+                    field_index: None,
+                    kind,
+                })
+            }
+            None => self.v.push(info),
+        }
+    }
+
+    /// Size of a record, including the deletion flag
+    fn record_size(&self) -> usize {
+        self.v
+            .iter()
+            .fold(1usize, |s, info| s + info.field_length as usize)
+    }
+
+    /// Number of bytes that can still be added to a record
+    /// before reaching the maximum record size of the table version.
+    ///
+    /// The version can change when adding fields that need a FoxPro file,
+    /// or when setting the database backlink.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{limits, FieldName, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    ///
+    /// let builder = TableWriterBuilder::new()
+    ///     .add_character_field(FieldName::try_from("Name").unwrap(), 50);
+    /// // 1 byte is used by the deletion flag
+    /// assert_eq!(builder.remaining_record_capacity(), limits::MAX_RECORD_SIZE - 51);
+    /// ```
+    pub fn remaining_record_capacity(&self) -> usize {
+        limits::max_record_size(self.hdr.file_type).saturating_sub(self.record_size())
+    }
+
+    /// Checks the fields against the limits of the version
+    /// and updates the sizes and offsets of the header
+    fn sync_header(&mut self) -> Result<(), Error> {
+        let error = |kind| Error {
+            record_num: 0,
+            field: None,
+            field_index: None,
+            kind,
+        };
+
+        let version = self.hdr.file_type;
+        let max_fields = limits::max_fields(version);
+        if self.v.len() > max_fields {
+            return Err(error(ErrorKind::FieldCountExceeded { max: max_fields }));
+        }
+
+        let record_size = self.record_size();
+        if record_size > limits::max_record_size(version) {
+            return Err(error(ErrorKind::RecordTooLarge));
+        }
+
+        let offset_to_first_record = offset_to_first_record(version, self.v.len());
+        if offset_to_first_record > limits::MAX_OFFSET_TO_FIRST_RECORD {
+            return Err(error(ErrorKind::InvalidHeader(format!(
+                "the offset to the first record ({}) does not fit in the header",
+                offset_to_first_record
+            ))));
+        }
 
         self.hdr.offset_to_first_record = offset_to_first_record as u16;
-        self.hdr.size_of_record = size_of_record;
-        self.hdr.code_page_mark = self.encoding.code_page_mark();
+        self.hdr.size_of_record = record_size as u16;
+        if self.v.iter().any(|info| info.field_type == FieldType::Memo) {
+            self.hdr.file_type = self.hdr.file_type.with_memo();
+        }
+        // Other versions have no memo flag, they tell it with the version byte
+        let has_memo_field = version.is_visual_fox_pro()
+            && self.v.iter().any(|info| info.field_type == FieldType::Memo);
+        self.hdr.table_flags = TableFlags::new(
+            self.table_flags.has_structural_cdx(),
+            has_memo_field,
+            self.table_flags.is_a_database(),
+        );
+        self.hdr.code_page_mark = self
+            .code_page_mark
+            .unwrap_or_else(|| self.encoding.code_page_mark());
+        Ok(())
     }
 
     /// Builds the writer and set the dst as where the file data will be written
-    pub fn build_with_dest<W: Write + Seek>(mut self, dst: W) -> TableWriter<W> {
-        self.sync_header();
-        TableWriter::new(dst, self.v, self.hdr, self.encoding)
+    ///
+    /// Returns an error if one of the fields added was invalid
+    /// (duplicated name, length of 0, record too large),
+    /// or if the table exceeds the [limits](crate::limits) of its version.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{ErrorKind, FieldName, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    /// use std::io::Cursor;
+    ///
+    /// let result = TableWriterBuilder::new()
+    ///     .add_character_field(FieldName::try_from("Name").unwrap(), 50)
+    ///     .add_character_field(FieldName::try_from("NAME").unwrap(), 50)
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    ///
+    /// match result {
+    ///     Err(error) => assert!(matches!(error.kind(), ErrorKind::DuplicateFieldName)),
+    ///     Ok(_) => panic!("Duplicated names should be rejected"),
+    /// }
+    /// ```
+    pub fn build_with_dest<W: Write + Seek>(mut self, dst: W) -> Result<TableWriter<W>, Error> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        self.sync_header()?;
+        let backlink = self
+            .backlink
+            .as_deref()
+            .map(|path| encode_backlink(path, &self.encoding))
+            .transpose()
+            .map_err(|kind| Error {
+                record_num: 0,
+                field: None,
+                field_index: None,
+                kind,
+            })?
+            .unwrap_or_default();
+        let mut writer = TableWriter::new(dst, self.v, self.hdr, self.encoding, backlink);
+        writer.auto_code_page = self.auto_code_page && self.code_page_mark.is_none();
+        writer.precheck = self.precheck;
+        writer.coerce_types = self.coerce_types;
+        writer.autoincrement_policy = self.autoincrement_policy;
+        writer.progress = self.progress;
+        Ok(writer)
     }
 
     /// Helper function to set create a file at the given path
     /// and make the writer write to the newly created file.
     ///
     /// This function wraps the `File` in a `BufWriter` to increase performance.
+    #[cfg(feature = "std-fs")]
     pub fn build_with_file_dest<P: AsRef<Path>>(
         self,
         path: P,
     ) -> Result<TableWriter<BufWriter<File>>, Error> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
         let file = File::create(path).map_err(|err| Error::io_error(err, 0))?;
         let dst = BufWriter::new(file);
-        Ok(self.build_with_dest(dst))
+        self.build_with_dest(dst)
+    }
+
+    /// Creates a file at the given path holding a table without records.
+    ///
+    /// The file has the header, the fields and the end of file marker,
+    /// like the files of writers closed before writing a record.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let dir = tempfile::tempdir()?;
+    /// # let path = dir.path().join("empty.dbf");
+    /// dbase::TableWriterBuilder::new()
+    ///     .add_character_field("Name".try_into().unwrap(), 50)
+    ///     .create_empty(&path)?;
+    /// assert_eq!(dbase::Reader::from_path(&path)?.read()?.len(), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "std-fs")]
+    pub fn create_empty<P: AsRef<Path>>(self, path: P) -> Result<(), Error> {
+        self.build_with_file_dest(path)?.close()
     }
 
-    pub fn build_table_info(mut self) -> TableInfo {
-        self.sync_header();
-        TableInfo {
+    /// Builds the table info, to be used to create a new [File](crate::File)
+    ///
+    /// Returns an error if one of the fields added was invalid,
+    /// or if the table exceeds the [limits](crate::limits) of its version.
+    pub fn build_table_info(mut self) -> Result<TableInfo, Error> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        self.sync_header()?;
+        Ok(TableInfo {
             header: self.hdr,
             fields_info: self.v,
             encoding: self.encoding,
-        }
+            backlink: self.backlink,
+            code_page_mark: self.code_page_mark,
+        })
     }
 }
 
@@ -302,22 +930,27 @@ mod private {
     impl_sealed_for!(std::string::String);
     impl_sealed_for!(Option<std::string::String>);
     impl_sealed_for!(&str);
+    impl_sealed_for!(&[u8]);
+    impl_sealed_for!(Vec<u8>);
     impl_sealed_for!(f64);
     impl_sealed_for!(f32);
     impl_sealed_for!(i32);
+    impl_sealed_for!(Option<i32>);
+    impl_sealed_for!(&std::string::String);
     impl_sealed_for!(Option<f64>);
     impl_sealed_for!(Option<f32>);
     impl_sealed_for!(crate::field::types::Date);
     impl_sealed_for!(Option<crate::field::types::Date>);
     impl_sealed_for!(crate::field::types::FieldValue);
     impl_sealed_for!(crate::field::types::DateTime);
+    impl_sealed_for!(Option<crate::field::types::DateTime>);
 }
 
 /// Trait implemented by types we can write as dBase types
 ///
 /// This trait is 'private' and cannot be implemented on your custom types.
 pub trait WritableAsDbaseField: private::Sealed {
-    fn write_as<E: Encoding, W: Write>(
+    fn write_as<E: Encoding + ?Sized, W: Write>(
         &self,
         field_info: &FieldInfo,
         encoding: &E,
@@ -325,6 +958,32 @@ pub trait WritableAsDbaseField: private::Sealed {
     ) -> Result<(), ErrorKind>;
 }
 
+/// Object safe counterpart of [WritableAsDbaseField], so that values of
+/// different types can be stored as `Box<dyn DynWritableField>`,
+/// see [FieldWriter::write_next_field_dyn].
+///
+/// It is implemented by all the types implementing [WritableAsDbaseField],
+/// other types can implement it by writing the bytes of the field to `dst`.
+pub trait DynWritableField {
+    fn write_dyn(
+        &self,
+        field_info: &FieldInfo,
+        encoding: &dyn Encoding,
+        dst: &mut dyn Write,
+    ) -> Result<(), ErrorKind>;
+}
+
+impl<T: WritableAsDbaseField> DynWritableField for T {
+    fn write_dyn(
+        &self,
+        field_info: &FieldInfo,
+        encoding: &dyn Encoding,
+        mut dst: &mut dyn Write,
+    ) -> Result<(), ErrorKind> {
+        self.write_as(field_info, encoding, &mut dst)
+    }
+}
+
 /// Trait to be implemented by struct that you want to be able to write to (serialize)
 /// a dBase file
 pub trait WritableRecord {
@@ -335,43 +994,447 @@ pub trait WritableRecord {
     ) -> Result<(), FieldIOError>;
 }
 
+/// Writes the values found by name with `get`, in the order of the fields,
+/// `names` are the names of all the values.
+///
+/// Shared by the map types: [Record], `HashMap` and `BTreeMap`.
+fn write_values_by_name<'a, 'v, W: Write>(
+    field_writer: &mut FieldWriter<'a, W>,
+    get: impl Fn(&str) -> Option<&'v FieldValue>,
+    names: impl Iterator<Item = &'v str>,
+) -> Result<(), FieldIOError> {
+    if field_writer.precheck {
+        if let Some((incompatibility, field)) = crate::schema::first_incompatibility(
+            field_writer.fields_info.clone(),
+            &get,
+            names,
+            field_writer.encoding,
+            field_writer.autoincrement.is_some(),
+            field_writer.coerce_types,
+        ) {
+            return Err(FieldIOError::new(
+                ErrorKind::IncompatibleRecord(incompatibility),
+                field,
+            ));
+        }
+    }
+    while let Some(name) = field_writer.next_field_name() {
+        match get(name) {
+            Some(value) => field_writer.write_next_field_record_value(value)?,
+            None => {
+                if !field_writer.write_next_generated_value()? {
+                    return Err(FieldIOError::new(
+                        ErrorKind::Message(format!(
+                            "Could not find field named '{}' in the record map",
+                            name
+                        )),
+                        None,
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Borrowed values paired with the name of their field, in any order,
+/// written by [TableWriter::write_named_values] and [File::append_named_values](crate::File::append_named_values)
+pub(crate) struct NamedValues<'v>(pub(crate) Vec<(&'v str, &'v FieldValue)>);
+
+impl<'v> NamedValues<'v> {
+    pub(crate) fn new(values: impl IntoIterator<Item = (&'v str, &'v FieldValue)>) -> Self {
+        Self(values.into_iter().collect())
+    }
+}
+
+impl WritableRecord for NamedValues<'_> {
+    fn write_using<'a, W: Write>(
+        &self,
+        field_writer: &mut FieldWriter<'a, W>,
+    ) -> Result<(), FieldIOError> {
+        // A linear scan is enough, tables have at most 255 fields.
+        // Like for a Record, names that differ by the case match
+        write_values_by_name(
+            field_writer,
+            |name| {
+                self.0
+                    .iter()
+                    .find(|(value_name, _)| *value_name == name)
+                    .or_else(|| {
+                        self.0
+                            .iter()
+                            .find(|(value_name, _)| names_match(value_name, name))
+                    })
+                    .map(|(_, value)| *value)
+            },
+            self.0.iter().map(|(name, _)| *name),
+        )
+    }
+}
+
 impl WritableRecord for Record {
     fn write_using<'a, W: Write>(
         &self,
         field_writer: &mut FieldWriter<'a, W>,
     ) -> Result<(), FieldIOError> {
-        while let Some(name) = field_writer.next_field_name() {
-            let value = self.get(name).ok_or_else(|| {
-                FieldIOError::new(
-                    ErrorKind::Message(format!(
-                        "Could not find field named '{}' in the record map",
-                        name
-                    )),
-                    None,
-                )
-            })?;
-            field_writer.write_next_field_value(value)?;
-        }
-        Ok(())
+        write_values_by_name(
+            field_writer,
+            |name| self.get_ignoring_case(name),
+            self.keys(),
+        )
     }
 }
 
-/// Struct that knows how to write a record
+/// Writes the value of each field found by its name, like [Record]
 ///
-/// You give it the values you want to write and it writes them.
+/// Not available with the `serde` feature, as it conflicts with
+/// the implementation for `Serialize` types,
+/// convert the map into a `Record` instead.
+#[cfg(not(feature = "serde"))]
+impl<S: BuildHasher> WritableRecord for HashMap<String, FieldValue, S> {
+    fn write_using<'a, W: Write>(
+        &self,
+        field_writer: &mut FieldWriter<'a, W>,
+    ) -> Result<(), FieldIOError> {
+        write_values_by_name(
+            field_writer,
+            |name| self.get(name),
+            self.keys().map(String::as_str),
+        )
+    }
+}
+
+/// Writes the value of each field found by its name, like [Record]
+///
+/// Not available with the `serde` feature, as it conflicts with
+/// the implementation for `Serialize` types,
+/// convert the map into a `Record` instead.
+#[cfg(not(feature = "serde"))]
+impl WritableRecord for BTreeMap<String, FieldValue> {
+    fn write_using<'a, W: Write>(
+        &self,
+        field_writer: &mut FieldWriter<'a, W>,
+    ) -> Result<(), FieldIOError> {
+        write_values_by_name(
+            field_writer,
+            |name| self.get(name),
+            self.keys().map(String::as_str),
+        )
+    }
+}
+
+/// Checks that there is exactly one value per field left to write
+fn check_num_values<W: Write>(
+    num_values: usize,
+    field_writer: &FieldWriter<'_, W>,
+) -> Result<(), FieldIOError> {
+    let num_fields = field_writer.fields_info.len();
+    if num_values < num_fields {
+        let missing_field = field_writer.fields_info.clone().nth(num_values).cloned();
+        let index = field_writer.next_field_index() + num_values;
+        return Err(
+            FieldIOError::new(ErrorKind::NotEnoughFields, missing_field).at_field_index(index)
+        );
+    }
+    if num_values > num_fields {
+        return Err(FieldIOError::new(ErrorKind::TooManyFields, None));
+    }
+    Ok(())
+}
+
+/// Writes the values in the order of the fields,
+/// the slice must have exactly one value per field
+impl WritableRecord for [FieldValue] {
+    fn write_using<'a, W: Write>(
+        &self,
+        field_writer: &mut FieldWriter<'a, W>,
+    ) -> Result<(), FieldIOError> {
+        check_num_values(self.len(), field_writer)?;
+        for value in self {
+            field_writer.write_next_field_record_value(value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the values in the order of the fields,
+/// the vec must have exactly one value per field
+///
+/// Not available with the `serde` feature, as it conflicts with
+/// the implementation for `Serialize` types,
+/// write `values.as_slice()` instead.
+#[cfg(not(feature = "serde"))]
+impl WritableRecord for Vec<FieldValue> {
+    fn write_using<'a, W: Write>(
+        &self,
+        field_writer: &mut FieldWriter<'a, W>,
+    ) -> Result<(), FieldIOError> {
+        self.as_slice().write_using(field_writer)
+    }
+}
+
+/// Writes the texts in the order of the fields, converted to the type of their field
+/// (see [FieldWriter::write_next_field_from_str]),
+/// the slice must have exactly one text per field.
+///
+/// Useful to write rows read from a CSV file.
+impl WritableRecord for [&str] {
+    fn write_using<'a, W: Write>(
+        &self,
+        field_writer: &mut FieldWriter<'a, W>,
+    ) -> Result<(), FieldIOError> {
+        check_num_values(self.len(), field_writer)?;
+        for text in self {
+            field_writer.write_next_field_from_str(text)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the texts in the order of the fields, converted to the type of their field
+/// (see [FieldWriter::write_next_field_from_str]),
+/// the slice must have exactly one text per field.
+impl WritableRecord for [String] {
+    fn write_using<'a, W: Write>(
+        &self,
+        field_writer: &mut FieldWriter<'a, W>,
+    ) -> Result<(), FieldIOError> {
+        check_num_values(self.len(), field_writer)?;
+        for text in self {
+            field_writer.write_next_field_from_str(text)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the texts in the order of the fields, converted to the type of their field
+/// (see [FieldWriter::write_next_field_from_str]),
+/// the vec must have exactly one text per field.
+///
+/// Not available with the `serde` feature, as it conflicts with
+/// the implementation for `Serialize` types,
+/// write `texts.as_slice()` instead.
+#[cfg(not(feature = "serde"))]
+impl WritableRecord for Vec<String> {
+    fn write_using<'a, W: Write>(
+        &self,
+        field_writer: &mut FieldWriter<'a, W>,
+    ) -> Result<(), FieldIOError> {
+        self.as_slice().write_using(field_writer)
+    }
+}
+
+/// Converts a trimmed text to a value of the field type,
+/// `None` if it cannot be converted
+pub(crate) fn value_from_text(text: &str, field_type: FieldType) -> Option<FieldValue> {
+    fn parse_or_none<T: std::str::FromStr>(text: &str) -> Option<Option<T>> {
+        if text.is_empty() {
+            Some(None)
+        } else {
+            text.parse().ok().map(Some)
+        }
+    }
+
+    let value = match field_type {
+        FieldType::Numeric => FieldValue::Numeric(parse_or_none(text)?),
+        FieldType::Float => FieldValue::Float(parse_or_none(text)?),
+        FieldType::Integer => FieldValue::Integer(parse_or_none(text)?.unwrap_or(0)),
+        FieldType::Currency => FieldValue::Currency(parse_or_none(text)?.unwrap_or(0.0)),
+        FieldType::Double => FieldValue::Double(parse_or_none(text)?.unwrap_or(0.0)),
+        FieldType::Logical => FieldValue::Logical(match text {
+            "" | "?" => None,
+            "T" | "t" | "Y" | "y" | "1" => Some(true),
+            "F" | "f" | "N" | "n" | "0" => Some(false),
+            _ => return None,
+        }),
+        FieldType::Date if text.is_empty() => FieldValue::Date(None),
+        FieldType::Date => FieldValue::Date(Some(date_from_text(text)?)),
+        FieldType::DateTime => {
+            let (date, time) = text.split_once([' ', 'T'])?;
+            FieldValue::DateTime(DateTime::new(date_from_text(date)?, time_from_text(time)?))
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Parses a `YYYYMMDD` or `YYYY-MM-DD` date, that must exist in the calendar
+fn date_from_text(text: &str) -> Option<Date> {
+    let digits = match text.as_bytes() {
+        [_, _, _, _, b'-', _, _, b'-', _, _] => text.replace('-', ""),
+        [_, _, _, _, _, _, _, _] => text.to_owned(),
+        _ => return None,
+    };
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let date = digits.parse::<Date>().ok()?;
+    time::Date::try_from(date).ok()?;
+    Some(date)
+}
+
+/// Parses a `HH:MM:SS` time
+fn time_from_text(text: &str) -> Option<Time> {
+    let mut parts = text.splitn(3, ':').map(|part| {
+        if part.len() == 2 && part.bytes().all(|b| b.is_ascii_digit()) {
+            part.parse::<u32>().ok()
+        } else {
+            None
+        }
+    });
+    let hours = parts.next()??;
+    let minutes = parts.next()??;
+    let seconds = parts.next()??;
+    if hours > 23 || minutes > 59 || seconds > 59 {
+        return None;
+    }
+    Some(Time::new(hours, minutes, seconds))
+}
+
+// With the serde feature, tuples are already writable
+// through their Serialize implementation
+#[cfg(not(feature = "serde"))]
+macro_rules! impl_writable_record_for_tuple {
+    ($($value:ident),+) => {
+        /// Writes the values in the order of the fields
+        impl<$($value: WritableAsDbaseField),+> WritableRecord for ($($value,)+) {
+            #[allow(non_snake_case)]
+            fn write_using<'a, W: Write>(
+                &self,
+                field_writer: &mut FieldWriter<'a, W>,
+            ) -> Result<(), FieldIOError> {
+                let ($($value,)+) = self;
+                $(field_writer.write_next_field_value($value)?;)+
+                if let Some(missing_field) = field_writer.fields_info.peek() {
+                    return Err(FieldIOError::new(
+                        ErrorKind::NotEnoughFields,
+                        Some((*missing_field).clone()),
+                    )
+                    .at_field_index(field_writer.next_field_index()));
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+mod tuples {
+    use super::*;
+
+    impl_writable_record_for_tuple!(A);
+    impl_writable_record_for_tuple!(A, B);
+    impl_writable_record_for_tuple!(A, B, C);
+    impl_writable_record_for_tuple!(A, B, C, D);
+    impl_writable_record_for_tuple!(A, B, C, D, E);
+    impl_writable_record_for_tuple!(A, B, C, D, E, F);
+    impl_writable_record_for_tuple!(A, B, C, D, E, F, G);
+    impl_writable_record_for_tuple!(A, B, C, D, E, F, G, H);
+}
+
+/// Shortens the encoded `bytes` of a Character value so that they fit in the field,
+/// without splitting a character
+fn fit_character_bytes(
+    encoding: &DynEncoding,
+    bytes: &[u8],
+    field_info: &FieldInfo,
+) -> Result<Vec<u8>, FieldIOError> {
+    let to_field_error = |kind: ErrorKind| FieldIOError::new(kind, Some(field_info.clone()));
+    let decoded = encoding
+        .decode(bytes)
+        .map_err(|error| to_field_error(error.into()))?;
+    let prefix = encoding
+        .encode_prefix_fitting(&decoded, usize::from(field_info.field_length))
+        .map_err(|error| to_field_error(error.into()))?;
+    Ok(prefix.into_owned())
+}
+
+/// Struct that knows how to write a record
+///
+/// You give it the values you want to write and it writes them.
 /// The order and type of value must match the one given when creating the
 /// [TableWriter](struct.TableWriter.html), otherwise an error will occur.
 pub struct FieldWriter<'a, W: Write> {
     pub(crate) dst: &'a mut W,
     pub(crate) fields_info: std::iter::Peekable<std::slice::Iter<'a, FieldInfo>>,
-    pub(crate) field_buffer: &'a mut Cursor<&'a mut [u8]>,
+    pub(crate) field_buffer: &'a mut Cursor<&'a mut Vec<u8>>,
     pub(crate) encoding: &'a DynEncoding,
+    /// Set when a Character value with non ASCII bytes was written
+    pub(crate) wrote_non_ascii: bool,
+    /// Whether [Record]s are compared with the fields before being written
+    pub(crate) precheck: bool,
+    /// Whether the [FieldValue]s of records are converted to the type of their field
+    pub(crate) coerce_types: bool,
+    /// Set when appending records, the values of autoincrement fields are generated
+    pub(crate) autoincrement: Option<AutoIncrementPolicy>,
+    /// Number of fields of the record, to know the index of the next one
+    pub(crate) num_fields: usize,
+    /// Index of the fields whose value was cut to the length of the field,
+    /// with the length of the value
+    pub(crate) truncated_values: Vec<(usize, usize)>,
+    /// Where the Memo values are written, their field holds the index of their block
+    pub(crate) memo_writer: Option<&'a mut dyn MemoWriter>,
 }
 
 impl<'a, W: Write> FieldWriter<'a, W> {
     /// Returns the name of the next field that is expected to be written
     pub fn next_field_name(&mut self) -> Option<&'a str> {
-        self.fields_info.peek().map(|info| info.name.as_str())
+        self.fields_info.peek().map(|info| &*info.name)
+    }
+
+    /// Index of the next field that will be written
+    pub(crate) fn next_field_index(&self) -> usize {
+        self.num_fields - self.fields_info.len()
+    }
+
+    /// Returns the index of the next field that will be written or skipped
+    pub fn current_index(&self) -> usize {
+        self.next_field_index()
+    }
+
+    /// Returns the number of fields of the record not written nor skipped yet
+    pub fn remaining(&self) -> usize {
+        self.fields_info.len()
+    }
+
+    /// Returns the info of the next field that is expected to be written
+    ///
+    /// Returns `None` when all the fields of the record were written or skipped.
+    pub fn peek_info(&self) -> Option<&'a FieldInfo> {
+        self.fields_info.clone().next()
+    }
+
+    /// Skips the next `n` fields of the record, an empty value is written in each of them.
+    ///
+    /// Empty Integer, Currency, Double and DateTime fields hold zeros,
+    /// the values of autoincrement fields are generated when appending records.
+    ///
+    /// Returns an [ErrorKind::EndOfRecord] error, without skipping any field,
+    /// when less than `n` fields remain.
+    pub fn skip_fields(&mut self, n: usize) -> Result<(), FieldIOError> {
+        if n > self.remaining() {
+            return Err(FieldIOError::end_of_record());
+        }
+        for _ in 0..n {
+            if self.write_next_generated_value()? {
+                continue;
+            }
+            let index = self.next_field_index();
+            self.write_to_next_field_with(|field_info, _encoding, dst| {
+                if matches!(
+                    field_info.field_type,
+                    FieldType::Integer
+                        | FieldType::Currency
+                        | FieldType::Double
+                        | FieldType::DateTime
+                ) {
+                    dst.write_all(&vec![0; usize::from(field_info.field_length)])?;
+                }
+                Ok(())
+            })
+            .map_err(|error| error.at_field_index(index))?;
+        }
+        Ok(())
     }
 
     /// Writes the given `field_value` to the record.
@@ -386,31 +1449,299 @@ impl<'a, W: Write> FieldWriter<'a, W> {
     ///
     /// Trying to write more values than was declared when creating the writer will cause
     /// an `EndOfRecord` error.
+    ///
+    /// When appending records, the values of autoincrement fields
+    /// are generated, see [AutoIncrementPolicy].
     pub fn write_next_field_value<T: WritableAsDbaseField>(
         &mut self,
         field_value: &T,
     ) -> Result<(), FieldIOError> {
+        if self.write_autoincrement_instead()? {
+            return Ok(());
+        }
+        self.write_next_value(field_value)
+    }
+
+    /// Same as [Self::write_next_field_value], for values whose type
+    /// is only known at runtime.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{DynWritableField, FieldIOError, FieldWriter, WritableRecord};
+    /// use std::io::Write;
+    ///
+    /// struct Row(Vec<Box<dyn DynWritableField>>);
+    ///
+    /// impl WritableRecord for Row {
+    ///     fn write_using<'a, W: Write>(
+    ///         &self,
+    ///         field_writer: &mut FieldWriter<'a, W>,
+    ///     ) -> Result<(), FieldIOError> {
+    ///         for value in &self.0 {
+    ///             field_writer.write_next_field_dyn(value.as_ref())?;
+    ///         }
+    ///         Ok(())
+    ///     }
+    /// }
+    /// ```
+    pub fn write_next_field_dyn(
+        &mut self,
+        field_value: &dyn DynWritableField,
+    ) -> Result<(), FieldIOError> {
+        if self.write_autoincrement_instead()? {
+            return Ok(());
+        }
+        let index = self.next_field_index();
+        self.write_to_next_field_with(|field_info, encoding, dst| {
+            field_value.write_dyn(field_info, encoding, dst)
+        })
+        .map_err(|error| error.at_field_index(index))
+    }
+
+    /// Writes the generated value of the next field instead of the value given,
+    /// when it is an autoincrement field, returns whether it was written
+    fn write_autoincrement_instead(&mut self) -> Result<bool, FieldIOError> {
+        if let Some(policy) = self.autoincrement {
+            if let Some(field_info) = self.fields_info.peek().copied() {
+                if field_info.autoincrement().is_some() {
+                    if policy == AutoIncrementPolicy::Error {
+                        return Err(FieldIOError::new(
+                            ErrorKind::AutoIncrementValueGiven,
+                            Some(field_info.clone()),
+                        )
+                        .at_field_index(self.next_field_index()));
+                    }
+                    self.write_next_generated_value()?;
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Converts the value using [ToFieldValue] and writes it as the next field.
+    ///
+    /// A conversion error is returned as an [ErrorKind::BadConversion].
+    pub fn write_next_field_as<T: ToFieldValue + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), FieldIOError> {
+        let Some(field_info) = self.fields_info.peek().copied() else {
+            return Err(FieldIOError::end_of_record());
+        };
+        match value.to_field_value(field_info) {
+            Ok(value) => self.write_next_field_value(&value),
+            Err(error) => Err(FieldIOError::new(error.into(), Some(field_info.clone()))
+                .at_field_index(self.next_field_index())),
+        }
+    }
+
+    /// Writes a value of a record, converted to the type of the field
+    /// when [TableWriterBuilder::coerce_types] is enabled
+    pub(crate) fn write_next_field_record_value(
+        &mut self,
+        value: &FieldValue,
+    ) -> Result<(), FieldIOError> {
+        let field_info = match self.fields_info.peek().copied() {
+            Some(field_info)
+                if self.coerce_types && value.field_type() != field_info.field_type =>
+            {
+                field_info
+            }
+            _ => return self.write_next_record_value(value),
+        };
+        match value.coerce_to(field_info.field_type) {
+            Ok(coerced) => self.write_next_record_value(&coerced),
+            Err(error) => Err(FieldIOError::new(error.into(), Some(field_info.clone()))
+                .at_field_index(self.next_field_index())),
+        }
+    }
+
+    /// Writes the value, in the memo file for Memo values when there is one
+    fn write_next_record_value(&mut self, value: &FieldValue) -> Result<(), FieldIOError> {
+        let next_is_memo = self
+            .fields_info
+            .peek()
+            .is_some_and(|info| info.field_type == FieldType::Memo);
+        match value {
+            FieldValue::Memo(text) if next_is_memo && self.memo_writer.is_some() => {
+                self.write_next_memo(text)
+            }
+            _ => self.write_next_field_value(value),
+        }
+    }
+
+    /// Writes the text in the memo file and its block index in the next field,
+    /// which is a Memo field
+    fn write_next_memo(&mut self, text: &str) -> Result<(), FieldIOError> {
+        let index = self.next_field_index();
+        let Some(memo_writer) = self.memo_writer.as_deref_mut() else {
+            return Err(FieldIOError::new(ErrorKind::MissingMemoFile, None).at_field_index(index));
+        };
+        let block_index = if text.is_empty() {
+            None
+        } else {
+            let result = self
+                .encoding
+                .encode(text)
+                .map_err(ErrorKind::from)
+                .and_then(|bytes| memo_writer.write_memo(&bytes));
+            match result {
+                Ok(block_index) => Some(block_index),
+                Err(kind) => {
+                    let field_info = self.fields_info.peek().map(|info| (*info).clone());
+                    return Err(FieldIOError::new(kind, field_info).at_field_index(index));
+                }
+            }
+        };
+        self.write_to_next_field_with(|field_info, _encoding, dst| {
+            match block_index {
+                // Visual FoxPro stores the index as a binary integer
+                Some(block_index) if field_info.field_length == 4 => {
+                    dst.write_u32::<LittleEndian>(block_index)?
+                }
+                Some(block_index) => write!(dst, "{}", block_index)?,
+                None => {}
+            }
+            Ok(())
+        })
+        .map_err(|error| error.at_field_index(index))
+    }
+
+    /// Converts the text to a value of the type of the next field and writes it.
+    ///
+    /// | Field type              | Accepted text                                  |
+    /// |-------------------------|------------------------------------------------|
+    /// | Character, Memo         | any, written as is                             |
+    /// | Numeric, Float          | a number, e.g. `-1.5`                          |
+    /// | Integer                 | an integer                                     |
+    /// | Currency, Double        | a number                                       |
+    /// | Logical                 | `T`, `Y`, `1`, `F`, `N`, `0` (any case) or `?` |
+    /// | Date                    | `YYYYMMDD` or `YYYY-MM-DD`                     |
+    /// | DateTime                | a date followed by `HH:MM:SS`, separated by a space or `T` |
+    ///
+    /// Surrounding spaces are ignored, except for Character and Memo fields.
+    /// An empty text gives the `None` value of the type, or 0
+    /// for types that do not have one (Integer, Currency and Double).
+    ///
+    /// Text that cannot be converted gives an [ErrorKind::InvalidFieldText] error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut writer = dbase::TableWriterBuilder::new()
+    ///     .add_numeric_field("AMOUNT".try_into().unwrap(), 10, 2)
+    ///     .add_date_field("DAY".try_into().unwrap())
+    ///     .build_with_dest(std::io::Cursor::new(Vec::<u8>::new()))?;
+    /// writer.write_record(&["12.5", "2024-03-01"][..])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_next_field_from_str(&mut self, text: &str) -> Result<(), FieldIOError> {
+        let Some(field_info) = self.fields_info.peek().copied() else {
+            return Err(FieldIOError::end_of_record());
+        };
+        match field_info.field_type {
+            FieldType::Character | FieldType::Memo => self.write_next_field_value(&text),
+            FieldType::DateTime if text.trim().is_empty() => {
+                self.write_next_field_value(&None::<DateTime>)
+            }
+            field_type => match value_from_text(text.trim(), field_type) {
+                Some(value) => self.write_next_field_value(&value),
+                None => Err(FieldIOError::new(
+                    ErrorKind::InvalidFieldText {
+                        text: text.to_owned(),
+                        field_type,
+                    },
+                    Some(field_info.clone()),
+                )
+                .at_field_index(self.next_field_index())),
+            },
+        }
+    }
+
+    /// Writes the next value of the next field if it is an autoincrement field
+    /// and records are being appended, returns whether it was written.
+    pub(crate) fn write_next_generated_value(&mut self) -> Result<bool, FieldIOError> {
+        let next_value = match (self.autoincrement, self.fields_info.peek()) {
+            (Some(_), Some(field_info)) => field_info.autoincrement().map(|(next, _)| next),
+            _ => None,
+        };
+        match next_value {
+            Some(next_value) => {
+                // Integer fields are signed
+                self.write_next_value(&(next_value as i32))?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn write_next_value<T: WritableAsDbaseField>(
+        &mut self,
+        field_value: &T,
+    ) -> Result<(), FieldIOError> {
+        let index = self.next_field_index();
+        self.write_to_next_field_with(|field_info, encoding, dst| {
+            field_value.write_as(field_info, encoding, dst)
+        })
+        .map_err(|error| error.at_field_index(index))
+    }
+
+    /// Writes the next field with the bytes `write` puts in the field buffer
+    fn write_to_next_field_with(
+        &mut self,
+        write: impl FnOnce(&FieldInfo, &DynEncoding, &mut Cursor<&mut Vec<u8>>) -> Result<(), ErrorKind>,
+    ) -> Result<(), FieldIOError> {
+        let index = self.next_field_index();
         if let Some(field_info) = self.fields_info.next() {
-            let pad_before = matches!(
-                field_info.field_type(),
-                FieldType::Numeric | FieldType::Float | FieldType::Memo
-            );
+            self.write_hidden_bytes_before(field_info)?;
+            // Numeric and Float values are already right-justified on the field length
+            let pad_before = field_info.field_type() == FieldType::Memo;
 
             self.field_buffer.set_position(0);
-            field_value
-                .write_as(field_info, self.encoding, &mut self.field_buffer)
+            write(field_info, self.encoding, self.field_buffer)
                 .map_err(|kind| FieldIOError::new(kind, Some(field_info.clone())))?;
             let value_len = self.field_buffer.position() as usize;
-            let bytes_to_pad = usize::from(field_info.field_length).saturating_sub(value_len);
+            let field_length = usize::from(field_info.field_length);
+            if value_len > field_length {
+                self.truncated_values.push((index, value_len));
+            }
+
+            // Cropping the bytes of a Character value could split a multibyte character
+            let fitted = if field_info.field_type == FieldType::Character
+                && !field_info.flags.is_binary()
+                && value_len > field_length
+            {
+                let bytes = &self.field_buffer.get_ref()[..value_len];
+                Some(fit_character_bytes(self.encoding, bytes, field_info)?)
+            } else {
+                None
+            };
+
+            // If the current field value size exceeds the one one set
+            // when creating the writer, it will be cropped
+            let write_len = fitted
+                .as_ref()
+                .map_or(value_len, Vec::len)
+                .min(field_length);
+            let bytes_to_pad = field_length - write_len;
 
             if bytes_to_pad > 0 && pad_before {
                 self.write_pad(bytes_to_pad, field_info)?;
             }
 
-            // If the current field value size exceeds the one one set
-            // when creating the writer, it will be cropped
-            let write_len = value_len.min(field_info.field_length as usize);
-            let field_bytes = self.field_buffer.get_ref();
+            let field_bytes = fitted
+                .as_deref()
+                .unwrap_or_else(|| self.field_buffer.get_ref());
+            if field_info.field_type == FieldType::Character
+                && !field_info.flags.is_binary()
+                && !field_bytes[..write_len].is_ascii()
+            {
+                self.wrote_non_ascii = true;
+            }
             self.dst
                 .write_all(&field_bytes[..write_len])
                 .map_err(|error| {
@@ -427,22 +1758,74 @@ impl<'a, W: Write> FieldWriter<'a, W> {
         }
     }
 
-    fn write_pad(&mut self, len: usize, field_info: &FieldInfo) -> Result<(), FieldIOError> {
-        for _ in 0..len {
-            write!(self.dst, " ").map_err(|error| {
-                FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
-            })?;
+    /// Writes zeros in place of the hidden fields (like Visual FoxPro system columns)
+    /// stored before the field, see [ReadingOptions::include_system_columns](crate::ReadingOptions::include_system_columns)
+    fn write_hidden_bytes_before(&mut self, field_info: &FieldInfo) -> Result<(), FieldIOError> {
+        let mut len = field_info.hidden_bytes_before;
+        while len > 0 {
+            let chunk_len = len.min(ZERO_BYTES.len());
+            self.dst
+                .write_all(&ZERO_BYTES[..chunk_len])
+                .map_err(|error| {
+                    FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
+                })?;
+            len -= chunk_len;
+        }
+        Ok(())
+    }
+
+    /// Writes `len` padding spaces (zeros for binary Character fields), at most 255 at once
+    fn write_pad(&mut self, mut len: usize, field_info: &FieldInfo) -> Result<(), FieldIOError> {
+        let pad_bytes =
+            if field_info.field_type == FieldType::Character && field_info.flags.is_binary() {
+                &ZERO_BYTES
+            } else {
+                &PAD_BYTES
+            };
+        while len > 0 {
+            let chunk_len = len.min(pad_bytes.len());
+            self.dst
+                .write_all(&pad_bytes[..chunk_len])
+                .map_err(|error| {
+                    FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
+                })?;
+            len -= chunk_len;
         }
         Ok(())
     }
 
     #[cfg(feature = "serde")]
     pub(crate) fn write_next_field_raw(&mut self, value: &[u8]) -> Result<(), FieldIOError> {
+        let index = self.next_field_index();
+        self.write_raw_to_next_field(value)
+            .map_err(|error| error.at_field_index(index))
+    }
+
+    #[cfg(feature = "serde")]
+    fn write_raw_to_next_field(&mut self, value: &[u8]) -> Result<(), FieldIOError> {
+        let index = self.next_field_index();
         if let Some(field_info) = self.fields_info.next() {
+            self.write_hidden_bytes_before(field_info)?;
+            if value.len() > usize::from(field_info.field_length) {
+                self.truncated_values.push((index, value.len()));
+            }
             let pad_before = matches!(
                 field_info.field_type(),
                 FieldType::Numeric | FieldType::Float | FieldType::Memo
             );
+            let is_text =
+                field_info.field_type == FieldType::Character && !field_info.flags.is_binary();
+            if is_text && !value.is_ascii() {
+                self.wrote_non_ascii = true;
+            }
+
+            let fitted;
+            let value = if is_text && value.len() > field_info.field_length as usize {
+                fitted = fit_character_bytes(self.encoding, value, field_info)?;
+                &fitted
+            } else {
+                value
+            };
 
             if value.len() == field_info.field_length as usize {
                 self.dst.write_all(value).map_err(|error| {
@@ -471,13 +1854,115 @@ impl<'a, W: Write> FieldWriter<'a, W> {
         }
     }
 
-    pub(crate) fn write_deletion_flag(&mut self) -> std::io::Result<()> {
-        DeletionFlag::NotDeleted.write_to(self.dst)
+    pub(crate) fn write_deletion_flag(&mut self, flag: DeletionFlag) -> std::io::Result<()> {
+        flag.write_to(self.dst)
     }
 
     fn all_fields_were_written(&mut self) -> bool {
         self.fields_info.peek().is_none()
     }
+
+    /// Writes all the fields of `record`, this is what writing records
+    /// and validating them share
+    fn write_record(
+        &mut self,
+        record: &(impl WritableRecord + ?Sized),
+    ) -> Result<(), FieldIOError> {
+        record.write_using(self)?;
+        if self.all_fields_were_written() {
+            Ok(())
+        } else {
+            Err(FieldIOError::new(ErrorKind::NotEnoughFields, None))
+        }
+    }
+}
+
+/// Default maximum number of issues collected by [TableWriter::validate_records]
+pub const DEFAULT_MAX_VALIDATION_ISSUES: usize = 100;
+
+/// Something that would go wrong when writing a record,
+/// found by [TableWriter::validate_records]
+#[derive(Debug)]
+pub enum ValidationIssue {
+    /// The record cannot be written, the error has its index and
+    /// the field that could not be written
+    Error(Error),
+    /// The value is longer than its field, it would be truncated
+    Truncated {
+        record_num: usize,
+        field_name: String,
+        /// Length of the value, in bytes
+        value_length: usize,
+        field_length: usize,
+    },
+}
+
+impl ValidationIssue {
+    /// Returns the index of the record
+    pub fn record_num(&self) -> usize {
+        match self {
+            ValidationIssue::Error(error) => error.record_num(),
+            ValidationIssue::Truncated { record_num, .. } => *record_num,
+        }
+    }
+
+    /// Returns the name of the field, `None` for errors about the whole record
+    pub fn field_name(&self) -> Option<&str> {
+        match self {
+            ValidationIssue::Error(error) => error.field().as_ref().map(FieldInfo::name),
+            ValidationIssue::Truncated { field_name, .. } => Some(field_name),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::Error(error) => error.fmt(f),
+            ValidationIssue::Truncated {
+                record_num,
+                field_name,
+                value_length,
+                field_length,
+            } => write!(
+                f,
+                "The value of the field '{}' of the record {} is {} bytes long, \
+                 it would be truncated to {} bytes",
+                field_name, record_num, value_length, field_length
+            ),
+        }
+    }
+}
+
+/// Issues found by [TableWriter::validate_records]
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    num_records: usize,
+    issues: Vec<ValidationIssue>,
+    reached_limit: bool,
+}
+
+impl ValidationReport {
+    /// Number of records that were validated
+    pub fn num_records(&self) -> usize {
+        self.num_records
+    }
+
+    /// Returns the issues found, in the order of the records
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+
+    /// Returns whether no issue was found, all the records can be written as they are
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns whether the validation stopped at the maximum number of issues,
+    /// the records after the last one with an issue were not validated
+    pub fn reached_limit(&self) -> bool {
+        self.reached_limit
+    }
 }
 
 /// Structs that writes dBase records to a destination
@@ -485,15 +1970,27 @@ impl<'a, W: Write> FieldWriter<'a, W> {
 /// The only way to create a TableWriter is to use its
 /// [TableWriterBuilder](struct.TableWriterBuilder.html)
 pub struct TableWriter<W: Write + Seek> {
-    dst: W,
+    dst: Inner<W>,
     fields_info: Vec<FieldInfo>,
     /// contains the header of the input file
     /// if this writer was created form a reader
     header: Header,
     /// Buffer used by the FieldWriter
-    buffer: [u8; 255],
+    buffer: Vec<u8>,
     closed: bool,
     encoding: DynEncoding,
+    /// Encoded database backlink (Visual FoxPro only)
+    backlink: Vec<u8>,
+    /// Chose the code page mark when closing
+    auto_code_page: bool,
+    /// Whether a non ASCII Character value was written
+    wrote_non_ascii: bool,
+    /// Compare records with the fields before writing them
+    precheck: bool,
+    /// Convert the values of records to the type of their field
+    coerce_types: bool,
+    autoincrement_policy: AutoIncrementPolicy,
+    progress: ProgressReporter,
 }
 
 impl<W: Write + Seek> TableWriter<W> {
@@ -502,17 +1999,59 @@ impl<W: Write + Seek> TableWriter<W> {
         fields_info: Vec<FieldInfo>,
         origin_header: Header,
         encoding: DynEncoding,
+        backlink: Vec<u8>,
     ) -> Self {
+        let buffer = field_data_buffer(&fields_info);
         Self {
-            dst,
+            dst: Inner::new(dst),
             fields_info,
             header: origin_header,
-            buffer: [0u8; 255],
+            buffer,
             closed: false,
             encoding,
+            backlink,
+            auto_code_page: false,
+            wrote_non_ascii: false,
+            precheck: false,
+            coerce_types: false,
+            autoincrement_policy: AutoIncrementPolicy::default(),
+            progress: ProgressReporter::default(),
         }
     }
 
+    /// Returns a reference to the destination of the writer.
+    ///
+    /// The header is only written when the writer is closed, until then
+    /// the destination does not hold a complete table.
+    pub fn get_ref(&self) -> &W {
+        &self.dst
+    }
+
+    /// Closes the writer and returns its destination
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// use std::convert::TryInto;
+    /// use std::io::Cursor;
+    ///
+    /// let mut writer = dbase::TableWriterBuilder::new()
+    ///     .add_character_field("NAME".try_into().unwrap(), 20)
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new()))?;
+    /// writer.write_record(&["Yoshi"][..])?;
+    ///
+    /// let bytes = writer.into_inner()?.into_inner();
+    /// assert_eq!(dbase::Reader::new(Cursor::new(bytes))?.read()?.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_inner(mut self) -> Result<W, Error> {
+        self.close()?;
+        // The writer is closed, dropping it does not use the destination anymore
+        Ok(self.dst.take())
+    }
+
     /// Writes a record the inner destination
     ///
     /// # Example
@@ -521,54 +2060,328 @@ impl<W: Write + Seek> TableWriter<W> {
     /// use std::convert::TryFrom;
     ///
     /// # fn main() -> Result<(), dbase::Error> {
+    /// # let tmp_dir = tempfile::tempdir().unwrap();
+    /// # let path = tmp_dir.path().join("records.dbf");
     /// let mut writer = dbase::TableWriterBuilder::new()
     ///     .add_character_field(dbase::FieldName::try_from("First Name").unwrap(), 50)
-    ///     .build_with_file_dest("records.dbf")?;
+    ///     .build_with_file_dest(&path)?;
     ///
     /// let mut record = dbase::Record::default();
     /// record.insert("First Name".to_string(), dbase::FieldValue::Character(Some("Yoshi".to_string())));
     ///
     /// writer.write_record(&record)?;
-    ///
-    /// # let ignored_result = std::fs::remove_file("record.dbf");
     /// Ok(())
     /// # }
     /// ```
-    pub fn write_record<R: WritableRecord>(&mut self, record: &R) -> Result<(), Error> {
+    pub fn write_record<R: WritableRecord + ?Sized>(&mut self, record: &R) -> Result<(), Error> {
+        self.write_record_with_flag(record, DeletionFlag::NotDeleted)
+    }
+
+    /// Writes a record marked as deleted, its bytes are the ones [Self::write_record]
+    /// would write, except for the deletion flag.
+    ///
+    /// Readers skip deleted records, this is used to write tombstones
+    /// that tell that a record was removed.
+    pub fn write_deleted_record<R: WritableRecord + ?Sized>(
+        &mut self,
+        record: &R,
+    ) -> Result<(), Error> {
+        self.write_record_with_flag(record, DeletionFlag::Deleted)
+    }
+
+    fn write_record_with_flag<R: WritableRecord + ?Sized>(
+        &mut self,
+        record: &R,
+        flag: DeletionFlag,
+    ) -> Result<(), Error> {
         if self.header.num_records == 0 {
             // reserve the header
             self.write_header()?;
         }
 
-        let mut field_writer = FieldWriter {
-            dst: &mut self.dst,
-            fields_info: self.fields_info.iter().peekable(),
-            field_buffer: &mut Cursor::new(&mut self.buffer),
-            encoding: &self.encoding,
-        };
-
         let current_record_num = self.header.num_records as usize;
+        self.write_record_fields(
+            record,
+            current_record_num,
+            Some(self.autoincrement_policy),
+            flag,
+        )?;
+        self.header.num_records += 1;
+        if self.header.num_records % DEFAULT_PROGRESS_INTERVAL == 0 {
+            trace_event!(
+                debug,
+                records_written = self.header.num_records,
+                "writing records"
+            );
+        }
+        self.fields_info
+            .iter_mut()
+            .for_each(FieldInfo::advance_autoincrement);
+        Ok(())
+    }
 
-        field_writer
-            .write_deletion_flag()
-            .map_err(|error| Error::io_error(error, current_record_num))?;
-
-        record
-            .write_using(&mut field_writer)
-            .map_err(|error| Error::new(error, current_record_num))?;
-
-        if !field_writer.all_fields_were_written() {
+    /// Rewrites the record at `index`, one of the records already written,
+    /// before the writer is closed.
+    ///
+    /// The record is written like with [Self::write_record], except that the values
+    /// of autoincrement fields are not generated: the ones given are written.
+    /// The next records written with [Self::write_record] still go after the last one.
+    ///
+    /// Returns [ErrorKind::InvalidRecordRange] when `index` is not the index of a written record.
+    /// If writing the record fails, it may be left partially written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, FieldValue, Record, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    /// use std::io::Cursor;
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut dst = Cursor::new(Vec::<u8>::new());
+    /// let mut writer = TableWriterBuilder::new()
+    ///     .add_character_field(FieldName::try_from("Name").unwrap(), 50)
+    ///     .build_with_dest(&mut dst)?;
+    ///
+    /// let record = |name: &str| Record::from_iter([("Name", FieldValue::Character(Some(name.to_string())))]);
+    /// writer.write_record(&record("Yoshi"))?;
+    /// writer.overwrite_record(0, &record("Mario"))?;
+    /// writer.write_record(&record("Luigi"))?;
+    /// drop(writer);
+    ///
+    /// dst.set_position(0);
+    /// let records = dbase::Reader::new(dst)?.read()?;
+    /// assert_eq!(records, vec![record("Mario"), record("Luigi")]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn overwrite_record<R: WritableRecord + ?Sized>(
+        &mut self,
+        index: u32,
+        record: &R,
+    ) -> Result<(), Error> {
+        let num_records = self.header.num_records;
+        if index >= num_records {
             return Err(Error {
-                record_num: current_record_num,
+                record_num: index as usize,
                 field: None,
-                kind: ErrorKind::NotEnoughFields,
+                field_index: None,
+                kind: ErrorKind::InvalidRecordRange {
+                    start: index as usize,
+                    end: index as usize + 1,
+                    num_records: num_records as usize,
+                },
             });
         }
 
-        self.header.num_records += 1;
+        let record_position = |index: u32| {
+            u64::from(self.header.offset_to_first_record)
+                + u64::from(index) * u64::from(self.header.size_of_record)
+        };
+        let start = record_position(index);
+        let end_of_records = record_position(num_records);
+        self.dst
+            .seek(SeekFrom::Start(start))
+            .map_err(|error| Error::io_error(error, index as usize))?;
+        let result =
+            self.write_record_fields(record, index as usize, None, DeletionFlag::NotDeleted);
+        // Even when the record could not be written, the next ones go after the last one
+        self.dst
+            .seek(SeekFrom::Start(end_of_records))
+            .map_err(|error| Error::io_error(error, index as usize))?;
+        result
+    }
+
+    /// Writes a record whose values are given with the name of their field,
+    /// in any order, without building a [Record].
+    ///
+    /// Values are found by name like with a [Record], a field without a value
+    /// is an error (unless its value is generated, see [AutoIncrementPolicy]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, FieldValue, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    /// use std::io::Cursor;
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut writer = TableWriterBuilder::new()
+    ///     .add_character_field(FieldName::try_from("Name").unwrap(), 50)
+    ///     .add_logical_field(FieldName::try_from("Open").unwrap())
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new()))?;
+    ///
+    /// let open = FieldValue::Logical(Some(true));
+    /// let name = FieldValue::Character(Some("Van Dorn Street".to_string()));
+    /// writer.write_named_values(vec![("Open", &open), ("Name", &name)])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_named_values<'v>(
+        &mut self,
+        values: impl IntoIterator<Item = (&'v str, &'v FieldValue)>,
+    ) -> Result<(), Error> {
+        self.write_record(&NamedValues::new(values))
+    }
+
+    /// Checks that the records can be written with the encoding and fields
+    /// of this writer, without writing anything.
+    ///
+    /// The error returned tells which record (its index in `records`)
+    /// and which field could not be written,
+    /// e.g. a string that cannot be represented in the code page.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, Record, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    /// use std::io::Cursor;
+    ///
+    /// let mut writer = TableWriterBuilder::new()
+    ///     .add_character_field(FieldName::try_from("Name").unwrap(), 50)
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new())).unwrap();
+    ///
+    /// let mut record = Record::default();
+    /// record.insert("Name".to_string(), "Yoshi".to_string().into());
+    /// let mut bad_record = Record::default();
+    /// bad_record.insert("Name".to_string(), dbase::FieldValue::Logical(Some(true)));
+    ///
+    /// let error = writer.validate_encodable(&[record, bad_record]).unwrap_err();
+    /// assert_eq!(error.record_num(), 1);
+    /// assert_eq!(error.field().as_ref().unwrap().name(), "Name");
+    /// ```
+    pub fn validate_encodable<R: WritableRecord>(&mut self, records: &[R]) -> Result<(), Error> {
+        let mut sink = std::io::sink();
+        for (record_num, record) in records.iter().enumerate() {
+            let mut field_writer = FieldWriter {
+                dst: &mut sink,
+                fields_info: self.fields_info.iter().peekable(),
+                field_buffer: &mut Cursor::new(&mut self.buffer),
+                encoding: &self.encoding,
+                wrote_non_ascii: false,
+                precheck: self.precheck,
+                coerce_types: self.coerce_types,
+                autoincrement: Some(self.autoincrement_policy),
+                num_fields: self.fields_info.len(),
+                truncated_values: vec![],
+                memo_writer: None,
+            };
+
+            field_writer
+                .write_record(record)
+                .map_err(|error| Error::new(error, record_num))?;
+        }
         Ok(())
     }
 
+    /// Checks that the records can be written, without writing them,
+    /// collecting at most [DEFAULT_MAX_VALIDATION_ISSUES] issues.
+    ///
+    /// See [Self::validate_records_with_limit].
+    pub fn validate_records<'a, R, C>(&self, records: C) -> Result<ValidationReport, Error>
+    where
+        R: WritableRecord + ?Sized + 'a,
+        C: IntoIterator<Item = &'a R>,
+    {
+        self.validate_records_with_limit(records, DEFAULT_MAX_VALIDATION_ISSUES)
+    }
+
+    /// Checks that the records can be written, without writing them.
+    ///
+    /// The records go through the same steps as when written, to a destination that
+    /// discards them: the errors [Self::write_record] would return (types that do not
+    /// match the fields, text that cannot be encoded, missing fields...) and the values
+    /// that would be truncated are the issues of the report, with the index of
+    /// the record (in `records`) and the name of the field.
+    ///
+    /// Unlike [Self::validate_encodable], the validation does not stop at the first issue,
+    /// but after `max_issues` issues.
+    /// Nothing is written and the values of autoincrement fields are not advanced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, FieldValue, Record, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    /// use std::io::Cursor;
+    ///
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let writer = TableWriterBuilder::new()
+    ///     .add_character_field(FieldName::try_from("Name").unwrap(), 5)
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new()))?;
+    ///
+    /// let record = |value: FieldValue| Record::from_iter([("Name", value)]);
+    /// let records = [
+    ///     record(FieldValue::Character(Some("Yoshi".to_string()))),
+    ///     record(FieldValue::Logical(Some(true))),
+    ///     record(FieldValue::Character(Some("Bowser".to_string()))),
+    /// ];
+    /// let report = writer.validate_records_with_limit(&records, 10)?;
+    /// assert_eq!(report.num_records(), 3);
+    /// assert_eq!(report.issues().len(), 2);
+    /// assert_eq!(report.issues()[0].record_num(), 1);
+    /// assert_eq!(report.issues()[1].record_num(), 2);
+    /// assert_eq!(report.issues()[1].field_name(), Some("Name"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn validate_records_with_limit<'a, R, C>(
+        &self,
+        records: C,
+        max_issues: usize,
+    ) -> Result<ValidationReport, Error>
+    where
+        R: WritableRecord + ?Sized + 'a,
+        C: IntoIterator<Item = &'a R>,
+    {
+        let mut report = ValidationReport::default();
+        let mut sink = std::io::sink();
+        let mut buffer = field_data_buffer(&self.fields_info);
+        for (record_num, record) in records.into_iter().enumerate() {
+            if report.issues.len() >= max_issues {
+                report.reached_limit = true;
+                break;
+            }
+            let mut field_writer = FieldWriter {
+                dst: &mut sink,
+                fields_info: self.fields_info.iter().peekable(),
+                field_buffer: &mut Cursor::new(&mut buffer),
+                encoding: &self.encoding,
+                wrote_non_ascii: false,
+                precheck: self.precheck,
+                coerce_types: self.coerce_types,
+                autoincrement: Some(self.autoincrement_policy),
+                num_fields: self.fields_info.len(),
+                truncated_values: vec![],
+                memo_writer: None,
+            };
+            let result = field_writer.write_record(record);
+            report.num_records += 1;
+
+            let truncated = field_writer
+                .truncated_values
+                .into_iter()
+                .map(|(index, len)| {
+                    let info = &self.fields_info[index];
+                    ValidationIssue::Truncated {
+                        record_num,
+                        field_name: info.name().to_string(),
+                        value_length: len,
+                        field_length: usize::from(info.length()),
+                    }
+                });
+            report.issues.extend(truncated);
+            if let Err(error) = result {
+                report
+                    .issues
+                    .push(ValidationIssue::Error(Error::new(error, record_num)));
+            }
+        }
+        report.issues.truncate(max_issues);
+        Ok(report)
+    }
+
     /// Writes the records to the inner destination
     ///
     /// Values for which the number of bytes written would exceed the specified field_length
@@ -594,7 +2407,7 @@ impl<W: Write + Seek> TableWriter<W> {
     /// let mut cursor = Cursor::new(Vec::<u8>::new());
     /// let writer = TableWriterBuilder::new()
     ///     .add_character_field(FieldName::try_from("First Name").unwrap(), 50)
-    ///     .build_with_dest(&mut cursor);
+    ///     .build_with_dest(&mut cursor).unwrap();
     ///
     /// let records = vec![
     ///     User {
@@ -608,20 +2421,82 @@ impl<W: Write + Seek> TableWriter<W> {
         mut self,
         records: C,
     ) -> Result<(), Error> {
-        for record in records.into_iter() {
+        let records = records.into_iter();
+        let records_total = match records.size_hint() {
+            (lower, Some(upper)) if lower == upper => lower as u32,
+            _ => 0,
+        };
+        for (i, record) in records.enumerate() {
             self.write_record(record)?;
+            self.progress.report(i as u32 + 1, records_total);
         }
         Ok(())
     }
 
+    /// Copies the records of `reader`, from its current position,
+    /// and returns the number of records written.
+    ///
+    /// The records are streamed, they are not all read in memory first.
+    /// The records marked as deleted are dropped or written as deleted
+    /// depending on [CopyOptions::deleted].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// use dbase::{CopyOptions, DeletedPolicy, TableWriterBuilder};
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations_with_deleted.dbf")?;
+    /// let mut cursor = std::io::Cursor::new(Vec::<u8>::new());
+    /// let mut writer = TableWriterBuilder::from_table_info(reader.table_info())
+    ///     .build_with_dest(&mut cursor)?;
+    /// let options = CopyOptions { deleted: DeletedPolicy::Preserve };
+    /// let num_copied = writer.copy_records_from(&mut reader, options)?;
+    /// assert_eq!(num_copied, 6);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn copy_records_from<T: std::io::Read + Seek>(
+        &mut self,
+        reader: &mut crate::reading::Reader<T>,
+        options: CopyOptions,
+    ) -> Result<usize, Error> {
+        let mut num_copied = 0;
+        match options.deleted {
+            DeletedPolicy::Drop => {
+                for record in reader.iter_records() {
+                    self.write_record(&record?)?;
+                    num_copied += 1;
+                }
+            }
+            DeletedPolicy::Preserve => {
+                for record in reader.iter_records_with_deletion() {
+                    let (record, flag) = record?;
+                    self.write_record_with_flag(&record, flag)?;
+                    num_copied += 1;
+                }
+            }
+        }
+        Ok(num_copied)
+    }
+
     /// Close the writer
     ///
     /// Automatically closed when the writer is dropped,
     /// use it if you want to handle error that can happen when the writer is closing
     ///
+    /// The header is rewritten, the end of file marker is written and the destination
+    /// is flushed, this gives a valid file even when no records were written.
+    ///
     /// Calling close on an already closed writer is a no-op
     pub fn close(&mut self) -> Result<(), Error> {
         if !self.closed {
+            if self.auto_code_page {
+                self.header.code_page_mark = if self.wrote_non_ascii {
+                    self.encoding.code_page_mark()
+                } else {
+                    crate::CodePageMark::Undefined
+                };
+            }
             self.dst
                 .seek(SeekFrom::Start(0))
                 .map_err(|error| Error::io_error(error, self.header.num_records as usize))?;
@@ -632,13 +2507,57 @@ impl<W: Write + Seek> TableWriter<W> {
             self.dst
                 .write_u8(FILE_TERMINATOR)
                 .map_err(|error| Error::io_error(error, self.header.num_records as usize))?;
+            self.dst
+                .flush()
+                .map_err(|error| Error::io_error(error, self.header.num_records as usize))?;
             self.closed = true;
         }
         Ok(())
     }
 
+    /// Writes the deletion flag and the fields of the record at the current position
+    fn write_record_fields<R: WritableRecord + ?Sized>(
+        &mut self,
+        record: &R,
+        current_record_num: usize,
+        autoincrement: Option<AutoIncrementPolicy>,
+        flag: DeletionFlag,
+    ) -> Result<(), Error> {
+        let mut field_writer = FieldWriter {
+            dst: &mut *self.dst,
+            fields_info: self.fields_info.iter().peekable(),
+            field_buffer: &mut Cursor::new(&mut self.buffer),
+            encoding: &self.encoding,
+            wrote_non_ascii: false,
+            precheck: self.precheck,
+            coerce_types: self.coerce_types,
+            autoincrement,
+            num_fields: self.fields_info.len(),
+            truncated_values: vec![],
+            memo_writer: None,
+        };
+
+        field_writer
+            .write_deletion_flag(flag)
+            .map_err(|error| Error::io_error(error, current_record_num))?;
+
+        field_writer.write_record(record).map_err(|error| {
+            let error = Error::new(error, current_record_num);
+            trace_event!(error, %error, "cannot write a record");
+            error
+        })?;
+
+        self.wrote_non_ascii |= field_writer.wrote_non_ascii;
+        Ok(())
+    }
+
     fn write_header(&mut self) -> Result<(), Error> {
-        write_header_parts(&mut self.dst, &self.header, &self.fields_info)
+        write_header_parts(
+            &mut *self.dst,
+            &self.header,
+            &self.fields_info,
+            &self.backlink,
+        )
     }
 }
 
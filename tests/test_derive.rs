@@ -0,0 +1,79 @@
+#[cfg(feature = "derive")]
+mod derive_tests {
+    use std::io::Cursor;
+
+    use dbase::{CompareTolerance, Date, DbaseTable, FieldType, Reader, RecordCompare};
+
+    #[derive(DbaseTable, Debug, Clone, PartialEq)]
+    struct Station {
+        #[dbase(len = 50)]
+        name: String,
+        #[dbase(rename = "LINE", len = 10)]
+        line: Option<String>,
+        #[dbase(width = 12, decimals = 3)]
+        length: f64,
+        ridership: Option<f64>,
+        accessible: bool,
+        opened: Option<Date>,
+    }
+
+    #[test]
+    fn test_derive_table_builder() -> Result<(), Box<dyn std::error::Error>> {
+        let table_info = Station::table_builder().build_table_info()?;
+        let file = dbase::File::create_new(Cursor::new(Vec::<u8>::new()), table_info)?;
+        let fields = file
+            .fields()
+            .iter()
+            .map(|info| (info.name(), info.field_type(), info.length()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            vec![
+                ("name", FieldType::Character, 50),
+                ("LINE", FieldType::Character, 10),
+                ("length", FieldType::Numeric, 12),
+                ("ridership", FieldType::Numeric, 24),
+                ("accessible", FieldType::Logical, 1),
+                ("opened", FieldType::Date, 8),
+            ]
+        );
+        assert_eq!(file.fields()[2].num_decimal_places(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_derive_write_read() -> Result<(), Box<dyn std::error::Error>> {
+        let stations = vec![
+            Station {
+                name: "Van Dorn Street".to_string(),
+                line: Some("blue".to_string()),
+                length: 1.25,
+                ridership: Some(3500.0),
+                accessible: true,
+                opened: Some(Date::new(15, 6, 1991)),
+            },
+            Station {
+                name: "Franconia-Springfield".to_string(),
+                line: None,
+                length: 0.5,
+                ridership: None,
+                accessible: false,
+                opened: None,
+            },
+        ];
+
+        let mut dst = Cursor::new(Vec::<u8>::new());
+        let writer = Station::table_builder().build_with_dest(&mut dst)?;
+        writer.write_records(&stations)?;
+
+        dst.set_position(0);
+        let records = Reader::new(dst.clone())?.read()?;
+        assert!(stations[0].matches_record(&records[0], false, CompareTolerance::default()));
+        assert!(stations[1].matches_record(&records[1], false, CompareTolerance::default()));
+
+        dst.set_position(0);
+        let read_stations = Reader::new(dst)?.read_as::<Station>()?;
+        assert_eq!(read_stations, stations);
+        Ok(())
+    }
+}
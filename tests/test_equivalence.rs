@@ -0,0 +1,67 @@
+//! Every record of every table read with a `Reader` and with a `File`,
+//! both must give the same values and the same errors.
+use dbase::{DecodeMode, File, Reader, ReadingOptions, Record, TrimOption};
+
+/// Records read from a table, with the error message of those that cannot be read,
+/// or the error message of opening the table
+type ReadResult = Result<Vec<Result<Record, String>>, String>;
+
+/// Reads the records not marked as deleted
+fn read_with_reader(path: &str, options: &ReadingOptions) -> ReadResult {
+    let mut reader = Reader::from_path(path).map_err(|error| error.to_string())?;
+    reader.set_options(options.clone());
+    let records = reader
+        .iter_records()
+        .map(|record| record.map_err(|error| error.to_string()))
+        .collect::<Vec<_>>();
+    Ok(records)
+}
+
+/// Same as [read_with_reader], with a [File]
+fn read_with_file(path: &str, options: &ReadingOptions) -> ReadResult {
+    let mut file = File::open_read_only(path).map_err(|error| error.to_string())?;
+    file.set_options(options.clone());
+    let counts = file.count_records().unwrap();
+    // The header may announce more records than the file has
+    let num_records = counts.header_count.min(counts.physical_count);
+    let mut records = vec![];
+    for index in 0..num_records {
+        let mut record = file.record(index).unwrap();
+        match record.is_deleted() {
+            Ok(true) => continue,
+            Ok(false) => records.push(record.read().map_err(|error| error.to_string())),
+            Err(error) => records.push(Err(error.to_string())),
+        }
+    }
+    Ok(records)
+}
+
+#[test]
+fn reader_and_file_read_the_same_records() {
+    let all_options = [
+        ReadingOptions::default(),
+        ReadingOptions::default().character_trim(TrimOption::End),
+        ReadingOptions::default().character_trim(TrimOption::Begin),
+        ReadingOptions::default().numeric_as_string(true),
+        ReadingOptions::default().lenient_memo(true),
+        ReadingOptions::default().strict_deletion_flag(true),
+        ReadingOptions::default().character_decode(DecodeMode::Lossy),
+    ];
+
+    let mut paths = std::fs::read_dir("tests/data")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "dbf"))
+        .collect::<Vec<_>>();
+    paths.sort();
+    assert!(!paths.is_empty());
+
+    for path in &paths {
+        let path = path.to_str().unwrap();
+        for options in &all_options {
+            let from_reader = read_with_reader(path, options);
+            let from_file = read_with_file(path, options);
+            assert_eq!(from_reader, from_file, "{} read with {:?}", path, options);
+        }
+    }
+}
@@ -273,7 +273,7 @@ fn test_file_classical_user_record_example() -> Result<(), Box<dyn std::error::E
     let table_info = dbase::TableWriterBuilder::new()
         .add_character_field("First Name".try_into().unwrap(), 50)
         .add_character_field("Last Name".try_into().unwrap(), 50)
-        .build_table_info();
+        .build_table_info()?;
 
     {
         let mut file = dbase::File::create_new(&mut cursor, table_info)?;
@@ -357,3 +357,833 @@ fn test_file_is_record_deleted() -> Result<(), Box<dyn std::error::Error>> {
     assert!(!is_second_record_deleted);
     Ok(())
 }
+
+#[test]
+fn test_database_backlink_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cursor = std::io::Cursor::new(Vec::<u8>::new());
+    {
+        let mut writer = dbase::TableWriterBuilder::new()
+            .set_database_backlink("..\\data\\sales.dbc")
+            .add_character_field("Name".try_into().unwrap(), 20)
+            .add_integer_field("Quantity".try_into().unwrap())
+            .build_with_dest(&mut cursor)?;
+        let mut record = dbase::Record::default();
+        record.insert("Name".to_string(), "Widget".to_string().into());
+        record.insert("Quantity".to_string(), dbase::FieldValue::Integer(3));
+        writer.write_record(&record)?;
+    }
+
+    cursor.set_position(0);
+    let file = dbase::File::open(&mut cursor)?;
+    assert_eq!(file.backlink(), Some("..\\data\\sales.dbc"));
+    assert_eq!(file.num_records(), 1);
+    drop(file);
+
+    cursor.set_position(0);
+    let mut reader = dbase::Reader::new(&mut cursor)?;
+    let records = reader.read()?;
+    assert_eq!(
+        records[0].get("Quantity"),
+        Some(&dbase::FieldValue::Integer(3))
+    );
+    let table_info = reader.into_table_info();
+    assert_eq!(table_info.backlink(), Some("..\\data\\sales.dbc"));
+
+    // The backlink is kept when creating a new file from the table info
+    let mut copy = std::io::Cursor::new(Vec::<u8>::new());
+    dbase::File::create_new(&mut copy, table_info)?;
+    copy.set_position(0);
+    let file = dbase::File::open(copy)?;
+    assert_eq!(file.backlink(), Some("..\\data\\sales.dbc"));
+
+    Ok(())
+}
+
+#[test]
+fn test_database_backlink_too_long() {
+    let path = "a".repeat(263);
+    let result = dbase::TableWriterBuilder::new()
+        .set_database_backlink(&path)
+        .add_character_field("Name".try_into().unwrap(), 20)
+        .build_table_info();
+    assert!(result.is_err());
+
+    let path = "a".repeat(262);
+    let table_info = dbase::TableWriterBuilder::new()
+        .set_database_backlink(&path)
+        .add_character_field("Name".try_into().unwrap(), 20)
+        .build_table_info()
+        .unwrap();
+    assert_eq!(table_info.backlink(), Some(path.as_str()));
+}
+
+#[test]
+fn test_free_table_has_no_backlink() -> Result<(), Box<dyn std::error::Error>> {
+    let file = dbase::File::open_read_only(STATIONS_WITH_DELETED)?;
+    assert_eq!(file.backlink(), None);
+    Ok(())
+}
+
+#[test]
+fn test_file_write_field_then_read_neighbouring_records() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tmp_file = copy_to_named_tmp_file("tests/data/stations.dbf")?;
+    let mut file = dbase::File::open_read_write(tmp_file.path())?;
+    let name_index = file.field_index("name").unwrap();
+
+    // Fills the read buffer with the first records
+    let first = file.record(0).unwrap().read()?;
+
+    let new_name = dbase::FieldValue::Character(Some("Montparnasse".to_string()));
+    file.record(1)
+        .unwrap()
+        .field(name_index)
+        .unwrap()
+        .write(&new_name)?;
+
+    assert_eq!(file.record(0).unwrap().read()?, first);
+    let second = file.record(1).unwrap().read()?;
+    assert_eq!(second.get("name"), Some(&new_name));
+    let third_name = file.record(2).unwrap().read_field(name_index)?;
+    assert_eq!(
+        third_name,
+        dbase::FieldValue::Character(Some("Federal Center SW".to_string()))
+    );
+
+    // Writing the last field leaves the file positioned at the next record,
+    // so reading it does not need to seek
+    let line_index = file.field_index("line").unwrap();
+    let new_line = dbase::FieldValue::Character(Some("purple".to_string()));
+    file.record(2)
+        .unwrap()
+        .field(line_index)
+        .unwrap()
+        .write(&new_line)?;
+    let fourth = file.record(3).unwrap().read()?;
+    assert_eq!(
+        fourth.get("name"),
+        Some(&dbase::FieldValue::Character(Some(
+            "Judiciary Sq".to_string()
+        )))
+    );
+    assert_eq!(file.record(2).unwrap().read_field(line_index)?, new_line);
+
+    file.flush()?;
+    let on_disk = std::fs::read(tmp_file.path())?;
+    assert!(on_disk
+        .windows("Montparnasse".len())
+        .any(|window| window == b"Montparnasse"));
+
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_round_trip_and_append() -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    let records = reader.read()?;
+    for record in &records {
+        let json = serde_json::Value::from(record);
+        let back = dbase::Record::from_json(&json, reader.fields())?;
+        assert_eq!(&back, record);
+    }
+
+    let tmp_file = copy_to_named_tmp_file("tests/data/stations.dbf")?;
+    let mut file = dbase::File::open_read_write(tmp_file.path())?;
+    let json = serde_json::json!({
+        "NAME": "Dalaran",
+        "marker-col": "#0f0f0f",
+        "marker-sym": null,
+    });
+    let record = dbase::Record::from_json(&json, file.fields())?;
+    file.append_record(&record)?;
+
+    let appended = file.record(STATIONS_DBG_NUM_RECORDS).unwrap().read()?;
+    assert_eq!(appended, record);
+    assert_eq!(
+        appended.get("name"),
+        Some(&dbase::FieldValue::Character(Some("Dalaran".to_string())))
+    );
+    assert_eq!(
+        appended.get("line"),
+        Some(&dbase::FieldValue::Character(None))
+    );
+    assert_eq!(
+        serde_json::Value::from(&appended)["marker-sym"],
+        serde_json::Value::Null
+    );
+
+    let not_an_object = serde_json::json!([1, 2]);
+    assert!(dbase::Record::from_json(&not_an_object, file.fields()).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_file_header_is_written_when_dropped() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_file = copy_to_named_tmp_file("tests/data/stations.dbf")?;
+    let original_date = dbase::Reader::from_path(tmp_file.path())?
+        .header()
+        .last_update;
+
+    let mut good_record = dbase::Record::default();
+    for name in ["name", "marker-col", "marker-sym", "line"] {
+        good_record.insert(name.to_string(), dbase::FieldValue::Character(None));
+    }
+    let mut bad_record = good_record.clone();
+    bad_record.remove("line");
+
+    {
+        let mut file = dbase::File::open_read_write(tmp_file.path())?;
+        assert!(!file.is_dirty());
+        // The first record is written, the second fails before the header is synced
+        assert!(file
+            .append_records(&[good_record.clone(), bad_record])
+            .is_err());
+        assert!(file.is_dirty());
+        assert_eq!(file.num_records(), STATIONS_DBG_NUM_RECORDS + 1);
+    }
+
+    let reader = dbase::Reader::from_path(tmp_file.path())?;
+    assert_eq!(
+        reader.header().num_records as usize,
+        STATIONS_DBG_NUM_RECORDS + 1
+    );
+    assert_ne!(reader.header().last_update, original_date);
+
+    {
+        let mut file = dbase::File::open_read_write(tmp_file.path())?;
+        let name_index = file.field_index("name").unwrap();
+        file.record(0)
+            .unwrap()
+            .write_field(name_index, &"Montparnasse".to_string())?;
+        assert!(file.is_dirty());
+        file.sync_all()?;
+        assert!(!file.is_dirty());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_file_into_inner() -> Result<(), Box<dyn std::error::Error>> {
+    let table_info = dbase::TableWriterBuilder::new()
+        .add_character_field("NAME".try_into().unwrap(), 20)
+        .build_table_info()?;
+    let mut file = dbase::File::create_new(std::io::Cursor::new(Vec::<u8>::new()), table_info)?;
+    let mut record = dbase::Record::default();
+    record.insert("NAME".to_string(), "Yoshi".to_string().into());
+    file.append_record(&record)?;
+    assert!(!file.get_ref().get_ref().is_empty());
+
+    let bytes = file.into_inner()?.into_inner();
+    let records = dbase::Reader::new(std::io::Cursor::new(bytes.clone()))?.read()?;
+    assert_eq!(records, vec![record.clone()]);
+
+    // The header of a modified file is written before the source is returned
+    let mut file = dbase::File::open(std::io::Cursor::new(bytes))?;
+    file.record(0)
+        .unwrap()
+        .write_field(dbase::FieldIndex(0), &"Mario".to_string())?;
+    assert!(file.is_dirty());
+    let mut cursor = file.into_inner()?;
+    cursor.set_position(0);
+    let records = dbase::Reader::new(cursor)?.read()?;
+    assert_eq!(
+        records[0].get("NAME"),
+        Some(&dbase::FieldValue::Character(Some("Mario".to_string())))
+    );
+
+    let mut writer = dbase::TableWriterBuilder::new()
+        .add_character_field("NAME".try_into().unwrap(), 20)
+        .build_with_dest(std::io::Cursor::new(Vec::<u8>::new()))?;
+    writer.write_record(&record)?;
+    assert_eq!(writer.get_ref().get_ref()[4..8], [0, 0, 0, 0]);
+    let bytes = writer.into_inner()?.into_inner();
+    let records = dbase::Reader::new(std::io::Cursor::new(bytes))?.read()?;
+    assert_eq!(records, vec![record]);
+    Ok(())
+}
+
+#[test]
+fn test_file_records_range() -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = dbase::File::open_read_only("tests/data/stations.dbf")?;
+    let name_idx = file.field_index("name").unwrap();
+
+    let mut names = vec![];
+    let mut records = file.records_range(2..5)?;
+    assert_eq!(records.index(), dbase::RecordIndex(2));
+    while let Some(mut record) = records.next() {
+        names.push(record.field(name_idx).unwrap().read_as::<String>()?);
+    }
+    assert_eq!(records.index(), dbase::RecordIndex(5));
+    assert_eq!(
+        names,
+        vec!["Federal Center SW", "Judiciary Sq", "Capitol South"]
+    );
+
+    let mut records = file.records_from(dbase::RecordIndex(84))?;
+    let mut count = 0;
+    while records.next().is_some() {
+        count += 1;
+    }
+    assert_eq!(count, 2);
+
+    let mut records = file.records_from(dbase::RecordIndex(STATIONS_DBG_NUM_RECORDS))?;
+    assert!(records.next().is_none());
+
+    assert!(file.records_from(dbase::RecordIndex(87)).is_err());
+    assert!(file.records_range(0..87).is_err());
+    #[allow(clippy::reversed_empty_ranges)]
+    let error = file.records_range(5..2).err().unwrap();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::InvalidRecordRange {
+            start: 5,
+            end: 2,
+            num_records: STATIONS_DBG_NUM_RECORDS
+        }
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_file_records_end_at_file_terminator() -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = dbase::File::open_read_only("tests/data/stations_inflated_num_records.dbf")?;
+    assert_eq!(file.num_records(), 9);
+    let name_idx = file.field_index("name").unwrap();
+
+    let mut count = 0;
+    let mut records = file.records();
+    while let Some(mut record) = records.next() {
+        record.read_field(name_idx)?;
+        count += 1;
+    }
+    assert_eq!(count, 6);
+
+    assert!(file.record(5).is_some());
+    assert!(file.record(6).is_none());
+    assert!(file.record(8).is_none());
+    assert!(file.record(9).is_none());
+
+    // The file ends just after the terminator
+    let data = std::fs::read("tests/data/stations_optional.dbf")?;
+    let mut data = std::io::Cursor::new(data);
+    data.get_mut()[4] = 9;
+    let mut file = dbase::File::open(data)?;
+    assert!(file.record(7).is_none());
+    assert!(file.record(5).is_some());
+    Ok(())
+}
+
+#[test]
+fn test_file_upsert_by_key() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_file = copy_to_named_tmp_file("tests/data/stations.dbf")?;
+    let mut file = dbase::File::open_read_write(tmp_file.path())?;
+    let name_idx = file.field_index("name").unwrap();
+    let line_idx = file.field_index("line").unwrap();
+
+    let metro_center = "Metro Center".to_string();
+    assert_eq!(
+        file.find_record_by(name_idx, &metro_center)?,
+        Some(dbase::RecordIndex(5))
+    );
+    assert_eq!(file.find_record_by(name_idx, &"Dalaran".to_string())?, None);
+
+    let mut record = file.record(5).unwrap().read()?;
+    record.insert(
+        "line".to_string(),
+        dbase::FieldValue::Character(Some("purple".to_string())),
+    );
+    let index = file.upsert_by_key(name_idx, &metro_center, &record)?;
+    assert_eq!(index, dbase::RecordIndex(5));
+    assert_eq!(file.num_records(), STATIONS_DBG_NUM_RECORDS);
+
+    let mut new_station = record.clone();
+    new_station.insert(
+        "name".to_string(),
+        dbase::FieldValue::Character(Some("Dalaran".to_string())),
+    );
+    let dalaran = "Dalaran".to_string();
+    let index = file.upsert_by_key(name_idx, &dalaran, &new_station)?;
+    assert_eq!(index, dbase::RecordIndex(STATIONS_DBG_NUM_RECORDS));
+    assert_eq!(file.num_records(), STATIONS_DBG_NUM_RECORDS + 1);
+    assert_eq!(file.find_record_by(name_idx, &dalaran)?, Some(index));
+    assert_eq!(file.record(index.0).unwrap().read()?, new_station);
+
+    let index = file.build_index(name_idx)?;
+    assert_eq!(index.get("Metro Center"), Some(&dbase::RecordIndex(5)));
+    assert_eq!(
+        index.get("Dalaran"),
+        Some(&dbase::RecordIndex(STATIONS_DBG_NUM_RECORDS))
+    );
+    drop(file);
+
+    let mut file = dbase::File::open_read_only(tmp_file.path())?;
+    let line = file.record(5).unwrap().read_field_as::<String>(line_idx)?;
+    assert_eq!(line, "purple");
+    assert_eq!(file.num_records(), STATIONS_DBG_NUM_RECORDS + 1);
+    Ok(())
+}
+
+#[test]
+fn test_file_warns_about_stale_structural_index() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = tempfile::tempdir()?;
+    let path = tmp_dir.path().join("indexed.DBF");
+    std::fs::copy("./tests/data/stations.dbf", &path)?;
+    {
+        // Table flags are the byte 28 of the header
+        let mut file = std::fs::OpenOptions::new().write(true).open(&path)?;
+        file.seek(SeekFrom::Start(28))?;
+        file.write_all(&[0x01])?;
+    }
+
+    let reader = dbase::Reader::from_path(&path)?;
+    assert!(reader.table_flags().has_structural_cdx());
+
+    let mut file = dbase::File::open_read_write(&path)?;
+    assert!(file.table_flags().has_structural_cdx());
+    assert_eq!(
+        file.companion_cdx_path(),
+        Some(tmp_dir.path().join("indexed.CDX"))
+    );
+    // The table has no code page
+    assert_eq!(
+        file.take_warnings(),
+        vec![dbase::Warning::UnknownCodePage(
+            dbase::CodePageMark::Undefined
+        )]
+    );
+
+    let record = file.record(0).unwrap().read()?;
+    file.append_record(&record)?;
+    file.append_record(&record)?;
+    assert_eq!(
+        file.take_warnings(),
+        vec![dbase::Warning::StaleStructuralIndex]
+    );
+    assert!(file.take_warnings().is_empty());
+
+    file.clear_cdx_flag();
+    drop(file);
+
+    let mut file = dbase::File::open_read_write(&path)?;
+    assert!(!file.table_flags().has_structural_cdx());
+    file.take_warnings();
+    file.append_record(&record)?;
+    assert!(file.take_warnings().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_file_truncate_records() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_file = copy_to_named_tmp_file("tests/data/stations.dbf")?;
+    let records = dbase::read(tmp_file.path())?;
+    let original_size = std::fs::metadata(tmp_file.path())?.len();
+
+    {
+        let mut file = dbase::File::open_read_write(tmp_file.path())?;
+        file.append_records(&records[..3])?;
+        assert_eq!(file.num_records(), STATIONS_DBG_NUM_RECORDS + 3);
+
+        let error = file
+            .truncate_records(STATIONS_DBG_NUM_RECORDS + 4)
+            .unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            dbase::ErrorKind::InvalidRecordRange { .. }
+        ));
+
+        // Read a record that is about to be removed, to fill the record buffer
+        file.record(STATIONS_DBG_NUM_RECORDS).unwrap().read()?;
+        file.truncate_records(6)?;
+        assert_eq!(file.num_records(), 6);
+        assert!(file.record(6).is_none());
+        assert_eq!(file.record(5).unwrap().read()?, records[5]);
+    }
+
+    let size = std::fs::metadata(tmp_file.path())?.len();
+    assert!(size < original_size);
+    let file = dbase::File::open_read_only(tmp_file.path())?;
+    assert_eq!(file.num_records(), 6);
+    let mut reader = dbase::Reader::from_path(tmp_file.path())?;
+    assert_eq!(reader.header().num_records, 6);
+    assert_eq!(reader.read()?, records[..6]);
+    // The header, the 6 records and the file terminator
+    assert_eq!(
+        size,
+        reader.header().offset_to_first_record as u64
+            + 6 * reader.header().size_of_record as u64
+            + 1
+    );
+    Ok(())
+}
+
+#[test]
+fn test_file_append_named_values() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_file = copy_to_named_tmp_file("tests/data/stations.dbf")?;
+    let line = dbase::FieldValue::Character(Some("purple".to_string()));
+    let name = dbase::FieldValue::Character(Some("New Station".to_string()));
+    let color = dbase::FieldValue::Character(Some("#800080".to_string()));
+    let symbol = dbase::FieldValue::Character(Some("rail-metro".to_string()));
+    let values = vec![
+        ("line", &line),
+        ("name", &name),
+        ("marker-sym", &symbol),
+        ("marker-col", &color),
+    ];
+
+    {
+        let mut file = dbase::File::open_read_write(tmp_file.path())?;
+        file.append_named_values(values.iter().copied())?;
+
+        let error = file
+            .append_named_values(values[..3].iter().copied())
+            .unwrap_err();
+        assert!(matches!(error.kind(), dbase::ErrorKind::Message(_)));
+    }
+
+    let records = dbase::read(tmp_file.path())?;
+    assert_eq!(records.len(), STATIONS_DBG_NUM_RECORDS + 1);
+    let last = records.last().unwrap();
+    for (name, value) in values {
+        assert_eq!(last.get(name), Some(value));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_open_or_create() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("data.dbf");
+    let builder = || {
+        dbase::TableWriterBuilder::new()
+            .add_character_field("NAME".try_into().unwrap(), 20)
+            .add_numeric_field("VALUE".try_into().unwrap(), 10, 2)
+    };
+
+    // Created when missing
+    let mut file = dbase::File::open_or_create(&path, builder().build_table_info()?)?;
+    assert_eq!(file.num_records(), 0);
+    file.append_record(&dbase::Record::from_iter([
+        (
+            "NAME",
+            dbase::FieldValue::Character(Some("one".to_string())),
+        ),
+        ("VALUE", dbase::FieldValue::Numeric(Some(1.0))),
+    ]))?;
+    drop(file);
+
+    // Opened when the fields match, whatever the number of records
+    let file = dbase::File::open_or_create(&path, builder().build_table_info()?)?;
+    assert_eq!(file.num_records(), 1);
+    drop(file);
+
+    // Refused when they do not
+    let other = dbase::TableWriterBuilder::new()
+        .add_character_field("TITLE".try_into().unwrap(), 20)
+        .add_numeric_field("VALUE".try_into().unwrap(), 12, 0)
+        .add_logical_field("OPEN".try_into().unwrap())
+        .build_table_info()?;
+    let Err(error) = dbase::File::open_or_create(&path, other) else {
+        panic!("the fields are different");
+    };
+    let dbase::ErrorKind::SchemaMismatch { differences } = error.kind() else {
+        panic!("unexpected error: {}", error);
+    };
+    assert_eq!(
+        differences,
+        &vec![
+            "field 0: expected name 'TITLE', found 'NAME'".to_string(),
+            "field 1 'VALUE': expected length 12, found 10".to_string(),
+            "field 1 'VALUE': expected decimals 0, found 2".to_string(),
+            "field 2 'OPEN' is missing".to_string(),
+        ]
+    );
+    let fewer = dbase::TableWriterBuilder::new()
+        .add_character_field("NAME".try_into().unwrap(), 20)
+        .build_table_info()?;
+    let Err(error) = dbase::File::open_or_create(&path, fewer) else {
+        panic!("the file has more fields");
+    };
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::SchemaMismatch { differences }
+            if differences == &vec!["field 1 'VALUE' is not expected".to_string()]
+    ));
+    // The existing file is left as is
+    assert_eq!(dbase::File::open_read_only(&path)?.num_records(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_reload_sees_appended_records() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("data.dbf");
+    let builder =
+        || dbase::TableWriterBuilder::new().add_character_field("NAME".try_into().unwrap(), 20);
+    let record = |name: &str| {
+        dbase::Record::from_iter([("NAME", dbase::FieldValue::Character(Some(name.to_string())))])
+    };
+    let mut writer = builder().build_with_file_dest(&path)?;
+    writer.write_record(&record("one"))?;
+    drop(writer);
+
+    let mut reader = dbase::File::open_read_only(&path)?;
+    assert_eq!(reader.num_records(), 1);
+    assert_eq!(reader.record(0).unwrap().read()?, record("one"));
+    assert!(reader.record(1).is_none());
+
+    let mut appender = dbase::File::open_read_write(&path)?;
+    appender.append_record(&record("two"))?;
+    appender.append_record(&record("three"))?;
+    drop(appender);
+
+    let report = reader.reload()?;
+    assert_eq!(report.previous_num_records, 1);
+    assert_eq!(report.num_records, 3);
+    assert_eq!(report.new_records(), 2);
+    let mut new_records = vec![];
+    let mut records = reader.records_from(dbase::RecordIndex(1))?;
+    while let Some(mut record) = records.next() {
+        new_records.push(record.read()?);
+    }
+    assert_eq!(new_records, vec![record("two"), record("three")]);
+    assert_eq!(reader.reload()?.new_records(), 0);
+
+    // The fields of the table changed
+    dbase::TableWriterBuilder::new()
+        .add_character_field("NAME".try_into().unwrap(), 30)
+        .create_empty(&path)?;
+    let error = reader.reload().unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::SchemaMismatch { differences } if differences.len() == 1
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_reload_sees_appended_memos() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("memo.dbf");
+    let record = |text: &str| {
+        dbase::Record::from_iter([("TEXT", dbase::FieldValue::Memo(text.to_string()))])
+    };
+    let table_info = dbase::TableWriterBuilder::new()
+        .add_memo_field("TEXT".try_into().unwrap())
+        .build_table_info()?;
+    let mut writer = dbase::File::create(&path, table_info)?;
+    writer.append_record(&record("first memo"))?;
+    drop(writer);
+
+    let mut reader = dbase::File::open_read_only(&path)?;
+    assert_eq!(reader.record(0).unwrap().read()?, record("first memo"));
+
+    let mut appender = dbase::File::open_read_write(&path)?;
+    appender.append_record(&record(&"long memo ".repeat(100)))?;
+    drop(appender);
+
+    assert_eq!(reader.reload()?.new_records(), 1);
+    assert_eq!(
+        reader.record(1).unwrap().read()?,
+        record(&"long memo ".repeat(100))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_write_tombstones() -> Result<(), Box<dyn std::error::Error>> {
+    let mut live = dbase::Record::default();
+    live.insert("NAME".to_string(), "Yoshi".to_string().into());
+    let mut removed = dbase::Record::default();
+    removed.insert("NAME".to_string(), "Mario".to_string().into());
+
+    let mut writer = dbase::TableWriterBuilder::new()
+        .add_character_field("NAME".try_into().unwrap(), 20)
+        .build_with_dest(std::io::Cursor::new(Vec::<u8>::new()))?;
+    writer.write_record(&live)?;
+    writer.write_deleted_record(&removed)?;
+    let bytes = writer.into_inner()?.into_inner();
+    // Only the deletion flags differ
+    assert_eq!(bytes[65], b' ');
+    assert_eq!(bytes[65 + 21], b'*');
+    assert_eq!(&bytes[65 + 22..65 + 27], b"Mario");
+
+    let records = dbase::Reader::new(std::io::Cursor::new(bytes.clone()))?.read()?;
+    assert_eq!(records, vec![live.clone()]);
+
+    let mut file = dbase::File::open(std::io::Cursor::new(bytes))?;
+    file.append_record_with_flag(&removed, dbase::DeletionFlag::Deleted)?;
+    file.append_record_with_flag(&live, dbase::DeletionFlag::NotDeleted)?;
+    assert_eq!(file.num_records(), 4);
+    let deleted = (0..4)
+        .map(|index| file.record(index).unwrap().is_deleted())
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(deleted, [false, true, true, false]);
+
+    let mut cursor = file.into_inner()?;
+    cursor.set_position(0);
+    let records = dbase::Reader::new(cursor)?.read()?;
+    assert_eq!(records, vec![live.clone(), live]);
+    Ok(())
+}
+
+#[test]
+fn test_create_with_memo_file() -> Result<(), Box<dyn std::error::Error>> {
+    use dbase::{FieldValue, Record, TableWriterBuilder};
+
+    let tmp_dir = tempfile::tempdir()?;
+    let record = |id: i32, note: String| {
+        Record::from_iter([
+            ("ID", FieldValue::Integer(id)),
+            ("NOTE", FieldValue::Memo(note)),
+        ])
+    };
+    let note = |id: usize| format!("{:04} ", id).repeat(205);
+    assert!(note(0).len() > 1024);
+
+    let builders = [
+        ("foxpro.dbf", "fpt", TableWriterBuilder::new()),
+        (
+            "visual_foxpro.dbf",
+            "fpt",
+            TableWriterBuilder::new().set_database_backlink("sales.dbc"),
+        ),
+    ];
+    for (name, memo_extension, builder) in builders {
+        let path = tmp_dir.path().join(name);
+        let table_info = builder
+            .add_integer_field("ID".try_into().unwrap())
+            .add_memo_field("NOTE".try_into().unwrap())
+            .build_table_info()?;
+
+        let mut file = dbase::File::create(&path, table_info)?;
+        assert!(path.with_extension(memo_extension).exists());
+        let records = vec![
+            record(0, note(0)),
+            record(1, String::new()),
+            record(2, note(2)),
+        ];
+        file.append_records(&records)?;
+        assert_eq!(file.record(2).unwrap().read()?, records[2]);
+        drop(file);
+
+        let mut file = dbase::File::open_read_write(&path)?;
+        file.append_record(&record(3, note(3)))?;
+        file.record(0).unwrap().write(&record(0, note(4)))?;
+        drop(file);
+
+        let expected = vec![
+            record(0, note(4)),
+            record(1, String::new()),
+            record(2, note(2)),
+            record(3, note(3)),
+        ];
+        let mut file = dbase::File::open_read_only(&path)?;
+        let read = (0..file.num_records())
+            .map(|index| file.record(index).unwrap().read())
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(read, expected, "{}", name);
+        assert_eq!(dbase::read(&path)?, expected, "{}", name);
+    }
+
+    // Without a memo file, Memo values cannot be written
+    let mut dst = std::io::Cursor::new(Vec::<u8>::new());
+    let table_info = TableWriterBuilder::new()
+        .add_integer_field("ID".try_into().unwrap())
+        .add_memo_field("NOTE".try_into().unwrap())
+        .build_table_info()?;
+    let mut file = dbase::File::create_new(&mut dst, table_info)?;
+    assert!(file.append_record(&record(0, note(0))).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_create_with_dbase_memo_file() -> Result<(), Box<dyn std::error::Error>> {
+    use dbase::{FieldValue, Record, TableWriterBuilder};
+
+    let tmp_dir = tempfile::tempdir()?;
+    let path = tmp_dir.path().join("notes.DBF");
+    let table_info = TableWriterBuilder::new()
+        .add_character_field("NAME".try_into().unwrap(), 10)
+        .add_memo_field("NOTE".try_into().unwrap())
+        .build_table_info()?;
+    let mut file = dbase::File::create(&path, table_info)?;
+    // The extension has the case of the one of the table
+    assert!(path.with_extension("DBT").exists());
+
+    let records = ["short", &"x".repeat(511), &"long text ".repeat(120)]
+        .iter()
+        .map(|note| {
+            Record::from_iter([
+                ("NAME", FieldValue::Character(Some("Yoshi".to_string()))),
+                ("NOTE", FieldValue::Memo(note.to_string())),
+            ])
+        })
+        .collect::<Vec<_>>();
+    file.append_records(&records)?;
+    drop(file);
+
+    assert_eq!(dbase::read(&path)?, records);
+    Ok(())
+}
+
+#[test]
+fn test_append_memo_without_next_block_in_the_header() -> Result<(), Box<dyn std::error::Error>> {
+    use dbase::{FieldValue, Record, TableWriterBuilder};
+
+    let tmp_dir = tempfile::tempdir()?;
+    let path = tmp_dir.path().join("notes.dbf");
+    let table_info = TableWriterBuilder::new()
+        .add_memo_field("NOTE".try_into().unwrap())
+        .build_table_info()?;
+    let record = |note: &str| Record::from_iter([("NOTE", FieldValue::Memo(note.to_string()))]);
+    let mut file = dbase::File::create(&path, table_info)?;
+    file.append_record(&record(&"first ".repeat(100)))?;
+    drop(file);
+
+    // 0 is written by the programs that do not track the next available block
+    let memo_path = path.with_extension("dbt");
+    let mut memo = std::fs::read(&memo_path)?;
+    let memo_len = memo.len();
+    memo[..4].copy_from_slice(&[0; 4]);
+    std::fs::write(&memo_path, &memo)?;
+
+    let mut file = dbase::File::open_read_write(&path)?;
+    file.append_record(&record("second"))?;
+    drop(file);
+
+    let memo = std::fs::read(&memo_path)?;
+    // The second memo went in the block after the end of the file
+    assert_eq!(memo[..4], (memo_len as u32 / 512 + 1).to_le_bytes());
+    assert_eq!(
+        dbase::read(&path)?,
+        vec![record(&"first ".repeat(100)), record("second")]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_memo_containing_the_terminator_is_refused() -> Result<(), Box<dyn std::error::Error>> {
+    use dbase::{ErrorKind, FieldValue, Record, TableWriterBuilder};
+
+    let tmp_dir = tempfile::tempdir()?;
+    let path = tmp_dir.path().join("notes.dbf");
+    let table_info = TableWriterBuilder::new()
+        .add_memo_field("NOTE".try_into().unwrap())
+        .build_table_info()?;
+    let mut file = dbase::File::create(&path, table_info)?;
+
+    // 0x1A ends the memos of dBase III memo files
+    let record = Record::from_iter([("NOTE", FieldValue::Memo("end\u{1A}more".to_string()))]);
+    let error = file.append_record(&record).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::MemoContainsTerminator {
+            terminator: 0x1A,
+            position: 3,
+        }
+    ));
+    Ok(())
+}
@@ -0,0 +1,113 @@
+//! Byte-level round trips: records read from a file and written back
+//! with a writer built from the same reader.
+use std::convert::TryInto;
+use std::io::Cursor;
+
+use dbase::{FieldValue, Reader, Record, TableWriterBuilder};
+
+const LINE_DBF: &str = "tests/data/line.dbf";
+const STATIONS_DBF: &str = "tests/data/stations.dbf";
+const NONE_FLOAT_DBF: &str = "tests/data/contain_none_float.dbf";
+const NULL_PADDED_NUMERIC_DBF: &str = "tests/data/contain_null_padded_numeric.dbf";
+const STATIONS_OPTIONAL_DBF: &str = "tests/data/stations_optional.dbf";
+
+/// Returns the bytes of the records of the file, deletion flags included
+fn record_area(data: &[u8]) -> &[u8] {
+    let num_records = u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize;
+    let offset = u16::from_le_bytes(data[8..10].try_into().unwrap()) as usize;
+    let record_size = u16::from_le_bytes(data[10..12].try_into().unwrap()) as usize;
+    &data[offset..offset + num_records * record_size]
+}
+
+/// Reads the records of the file and writes them with a writer built from the reader
+fn rewrite(data: &[u8]) -> (Vec<Record>, Vec<u8>) {
+    let mut reader = Reader::new(Cursor::new(data.to_vec())).unwrap();
+    let records = reader.read().unwrap();
+
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let writer = TableWriterBuilder::from_reader(reader)
+        .build_with_dest(&mut dst)
+        .unwrap();
+    writer.write_records(&records).unwrap();
+    (records, dst.into_inner())
+}
+
+fn assert_same_records(written: &[u8], expected: &[u8], name: &str) {
+    let record_size = u16::from_le_bytes(expected[10..12].try_into().unwrap()) as usize;
+    let written = record_area(written);
+    let expected = record_area(expected);
+    assert_eq!(written.len(), expected.len(), "{}", name);
+    for (i, (written, expected)) in written
+        .chunks(record_size)
+        .zip(expected.chunks(record_size))
+        .enumerate()
+    {
+        assert_eq!(
+            String::from_utf8_lossy(written),
+            String::from_utf8_lossy(expected),
+            "record {} of {}",
+            i,
+            name
+        );
+    }
+}
+
+/// The records written are the same bytes as the ones of the file
+fn assert_exact_round_trip(data: &[u8], name: &str) {
+    let (_, written) = rewrite(data);
+    assert_same_records(&written, data, name);
+}
+
+/// For files whose layout differs from ours (left-justified numbers,
+/// `*` or NUL bytes for empty values, leading spaces): the values
+/// read back are the same, and writing them again gives the same bytes
+fn assert_stable_round_trip(data: &[u8], name: &str) {
+    let (records, written) = rewrite(data);
+    let (records_again, written_again) = rewrite(&written);
+    assert_eq!(records_again, records, "{}", name);
+    assert_same_records(&written_again, &written, name);
+}
+
+#[test]
+fn test_exact_round_trip_of_fixtures() {
+    for path in [LINE_DBF, STATIONS_DBF] {
+        assert_exact_round_trip(&std::fs::read(path).unwrap(), path);
+    }
+}
+
+#[test]
+fn test_stable_round_trip_of_fixtures() {
+    for path in [
+        NONE_FLOAT_DBF,
+        NULL_PADDED_NUMERIC_DBF,
+        STATIONS_OPTIONAL_DBF,
+    ] {
+        assert_stable_round_trip(&std::fs::read(path).unwrap(), path);
+    }
+}
+
+#[test]
+fn test_exact_round_trip_of_numbers_with_trailing_zeros() {
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_numeric_field("FLOAT".try_into().unwrap(), 20, 15)
+        .add_numeric_field("PRICE".try_into().unwrap(), 10, 2)
+        .add_numeric_field("COUNT".try_into().unwrap(), 5, 0)
+        .build_with_dest(&mut dst)
+        .unwrap();
+    for values in [[12.345, 12.1, 7.0], [-0.5, 0.0, -12.0]] {
+        let values = values.map(|value| FieldValue::Numeric(Some(value)));
+        writer.write_record(&values[..]).unwrap();
+    }
+    writer.close().unwrap();
+    drop(writer);
+    let mut data = dst.into_inner();
+    // Makes the first field a Float field, written by another program
+    data[32 + 11] = b'F';
+
+    assert_eq!(
+        std::str::from_utf8(&record_area(&data)[1..36]).unwrap(),
+        "  12.345000000000000     12.10    7"
+    );
+    assert_exact_round_trip(&data, "numbers with trailing zeros");
+}
@@ -17,7 +17,7 @@ mod serde_tests {
         R: WritableRecord + ReadableRecord + Debug + PartialEq,
     {
         let mut dst = Cursor::new(Vec::<u8>::new());
-        let writer = writer_builder.build_with_dest(&mut dst);
+        let writer = writer_builder.build_with_dest(&mut dst).unwrap();
 
         writer.write_records(records).unwrap();
         dst.set_position(0);
@@ -161,7 +161,8 @@ mod serde_tests {
         let writer = TableWriterBuilder::new()
             .add_logical_field(FieldName::try_from("yes").unwrap())
             .add_character_field(FieldName::try_from("not present").unwrap(), 50)
-            .build_with_dest(Cursor::new(Vec::<u8>::new()));
+            .build_with_dest(Cursor::new(Vec::<u8>::new()))
+            .unwrap();
 
         let error = writer
             .write_records(&records)
@@ -181,7 +182,9 @@ mod serde_tests {
 
         let records = vec![Record { yes: false }];
 
-        let writer = TableWriterBuilder::new().build_with_dest(Cursor::new(Vec::<u8>::new()));
+        let writer = TableWriterBuilder::new()
+            .build_with_dest(Cursor::new(Vec::<u8>::new()))
+            .unwrap();
 
         let error = writer
             .write_records(&records)
@@ -221,4 +224,101 @@ mod serde_tests {
 
         write_read_compare(&records, writer_builder);
     }
+
+    #[test]
+    fn test_serde_check_field_names() {
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Place {
+            name: String,
+            city: String,
+        }
+
+        #[derive(Deserialize, PartialEq, Debug)]
+        struct SwappedPlace {
+            city: String,
+            name: String,
+        }
+
+        let mut dst = Cursor::new(Vec::<u8>::new());
+        let writer = TableWriterBuilder::new()
+            .add_character_field(FieldName::try_from("Name").unwrap(), 20)
+            .add_character_field(FieldName::try_from("City").unwrap(), 20)
+            .build_with_dest(&mut dst)
+            .unwrap();
+        let places = vec![Place {
+            name: "Louvre".to_string(),
+            city: "Paris".to_string(),
+        }];
+        writer.write_records(&places).unwrap();
+
+        let options = dbase::ReadingOptions::default().check_field_names(true);
+        dst.set_position(0);
+        let mut reader = Reader::new(dst.clone()).unwrap();
+        reader.set_options(options.clone());
+        let error = reader.read_as::<SwappedPlace>().unwrap_err();
+        match error.kind() {
+            ErrorKind::FieldNameMismatch { expected, actual } => {
+                assert_eq!(expected, "city");
+                assert_eq!(actual, "Name");
+            }
+            kind => panic!("The kind is not the expected one: {}", kind),
+        }
+
+        dst.set_position(0);
+        let mut reader = Reader::new(dst).unwrap();
+        reader.set_options(options);
+        assert_eq!(reader.read_as::<Place>().unwrap(), places);
+    }
+
+    #[test]
+    fn test_serde_dates_in_fields_of_another_type() {
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Event {
+            name: String,
+            day: dbase::Date,
+            at: dbase::DateTime,
+        }
+
+        let event = Event {
+            name: "Launch".to_string(),
+            day: dbase::Date::new(1, 1, 2024),
+            at: dbase::DateTime::new(dbase::Date::new(1, 1, 2024), dbase::Time::new(9, 30, 0)),
+        };
+        let write = |builder: TableWriterBuilder| {
+            let mut dst = Cursor::new(Vec::<u8>::new());
+            let mut writer = builder.build_with_dest(&mut dst).unwrap();
+            writer.write_record(&event).map(|_| ())
+        };
+        let name = || FieldName::try_from("name").unwrap();
+
+        // The date column was declared as a Character field
+        let error = write(
+            TableWriterBuilder::new()
+                .add_character_field(name(), 20)
+                .add_character_field(FieldName::try_from("day").unwrap(), 10)
+                .add_datetime_field(FieldName::try_from("at").unwrap()),
+        )
+        .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::IncompatibleType));
+        assert_eq!(error.field().as_ref().unwrap().name(), "day");
+
+        // The datetime column was declared as a Date field
+        let error = write(
+            TableWriterBuilder::new()
+                .add_character_field(name(), 20)
+                .add_date_field(FieldName::try_from("day").unwrap())
+                .add_date_field(FieldName::try_from("at").unwrap()),
+        )
+        .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::IncompatibleType));
+        assert_eq!(error.field().as_ref().unwrap().name(), "at");
+
+        write_read_compare(
+            &vec![event],
+            TableWriterBuilder::new()
+                .add_character_field(name(), 20)
+                .add_date_field(FieldName::try_from("day").unwrap())
+                .add_datetime_field(FieldName::try_from("at").unwrap()),
+        );
+    }
 }
@@ -0,0 +1,139 @@
+#![cfg(feature = "tracing")]
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+const STATIONS: &str = "./tests/data/stations.dbf";
+const STATIONS_WITH_DELETED: &str = "./tests/data/stations_with_deleted.dbf";
+
+/// What a span or an event contained: its name (or message) and fields
+#[derive(Debug, Default, Clone)]
+struct Captured {
+    name: String,
+    level: String,
+    fields: Vec<(String, String)>,
+}
+
+impl Captured {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl Visit for Captured {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        let value = format!("{:?}", value);
+        if field.name() == "message" {
+            self.name = value;
+        } else {
+            self.fields.push((field.name().to_string(), value));
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+struct CapturingSubscriber {
+    spans: Arc<Mutex<Vec<Captured>>>,
+    events: Arc<Mutex<Vec<Captured>>>,
+}
+
+impl Subscriber for CapturingSubscriber {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target() == "dbase"
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut captured = Captured {
+            name: span.metadata().name().to_string(),
+            level: span.metadata().level().to_string(),
+            fields: vec![],
+        };
+        span.record(&mut captured);
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(captured);
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        values.record(&mut spans[span.into_u64() as usize - 1]);
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut captured = Captured {
+            level: event.metadata().level().to_string(),
+            ..Default::default()
+        };
+        event.record(&mut captured);
+        self.events.lock().unwrap().push(captured);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn test_open_span_and_events_of_stations() {
+    let subscriber = CapturingSubscriber::default();
+    let records = tracing::subscriber::with_default(subscriber.clone(), || {
+        dbase::Reader::from_path(STATIONS).unwrap().read().unwrap()
+    });
+    assert_eq!(records.len(), 86);
+
+    let spans = subscriber.spans.lock().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+    assert_eq!(span.name, "open_table");
+    assert_eq!(span.level, "DEBUG");
+    assert_eq!(
+        span.field("version"),
+        Some("DBase3 { supports_memo: false }")
+    );
+    assert_eq!(span.field("code_page"), Some("Undefined"));
+    assert_eq!(span.field("num_records"), Some("86"));
+    assert_eq!(span.field("num_fields"), Some("4"));
+
+    let events = subscriber.events.lock().unwrap();
+    assert!(events
+        .iter()
+        .any(|event| event.level == "WARN" && event.name.contains("no code page")));
+    let end = events
+        .iter()
+        .find(|event| event.name == "end of the records")
+        .unwrap();
+    assert_eq!(end.field("records_read"), Some("86"));
+    assert_eq!(end.field("deleted_records_skipped"), Some("0"));
+}
+
+#[test]
+fn test_deleted_records_skipped_are_counted() {
+    let subscriber = CapturingSubscriber::default();
+    let records = tracing::subscriber::with_default(subscriber.clone(), || {
+        dbase::Reader::from_path(STATIONS_WITH_DELETED)
+            .unwrap()
+            .read()
+            .unwrap()
+    });
+
+    let events = subscriber.events.lock().unwrap();
+    let end = events
+        .iter()
+        .find(|event| event.name == "end of the records")
+        .unwrap();
+    let read = end.field("records_read").unwrap().parse::<usize>().unwrap();
+    let deleted = end
+        .field("deleted_records_skipped")
+        .unwrap()
+        .parse::<usize>()
+        .unwrap();
+    assert!(deleted > 0);
+    assert_eq!(read - deleted, records.len());
+}
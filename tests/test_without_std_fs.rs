@@ -0,0 +1,44 @@
+//! The in-memory paths, to be run without the `std-fs` feature:
+//! `cargo test --no-default-features --test test_without_std_fs`
+#![cfg(not(feature = "std-fs"))]
+use std::convert::TryInto;
+use std::io::Cursor;
+
+use dbase::{FieldValue, Record, TableWriterBuilder};
+
+const STATIONS: &[u8] = include_bytes!("data/stations.dbf");
+
+#[test]
+fn read_with_reader_and_file() {
+    let mut reader = dbase::Reader::new(Cursor::new(STATIONS)).unwrap();
+    let records = reader.read().unwrap();
+    assert_eq!(records.len(), 86);
+
+    let mut file = dbase::File::open(Cursor::new(STATIONS.to_vec())).unwrap();
+    assert_eq!(file.num_records(), 86);
+    assert_eq!(file.record(0).unwrap().read().unwrap(), records[0]);
+
+    let table_info = dbase::read_table_info(STATIONS).unwrap();
+    assert_eq!(table_info.fields().len(), 4);
+}
+
+#[test]
+fn write_and_modify_in_memory() {
+    let record =
+        |name: &str| Record::from_iter([("NAME", FieldValue::Character(Some(name.to_string())))]);
+
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::new()
+        .add_character_field("NAME".try_into().unwrap(), 10)
+        .build_with_dest(&mut dst)
+        .unwrap()
+        .write_records(&[record("Yoshi")])
+        .unwrap();
+
+    dst.set_position(0);
+    let mut file = dbase::File::open(dst).unwrap();
+    file.append_record(&record("Mario")).unwrap();
+    assert_eq!(file.num_records(), 2);
+    assert_eq!(file.record(0).unwrap().read().unwrap(), record("Yoshi"));
+    assert_eq!(file.record(1).unwrap().read().unwrap(), record("Mario"));
+}
@@ -5,7 +5,7 @@ use std::io::{Cursor, Read, Seek, Write};
 
 use dbase::{
     Date, DateTime, FieldIOError, FieldIterator, FieldName, FieldValue, FieldWriter,
-    ReadableRecord, Reader, Record, TableWriterBuilder, Time, WritableRecord,
+    FromFieldValue, ReadableRecord, Reader, Record, TableWriterBuilder, Time, WritableRecord,
 };
 use std::convert::{TryFrom, TryInto};
 use std::fmt::Debug;
@@ -15,6 +15,11 @@ const NONE_FLOAT_DBF: &str = "./tests/data/contain_none_float.dbf";
 const NULL_PADDED_NUMERIC_DBF: &str = "./tests/data/contain_null_padded_numeric.dbf";
 const STATIONS: &str = "./tests/data/stations.dbf";
 const STATIONS_WITH_DELETED: &str = "./tests/data/stations_with_deleted.dbf";
+const STATIONS_INFLATED_NUM_RECORDS: &str = "./tests/data/stations_inflated_num_records.dbf";
+const MEMO_DBF: &str = "./tests/data/memo.dbf";
+const MEMO_FPT: &str = "./tests/data/memo.fpt";
+const DBASE2_DBF: &str = "./tests/data/dbase2.dbf";
+const LONG_CHARACTER_DBF: &str = "./tests/data/long_character.dbf";
 #[cfg(feature = "yore")]
 const CP850_DBF: &str = "tests/data/cp850.dbf";
 
@@ -26,7 +31,7 @@ where
     R: WritableRecord + ReadableRecord + Debug + PartialEq,
 {
     let mut dst = Cursor::new(Vec::<u8>::new());
-    let writer = writer_builder.build_with_dest(&mut dst);
+    let writer = writer_builder.build_with_dest(&mut dst).unwrap();
 
     writer.write_records(records).unwrap();
     dst.set_position(0);
@@ -82,7 +87,9 @@ fn test_read_write_simple_file() {
     assert_eq!(records[0], expected_fields);
 
     let mut dst = Cursor::new(Vec::<u8>::new());
-    let writer = TableWriterBuilder::from_reader(reader).build_with_dest(&mut dst);
+    let writer = TableWriterBuilder::from_reader(reader)
+        .build_with_dest(&mut dst)
+        .unwrap();
     writer.write_records(&records).unwrap();
     dst.set_position(0);
 
@@ -183,10 +190,7 @@ fn from_scratch_fox_pro_record() {
     record.insert(String::from("currency"), FieldValue::Currency(4567.134));
     record.insert(
         String::from("datetime"),
-        FieldValue::DateTime(DateTime::new(
-            Date::new(01, 06, 2006),
-            Time::new(12, 50, 20),
-        )),
+        FieldValue::DateTime(DateTime::new(Date::new(1, 6, 2006), Time::new(12, 50, 20))),
     );
 
     let records = vec![record];
@@ -212,7 +216,7 @@ fn from_scratch_fox_pro_struct_record() {
         .add_integer_field(FieldName::try_from("integer").unwrap());
 
     let records = vec![FoxProRecord {
-        datetime: DateTime::new(Date::new(12, 02, 1999), Time::new(21, 20, 35)),
+        datetime: DateTime::new(Date::new(12, 2, 1999), Time::new(21, 20, 35)),
         double: 8649.48851,
         currency: 3489.9612314,
         integer: 42069,
@@ -221,6 +225,46 @@ fn from_scratch_fox_pro_struct_record() {
     write_read_compare(&records, writer_builder);
 }
 
+dbase_record! {
+    #[derive(Clone, Debug, PartialEq)]
+    struct OptionalFoxProRecord {
+        maturity: Option<DateTime>,
+        count: Option<i32>
+    }
+}
+
+#[test]
+fn optional_fox_pro_values_round_trip() {
+    let writer_builder = TableWriterBuilder::new()
+        .add_datetime_field(FieldName::try_from("maturity").unwrap())
+        .add_integer_field(FieldName::try_from("count").unwrap());
+
+    let records = vec![
+        OptionalFoxProRecord {
+            maturity: Some(DateTime::new(Date::new(12, 2, 1999), Time::new(21, 20, 35))),
+            count: Some(-12),
+        },
+        OptionalFoxProRecord {
+            maturity: None,
+            count: None,
+        },
+    ];
+
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let writer = writer_builder.build_with_dest(&mut dst).unwrap();
+    writer.write_records(&records).unwrap();
+    dst.set_position(0);
+
+    let read_records = Reader::new(dst)
+        .unwrap()
+        .read_as::<OptionalFoxProRecord>()
+        .unwrap();
+    assert_eq!(read_records[0], records[0]);
+    assert_eq!(read_records[1].maturity, None);
+    // Integer fields have no empty value, None is stored as 0
+    assert_eq!(read_records[1].count, Some(0));
+}
+
 dbase_record! {
     #[derive(Clone, Debug, PartialEq)]
     struct User {
@@ -257,7 +301,8 @@ fn the_classical_user_record_example() {
     let writer = TableWriterBuilder::new()
         .add_character_field("First Name".try_into().unwrap(), 50)
         .add_character_field("Last Name".try_into().unwrap(), 50)
-        .build_with_dest(&mut cursor);
+        .build_with_dest(&mut cursor)
+        .unwrap();
 
     writer.write_records(&users).unwrap();
     cursor.set_position(0);
@@ -283,7 +328,9 @@ fn non_unicode_codepages() {
     // Write back with same encoding
     let mut cursor = Cursor::new(Vec::<u8>::new());
     {
-        let writer = TableWriterBuilder::from_reader(reader).build_with_dest(&mut cursor);
+        let writer = TableWriterBuilder::from_reader(reader)
+            .build_with_dest(&mut cursor)
+            .unwrap();
         writer.write_records(&records).unwrap();
     }
 
@@ -299,6 +346,115 @@ fn non_unicode_codepages() {
     );
 }
 
+#[cfg(feature = "yore")]
+#[test]
+fn test_convert_encoding_to_utf8() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = tempfile::tempdir()?;
+    let dst_path = tmp_dir.path().join("utf8.dbf");
+    let expected = Some(&dbase::FieldValue::Character(Some("Äöü!§$%&/".to_string())));
+
+    // The 9 characters need 13 bytes in UTF-8
+    let report = dbase::convert_encoding(
+        CP850_DBF,
+        &dst_path,
+        Some(yore::code_pages::CP850),
+        dbase::Unicode,
+    )?;
+    assert_eq!(report.num_records(), 1);
+    assert_eq!(report.widened_fields().get("TEXT"), Some(&13));
+    assert_eq!(report.num_truncated("TEXT"), 0);
+
+    let mut reader = Reader::from_path(&dst_path)?;
+    assert_eq!(reader.header().code_page_mark, dbase::CodePageMark::Utf8);
+    assert_eq!(reader.fields()[0].length(), 13);
+    let records = reader.read()?;
+    assert_eq!(records[0].get("TEXT"), expected);
+
+    let report = dbase::convert_encoding_with_policy(
+        CP850_DBF,
+        &dst_path,
+        Some(yore::code_pages::CP850),
+        dbase::Unicode,
+        dbase::OverflowPolicy::Truncate,
+    )?;
+    assert!(report.widened_fields().is_empty());
+    assert_eq!(report.num_truncated("TEXT"), 1);
+    let records = dbase::read(&dst_path)?;
+    assert_eq!(
+        records[0].get("TEXT"),
+        Some(&dbase::FieldValue::Character(Some("Äöü!§".to_string())))
+    );
+
+    let result = dbase::convert_encoding_with_policy(
+        CP850_DBF,
+        &dst_path,
+        Some(yore::code_pages::CP850),
+        dbase::Unicode,
+        dbase::OverflowPolicy::Error,
+    );
+    match result {
+        Err(error) => assert!(matches!(
+            error.kind(),
+            dbase::ErrorKind::IncompatibleRecord(_)
+        )),
+        Ok(_) => panic!("The value does not fit in its field"),
+    }
+    Ok(())
+}
+
+#[cfg(feature = "yore")]
+#[test]
+fn test_convert_encoding_widens_past_255_bytes() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = tempfile::tempdir()?;
+    let src_path = tmp_dir.path().join("cp850.dbf");
+    let dst_path = tmp_dir.path().join("utf8.dbf");
+    let text = "é".repeat(200);
+
+    let mut writer = TableWriterBuilder::with_encoding(yore::code_pages::CP850)
+        .add_character_field("TEXT".try_into().unwrap(), 200)
+        .build_with_file_dest(&src_path)?;
+    let mut record = Record::default();
+    record.insert(
+        "TEXT".to_string(),
+        FieldValue::Character(Some(text.clone())),
+    );
+    writer.write_record(&record)?;
+    writer.close()?;
+
+    let report = dbase::convert_encoding(
+        &src_path,
+        &dst_path,
+        Some(yore::code_pages::CP850),
+        dbase::Unicode,
+    )?;
+    assert_eq!(report.widened_fields().get("TEXT"), Some(&400));
+    assert_eq!(report.num_truncated("TEXT"), 0);
+
+    let records = dbase::read(&dst_path)?;
+    assert_eq!(
+        records[0].get("TEXT"),
+        Some(&FieldValue::Character(Some(text)))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_convert_encoding_with_memo() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = tempfile::tempdir()?;
+    let dst_path = tmp_dir.path().join("memo.dbf");
+
+    let report = dbase::convert_encoding(
+        MEMO_DBF,
+        &dst_path,
+        None::<dbase::Unicode>,
+        dbase::UnicodeLossy,
+    )?;
+    let expected = dbase::read(MEMO_DBF)?;
+    assert_eq!(report.num_records(), expected.len());
+    assert_eq!(dbase::read(&dst_path)?, expected);
+    Ok(())
+}
+
 dbase::dbase_record!(
     #[derive(PartialOrd, PartialEq, Debug)]
     struct StationRecord {
@@ -376,3 +532,3726 @@ fn test_codepages_cp936() {
         Some(&dbase::FieldValue::Character(Some("测试中文".to_string())))
     );
 }
+
+#[cfg(feature = "encoding_rs")]
+#[test]
+fn test_field_names_use_the_encoding() -> Result<(), Box<dyn std::error::Error>> {
+    const PATH: &str = "tests/data/cp936_field_names.dbf";
+    let expected = Some(FieldValue::Character(Some("你好".to_string())));
+
+    // Encoding of the header's code page
+    let mut file = dbase::File::open_read_only(PATH)?;
+    let index = file.field_index("中文列").unwrap();
+    let value = file.record(0).unwrap().read_field(index)?;
+    assert_eq!(Some(value), expected);
+
+    let mut reader = Reader::from_path(PATH)?;
+    assert_eq!(reader.fields()[0].name(), "中文列");
+    let records = reader.read()?;
+    assert_eq!(records[0].get("中文列").cloned(), expected);
+
+    // Encoding given by the user
+    let gbk = dbase::encoding::EncodingRs::from(encoding_rs::GBK);
+    let mut reader = Reader::from_path_with_encoding(PATH, gbk)?;
+    let records = reader.read()?;
+    assert_eq!(records[0].get("中文列").cloned(), expected);
+
+    let reader = Reader::new_with_encoding(std::fs::File::open(PATH)?, gbk)?;
+    assert_eq!(reader.fields()[0].name(), "中文列");
+    Ok(())
+}
+
+dbase::dbase_record!(
+    #[derive(Debug)]
+    struct StationNameAndLine {
+        name: String,
+        line: String,
+    }
+);
+
+#[test]
+fn test_record_compare_matches_read_record() -> Result<(), Box<dyn std::error::Error>> {
+    use dbase::{CompareTolerance, RecordCompare};
+
+    let records = dbase::read(STATIONS)?;
+
+    let station = StationNameAndLine {
+        name: "Van Dorn Street".to_string(),
+        line: "blue".to_string(),
+    };
+    assert!(station.matches_record(&records[0], false, CompareTolerance::default()));
+
+    // Trailing spaces are not significant
+    let station = StationNameAndLine {
+        name: "Van Dorn Street  ".to_string(),
+        line: "blue".to_string(),
+    };
+    assert!(station.matches_record(&records[0], false, CompareTolerance::default()));
+    assert!(!station.matches_record(&records[0], false, CompareTolerance::exact()));
+    let trim_end = CompareTolerance {
+        trim: Some(dbase::TrimOption::End),
+        ..CompareTolerance::default()
+    };
+    assert!(station.matches_record(&records[0], false, trim_end));
+
+    let station = StationNameAndLine {
+        name: "Van Dorn Street".to_string(),
+        line: "red".to_string(),
+    };
+    assert!(!station.matches_record(&records[0], false, CompareTolerance::default()));
+
+    let expected = station.to_record();
+    assert_eq!(
+        expected.get("line"),
+        Some(&FieldValue::Character(Some("red".to_string())))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_record_compare_case_insensitive_names() {
+    use dbase::{CompareTolerance, RecordCompare};
+
+    let mut record = Record::default();
+    record.insert(
+        "NAME".to_string(),
+        FieldValue::Character(Some("Van Dorn Street".to_string())),
+    );
+    record.insert(
+        "LINE".to_string(),
+        FieldValue::Character(Some("blue".to_string())),
+    );
+
+    let station = StationNameAndLine {
+        name: "Van Dorn Street".to_string(),
+        line: "blue".to_string(),
+    };
+    assert!(!station.matches_record(&record, false, CompareTolerance::default()));
+    assert!(station.matches_record(&record, true, CompareTolerance::default()));
+}
+
+#[test]
+fn test_record_compare_fox_pro_types() {
+    use dbase::{CompareTolerance, RecordCompare};
+
+    let fox_pro_record = FoxProRecord {
+        datetime: DateTime::new(Date::new(12, 2, 1999), Time::new(21, 20, 35)),
+        double: 8649.48851,
+        currency: 3489.9612314,
+        integer: 42069,
+    };
+
+    let mut record = Record::default();
+    record.insert(
+        "datetime".to_string(),
+        FieldValue::DateTime(fox_pro_record.datetime),
+    );
+    record.insert("double".to_string(), FieldValue::Double(8649.48851));
+    record.insert("currency".to_string(), FieldValue::Currency(3489.9612314));
+    record.insert("integer".to_string(), FieldValue::Integer(42069));
+    assert!(fox_pro_record.matches_record(&record, false, CompareTolerance::default()));
+
+    record.insert("double".to_string(), FieldValue::Double(8649.4885));
+    assert!(!fox_pro_record.matches_record(&record, false, CompareTolerance::default()));
+    let tolerance = CompareTolerance {
+        max_float_difference: 0.001,
+        ..CompareTolerance::default()
+    };
+    assert!(fox_pro_record.matches_record(&record, false, tolerance));
+
+    record.insert("integer".to_string(), FieldValue::Integer(0));
+    assert!(!fox_pro_record.matches_record(&record, false, CompareTolerance::default()));
+    record.insert("integer".to_string(), FieldValue::Integer(42070));
+    let tolerance = CompareTolerance {
+        max_float_difference: 1.0,
+        ..CompareTolerance::default()
+    };
+    assert!(fox_pro_record.matches_record(&record, false, tolerance));
+}
+
+dbase_record! {
+    struct Measure {
+        ratio: f32
+    }
+}
+
+#[test]
+fn test_record_compare_float_values() {
+    use dbase::{CompareTolerance, RecordCompare};
+
+    let measure = Measure { ratio: 0.1 };
+    // A value written as the text of a Float field can be read back slightly different
+    let record = Record::from_iter([("ratio", FieldValue::Float(Some(0.100_01)))]);
+    assert!(!measure.matches_record(&record, false, CompareTolerance::default()));
+    let tolerance = CompareTolerance {
+        max_float_difference: 0.001,
+        ..CompareTolerance::default()
+    };
+    assert!(measure.matches_record(&record, false, tolerance));
+
+    let record = Record::from_iter([("ratio", FieldValue::Float(Some(0.2)))]);
+    assert!(!measure.matches_record(&record, false, tolerance));
+}
+
+#[test]
+fn test_read_memo() -> Result<(), Box<dyn std::error::Error>> {
+    let records = dbase::read(MEMO_DBF)?;
+    assert_eq!(records.len(), 3);
+    assert_eq!(
+        records[0].get("NOTES"),
+        Some(&FieldValue::Memo("First memo".to_string()))
+    );
+    assert_eq!(
+        records[2].get("NOTES"),
+        Some(&FieldValue::Memo(String::new()))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_memo_file_with_uppercase_extension_is_found() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let dbf_path = dir.path().join("memo.dbf");
+    std::fs::copy(MEMO_DBF, &dbf_path)?;
+    std::fs::copy(MEMO_FPT, dir.path().join("memo.FPT"))?;
+
+    let records = Reader::from_path(&dbf_path)?.read()?;
+    assert_eq!(
+        records[0].get("NOTES"),
+        Some(&FieldValue::Memo("First memo".to_string()))
+    );
+
+    let mut file = dbase::File::open_read_only(&dbf_path)?;
+    let notes = file.record(0).unwrap().read_field(dbase::FieldIndex(1))?;
+    assert_eq!(notes, FieldValue::Memo("First memo".to_string()));
+    Ok(())
+}
+
+#[test]
+fn test_read_character_field_longer_than_255_bytes() -> Result<(), Box<dyn std::error::Error>> {
+    let long_text = format!("{} end", "A".repeat(300));
+
+    let mut reader = Reader::from_path(LONG_CHARACTER_DBF)?;
+    let info = &reader.fields()[1];
+    assert_eq!(info.name(), "LONGTEXT");
+    assert_eq!(info.length(), 500);
+    assert_eq!(info.num_decimal_places(), 0);
+    let records = reader.read()?;
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        records[0].get("LONGTEXT"),
+        Some(&FieldValue::Character(Some(long_text.clone())))
+    );
+    // The fields after the long one are at the right offset
+    assert_eq!(
+        records[0].get("COUNT"),
+        Some(&FieldValue::Numeric(Some(1.0)))
+    );
+    assert_eq!(
+        records[1].get("COUNT"),
+        Some(&FieldValue::Numeric(Some(42.0)))
+    );
+
+    let mut file = dbase::File::open_read_only(LONG_CHARACTER_DBF)?;
+    let mut record = file.record(0).unwrap();
+    assert_eq!(
+        record.read_field(dbase::FieldIndex(1))?,
+        FieldValue::Character(Some(long_text))
+    );
+    assert_eq!(record.read()?, records[0]);
+    Ok(())
+}
+
+#[test]
+fn test_character_decimal_count_outside_of_the_length() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::new()
+        .add_character_field("NAME".try_into()?, 10)
+        .add_numeric_field("COUNT".try_into()?, 5, 0)
+        .build_with_dest(&mut dst)?
+        .write_record(&Record::from_iter([
+            ("NAME", FieldValue::Character(Some("Yoshi".to_string()))),
+            ("COUNT", FieldValue::Numeric(Some(3.0))),
+        ]))?;
+    // A decimal count left in the descriptor of the Character field,
+    // the record size of the header is the one of a 10 bytes field
+    let mut bytes = dst.into_inner();
+    bytes[32 + 17] = 2;
+
+    let mut reader = Reader::new(Cursor::new(bytes))?;
+    assert_eq!(reader.fields()[0].length(), 10);
+    assert_eq!(reader.fields()[0].num_decimal_places(), 2);
+    let records = reader.read()?;
+    assert_eq!(
+        records[0].get("NAME"),
+        Some(&FieldValue::Character(Some("Yoshi".to_string())))
+    );
+    assert_eq!(
+        records[0].get("COUNT"),
+        Some(&FieldValue::Numeric(Some(3.0)))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_write_character_field_longer_than_255_bytes() -> Result<(), Box<dyn std::error::Error>> {
+    let records = dbase::read(LONG_CHARACTER_DBF)?;
+    let reader = Reader::from_path(LONG_CHARACTER_DBF)?;
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::from_reader(reader)
+        .build_with_dest(&mut dst)?
+        .write_records(&records)?;
+    // Same field descriptors
+    let fixture = std::fs::read(LONG_CHARACTER_DBF)?;
+    assert_eq!(dst.get_ref()[32..129], fixture[32..129]);
+    dst.set_position(0);
+    assert_eq!(Reader::new(dst)?.read()?, records);
+
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let writer = TableWriterBuilder::new()
+        .add_character_field_long("notes".try_into()?, 300)
+        .add_character_field("code".try_into()?, 4)
+        .build_with_dest(&mut dst)?;
+    let mut record = Record::default();
+    record.insert(
+        "notes".to_string(),
+        FieldValue::Character(Some("n".repeat(400))),
+    );
+    record.insert(
+        "code".to_string(),
+        FieldValue::Character(Some("AB12".to_string())),
+    );
+    writer.write_records(&[record])?;
+    dst.set_position(0);
+    let mut reader = Reader::new(dst)?;
+    assert_eq!(reader.fields()[0].length(), 300);
+    let records = reader.read()?;
+    // Values longer than the field are cut
+    assert_eq!(
+        records[0].get("notes"),
+        Some(&FieldValue::Character(Some("n".repeat(300))))
+    );
+    assert_eq!(
+        records[0].get("code"),
+        Some(&FieldValue::Character(Some("AB12".to_string())))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_required_memo_path() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(dbase::required_memo_path(STATIONS)?, None);
+    assert_eq!(
+        dbase::required_memo_path(MEMO_DBF)?,
+        Some(std::path::PathBuf::from("./tests/data/memo.fpt"))
+    );
+
+    let table_info = Reader::from_path(MEMO_DBF)?.into_table_info();
+    let memo_type = table_info.header().file_type.supported_memo_type();
+    assert_eq!(memo_type, Some(dbase::MemoFileType::FoxBaseMemo));
+    assert_eq!(memo_type.unwrap().extension(), "fpt");
+
+    // The memo file does not need to exist
+    let dir = tempfile::tempdir()?;
+    let dbf_path = dir.path().join("MEMO.DBF");
+    std::fs::copy(MEMO_DBF, &dbf_path)?;
+    assert_eq!(
+        dbase::required_memo_path(&dbf_path)?,
+        Some(dir.path().join("MEMO.FPT"))
+    );
+    // The existing one is returned, whatever the case of its extension
+    std::fs::copy(MEMO_FPT, dir.path().join("MEMO.fpt"))?;
+    assert_eq!(
+        dbase::required_memo_path(&dbf_path)?,
+        Some(dir.path().join("MEMO.fpt"))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_missing_memo_file_error_contains_path() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let dbf_path = dir.path().join("memo.dbf");
+    std::fs::copy(MEMO_DBF, &dbf_path)?;
+
+    let error = match Reader::from_path(&dbf_path) {
+        Ok(_) => panic!("The memo file does not exist"),
+        Err(error) => error,
+    };
+    match error.kind() {
+        dbase::ErrorKind::ErrorOpeningMemoFile { path, .. } => {
+            assert_eq!(path, &dir.path().join("memo.fpt"));
+        }
+        kind => panic!("Unexpected error kind: {}", kind),
+    }
+    assert!(error.to_string().contains("memo.fpt"));
+    Ok(())
+}
+
+#[test]
+fn test_record_map_operations() {
+    let mut record: Record = vec![
+        (
+            "name".to_string(),
+            FieldValue::Character(Some("Yoshi".to_string())),
+        ),
+        ("age".to_string(), FieldValue::Numeric(Some(32.0))),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(record.len(), 2);
+    assert!(!record.is_empty());
+    assert!(record.contains_key("name"));
+
+    assert!(record.rename_field("name", "nick".to_string()));
+    assert!(!record.rename_field("name", "other".to_string()));
+    assert!(!record.contains_key("name"));
+    assert_eq!(
+        record.get("nick"),
+        Some(&FieldValue::Character(Some("Yoshi".to_string())))
+    );
+
+    record.extend(vec![("alive".to_string(), FieldValue::Logical(Some(true)))]);
+    let mut keys = record.keys().collect::<Vec<_>>();
+    keys.sort_unstable();
+    assert_eq!(keys, vec!["age", "alive", "nick"]);
+    assert_eq!(record.values().count(), 3);
+
+    assert_eq!(record.remove("age"), Some(FieldValue::Numeric(Some(32.0))));
+    assert_eq!(record.len(), 2);
+}
+
+#[test]
+fn test_writing_record_with_removed_field_fails() {
+    let mut records = dbase::read(STATIONS).unwrap();
+    records[0].remove("line");
+
+    let reader = Reader::from_path(STATIONS).unwrap();
+    let writer = TableWriterBuilder::from_reader(reader)
+        .build_with_dest(Cursor::new(Vec::new()))
+        .unwrap();
+    let error = writer
+        .write_records(&records[..1])
+        .expect_err("The 'line' field is missing");
+    match error.kind() {
+        dbase::ErrorKind::Message(msg) => {
+            assert_eq!(msg, "Could not find field named 'line' in the record map")
+        }
+        kind => panic!("Unexpected error kind: {}", kind),
+    }
+}
+
+#[test]
+fn test_writing_record_with_renamed_field() {
+    let mut records = dbase::read(LINE_DBF).unwrap();
+    assert!(records[0].rename_field("name", "label".to_string()));
+
+    let writer = TableWriterBuilder::new()
+        .add_character_field("label".try_into().unwrap(), 20)
+        .build_with_dest(Cursor::new(Vec::new()))
+        .unwrap();
+    writer.write_records(&records).unwrap();
+}
+
+/// Builds the bytes of a dBase III file with character fields,
+/// without going through the writer (which refuses odd field definitions)
+fn synthetic_dbf(fields: &[(&str, u8)], records: &[&str]) -> Cursor<Vec<u8>> {
+    let header_size = 32 + fields.len() * 32 + 1;
+    let record_size = 1 + fields.iter().map(|(_, len)| *len as usize).sum::<usize>();
+
+    let mut bytes = vec![0x03, 124, 1, 1];
+    bytes.extend_from_slice(&(records.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&(header_size as u16).to_le_bytes());
+    bytes.extend_from_slice(&(record_size as u16).to_le_bytes());
+    bytes.resize(32, 0);
+
+    for (name, length) in fields {
+        let mut descriptor = [0u8; 32];
+        descriptor[..name.len()].copy_from_slice(name.as_bytes());
+        descriptor[11] = b'C';
+        descriptor[16] = *length;
+        bytes.extend_from_slice(&descriptor);
+    }
+    bytes.push(0x0D);
+
+    for record in records {
+        assert_eq!(record.len(), record_size - 1);
+        bytes.push(b' ');
+        bytes.extend_from_slice(record.as_bytes());
+    }
+    bytes.push(0x1A);
+
+    Cursor::new(bytes)
+}
+
+#[test]
+fn test_schema_warnings_duplicate_field_names() {
+    let source = synthetic_dbf(&[("NAME", 5), ("name", 4)], &["alphabeta"]);
+    let reader = Reader::new(source).unwrap();
+
+    assert_eq!(
+        reader.schema_warnings(),
+        &[dbase::SchemaWarning::DuplicateFieldName {
+            index: 1,
+            first_index: 0,
+            name: "name".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_schema_warnings_zero_length_field() {
+    let source = synthetic_dbf(&[("EMPTY", 0), ("CODE", 3)], &["abc"]);
+    let mut reader = Reader::new(source).unwrap();
+
+    assert_eq!(
+        reader.schema_warnings(),
+        &[dbase::SchemaWarning::ZeroLengthField {
+            index: 0,
+            name: "EMPTY".to_string(),
+        }]
+    );
+
+    let records = reader.read().unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].get("EMPTY"), Some(&FieldValue::Character(None)));
+    assert_eq!(
+        records[0].get("CODE"),
+        Some(&FieldValue::Character(Some("abc".to_string())))
+    );
+}
+
+/// Changes the offset to the first record of the bytes made by [synthetic_dbf]
+/// by `delta`, after `bytes` were inserted or removed before the records
+fn shift_offset_to_first_record(bytes: &mut [u8], delta: i16) {
+    let offset = u16::from_le_bytes([bytes[8], bytes[9]]);
+    let offset = offset.checked_add_signed(delta).unwrap();
+    bytes[8..10].copy_from_slice(&offset.to_le_bytes());
+}
+
+#[test]
+fn test_read_descriptors_without_terminator() {
+    let fields = [("NAME", 5), ("CODE", 3)];
+    let mut bytes = synthetic_dbf(&fields, &["alphaabc", "beta xyz"]).into_inner();
+    assert_eq!(bytes.remove(32 + 2 * 32), 0x0D);
+    shift_offset_to_first_record(&mut bytes, -1);
+
+    let mut reader = Reader::new(Cursor::new(bytes.clone())).unwrap();
+    assert_eq!(
+        reader.schema_warnings(),
+        &[dbase::SchemaWarning::MissingTerminator { num_descriptors: 2 }]
+    );
+    let records = reader.read().unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        records[1].get("NAME"),
+        Some(&FieldValue::Character(Some("beta".to_string())))
+    );
+    assert_eq!(
+        records[1].get("CODE"),
+        Some(&FieldValue::Character(Some("xyz".to_string())))
+    );
+
+    let mut file = dbase::File::open(Cursor::new(bytes)).unwrap();
+    assert_eq!(file.fields().len(), 2);
+    assert_eq!(file.schema_warnings(), reader.schema_warnings());
+    assert_eq!(file.record(1).unwrap().read().unwrap(), records[1]);
+}
+
+#[test]
+fn test_read_descriptors_followed_by_empty_descriptor() {
+    let fields = [("NAME", 5), ("CODE", 3)];
+    let mut bytes = synthetic_dbf(&fields, &["alphaabc", "beta xyz"]).into_inner();
+    let terminator_position = 32 + 2 * 32;
+    bytes.splice(terminator_position..terminator_position, [0u8; 32]);
+    shift_offset_to_first_record(&mut bytes, 32);
+
+    let mut reader = Reader::new(Cursor::new(bytes.clone())).unwrap();
+    assert_eq!(
+        reader.schema_warnings(),
+        &[dbase::SchemaWarning::InvalidDescriptor {
+            descriptor_index: 2,
+            type_byte: 0
+        }]
+    );
+    assert_eq!(reader.fields().len(), 2);
+    let records = reader.read().unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        records[0].get("NAME"),
+        Some(&FieldValue::Character(Some("alpha".to_string())))
+    );
+    assert_eq!(
+        records[0].get("CODE"),
+        Some(&FieldValue::Character(Some("abc".to_string())))
+    );
+
+    let mut file = dbase::File::open(Cursor::new(bytes)).unwrap();
+    assert_eq!(file.fields().len(), 2);
+    assert_eq!(file.schema_warnings(), reader.schema_warnings());
+    assert_eq!(file.record(0).unwrap().read().unwrap(), records[0]);
+}
+
+#[test]
+fn test_read_record_with_duplicated_field_names() {
+    let source = synthetic_dbf(&[("NAME", 5), ("NAME", 4), ("NAME", 2)], &["alphabetaxy"]);
+    let mut reader = Reader::new(source).unwrap();
+    assert_eq!(reader.schema_warnings().len(), 2);
+
+    let records = reader.read().unwrap();
+    assert_eq!(records[0].len(), 3);
+    assert_eq!(
+        records[0].get("NAME"),
+        Some(&FieldValue::Character(Some("alpha".to_string())))
+    );
+    assert_eq!(
+        records[0].get("NAME_2"),
+        Some(&FieldValue::Character(Some("beta".to_string())))
+    );
+    assert_eq!(
+        records[0].get("NAME_3"),
+        Some(&FieldValue::Character(Some("xy".to_string())))
+    );
+
+    // The suffixed name of a duplicate is not the name of another field
+    let source = synthetic_dbf(&[("NAME", 5), ("NAME", 4), ("NAME_2", 2)], &["alphabetaxy"]);
+    let records = Reader::new(source).unwrap().read().unwrap();
+    assert_eq!(records[0].len(), 3);
+    assert_eq!(
+        records[0].get("NAME_2"),
+        Some(&FieldValue::Character(Some("xy".to_string())))
+    );
+    assert_eq!(
+        records[0].get("NAME_3"),
+        Some(&FieldValue::Character(Some("beta".to_string())))
+    );
+}
+
+#[test]
+fn test_builder_rejects_invalid_fields() {
+    let result = TableWriterBuilder::new()
+        .add_character_field("Name".try_into().unwrap(), 10)
+        .add_numeric_field("NAME".try_into().unwrap(), 10, 2)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    match result {
+        Err(error) => {
+            assert!(matches!(error.kind(), dbase::ErrorKind::DuplicateFieldName));
+            assert_eq!(error.field().as_ref().map(|f| f.name()), Some("NAME"));
+        }
+        Ok(_) => panic!("Duplicated field names should be rejected"),
+    }
+
+    let result = TableWriterBuilder::new()
+        .add_character_field("Name".try_into().unwrap(), 0)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    match result {
+        Err(error) => assert!(matches!(error.kind(), dbase::ErrorKind::ZeroLengthField)),
+        Ok(_) => panic!("Zero length fields should be rejected"),
+    }
+
+    let mut builder = TableWriterBuilder::new();
+    for i in 0..257 {
+        let name = format!("F{}", i);
+        builder = builder.add_character_field(name.as_str().try_into().unwrap(), 255);
+    }
+    match builder.build_table_info() {
+        Err(error) => {
+            assert!(matches!(error.kind(), dbase::ErrorKind::RecordTooLarge));
+            assert_eq!(error.field().as_ref().map(|f| f.name()), Some("F256"));
+        }
+        Ok(_) => panic!("Records larger than u16::MAX should be rejected"),
+    }
+}
+
+#[test]
+fn test_builder_enforces_version_limits() {
+    let mut builder = TableWriterBuilder::new();
+    assert_eq!(
+        builder.remaining_record_capacity(),
+        dbase::limits::MAX_RECORD_SIZE - 1
+    );
+    let mut num_fields = 0;
+    while builder.remaining_record_capacity() >= 255 {
+        let name = format!("F{}", num_fields);
+        builder = builder.add_character_field(name.as_str().try_into().unwrap(), 255);
+        num_fields += 1;
+    }
+    assert_eq!(num_fields, 256);
+    let result = builder
+        .add_character_field("LAST".try_into().unwrap(), 255)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    match result {
+        Err(error) => assert!(matches!(error.kind(), dbase::ErrorKind::RecordTooLarge)),
+        Ok(_) => panic!("Records larger than u16::MAX should be rejected"),
+    }
+
+    // Fits in a record, but dBase III tables cannot have that many fields
+    let mut builder = TableWriterBuilder::new();
+    for i in 0..=dbase::limits::DBASE3_MAX_FIELDS {
+        let name = format!("F{}", i);
+        builder = builder.add_character_field(name.as_str().try_into().unwrap(), 1);
+    }
+    match builder.build_table_info() {
+        Err(error) => assert!(matches!(
+            error.kind(),
+            dbase::ErrorKind::FieldCountExceeded {
+                max: dbase::limits::DBASE3_MAX_FIELDS
+            }
+        )),
+        Ok(_) => panic!("dBase III tables with more than 128 fields should be rejected"),
+    }
+
+    // FoxPro tables can
+    let mut builder = TableWriterBuilder::new().add_integer_field("ID".try_into().unwrap());
+    for i in 0..dbase::limits::DBASE3_MAX_FIELDS {
+        let name = format!("F{}", i);
+        builder = builder.add_character_field(name.as_str().try_into().unwrap(), 1);
+    }
+    let table_info = builder.build_table_info().unwrap();
+    let file = dbase::File::create_new(Cursor::new(Vec::<u8>::new()), table_info).unwrap();
+    assert_eq!(file.fields().len(), dbase::limits::DBASE3_MAX_FIELDS + 1);
+}
+
+/// Reads everything there is to read, returning the first error
+fn read_whole_source(data: &[u8]) -> Result<(), dbase::Error> {
+    let mut reader = Reader::new(Cursor::new(data))?;
+    reader.read()?;
+
+    let mut file = dbase::File::open(Cursor::new(data))?;
+    let num_fields = file.fields().len();
+    for index in 0..file.num_records() {
+        // Records after the file terminator do not exist
+        let Some(mut record) = file.record(index) else {
+            break;
+        };
+        for field_index in 0..num_fields {
+            record
+                .field(dbase::FieldIndex(field_index))
+                .unwrap()
+                .read()?;
+        }
+    }
+    Ok(())
+}
+
+/// Inputs that made the reading panic, found by fuzzing,
+/// and whether reading them must result in an error
+const FUZZ_INPUTS: [(&str, bool); 6] = [
+    // The header claims records that are not there, but there are no fields to read
+    ("bad_terminator.dbf", false),
+    // The julian day number is absurd, but it still gives a date
+    ("datetime_overflow.dbf", false),
+    ("double_field_too_short.dbf", true),
+    ("memo_index_too_short.dbf", true),
+    ("offset_too_small.dbf", true),
+    ("vfp_offset_too_small.dbf", true),
+];
+
+#[test]
+fn test_replay_fuzz_inputs() {
+    for (name, is_err) in FUZZ_INPUTS {
+        let path = format!("tests/data/fuzz/{}", name);
+        let data = std::fs::read(&path).unwrap();
+        match std::panic::catch_unwind(|| read_whole_source(&data)) {
+            Ok(result) => assert_eq!(result.is_err(), is_err, "{}", path),
+            Err(_) => panic!("{} made the reading panic", path),
+        }
+    }
+}
+
+#[test]
+fn test_memo_length_larger_than_memo_file() {
+    let mut memo = std::fs::read(MEMO_FPT).unwrap();
+    // The first record's memo is in block 8 (blocks are 64 bytes),
+    // its length is the big endian u32 after the block type
+    memo[8 * 64 + 4..8 * 64 + 8].copy_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF]);
+
+    let mut reader = dbase::ReaderBuilder::<_, dbase::UnicodeLossy>::new(Cursor::new(
+        std::fs::read(MEMO_DBF).unwrap(),
+    ))
+    .with_memo(Cursor::new(memo))
+    .build()
+    .unwrap();
+    let error = reader.read().unwrap_err();
+    assert_eq!(error.record_num(), 0);
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::CorruptMemo {
+            index: 8,
+            length: 0xFFFF_FFFF,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_select_fields() {
+    let mut reader = Reader::from_path(STATIONS).unwrap();
+    reader.select_fields(&["name", "LINE"]).unwrap();
+    let records = reader.read().unwrap();
+    assert_eq!(records.len(), 86);
+
+    let first = &records[0];
+    assert_eq!(first.len(), 2);
+    assert_eq!(
+        first.get("name"),
+        Some(&FieldValue::Character(Some("Van Dorn Street".to_string())))
+    );
+    assert_eq!(
+        first.get("line"),
+        Some(&FieldValue::Character(Some("blue".to_string())))
+    );
+    assert!(first.get("marker-col").is_none());
+    assert!(first.get("marker-sym").is_none());
+
+    // Typed reads still get every field
+    reader.seek(0).unwrap();
+    let stations = reader.read_as::<StationRecord>().unwrap();
+    assert_eq!(stations[0].marker_col, "#0000ff");
+
+    reader.clear_selected_fields();
+    reader.seek(0).unwrap();
+    assert_eq!(reader.read().unwrap()[0].len(), 4);
+
+    assert!(reader.select_fields(&["name", "not a field"]).is_err());
+    reader.seek(0).unwrap();
+    assert_eq!(reader.read().unwrap()[0].len(), 4);
+}
+
+#[test]
+fn test_select_memo_field() {
+    let mut reader = Reader::from_path(MEMO_DBF).unwrap();
+    reader.select_fields(&["notes"]).unwrap();
+    let records = reader.read().unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].len(), 1);
+    assert_eq!(
+        records[0].get("NOTES"),
+        Some(&FieldValue::Memo("First memo".to_string()))
+    );
+}
+
+fn write_names_with_auto_code_page(builder: TableWriterBuilder, names: &[&str]) -> Cursor<Vec<u8>> {
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let records = names
+        .iter()
+        .map(|name| {
+            let mut record = Record::default();
+            record.insert("NAME".to_string(), name.to_string().into());
+            record
+        })
+        .collect::<Vec<_>>();
+    let writer = builder
+        .auto_code_page(true)
+        .add_character_field("NAME".try_into().unwrap(), 20)
+        .build_with_dest(&mut cursor)
+        .unwrap();
+    writer.write_records(&records).unwrap();
+    cursor.set_position(0);
+    cursor
+}
+
+#[test]
+fn test_auto_code_page_ascii_stays_undefined() {
+    let cursor = write_names_with_auto_code_page(TableWriterBuilder::new(), &["Yoshi", "Mario"]);
+    let reader = Reader::new(cursor).unwrap();
+    assert_eq!(
+        reader.header().code_page_mark,
+        dbase::CodePageMark::Undefined
+    );
+
+    let cursor = write_names_with_auto_code_page(TableWriterBuilder::new(), &["Yoshi", "Crème"]);
+    let reader = Reader::new(cursor).unwrap();
+    assert_eq!(reader.header().code_page_mark, dbase::CodePageMark::Utf8);
+}
+
+#[test]
+fn test_code_page_mark_override() {
+    let builder = TableWriterBuilder::with_encoding(dbase::encoding::Ascii)
+        .set_code_page_mark(dbase::CodePageMark::CP1252);
+    let cursor = write_names_with_auto_code_page(builder, &["Yoshi", "Mario"]);
+    assert_eq!(cursor.get_ref()[29], 0x03);
+    let mut reader = Reader::new_with_encoding(cursor, dbase::encoding::Ascii).unwrap();
+    assert_eq!(reader.header().code_page_mark, dbase::CodePageMark::CP1252);
+    let records = reader.read().unwrap();
+    assert_eq!(
+        records[1].get("NAME"),
+        Some(&FieldValue::Character(Some("Mario".to_string())))
+    );
+
+    let mut table_info = reader.into_table_info();
+    table_info.set_code_page_mark(dbase::CodePageMark::CP437);
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let writer = TableWriterBuilder::from_table_info(table_info)
+        .build_with_dest(&mut cursor)
+        .unwrap();
+    writer.write_records(&records).unwrap();
+    assert_eq!(cursor.get_ref()[29], dbase::CodePageMark::CP437.as_byte());
+    assert_eq!(
+        dbase::CodePageMark::from_byte(0x03),
+        dbase::CodePageMark::CP1252
+    );
+}
+
+#[cfg(feature = "yore")]
+#[test]
+fn test_auto_code_page_with_cp1252() {
+    let builder = TableWriterBuilder::with_encoding(yore::code_pages::CP1252);
+    let cursor = write_names_with_auto_code_page(builder, &["Yoshi", "Crème brûlée"]);
+    let mut reader = Reader::new_with_encoding(cursor, yore::code_pages::CP1252).unwrap();
+    assert_eq!(reader.header().code_page_mark, dbase::CodePageMark::CP1252);
+    let records = reader.read().unwrap();
+    assert_eq!(
+        records[1].get("NAME"),
+        Some(&FieldValue::Character(Some("Crème brûlée".to_string())))
+    );
+
+    let builder = TableWriterBuilder::with_encoding(yore::code_pages::CP1252);
+    let cursor = write_names_with_auto_code_page(builder, &["Yoshi"]);
+    let reader = Reader::new_with_encoding(cursor, yore::code_pages::CP1252).unwrap();
+    assert_eq!(
+        reader.header().code_page_mark,
+        dbase::CodePageMark::Undefined
+    );
+}
+
+#[cfg(feature = "yore")]
+#[test]
+fn test_validate_encodable_reports_record_and_field() {
+    let mut writer = TableWriterBuilder::with_encoding(yore::code_pages::CP1252)
+        .add_character_field("ID".try_into().unwrap(), 5)
+        .add_character_field("NAME".try_into().unwrap(), 20)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()))
+        .unwrap();
+
+    let records = ["Crème", "東京"]
+        .iter()
+        .map(|name| {
+            let mut record = Record::default();
+            record.insert("ID".to_string(), "1".to_string().into());
+            record.insert("NAME".to_string(), name.to_string().into());
+            record
+        })
+        .collect::<Vec<_>>();
+
+    let error = writer.validate_encodable(&records).unwrap_err();
+    assert_eq!(error.record_num(), 1);
+    assert_eq!(error.field().as_ref().unwrap().name(), "NAME");
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::StringEncodeError(_)
+    ));
+
+    assert!(writer.validate_encodable(&records[..1]).is_ok());
+}
+
+#[test]
+fn test_validate_records_reports_issues_without_writing() {
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("NAME".try_into().unwrap(), 6)
+        .add_numeric_field("SIZE".try_into().unwrap(), 5, 1)
+        .build_with_dest(&mut dst)
+        .unwrap();
+
+    let record = |name: &str, size: FieldValue| {
+        Record::from_iter([
+            ("NAME", FieldValue::Character(Some(name.to_string()))),
+            ("SIZE", size),
+        ])
+    };
+    let mut records = (0..10)
+        .map(|i| record("Yoshi", FieldValue::Numeric(Some(f64::from(i)))))
+        .collect::<Vec<_>>();
+    records[4] = record("Mario", FieldValue::Logical(Some(true)));
+    records[7] = record("Donkey Kong", FieldValue::Numeric(Some(1.0)));
+
+    let report = writer.validate_records(&records).unwrap();
+    assert_eq!(report.num_records(), 10);
+    assert!(!report.is_valid());
+    assert!(!report.reached_limit());
+    let issues = report.issues();
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].record_num(), 4);
+    assert_eq!(issues[0].field_name(), Some("SIZE"));
+    match &issues[0] {
+        dbase::ValidationIssue::Error(error) => {
+            assert!(matches!(error.kind(), dbase::ErrorKind::IncompatibleType))
+        }
+        issue => panic!("Unexpected issue: {}", issue),
+    }
+    assert_eq!(issues[1].record_num(), 7);
+    assert!(matches!(
+        issues[1],
+        dbase::ValidationIssue::Truncated {
+            value_length: 11,
+            field_length: 6,
+            ..
+        }
+    ));
+
+    let report = writer.validate_records_with_limit(&records, 1).unwrap();
+    assert_eq!(report.issues().len(), 1);
+    assert!(report.reached_limit());
+    assert_eq!(report.num_records(), 5);
+
+    assert!(writer.validate_records(&records[..4]).unwrap().is_valid());
+
+    // Nothing was written, not even the header
+    assert!(writer.get_ref().get_ref().is_empty());
+    writer.write_record(&records[0]).unwrap();
+    drop(writer);
+    dst.set_position(0);
+    assert_eq!(Reader::new(dst).unwrap().read().unwrap(), records[..1]);
+}
+
+#[test]
+fn test_read_dbase2_file() -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = Reader::from_path(DBASE2_DBF)?;
+    assert!(matches!(reader.header().file_type, dbase::Version::DBase2));
+    assert_eq!(reader.header().num_records, 3);
+
+    let fields = reader
+        .fields()
+        .iter()
+        .map(|info| (info.name(), info.field_type(), info.length()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        fields,
+        vec![
+            ("NAME", dbase::FieldType::Character, 10),
+            ("AGE", dbase::FieldType::Numeric, 3),
+            ("BALANCE", dbase::FieldType::Numeric, 7),
+            ("ACTIVE", dbase::FieldType::Logical, 1),
+        ]
+    );
+
+    // The second record is deleted
+    let records = reader.read()?;
+    assert_eq!(records.len(), 2);
+
+    let mut expected = Record::default();
+    expected.insert("NAME".to_string(), "Alice".to_string().into());
+    expected.insert("AGE".to_string(), FieldValue::Numeric(Some(34.0)));
+    expected.insert("BALANCE".to_string(), FieldValue::Numeric(Some(1024.5)));
+    expected.insert("ACTIVE".to_string(), FieldValue::Logical(Some(true)));
+    assert_eq!(records[0], expected);
+
+    let mut expected = Record::default();
+    expected.insert("NAME".to_string(), "Charlie".to_string().into());
+    expected.insert("AGE".to_string(), FieldValue::Numeric(Some(51.0)));
+    expected.insert("BALANCE".to_string(), FieldValue::Numeric(None));
+    expected.insert("ACTIVE".to_string(), FieldValue::Logical(None));
+    assert_eq!(records[1], expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_dbase2_file_cannot_be_appended_to() -> Result<(), Box<dyn std::error::Error>> {
+    let mut data = Cursor::new(std::fs::read(DBASE2_DBF)?);
+    let mut file = dbase::File::open(&mut data)?;
+    let record = file.record(0).unwrap().read()?;
+    assert_eq!(
+        record.get("NAME"),
+        Some(&FieldValue::Character(Some("Alice".to_string())))
+    );
+    assert!(file.append_record(&record).is_err());
+    Ok(())
+}
+
+fn incompatibility_test_fields() -> Vec<dbase::FieldInfo> {
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let writer = TableWriterBuilder::new()
+        .add_character_field("NAME".try_into().unwrap(), 5)
+        .add_numeric_field("AGE".try_into().unwrap(), 3, 0)
+        .add_logical_field("ACTIVE".try_into().unwrap())
+        .build_with_dest(&mut cursor)
+        .unwrap();
+    writer.write_records(&Vec::<Record>::new()).unwrap();
+    cursor.set_position(0);
+    Reader::new(cursor).unwrap().fields().to_vec()
+}
+
+#[test]
+fn test_check_compatibility() {
+    use dbase::schema::{check_compatibility, Incompatibility};
+
+    let fields = incompatibility_test_fields();
+
+    let mut record = Record::default();
+    record.insert("NAME".to_string(), "Yoshi".to_string().into());
+    record.insert("AGE".to_string(), FieldValue::Numeric(Some(32.0)));
+    record.insert("ACTIVE".to_string(), FieldValue::Logical(Some(true)));
+    assert!(check_compatibility(&fields, &record).is_empty());
+
+    let mut incompatible = record.clone();
+    incompatible.remove("ACTIVE");
+    assert_eq!(
+        check_compatibility(&fields, &incompatible),
+        vec![Incompatibility::MissingField("ACTIVE".to_string())]
+    );
+
+    let mut incompatible = record.clone();
+    incompatible.insert("COLOR".to_string(), "green".to_string().into());
+    assert_eq!(
+        check_compatibility(&fields, &incompatible),
+        vec![Incompatibility::ExtraField("COLOR".to_string())]
+    );
+
+    let mut incompatible = record.clone();
+    incompatible.insert("AGE".to_string(), "32".to_string().into());
+    assert_eq!(
+        check_compatibility(&fields, &incompatible),
+        vec![Incompatibility::TypeMismatch {
+            name: "AGE".to_string(),
+            expected: dbase::FieldType::Numeric,
+            actual: dbase::FieldType::Character,
+        }]
+    );
+
+    let mut incompatible = record.clone();
+    incompatible.insert("NAME".to_string(), "Mario Bros".to_string().into());
+    incompatible.insert("AGE".to_string(), FieldValue::Numeric(Some(1234.0)));
+    assert_eq!(
+        check_compatibility(&fields, &incompatible),
+        vec![
+            Incompatibility::ValueTooLong {
+                name: "NAME".to_string(),
+                max: 5,
+                actual: 10,
+            },
+            Incompatibility::ValueTooLong {
+                name: "AGE".to_string(),
+                max: 3,
+                actual: 4,
+            }
+        ]
+    );
+}
+
+#[test]
+fn test_precheck_records_when_writing() -> Result<(), Box<dyn std::error::Error>> {
+    let mut record = Record::default();
+    record.insert("NAME".to_string(), "Mario Bros".to_string().into());
+    record.insert("AGE".to_string(), FieldValue::Numeric(Some(32.0)));
+    record.insert("ACTIVE".to_string(), FieldValue::Logical(Some(true)));
+
+    let builder = || {
+        TableWriterBuilder::new()
+            .add_character_field("NAME".try_into().unwrap(), 5)
+            .add_numeric_field("AGE".try_into().unwrap(), 3, 0)
+            .add_logical_field("ACTIVE".try_into().unwrap())
+    };
+
+    // Without the check, the name is truncated
+    let mut writer = builder().build_with_dest(Cursor::new(Vec::<u8>::new()))?;
+    writer.write_record(&record)?;
+
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let mut writer = builder()
+        .precheck_records(true)
+        .build_with_dest(&mut cursor)?;
+    let error = writer.write_record(&record).unwrap_err();
+    match error.kind() {
+        dbase::ErrorKind::IncompatibleRecord(dbase::schema::Incompatibility::ValueTooLong {
+            name,
+            ..
+        }) => assert_eq!(name, "NAME"),
+        kind => panic!("Unexpected error {}", kind),
+    }
+    assert_eq!(error.field().as_ref().unwrap().name(), "NAME");
+    assert!(error.to_string().contains("10 bytes long"));
+
+    record.insert("NAME".to_string(), "Mario".to_string().into());
+    writer.write_record(&record)?;
+    drop(writer);
+
+    cursor.set_position(0);
+    let mut file = dbase::File::open(cursor)?;
+    file.set_precheck_records(true);
+    record.insert("EXTRA".to_string(), FieldValue::Logical(None));
+    let error = file.append_record(&record).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::IncompatibleRecord(dbase::schema::Incompatibility::ExtraField(_))
+    ));
+    assert!(error.field().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_records_share_field_names() -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = Reader::from_path(STATIONS)?;
+    let records = reader.read()?;
+    let (first, second) = (&records[0], &records[1]);
+
+    for name in first.as_ref().keys() {
+        let (other_name, _) = second.as_ref().get_key_value(name).unwrap();
+        assert!(std::sync::Arc::ptr_eq(name, other_name));
+    }
+    assert_eq!(
+        first.get("name"),
+        Some(&FieldValue::Character(Some("Van Dorn Street".to_string())))
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "serde"))]
+#[test]
+fn test_write_hash_map_and_read_btree_map() -> Result<(), Box<dyn std::error::Error>> {
+    use std::collections::{BTreeMap, HashMap};
+
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("name".try_into().unwrap(), 20)
+        .add_numeric_field("age".try_into().unwrap(), 3, 0)
+        .add_logical_field("active".try_into().unwrap())
+        .build_with_dest(&mut dst)?;
+
+    let mut row = HashMap::new();
+    row.insert(
+        "name".to_string(),
+        FieldValue::Character(Some("Yoshi".to_string())),
+    );
+    row.insert("active".to_string(), FieldValue::Logical(Some(true)));
+    row.insert("age".to_string(), FieldValue::Numeric(Some(32.0)));
+    writer.write_record(&row)?;
+
+    let mut incomplete = row.clone();
+    incomplete.remove("age");
+    let error = writer.write_record(&incomplete).unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::Message(_)));
+    drop(writer);
+    dst.set_position(0);
+
+    let rows = Reader::new(dst)?.read_as::<BTreeMap<String, FieldValue>>()?;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0], row.into_iter().collect::<BTreeMap<_, _>>());
+    Ok(())
+}
+
+#[cfg(not(feature = "serde"))]
+#[test]
+fn test_write_and_read_vec_of_values() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("name".try_into().unwrap(), 20)
+        .add_numeric_field("age".try_into().unwrap(), 3, 0)
+        .add_logical_field("active".try_into().unwrap())
+        .add_date_field("joined".try_into().unwrap())
+        .build_with_dest(&mut dst)?;
+
+    let row = vec![
+        FieldValue::Character(Some("Yoshi".to_string())),
+        FieldValue::Numeric(Some(32.0)),
+        FieldValue::Logical(Some(true)),
+        FieldValue::Date(Some(Date::new(1, 2, 2003))),
+    ];
+    writer.write_record(&row)?;
+    writer.write_record(&("Mario".to_string(), 40.0, false, Date::new(4, 5, 2006)))?;
+
+    let error = writer.write_record(&row[..2]).unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::NotEnoughFields));
+    assert_eq!(error.field().as_ref().unwrap().name(), "active");
+    let mut too_long = row.clone();
+    too_long.push(FieldValue::Numeric(None));
+    let error = writer.write_record(&too_long).unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::TooManyFields));
+    let error = writer
+        .write_record(&("Luigi".to_string(), 40.0))
+        .unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::NotEnoughFields));
+
+    writer.close()?;
+    drop(writer);
+    dst.set_position(0);
+
+    let mut reader = Reader::new(dst.clone())?;
+    let rows = reader.read_as::<Vec<FieldValue>>()?;
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0], row);
+    assert_eq!(rows[1][0], FieldValue::Character(Some("Mario".to_string())));
+
+    let mut bytes = dst.get_ref().clone();
+    let header_size = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+    bytes[header_size + 21..header_size + 24].copy_from_slice(b"abc");
+    let error = Reader::new(Cursor::new(bytes))?
+        .read_as::<Vec<FieldValue>>()
+        .unwrap_err();
+    assert_eq!(error.field_index(), Some(dbase::FieldIndex(1)));
+
+    dst.set_position(0);
+    let records = Reader::new(dst)?.read()?;
+    assert_eq!(
+        records[0].get("age"),
+        Some(&FieldValue::Numeric(Some(32.0)))
+    );
+    assert_eq!(
+        records[1].get("active"),
+        Some(&FieldValue::Logical(Some(false)))
+    );
+    Ok(())
+}
+
+/// Writes a table with fields `name`, `flags`, `age` and `nullflags`,
+/// and changes the type of `flags` to 'V' and of `nullflags` to 'G'
+fn table_with_unknown_field_types() -> Cursor<Vec<u8>> {
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("name".try_into().unwrap(), 10)
+        .add_character_field("flags".try_into().unwrap(), 2)
+        .add_numeric_field("age".try_into().unwrap(), 3, 0)
+        .add_character_field("nullflags".try_into().unwrap(), 1)
+        .build_with_dest(&mut dst)
+        .unwrap();
+    for (name, age) in [("Yoshi", 32.0), ("Mario", 40.0)] {
+        let mut record = Record::default();
+        record.insert("name".to_string(), FieldValue::from(name.to_string()));
+        record.insert("flags".to_string(), FieldValue::from("ab".to_string()));
+        record.insert("age".to_string(), FieldValue::Numeric(Some(age)));
+        record.insert("nullflags".to_string(), FieldValue::from("x".to_string()));
+        writer.write_record(&record).unwrap();
+    }
+    writer.close().unwrap();
+    drop(writer);
+
+    let mut bytes = dst.into_inner();
+    // type byte of the 2nd and 4th field descriptors
+    bytes[32 + 32 + 11] = b'V';
+    bytes[32 + 3 * 32 + 11] = b'G';
+    Cursor::new(bytes)
+}
+
+#[test]
+fn test_unknown_field_types() -> Result<(), Box<dyn std::error::Error>> {
+    let error = Reader::new(table_with_unknown_field_types()).err().unwrap();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::InvalidFieldType('V')
+    ));
+
+    let options = dbase::ReadingOptions::default()
+        .on_unknown_field_type(dbase::UnknownFieldTypeHandling::TreatAsCharacter);
+    let mut reader = dbase::ReaderBuilder::new(table_with_unknown_field_types())
+        .with_options(options)
+        .with_encoding(dbase::encoding::UnicodeLossy)
+        .build()?;
+    assert_eq!(reader.fields().len(), 4);
+    assert_eq!(reader.fields()[1].field_type(), dbase::FieldType::Character);
+    let records = reader.read()?;
+    assert_eq!(
+        records[0].get("flags"),
+        Some(&FieldValue::from("ab".to_string()))
+    );
+    assert_eq!(
+        records[1].get("age"),
+        Some(&FieldValue::Numeric(Some(40.0)))
+    );
+    assert_eq!(
+        records[1].get("nullflags"),
+        Some(&FieldValue::from("x".to_string()))
+    );
+
+    let options = dbase::ReadingOptions::default()
+        .on_unknown_field_type(dbase::UnknownFieldTypeHandling::Skip);
+    let mut reader = dbase::ReaderBuilder::new(table_with_unknown_field_types())
+        .with_options(options)
+        .with_encoding(dbase::encoding::UnicodeLossy)
+        .build()?;
+    let names = reader.fields().iter().map(|f| f.name()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["name", "age"]);
+    let records = reader.read()?;
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].len(), 2);
+    assert_eq!(
+        records[0].get("name"),
+        Some(&FieldValue::from("Yoshi".to_string()))
+    );
+    assert_eq!(
+        records[0].get("age"),
+        Some(&FieldValue::Numeric(Some(32.0)))
+    );
+    assert_eq!(
+        records[1].get("name"),
+        Some(&FieldValue::from("Mario".to_string()))
+    );
+    assert_eq!(
+        records[1].get("age"),
+        Some(&FieldValue::Numeric(Some(40.0)))
+    );
+
+    reader.seek(1)?;
+    let second = reader.iter_records().next().unwrap()?;
+    assert_eq!(second, records[1]);
+    Ok(())
+}
+
+/// Writes `value` in a Character field of `length` bytes and reads it back
+fn write_and_read_back_character<E>(value: &str, length: u8, encoding: E) -> String
+where
+    E: dbase::encoding::Encoding + Clone + 'static,
+{
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::with_encoding(encoding.clone())
+        .add_character_field("text".try_into().unwrap(), length)
+        .build_with_dest(&mut dst)
+        .unwrap();
+    writer.write_record(&(value.to_string(),)).unwrap();
+    writer.close().unwrap();
+    drop(writer);
+
+    dst.set_position(0);
+    let mut reader = Reader::new_with_encoding(dst, encoding).unwrap();
+    let mut records = reader.read().unwrap();
+    match records.remove(0).remove("text") {
+        Some(FieldValue::Character(Some(text))) => text,
+        other => panic!("unexpected value {:?}", other),
+    }
+}
+
+#[test]
+fn test_truncated_character_keeps_whole_characters() {
+    let text = write_and_read_back_character("ééééé", 5, dbase::encoding::UnicodeLossy);
+    assert_eq!(text, "éé");
+
+    let text = write_and_read_back_character("abcdé", 5, dbase::encoding::UnicodeLossy);
+    assert_eq!(text, "abcd");
+}
+
+#[cfg(feature = "encoding_rs")]
+#[test]
+fn test_truncated_cp936_character_keeps_whole_characters() {
+    let encoding = dbase::encoding::EncodingRs::from(encoding_rs::GBK);
+    let original = "测试中文测试中文";
+    let text = write_and_read_back_character(original, 15, encoding);
+    assert_eq!(text, "测试中文测试中");
+    assert!(original.starts_with(&text));
+
+    let text = write_and_read_back_character("ab测试中文", 7, encoding);
+    assert_eq!(text, "ab测试");
+}
+
+#[derive(Debug, PartialEq)]
+struct NameAndMemoIndex {
+    name: String,
+    notes: Option<u32>,
+}
+
+impl ReadableRecord for NameAndMemoIndex {
+    fn read_using<T, M>(field_iterator: &mut FieldIterator<T, M>) -> Result<Self, FieldIOError>
+    where
+        T: Read + Seek,
+        M: Read + Seek,
+    {
+        Ok(Self {
+            name: field_iterator.read_next_field_as()?.value,
+            notes: field_iterator.read_next_memo_index()?,
+        })
+    }
+}
+
+#[test]
+fn test_read_memo_indices() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let dbf_path = dir.path().join("memo.dbf");
+    std::fs::copy(MEMO_FPT, dir.path().join("memo.fpt"))?;
+    // Make the second record point to the memo of the first one
+    let mut dbf = std::fs::read(MEMO_DBF)?;
+    let first_record = usize::from(u16::from_le_bytes([dbf[8], dbf[9]]));
+    let record_size = usize::from(u16::from_le_bytes([dbf[10], dbf[11]]));
+    let notes_start = first_record + record_size + 1 + 10;
+    dbf[notes_start..notes_start + 10].copy_from_slice(b"         8");
+    std::fs::write(&dbf_path, dbf)?;
+
+    // Indices are read without the memo file
+    let mut reader = Reader::new(std::fs::File::open(&dbf_path)?)?;
+    let records = reader.read_as::<NameAndMemoIndex>()?;
+    let indices = records.iter().map(|r| r.notes).collect::<Vec<_>>();
+    assert_eq!(indices, vec![Some(8), Some(8), None]);
+    assert!(matches!(
+        reader.read_memo_text(8).unwrap_err().kind(),
+        dbase::ErrorKind::MissingMemoFile
+    ));
+
+    let mut reader = Reader::from_path(&dbf_path)?;
+    let records = reader.read_as::<NameAndMemoIndex>()?;
+    let first = reader.read_memo_text(records[0].notes.unwrap())?;
+    let second = reader.read_memo_text(records[1].notes.unwrap())?;
+    assert_eq!(first, "First memo");
+    assert_eq!(first, second);
+
+    let mut file = dbase::File::open_read_only(&dbf_path)?;
+    let index = file
+        .record(1)
+        .unwrap()
+        .field(dbase::FieldIndex(1))
+        .unwrap()
+        .read_memo_index()?;
+    assert_eq!(index, Some(8));
+    assert_eq!(file.read_memo_text(8)?, "First memo");
+    let error = file
+        .record(1)
+        .unwrap()
+        .field(dbase::FieldIndex(0))
+        .unwrap()
+        .read_memo_index()
+        .unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::IncompatibleType));
+    Ok(())
+}
+
+struct BorrowedStationName {
+    name: Option<String>,
+    was_borrowed: bool,
+}
+
+impl ReadableRecord for BorrowedStationName {
+    fn read_using<T, M>(field_iterator: &mut FieldIterator<T, M>) -> Result<Self, FieldIOError>
+    where
+        T: Read + Seek,
+        M: Read + Seek,
+    {
+        let name = field_iterator.read_next_field_str()?;
+        assert_eq!(name.name, "name");
+        Ok(Self {
+            was_borrowed: matches!(name.value, Some(std::borrow::Cow::Borrowed(_))),
+            name: name.value.map(std::borrow::Cow::into_owned),
+        })
+    }
+}
+
+#[test]
+fn test_read_borrowed_character_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let records = dbase::read(STATIONS)?;
+    let mut reader = Reader::from_path(STATIONS)?;
+    let borrowed_records = reader.read_as::<BorrowedStationName>()?;
+    assert_eq!(records.len(), borrowed_records.len());
+    for (record, borrowed) in records.iter().zip(&borrowed_records) {
+        assert!(borrowed.was_borrowed);
+        assert_eq!(
+            record.get("name"),
+            Some(&FieldValue::Character(borrowed.name.clone()))
+        );
+    }
+
+    let mut file = dbase::File::open_read_only(STATIONS)?;
+    let name_idx = file.field_index("name").unwrap();
+    let mut record = file.record(3).unwrap();
+    assert_eq!(
+        record.read_field_str(name_idx)?.as_deref(),
+        Some("Judiciary Sq")
+    );
+    assert_eq!(
+        record.field(name_idx).unwrap().read_str()?.as_deref(),
+        Some("Judiciary Sq")
+    );
+    assert!(record.read_field_str(dbase::FieldIndex(100)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_records_end_at_file_terminator() -> Result<(), Box<dyn std::error::Error>> {
+    let expected = dbase::read("./tests/data/stations_optional.dbf")?;
+
+    let mut reader = Reader::from_path(STATIONS_INFLATED_NUM_RECORDS)?;
+    assert_eq!(reader.header().num_records, 9);
+    assert_eq!(reader.actual_record_count(), None);
+    let records = reader.read()?;
+    assert_eq!(records, expected);
+    assert_eq!(reader.actual_record_count(), Some(6));
+
+    let mut reader = Reader::from_path(STATIONS_WITH_DELETED)?;
+    let records = reader.read()?;
+    assert_eq!(records.len(), 5);
+    assert_eq!(reader.actual_record_count(), Some(6));
+    Ok(())
+}
+
+/// Fails the reads past `fail_at` bytes with an error other than the end of the source
+struct FailingReader {
+    inner: Cursor<Vec<u8>>,
+    fail_at: u64,
+}
+
+impl Read for FailingReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.inner.position() >= self.fail_at {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "disk failure",
+            ));
+        }
+        self.inner.read(buf)
+    }
+}
+
+impl Seek for FailingReader {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn test_records_end_at_end_of_truncated_file() -> Result<(), Box<dyn std::error::Error>> {
+    use dbase::Warning;
+
+    let bytes = std::fs::read(STATIONS)?;
+    let offset = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+    let record_size = u16::from_le_bytes([bytes[10], bytes[11]]) as usize;
+
+    // Without the file terminator, after a whole record or in the middle of one
+    for end in [offset + 10 * record_size, offset + 10 * record_size + 5] {
+        let mut reader = Reader::new(Cursor::new(bytes[..end].to_vec()))?;
+        reader.take_warnings();
+        assert_eq!(reader.read()?.len(), 10);
+        assert_eq!(reader.actual_record_count(), Some(10));
+        assert_eq!(
+            reader.take_warnings(),
+            vec![Warning::MissingRecords {
+                num_records: 86,
+                records_found: 10,
+            }]
+        );
+    }
+
+    // The other errors of the source are returned
+    let source = FailingReader {
+        inner: Cursor::new(bytes),
+        fail_at: (offset + 10 * record_size) as u64,
+    };
+    let mut reader = Reader::new(source)?;
+    let mut records = reader.iter_records();
+    for _ in 0..10 {
+        assert!(records.next().unwrap().is_ok());
+    }
+    let error = records.next().unwrap().unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::IoError(_)));
+    assert_eq!(error.record_num(), 10);
+    assert!(records.next().is_none());
+    Ok(())
+}
+
+/// Counts the calls to `write`
+struct CountingWriter {
+    inner: Cursor<Vec<u8>>,
+    num_writes: std::rc::Rc<std::cell::Cell<usize>>,
+}
+
+impl Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.num_writes.set(self.num_writes.get() + 1);
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl Seek for CountingWriter {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn test_padding_is_written_at_once() -> Result<(), Box<dyn std::error::Error>> {
+    let num_writes = std::rc::Rc::new(std::cell::Cell::new(0));
+    let mut dst = CountingWriter {
+        inner: Cursor::new(vec![]),
+        num_writes: num_writes.clone(),
+    };
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("name".try_into().unwrap(), 254)
+        .add_numeric_field("value".try_into().unwrap(), 10, 2)
+        .add_date_field("date".try_into().unwrap())
+        .build_with_dest(&mut dst)?;
+
+    let mut record = Record::default();
+    record.insert("name".to_string(), FieldValue::Character(Some("a".into())));
+    record.insert("value".to_string(), FieldValue::Numeric(Some(1.5)));
+    record.insert("date".to_string(), FieldValue::Date(None));
+    // The first record also writes the header
+    writer.write_record(&record)?;
+    let before = num_writes.get();
+    writer.write_record(&record)?;
+    // Deletion flag, value and padding of the Character field,
+    // Numeric value (already padded to the field length), Date value
+    assert_eq!(num_writes.get() - before, 5);
+    writer.close()?;
+    drop(writer);
+
+    dst.inner.set_position(0);
+    let records = Reader::new(dst.inner)?.read()?;
+    assert_eq!(records, vec![record.clone(), record]);
+    Ok(())
+}
+
+#[test]
+fn test_for_each_record_reuses_one_record() -> Result<(), Box<dyn std::error::Error>> {
+    fn count_line(counts: &mut std::collections::BTreeMap<String, usize>, record: &Record) {
+        if let Some(FieldValue::Character(Some(line))) = record.get("line") {
+            *counts.entry(line.clone()).or_default() += 1;
+        }
+    }
+
+    let mut expected = std::collections::BTreeMap::new();
+    let records = dbase::read(STATIONS)?;
+    records
+        .iter()
+        .for_each(|record| count_line(&mut expected, record));
+
+    let mut counts = std::collections::BTreeMap::new();
+    let mut num_records = 0;
+    let mut reader = Reader::from_path(STATIONS)?;
+    reader.for_each_record(|record| {
+        assert_eq!(record, &records[num_records]);
+        count_line(&mut counts, record);
+        num_records += 1;
+        Ok::<(), dbase::Error>(())
+    })?;
+    assert_eq!(num_records, records.len());
+    assert_eq!(counts, expected);
+
+    // Errors of the closure stop the iteration
+    let mut num_calls = 0;
+    let result = Reader::from_path(STATIONS)?.for_each_record(|_| {
+        num_calls += 1;
+        Err(Box::<dyn std::error::Error>::from("stop"))
+    });
+    assert!(result.is_err());
+    assert_eq!(num_calls, 1);
+    Ok(())
+}
+
+#[cfg(all(unix, feature = "locking"))]
+#[test]
+fn test_open_options_locks() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = tempfile::tempdir()?;
+    let path = tmp_dir.path().join("stations.dbf");
+    std::fs::copy(STATIONS, &path)?;
+
+    let is_would_block = |error: &dbase::Error| matches!(error.kind(), dbase::ErrorKind::IoError(e) if e.kind() == std::io::ErrorKind::WouldBlock);
+
+    let mut options = dbase::File::open_options();
+    options.read(true).write(true).exclusive_lock(true);
+    let file = options.open(&path)?;
+
+    match options.open(&path) {
+        Err(error) => assert!(is_would_block(&error)),
+        Ok(_) => panic!("The file should already be locked"),
+    }
+    let mut shared = dbase::File::open_options();
+    shared.read(true).shared_lock(true);
+    match shared.open(&path) {
+        Err(error) => assert!(is_would_block(&error)),
+        Ok(_) => panic!("The file should already be locked"),
+    }
+
+    // Opening without a lock is still possible
+    dbase::File::open_read_only(&path)?;
+
+    drop(file);
+    let first = shared.open(&path)?;
+    let second = shared.open(&path)?;
+    assert!(options.open(&path).is_err());
+    drop((first, second));
+    options.open(&path)?;
+    Ok(())
+}
+
+#[test]
+fn test_binary_character_fields_are_not_decoded() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes: Vec<u8> = vec![0xff, 0x00, 0x80, 0xc3, 0x28, 0x1a];
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::with_encoding(dbase::Unicode)
+        .add_character_field("NAME".try_into()?, 10)
+        .add_binary_character_field("RAW".try_into()?, 6)
+        .build_with_dest(&mut dst)?;
+
+    let mut record = Record::default();
+    record.insert("NAME".to_string(), "raw".to_string().into());
+    record.insert(
+        "RAW".to_string(),
+        FieldValue::CharacterBinary(bytes.clone()),
+    );
+    writer.write_record(&record)?;
+    drop(writer);
+
+    dst.set_position(0);
+    let mut reader = Reader::new_with_encoding(dst.clone(), dbase::Unicode)?;
+    assert!(!reader.fields()[0].flags().is_binary());
+    assert!(reader.fields()[1].flags().is_binary());
+    let records = reader.read()?;
+    assert_eq!(records, vec![record]);
+
+    // File pads values shorter than the field with zeros, they are kept
+    let mut file = dbase::File::open(dst)?;
+    let raw = file.field_index("RAW").unwrap();
+    let mut record = file.record(0).unwrap();
+    record.field(raw).unwrap().write(&&bytes[..2])?;
+    assert_eq!(
+        record.field(raw).unwrap().read()?,
+        FieldValue::CharacterBinary(vec![0xff, 0x00, 0, 0, 0, 0])
+    );
+    record.field(raw).unwrap().write(&bytes)?;
+    assert_eq!(
+        record.field(raw).unwrap().read()?,
+        FieldValue::CharacterBinary(bytes)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_binary_character_fields_round_trip_byte_exact() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_binary_character_field("RAW".try_into()?, 6)
+        .build_with_dest(&mut dst)?;
+    let mut record = Record::default();
+    record.insert(
+        "RAW".to_string(),
+        FieldValue::CharacterBinary(vec![0xff, 0x20]),
+    );
+    writer.write_record(&record)?;
+    drop(writer);
+
+    // The value is padded with zeros, not spaces
+    let original = dst.into_inner();
+    let mut reader = Reader::new(Cursor::new(original.clone()))?;
+    let offset = reader.header().offset_to_first_record as usize;
+    assert_eq!(
+        &original[offset..offset + 7],
+        &[b' ', 0xff, 0x20, 0x00, 0x00, 0x00, 0x00]
+    );
+
+    let table_info = reader.table_info();
+    let records = reader.read()?;
+    assert_eq!(
+        records[0].get("RAW"),
+        Some(&FieldValue::CharacterBinary(vec![0xff, 0x20, 0, 0, 0, 0]))
+    );
+    let mut copy = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::from_table_info(table_info)
+        .build_with_dest(&mut copy)?
+        .write_records(&records)?;
+    assert_eq!(&copy.get_ref()[offset..], &original[offset..]);
+    Ok(())
+}
+
+dbase::dbase_record!(
+    #[derive(Debug, PartialEq)]
+    struct SwappedPlace {
+        city: String,
+        name: String,
+    }
+);
+
+dbase::dbase_record!(
+    #[dbase(check_names)]
+    #[derive(Debug, PartialEq)]
+    struct CheckedSwappedPlace {
+        city: String,
+        name: String,
+    }
+);
+
+dbase::dbase_record!(
+    #[dbase(check_names)]
+    #[derive(Debug, PartialEq)]
+    struct CheckedPlace {
+        name: String,
+        city: String,
+    }
+);
+
+#[test]
+fn test_read_with_field_names_checked() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("NAME".try_into()?, 20)
+        .add_character_field("CITY".try_into()?, 20)
+        .build_with_dest(&mut dst)?;
+    writer.write_record(&CheckedPlace {
+        name: "Louvre".to_string(),
+        city: "Paris".to_string(),
+    })?;
+    drop(writer);
+
+    // Only positions are used, the values end up in the wrong fields
+    dst.set_position(0);
+    let places = Reader::new(dst.clone())?.read_as::<SwappedPlace>()?;
+    assert_eq!(places[0].city, "Louvre");
+
+    dst.set_position(0);
+    let error = Reader::new(dst.clone())?
+        .read_as::<CheckedSwappedPlace>()
+        .unwrap_err();
+    match error.kind() {
+        dbase::ErrorKind::FieldNameMismatch { expected, actual } => {
+            assert_eq!(expected, "city");
+            assert_eq!(actual, "NAME");
+        }
+        kind => panic!("Unexpected error {:?}", kind),
+    }
+    assert_eq!(error.field().as_ref().map(|f| f.name()), Some("NAME"));
+    assert_eq!(error.record_num(), 0);
+
+    dst.set_position(0);
+    let places = Reader::new(dst)?.read_as::<CheckedPlace>()?;
+    assert_eq!(
+        places,
+        vec![CheckedPlace {
+            name: "Louvre".to_string(),
+            city: "Paris".to_string(),
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_append_generates_autoincrement_values() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = tempfile::tempdir()?;
+    let path = tmp_dir.path().join("autoincrement.dbf");
+    let table_info = TableWriterBuilder::new()
+        .add_autoincrement_field("ID".try_into()?, 1, 1)
+        .add_character_field("NAME".try_into()?, 10)
+        .build_table_info()?;
+
+    let mut file = dbase::File::create(&path, table_info)?;
+    let records = ["a", "b", "c"]
+        .iter()
+        .map(|name| {
+            let mut record = Record::default();
+            record.insert("NAME".to_string(), name.to_string().into());
+            record
+        })
+        .collect::<Vec<_>>();
+    file.append_records(&records)?;
+    drop(file);
+
+    let mut file = dbase::File::open_read_write(&path)?;
+    assert_eq!(file.fields()[0].autoincrement(), Some((4, 1)));
+    let ids = (0..3)
+        .map(|i| file.record(i).unwrap().read_field(dbase::FieldIndex(0)))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        ids,
+        vec![
+            FieldValue::Integer(1),
+            FieldValue::Integer(2),
+            FieldValue::Integer(3)
+        ]
+    );
+
+    // By default, a given value is replaced
+    let mut record = records[0].clone();
+    record.insert("ID".to_string(), FieldValue::Integer(42));
+    file.append_record(&record)?;
+    assert_eq!(
+        file.record(3).unwrap().read_field(dbase::FieldIndex(0))?,
+        FieldValue::Integer(4)
+    );
+
+    file.set_autoincrement_policy(dbase::AutoIncrementPolicy::Error);
+    let error = file.append_record(&record).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::AutoIncrementValueGiven
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_progress_callbacks() -> Result<(), Box<dyn std::error::Error>> {
+    use std::sync::{Arc, Mutex};
+
+    let calls = Arc::new(Mutex::new(Vec::<dbase::Progress>::new()));
+    let calls_in_callback = Arc::clone(&calls);
+    let options = dbase::ReadingOptions::default()
+        .with_progress(Box::new(move |progress| {
+            calls_in_callback.lock().unwrap().push(progress)
+        }))
+        .progress_interval(10);
+    let mut reader = Reader::from_path(STATIONS)?;
+    reader.set_options(options);
+    let records = reader.read()?;
+    assert_eq!(records.len(), 86);
+    {
+        let calls = calls.lock().unwrap();
+        // Every 10 records and after the last one
+        assert_eq!(calls.len(), 9);
+        assert_eq!(
+            calls.last(),
+            Some(&dbase::Progress {
+                records_done: 86,
+                records_total: 86
+            })
+        );
+    }
+
+    let written = Arc::new(Mutex::new(Vec::<u32>::new()));
+    let written_in_callback = Arc::clone(&written);
+    let writer = TableWriterBuilder::from_reader(reader)
+        .with_progress(Box::new(move |progress| {
+            assert_eq!(progress.records_total, 86);
+            written_in_callback
+                .lock()
+                .unwrap()
+                .push(progress.records_done)
+        }))
+        .progress_interval(40)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()))?;
+    writer.write_records(&records)?;
+    assert_eq!(*written.lock().unwrap(), vec![40, 80, 86]);
+    Ok(())
+}
+
+#[test]
+fn test_infer_schema_from_stations() -> Result<(), Box<dyn std::error::Error>> {
+    let records = dbase::read(STATIONS)?;
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let writer = dbase::infer_schema(&records)?
+        .precheck_records(true)
+        .build_with_dest(&mut dst)?;
+    writer.write_records(&records)?;
+
+    dst.set_position(0);
+    let mut reader = Reader::new(dst)?;
+    for field in reader.fields() {
+        let longest = records
+            .iter()
+            .filter_map(|record| match record.get(field.name()) {
+                Some(FieldValue::Character(Some(text))) => Some(text.len()),
+                _ => None,
+            })
+            .max()
+            .unwrap();
+        assert_eq!(field.length() as usize, longest);
+    }
+    assert_eq!(reader.read()?, records);
+    Ok(())
+}
+
+#[test]
+fn test_infer_schema_numbers_and_margin() -> Result<(), Box<dyn std::error::Error>> {
+    use dbase::schema::SchemaInferer;
+
+    let values = [
+        (Some(12.5), Some("été"), Some(true)),
+        (Some(-1234.0), None, None),
+        (None, Some("a"), Some(false)),
+        (Some(0.125), Some("ab"), None),
+    ];
+    let records = values
+        .iter()
+        .map(|(number, text, flag)| {
+            let mut record = Record::default();
+            record.insert("number".to_string(), FieldValue::Numeric(*number));
+            record.insert(
+                "text".to_string(),
+                FieldValue::Character(text.map(str::to_string)),
+            );
+            record.insert("flag".to_string(), FieldValue::Logical(*flag));
+            record
+        })
+        .collect::<Vec<_>>();
+
+    let mut inferer = SchemaInferer::new().margin(1);
+    for record in &records {
+        inferer.observe(record)?;
+    }
+    let number = &inferer.fields()[1];
+    assert_eq!(number.name(), "number");
+    assert_eq!(number.max_length(), 5);
+    assert_eq!(number.max_decimals(), 3);
+    assert!(number.has_nulls());
+    assert_eq!(inferer.fields()[2].max_length(), "été".len());
+
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let writer = inferer
+        .clone()
+        .finish()
+        .precheck_records(true)
+        .build_with_dest(&mut dst)?;
+    writer.write_records(&records)?;
+    dst.set_position(0);
+    let mut reader = Reader::new(dst)?;
+    let lengths = reader
+        .fields()
+        .iter()
+        .map(|field| (field.name().to_string(), field.length()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        lengths,
+        vec![
+            ("flag".to_string(), 1),
+            ("number".to_string(), 10),
+            ("text".to_string(), 6)
+        ]
+    );
+    assert_eq!(reader.read()?, records);
+
+    let mut mixed = Record::default();
+    mixed.insert("number".to_string(), FieldValue::Float(Some(1.0)));
+    let error = inferer.observe(&mixed).unwrap_err();
+    assert_eq!(error.record_num(), 4);
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::IncompatibleRecord(dbase::schema::Incompatibility::TypeMismatch { .. })
+    ));
+    Ok(())
+}
+
+#[derive(Debug)]
+struct StationWithNumericMarker {
+    _name: String,
+    _marker_col: f64,
+}
+
+impl ReadableRecord for StationWithNumericMarker {
+    fn read_using<T, R>(field_iterator: &mut FieldIterator<T, R>) -> Result<Self, FieldIOError>
+    where
+        T: Read + Seek,
+        R: Read + Seek,
+    {
+        Ok(Self {
+            _name: field_iterator.read_next_field_as()?.value,
+            _marker_col: field_iterator.read_next_field_as()?.value,
+        })
+    }
+}
+
+#[test]
+fn test_errors_have_the_field_index() -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = Reader::from_path(STATIONS)?;
+    let error = reader
+        .iter_records_as::<StationWithNumericMarker>()
+        .next()
+        .unwrap()
+        .unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::BadConversion(_)));
+    assert_eq!(error.record_num(), 0);
+    assert_eq!(error.field_index(), Some(dbase::FieldIndex(1)));
+    assert!(error
+        .to_string()
+        .starts_with("Error { record_num: 0, field #1 'marker-col', kind: "));
+
+    let mut file = dbase::File::open_read_only(STATIONS)?;
+    let error = file
+        .record(2)
+        .unwrap()
+        .field(dbase::FieldIndex(3))
+        .unwrap()
+        .read_as::<bool>()
+        .unwrap_err();
+    assert_eq!(error.field_index(), Some(dbase::FieldIndex(3)));
+
+    let mut record = dbase::read(STATIONS)?.remove(0);
+    record.insert("marker-sym".to_string(), FieldValue::Logical(Some(true)));
+    let mut writer = TableWriterBuilder::from_reader(Reader::from_path(STATIONS)?)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()))?;
+    let error = writer.write_record(&record).unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::IncompatibleType));
+    assert_eq!(error.field_index(), Some(dbase::FieldIndex(2)));
+    assert_eq!(error.field().as_ref().unwrap().name(), "marker-sym");
+    Ok(())
+}
+
+#[test]
+fn test_numeric_values_are_right_justified() -> Result<(), Box<dyn std::error::Error>> {
+    let builder = || {
+        TableWriterBuilder::new()
+            .add_numeric_field("value".try_into().unwrap(), 10, 2)
+            .add_float_field("float".try_into().unwrap(), 6, 1)
+    };
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = builder().build_with_dest(&mut dst)?;
+    writer.write_record(&[FieldValue::Numeric(Some(-12.5)), FieldValue::Float(None)][..])?;
+    writer.write_record(
+        &[
+            FieldValue::Numeric(Some(1234567.891)),
+            FieldValue::Float(Some(3.25)),
+        ][..],
+    )?;
+    writer.close()?;
+    drop(writer);
+
+    // Two records of 17 bytes (deletion flag, 10 and 6 bytes) and the file terminator
+    let records = &dst.get_ref()[dst.get_ref().len() - 2 * 17 - 1..];
+    assert_eq!(&records[1..11], b"    -12.50");
+    assert_eq!(&records[11..17], b"      ");
+    assert_eq!(&records[18..28], b"1234567.89");
+    assert_eq!(&records[28..34], b"   3.2");
+
+    let mut writer = builder().build_with_dest(Cursor::new(Vec::<u8>::new()))?;
+    let error = writer
+        .write_record(
+            &[
+                FieldValue::Numeric(Some(12345678.0)),
+                FieldValue::Float(None),
+            ][..],
+        )
+        .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::NumericValueTooWide {
+            width: 10,
+            required: 11
+        }
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_preview_of_stations() -> Result<(), dbase::Error> {
+    let preview = dbase::Reader::from_path(STATIONS)?.preview(3)?;
+    assert_eq!(preview.num_records(), 3);
+    let expected = "  \
+name C(254)          | marker-col C(254) | marker-sym C(254) | line C(254)
+-----------------------+-------------------+-------------------+------------
+  Van Dorn Street      | #0000ff           | rail-metro        | blue
+  Franconia-Springfie… | #0000ff           | rail-metro        | blue
+  Federal Center SW    | #0000ff           | rail-metro        | blue
+";
+    assert_eq!(preview.to_string(), expected);
+
+    let mut file = dbase::File::open_read_only(STATIONS_WITH_DELETED)?;
+    let preview = file.preview(2)?.max_column_width(10);
+    let expected = "  \
+name C(25… | marker-co… | marker-sy… | line C(25…
+-------------+------------+------------+-----------
+* Van Dorn … | #0000ff    | rail-metro | blue
+  Franconia… | #0000ff    | rail-metro | blue
+";
+    assert_eq!(preview.to_string(), expected);
+
+    let mut reader = dbase::Reader::from_path(STATIONS_WITH_DELETED)?;
+    let preview = reader.preview(2)?.max_column_width(10);
+    assert_eq!(preview.to_string(), expected);
+    Ok(())
+}
+
+#[test]
+fn test_reader_and_file_borrowing_the_source() -> Result<(), dbase::Error> {
+    let mut source = Cursor::new(std::fs::read(STATIONS).unwrap());
+    let (records, table_info) = {
+        let mut reader = dbase::Reader::from_mut(&mut source)?;
+        let records = reader.read()?;
+        (records, reader.table_info())
+    };
+    assert_eq!(records.len(), 86);
+    // The reader read up to the end of the records
+    assert!(source.position() > 0);
+    source.set_position(0);
+
+    {
+        let mut file = dbase::File::open(&mut source)?;
+        assert_eq!(file.fields().len(), 4);
+        let record = file.record(0).unwrap().read()?;
+        assert_eq!(record, records[0]);
+    }
+    source.set_position(0);
+    assert_eq!(dbase::Reader::new(&mut source)?.read()?, records);
+
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::from_table_info(table_info)
+        .build_with_dest(&mut dst)?
+        .write_records(&records)?;
+    dst.set_position(0);
+    assert_eq!(dbase::Reader::new(dst)?.read()?, records);
+    Ok(())
+}
+
+#[test]
+fn test_reserved_header_bytes_are_kept_on_rewrite() -> Result<(), dbase::Error> {
+    let reserved_ranges = [12..14, 16..28, 30..32];
+    let mut bytes = std::fs::read(STATIONS).unwrap();
+    for range in reserved_ranges.iter().cloned() {
+        for i in range {
+            bytes[i] = 0xA0 + i as u8;
+        }
+    }
+
+    let mut reader = Reader::new(Cursor::new(bytes.clone()))?;
+    let records = reader.read()?;
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::from_reader(reader)
+        .build_with_dest(&mut dst)?
+        .write_records(&records)?;
+    let written = dst.into_inner();
+    for range in reserved_ranges.iter().cloned() {
+        assert_eq!(written[range.clone()], bytes[range]);
+    }
+
+    // Headers that were not read have zeros
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::new()
+        .add_logical_field("FLAG".try_into().unwrap())
+        .build_with_dest(&mut dst)?
+        .write_record(&[FieldValue::Logical(Some(true))][..])?;
+    let written = dst.into_inner();
+    for range in reserved_ranges.iter().cloned() {
+        assert!(written[range].iter().all(|b| *b == 0));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_double_fields_stored_as_text_or_binary() -> Result<(), dbase::Error> {
+    const TYPE_OF_FIRST_FIELD: usize = 32 + 11;
+    const LENGTH_OF_FIRST_FIELD: usize = 32 + 16;
+
+    // Clipper: a 'B' field of 10 bytes in a dBase III file, the values are text
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::new()
+        .add_numeric_field("VALUE".try_into().unwrap(), 10, 2)
+        .build_with_dest(&mut dst)?
+        .write_records(&[
+            Record::from_iter([("VALUE".to_string(), FieldValue::Numeric(Some(12.5)))]),
+            Record::from_iter([("VALUE".to_string(), FieldValue::Numeric(None))]),
+        ])?;
+    let mut bytes = dst.into_inner();
+    assert_eq!(bytes[0], 0x03);
+    bytes[TYPE_OF_FIRST_FIELD] = b'B';
+
+    let mut reader = Reader::new(Cursor::new(bytes))?;
+    assert_eq!(reader.fields()[0].field_type(), dbase::FieldType::Double);
+    assert!(reader.fields()[0].is_text_double());
+    let records = reader.read()?;
+    assert_eq!(
+        records[0].get("VALUE"),
+        Some(&FieldValue::Numeric(Some(12.5)))
+    );
+    assert_eq!(records[1].get("VALUE"), Some(&FieldValue::Numeric(None)));
+
+    // FoxPro: a 'B' field of 8 bytes, the values are binary
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::new()
+        .add_double_field("VALUE".try_into().unwrap())
+        .build_with_dest(&mut dst)?
+        .write_record(&Record::from_iter([(
+            "VALUE".to_string(),
+            FieldValue::Double(1.5),
+        )]))?;
+    let mut bytes = dst.into_inner();
+
+    let mut reader = Reader::new(Cursor::new(bytes.clone()))?;
+    assert!(!reader.fields()[0].is_text_double());
+    let records = reader.read()?;
+    assert_eq!(records[0].get("VALUE"), Some(&FieldValue::Double(1.5)));
+
+    // Neither of them
+    bytes[LENGTH_OF_FIRST_FIELD] = 12;
+    let Err(error) = Reader::new(Cursor::new(bytes)) else {
+        panic!("a 'B' field of 12 bytes should not be readable");
+    };
+    assert!(matches!(error.kind(), dbase::ErrorKind::InvalidHeader(_)));
+    assert_eq!(error.field_index(), Some(dbase::FieldIndex(0)));
+    Ok(())
+}
+
+#[test]
+fn test_write_named_values_from_borrowed_pairs() -> Result<(), dbase::Error> {
+    let stations = Reader::from_path(STATIONS)?.read()?;
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer =
+        TableWriterBuilder::from_reader(Reader::from_path(STATIONS)?).build_with_dest(&mut dst)?;
+    for station in &stations {
+        // In the reverse order of the fields
+        let mut values = station
+            .keys()
+            .zip(station.values())
+            .collect::<Vec<(&str, &FieldValue)>>();
+        values.sort_by(|a, b| b.0.cmp(a.0));
+        writer.write_named_values(values)?;
+    }
+    drop(writer);
+    dst.set_position(0);
+    assert_eq!(Reader::new(dst)?.read()?, stations);
+
+    let missing = FieldValue::Character(None);
+    let error = TableWriterBuilder::from_reader(Reader::from_path(STATIONS)?)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()))?
+        .write_named_values(vec![("name", &missing)])
+        .unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::Message(msg) if msg.contains("marker-col")));
+    Ok(())
+}
+
+#[test]
+fn test_non_ascii_names_are_matched_ignoring_case() -> Result<(), dbase::Error> {
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::with_encoding(dbase::Unicode)
+        .add_character_field("город".try_into().unwrap(), 20)
+        .add_numeric_field("straße".try_into().unwrap(), 10, 0)
+        .build_with_dest(&mut dst)?
+        // Written from a record whose names differ by the case
+        .write_record(&Record::from_iter([
+            ("ГОРОД", FieldValue::Character(Some("Москва".to_string()))),
+            ("STRASSE", FieldValue::Numeric(Some(12.0))),
+        ]))?;
+
+    dst.set_position(0);
+    let mut file = dbase::File::open(&mut dst)?;
+    assert_eq!(file.field_index("Город"), Some(dbase::FieldIndex(0)));
+    assert_eq!(file.field_index("STRASSE"), Some(dbase::FieldIndex(1)));
+    assert_eq!(file.field_index("улица"), None);
+    let record = file.record(0).unwrap().read()?;
+    assert_eq!(record.get("ГОРОД"), None);
+    assert_eq!(
+        record.get_ignoring_case("ГОРОД"),
+        Some(&FieldValue::Character(Some("Москва".to_string())))
+    );
+    drop(file);
+
+    dst.set_position(0);
+    let mut reader = Reader::new(dst)?;
+    reader.set_options(dbase::ReadingOptions::default().case_insensitive_names(true));
+    let records = reader.read()?;
+    assert_eq!(
+        records[0].get("Город"),
+        Some(&FieldValue::Character(Some("Москва".to_string())))
+    );
+    assert_eq!(
+        records[0].get("Strasse"),
+        Some(&FieldValue::Numeric(Some(12.0)))
+    );
+    reader.select_fields(&["ГОРОД"])?;
+    reader.seek(0)?;
+    assert_eq!(reader.read()?[0].len(), 1);
+
+    // Named values find their field the same way
+    let city = FieldValue::Character(Some("Киев".to_string()));
+    let street = FieldValue::Numeric(Some(3.0));
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::with_encoding(dbase::Unicode)
+        .add_character_field("город".try_into().unwrap(), 20)
+        .add_numeric_field("straße".try_into().unwrap(), 10, 0)
+        .build_with_dest(&mut dst)?
+        .write_named_values(vec![("STRASSE", &street), ("Город", &city)])?;
+    dst.set_position(0);
+    let records = Reader::new(dst)?.read()?;
+    assert_eq!(records[0].get("город"), Some(&city));
+    assert_eq!(records[0].get("straße"), Some(&street));
+
+    // Names that are the same ignoring the case are duplicates
+    let error = TableWriterBuilder::with_encoding(dbase::Unicode)
+        .add_character_field("город".try_into().unwrap(), 20)
+        .add_character_field("ГОРОД".try_into().unwrap(), 20)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()))
+        .err()
+        .unwrap();
+    assert!(matches!(error.kind(), dbase::ErrorKind::DuplicateFieldName));
+    Ok(())
+}
+
+#[test]
+fn test_overwrite_record_of_table_writer() -> Result<(), dbase::Error> {
+    let stations = Reader::from_path(STATIONS)?.read()?;
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer =
+        TableWriterBuilder::from_reader(Reader::from_path(STATIONS)?).build_with_dest(&mut dst)?;
+    for station in &stations[..3] {
+        writer.write_record(station)?;
+    }
+
+    let mut modified = stations[1].clone();
+    modified.insert(
+        "name".to_string(),
+        FieldValue::Character(Some("Renamed Station".to_string())),
+    );
+    writer.overwrite_record(1, &modified)?;
+
+    let error = writer.overwrite_record(3, &modified).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::InvalidRecordRange {
+            start: 3,
+            num_records: 3,
+            ..
+        }
+    ));
+    let mut incomplete = modified.clone();
+    incomplete.remove("line");
+    assert!(writer.overwrite_record(1, &incomplete).is_err());
+    writer.overwrite_record(1, &modified)?;
+
+    // Appending continues after the last record
+    writer.write_record(&stations[3])?;
+    writer.close()?;
+    drop(writer);
+
+    dst.set_position(0);
+    let records = Reader::new(dst)?.read()?;
+    assert_eq!(
+        records,
+        vec![
+            stations[0].clone(),
+            modified,
+            stations[2].clone(),
+            stations[3].clone()
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unexpected_deletion_flags() -> Result<(), dbase::Error> {
+    let mut bytes = std::fs::read(STATIONS).unwrap();
+    let offset = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+    let record_size = u16::from_le_bytes([bytes[10], bytes[11]]) as usize;
+    bytes[offset + 2 * record_size] = 0x00;
+
+    // Lenient by default: read as not deleted, and counted
+    let mut reader = Reader::new(Cursor::new(bytes.clone()))?;
+    assert_eq!(reader.read()?.len(), 86);
+    assert_eq!(reader.unexpected_deletion_flag_count(), 1);
+    reader.seek(0)?;
+    assert_eq!(reader.read()?.len(), 86);
+    assert_eq!(reader.unexpected_deletion_flag_count(), 1);
+
+    let mut reader = Reader::new(Cursor::new(bytes.clone()))?;
+    reader.set_options(dbase::ReadingOptions::default().strict_deletion_flag(true));
+    let results = reader.iter_records().collect::<Vec<_>>();
+    assert_eq!(results.len(), 86);
+    let error = results[2].as_ref().unwrap_err();
+    assert_eq!(error.record_num(), 2);
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::InvalidDeletionFlag(0x00)
+    ));
+    assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 85);
+    assert_eq!(reader.unexpected_deletion_flag_count(), 1);
+
+    let mut file = dbase::File::open(Cursor::new(bytes))?;
+    assert!(!file.record(2).unwrap().is_deleted()?);
+    file.set_options(dbase::ReadingOptions::default().strict_deletion_flag(true));
+    assert!(file.record(2).unwrap().is_deleted().is_err());
+    assert!(!file.record(3).unwrap().is_deleted()?);
+    Ok(())
+}
+
+#[test]
+fn test_write_records_from_text() -> Result<(), dbase::Error> {
+    let builder = || {
+        TableWriterBuilder::new()
+            .add_character_field("NAME".try_into().unwrap(), 10)
+            .add_numeric_field("AMOUNT".try_into().unwrap(), 10, 2)
+            .add_float_field("RATIO".try_into().unwrap(), 8, 3)
+            .add_logical_field("OPEN".try_into().unwrap())
+            .add_date_field("DAY".try_into().unwrap())
+            .add_integer_field("COUNT".try_into().unwrap())
+            .add_double_field("DOUBLE".try_into().unwrap())
+            .add_currency_field("PRICE".try_into().unwrap())
+            .add_datetime_field("STAMP".try_into().unwrap())
+    };
+
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = builder().build_with_dest(&mut dst)?;
+    writer.write_record(
+        &[
+            "Paris",
+            "12.5",
+            " -0.25 ",
+            "y",
+            "2024-03-01",
+            "42",
+            "1e3",
+            "9.99",
+            "2024-03-01T12:30:45",
+        ][..],
+    )?;
+    writer.write_record(&["", "", "", "?", "", "", "", "", ""][..])?;
+    let texts = [
+        "Lyon",
+        "-3",
+        "1",
+        "0",
+        "19991231",
+        "-7",
+        "0.5",
+        "1",
+        "1999-12-31 23:59:59",
+    ]
+    .map(String::from);
+    writer.write_record(&texts[..])?;
+    drop(writer);
+
+    dst.set_position(0);
+    let records = Reader::new(dst)?.read()?;
+    let values = records
+        .iter()
+        .map(|record| {
+            [
+                "NAME", "AMOUNT", "RATIO", "OPEN", "DAY", "COUNT", "DOUBLE", "PRICE", "STAMP",
+            ]
+            .map(|name| record.get(name).cloned().unwrap())
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        values[0],
+        [
+            FieldValue::Character(Some("Paris".to_string())),
+            FieldValue::Numeric(Some(12.5)),
+            FieldValue::Float(Some(-0.25)),
+            FieldValue::Logical(Some(true)),
+            FieldValue::Date(Some(Date::new(1, 3, 2024))),
+            FieldValue::Integer(42),
+            FieldValue::Double(1000.0),
+            FieldValue::Currency(9.99),
+            FieldValue::DateTime(DateTime::new(Date::new(1, 3, 2024), Time::new(12, 30, 45))),
+        ]
+    );
+    assert_eq!(values[1][0], FieldValue::Character(None));
+    assert_eq!(values[1][1], FieldValue::Numeric(None));
+    assert_eq!(values[1][2], FieldValue::Float(None));
+    assert_eq!(values[1][3], FieldValue::Logical(None));
+    assert_eq!(values[1][4], FieldValue::Date(None));
+    assert_eq!(values[1][5], FieldValue::Integer(0));
+    assert_eq!(values[1][6], FieldValue::Double(0.0));
+    assert_eq!(values[1][7], FieldValue::Currency(0.0));
+    assert_eq!(values[2][3], FieldValue::Logical(Some(false)));
+    assert_eq!(
+        values[2][4],
+        FieldValue::Date(Some(Date::new(31, 12, 1999)))
+    );
+    assert_eq!(
+        values[2][8],
+        FieldValue::DateTime(DateTime::new(
+            Date::new(31, 12, 1999),
+            Time::new(23, 59, 59)
+        ))
+    );
+
+    let valid = [
+        "Paris",
+        "1",
+        "1",
+        "T",
+        "20240301",
+        "1",
+        "1",
+        "1",
+        "2024-03-01 00:00:00",
+    ];
+    let invalid = [
+        (1, "12,5"),
+        (2, "abc"),
+        (3, "yes"),
+        (4, "2024-02-30"),
+        (4, "2024/03/01"),
+        (4, "1.3.2024"),
+        (5, "1.5"),
+        (6, "one"),
+        (7, "$1"),
+        (8, "2024-03-01 25:00:00"),
+        (8, "2024-03-01"),
+    ];
+    for (index, text) in invalid {
+        let mut texts = valid;
+        texts[index] = text;
+        let error = builder()
+            .build_with_dest(Cursor::new(Vec::<u8>::new()))?
+            .write_record(&texts[..])
+            .unwrap_err();
+        assert!(
+            matches!(error.kind(), dbase::ErrorKind::InvalidFieldText { text: t, .. } if t == text),
+            "{}",
+            error
+        );
+        assert_eq!(error.field_index(), Some(dbase::FieldIndex(index)));
+        let message = error.to_string();
+        assert!(message.contains(text), "{}", message);
+        let name = error.field().as_ref().unwrap().name().to_string();
+        assert!(message.contains(&name), "{}", message);
+    }
+
+    let error = builder()
+        .build_with_dest(Cursor::new(Vec::<u8>::new()))?
+        .write_record(&valid[..3])
+        .unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::NotEnoughFields));
+    Ok(())
+}
+
+#[test]
+fn test_table_info_sizes_match_the_written_file() -> Result<(), dbase::Error> {
+    let stations = Reader::from_path(STATIONS)?.read()?;
+    let info = Reader::from_path(STATIONS)?.into_table_info();
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::from_table_info(info.clone())
+        .build_with_dest(&mut dst)?
+        .write_records(&stations)?;
+    assert_eq!(
+        dst.get_ref().len() as u64,
+        info.expected_file_size(stations.len() as u32)
+    );
+    dst.set_position(0);
+    let header = *Reader::new(&mut dst)?.header();
+    assert_eq!(info.record_size(), header.size_of_record);
+    assert_eq!(info.offset_to_first_record(), header.offset_to_first_record);
+
+    let info = TableWriterBuilder::new()
+        .add_character_field("NAME".try_into().unwrap(), 10)
+        .add_integer_field("COUNT".try_into().unwrap())
+        .add_date_field("DAY".try_into().unwrap())
+        .set_database_backlink("stations.dbc")
+        .build_table_info()?;
+    assert_eq!(info.record_size(), 1 + 10 + 4 + 8);
+    assert_eq!(info.field_offsets(), vec![1, 11, 15]);
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::from_table_info(info.clone()).build_with_dest(&mut dst)?;
+    for i in 0..7 {
+        writer.write_record(&Record::from_iter([
+            ("NAME", FieldValue::Character(Some(i.to_string()))),
+            ("COUNT", FieldValue::Integer(i)),
+            ("DAY", FieldValue::Date(None)),
+        ]))?;
+    }
+    drop(writer);
+    assert_eq!(dst.get_ref().len() as u64, info.expected_file_size(7));
+
+    let mut empty = Cursor::new(Vec::<u8>::new());
+    drop(TableWriterBuilder::from_table_info(info.clone()).build_with_dest(&mut empty)?);
+    assert_eq!(empty.get_ref().len() as u64, info.expected_file_size(0));
+    Ok(())
+}
+
+#[test]
+fn test_count_records() -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = Reader::from_path(STATIONS)?;
+    let counts = reader.count_records()?;
+    assert_eq!(
+        counts,
+        dbase::RecordCounts {
+            header_count: 86,
+            physical_count: 86,
+            deleted_count: 0,
+        }
+    );
+    // The position is restored
+    assert_eq!(reader.read()?.len(), 86);
+
+    let counts = Reader::from_path(STATIONS_WITH_DELETED)?.count_records()?;
+    assert_eq!(counts.header_count, 6);
+    assert_eq!(counts.physical_count, 6);
+    assert_eq!(counts.deleted_count, 1);
+
+    let counts = Reader::from_path(STATIONS_INFLATED_NUM_RECORDS)?.count_records()?;
+    assert!(counts.physical_count < counts.header_count);
+
+    // Cut in the middle of the 4th record
+    let bytes = std::fs::read(STATIONS_WITH_DELETED)?;
+    let first_record = usize::from(u16::from_le_bytes([bytes[8], bytes[9]]));
+    let record_size = usize::from(u16::from_le_bytes([bytes[10], bytes[11]]));
+    let truncated = bytes[..first_record + 3 * record_size + 10].to_vec();
+    let counts = Reader::new(Cursor::new(truncated.clone()))?.count_records()?;
+    assert_eq!(counts.header_count, 6);
+    assert_eq!(counts.physical_count, 3);
+
+    let mut file = dbase::File::open(Cursor::new(truncated))?;
+    assert_eq!(file.count_records()?, counts);
+    let mut file = dbase::File::open_read_only(STATIONS_WITH_DELETED)?;
+    let first = file.record(0).unwrap().read()?;
+    assert_eq!(file.count_records()?.deleted_count, 1);
+    assert_eq!(file.record(0).unwrap().read()?, first);
+    Ok(())
+}
+
+#[test]
+fn test_create_empty_table() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("empty.dbf");
+    let builder = || {
+        TableWriterBuilder::new()
+            .add_character_field("NAME".try_into().unwrap(), 20)
+            .add_date_field("DAY".try_into().unwrap())
+    };
+    let info = builder().build_table_info()?;
+    builder().create_empty(&path)?;
+
+    let bytes = std::fs::read(&path)?;
+    assert_eq!(bytes.len() as u64, info.expected_file_size(0));
+    assert_eq!(bytes.last(), Some(&0x1A));
+
+    let mut reader = Reader::from_path(&path)?;
+    assert_eq!(reader.header().num_records, 0);
+    assert_eq!(reader.fields().len(), 2);
+    assert!(reader.read()?.is_empty());
+    assert_eq!(reader.count_records()?.physical_count, 0);
+
+    let mut file = dbase::File::open_read_only(&path)?;
+    assert_eq!(file.num_records(), 0);
+    assert!(file.record(0).is_none());
+    assert_eq!(file.count_records()?.physical_count, 0);
+
+    // Closing a writer that did not write records gives the same file
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::from_table_info(info)
+        .build_with_dest(&mut dst)?
+        .close()?;
+    assert_eq!(dst.get_ref()[32..], bytes[32..]);
+    Ok(())
+}
+
+#[test]
+fn test_iterate_records_several_times() -> Result<(), dbase::Error> {
+    let mut reader = Reader::new(Cursor::new(std::fs::read(STATIONS).unwrap()))?;
+    let first = reader.read()?;
+    let second = reader.iter_records().collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(first.len(), 86);
+    assert_eq!(first, second);
+
+    // Partially iterated, the clone starts at the first record
+    let partial = reader
+        .iter_records()
+        .take(5)
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(partial, first[..5]);
+    let mut clone = reader.clone();
+    assert_eq!(clone.iter_records().next().unwrap()?, first[0]);
+    assert_eq!(clone.read()?, first);
+
+    // Seeking sets the start of the next iteration only
+    reader.seek(84)?;
+    assert_eq!(reader.read()?, first[84..]);
+    assert_eq!(reader.read()?, first);
+    reader.seek(84)?;
+    reader.rewind()?;
+    assert_eq!(reader.read()?, first);
+    Ok(())
+}
+
+#[test]
+fn test_system_columns_are_skipped() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("NAME".try_into().unwrap(), 10)
+        .add_binary_character_field("_NullFlags".try_into().unwrap(), 2)
+        .add_numeric_field("VALUE".try_into().unwrap(), 10, 2)
+        .build_with_dest(&mut dst)?;
+    for (name, value) in [("one", 1.0), ("two", 2.5)] {
+        writer.write_record(&Record::from_iter([
+            ("NAME", FieldValue::Character(Some(name.to_string()))),
+            ("_NullFlags", FieldValue::CharacterBinary(vec![0xFF, 0x01])),
+            ("VALUE", FieldValue::Numeric(Some(value))),
+        ]))?;
+    }
+    drop(writer);
+    // Flag the second field as a system column
+    let mut bytes = dst.into_inner();
+    bytes[32 + 32 + 18] |= 0x01;
+
+    let mut reader = Reader::new(Cursor::new(bytes.clone()))?;
+    let names = reader
+        .fields()
+        .iter()
+        .map(|info| info.name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["NAME", "VALUE"]);
+    let records = reader.read()?;
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1].len(), 2);
+    assert_eq!(
+        records[1].get("NAME"),
+        Some(&FieldValue::Character(Some("two".to_string())))
+    );
+    assert_eq!(
+        records[1].get("VALUE"),
+        Some(&FieldValue::Numeric(Some(2.5)))
+    );
+    assert!(records[1].get("_NullFlags").is_none());
+
+    let mut file = dbase::File::open(Cursor::new(bytes.clone()))?;
+    assert_eq!(file.field_index("_NullFlags"), None);
+    let value_index = file.field_index("VALUE").unwrap();
+    let value = file.record(1).unwrap().field(value_index).unwrap().read()?;
+    assert_eq!(value, FieldValue::Numeric(Some(2.5)));
+
+    let options = dbase::ReadingOptions::default().include_system_columns(true);
+    let mut reader =
+        dbase::ReaderBuilder::<_, dbase::UnicodeLossy>::new(Cursor::new(bytes.clone()))
+            .with_options(options.clone())
+            .build()?;
+    assert_eq!(reader.fields().len(), 3);
+    assert!(reader.fields()[1].flags().is_system_column());
+    let records = reader.read()?;
+    assert_eq!(
+        records[0].get("_NullFlags"),
+        Some(&FieldValue::CharacterBinary(vec![0xFF, 0x01]))
+    );
+    let file = dbase::File::open_with_reading_options(Cursor::new(bytes), options)?;
+    assert_eq!(file.field_index("_NullFlags"), Some(dbase::FieldIndex(1)));
+    Ok(())
+}
+
+#[test]
+fn test_writing_keeps_the_system_columns() -> Result<(), Box<dyn std::error::Error>> {
+    // The system column is between the fields, or after the last one
+    for flags_index in [1, 2] {
+        let builder = TableWriterBuilder::new().add_character_field("NAME".try_into().unwrap(), 10);
+        let builder = if flags_index == 1 {
+            builder
+                .add_binary_character_field("_NullFlags".try_into().unwrap(), 2)
+                .add_numeric_field("VALUE".try_into().unwrap(), 10, 2)
+        } else {
+            builder
+                .add_numeric_field("VALUE".try_into().unwrap(), 10, 2)
+                .add_binary_character_field("_NullFlags".try_into().unwrap(), 2)
+        };
+        let mut dst = Cursor::new(Vec::<u8>::new());
+        let mut writer = builder.build_with_dest(&mut dst)?;
+        writer.write_record(&Record::from_iter([
+            ("NAME", FieldValue::Character(Some("one".to_string()))),
+            ("_NullFlags", FieldValue::CharacterBinary(vec![0xFF, 0x01])),
+            ("VALUE", FieldValue::Numeric(Some(1.0))),
+        ]))?;
+        drop(writer);
+        let mut bytes = dst.into_inner();
+        bytes[32 + 32 * flags_index + 18] |= 0x01;
+
+        let mut file = dbase::File::open(Cursor::new(&mut bytes))?;
+        assert_eq!(file.fields().len(), 2);
+        file.record(0).unwrap().write(&Record::from_iter([
+            ("NAME", FieldValue::Character(Some("uno".to_string()))),
+            ("VALUE", FieldValue::Numeric(Some(1.5))),
+        ]))?;
+        file.append_record(&Record::from_iter([
+            ("NAME", FieldValue::Character(Some("two".to_string()))),
+            ("VALUE", FieldValue::Numeric(Some(2.0))),
+        ]))?;
+        drop(file);
+
+        let options = dbase::ReadingOptions::default().include_system_columns(true);
+        let mut reader =
+            dbase::ReaderBuilder::<_, dbase::UnicodeLossy>::new(Cursor::new(bytes.clone()))
+                .with_options(options)
+                .build()?;
+        let header = *reader.header();
+        let records = reader.read()?;
+        // The existing flags are kept, the ones of new records are zeroed
+        assert_eq!(
+            records,
+            vec![
+                Record::from_iter([
+                    ("NAME", FieldValue::Character(Some("uno".to_string()))),
+                    ("_NullFlags", FieldValue::CharacterBinary(vec![0xFF, 0x01])),
+                    ("VALUE", FieldValue::Numeric(Some(1.5))),
+                ]),
+                Record::from_iter([
+                    ("NAME", FieldValue::Character(Some("two".to_string()))),
+                    ("_NullFlags", FieldValue::CharacterBinary(vec![0x00, 0x00])),
+                    ("VALUE", FieldValue::Numeric(Some(2.0))),
+                ]),
+            ]
+        );
+        let end_of_records =
+            usize::from(header.offset_to_first_record) + 2 * usize::from(header.size_of_record);
+        assert_eq!(bytes.len(), end_of_records + 1);
+        assert_eq!(bytes[end_of_records], 0x1A);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_many_consecutive_deleted_records() -> Result<(), dbase::Error> {
+    const NUM_DELETED: usize = 10_000;
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_integer_field("ID".try_into().unwrap())
+        .build_with_dest(&mut dst)?;
+    for id in 0..=NUM_DELETED as i32 {
+        writer.write_record(&(id,))?;
+    }
+    drop(writer);
+
+    let mut bytes = dst.into_inner();
+    let first_record = usize::from(u16::from_le_bytes([bytes[8], bytes[9]]));
+    let record_size = usize::from(u16::from_le_bytes([bytes[10], bytes[11]]));
+    for index in 0..NUM_DELETED {
+        bytes[first_record + index * record_size] = b'*';
+    }
+
+    let mut reader = Reader::new(Cursor::new(bytes))?;
+    let mut records = reader.iter_records();
+    let live = records.next().unwrap()?;
+    assert_eq!(
+        live.get("ID"),
+        Some(&FieldValue::Integer(NUM_DELETED as i32))
+    );
+    assert!(records.next().is_none());
+    drop(records);
+    assert_eq!(reader.actual_record_count(), Some(NUM_DELETED + 1));
+    assert_eq!(reader.count_records()?.deleted_count, NUM_DELETED);
+    Ok(())
+}
+
+#[cfg(feature = "yore")]
+#[test]
+fn test_invalid_bytes_in_character_field() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::with_encoding(yore::code_pages::CP1253)
+        .add_character_field("ID".try_into().unwrap(), 4)
+        .add_character_field("NAME".try_into().unwrap(), 10)
+        .build_with_dest(&mut dst)?;
+    writer.write_record(&("1".to_string(), "Αθήνα".to_string()))?;
+    writer.write_record(&("2".to_string(), "Caf?".to_string()))?;
+    drop(writer);
+
+    // 0xAA is not a character of CP1253
+    let mut bytes = dst.into_inner();
+    let first_record = usize::from(u16::from_le_bytes([bytes[8], bytes[9]]));
+    let record_size = usize::from(u16::from_le_bytes([bytes[10], bytes[11]]));
+    bytes[first_record + record_size + 1 + 4 + 3] = 0xAA;
+
+    let mut reader =
+        Reader::new_with_encoding(Cursor::new(bytes.clone()), yore::code_pages::CP1253)?;
+    let error = reader.read().unwrap_err();
+    assert_eq!(error.record_num(), 1);
+    assert_eq!(error.field().as_ref().unwrap().name(), "NAME");
+    let dbase::ErrorKind::StringDecodeError(dbase::DecodeError::InvalidBytes {
+        offset,
+        bytes: invalid_bytes,
+        ..
+    }) = error.kind()
+    else {
+        panic!("unexpected error: {}", error);
+    };
+    assert_eq!(*offset, 3);
+    assert_eq!(invalid_bytes[0], 0xAA);
+    assert!(error.to_string().contains("invalid byte at offset 3 (AA"));
+
+    for (mode, expected_issues) in [
+        (dbase::DecodeMode::Lossy, vec![]),
+        (
+            dbase::DecodeMode::LossyWithReport,
+            vec![dbase::DecodeIssue {
+                record_index: 1,
+                field_index: 1,
+                byte_offset: 3,
+            }],
+        ),
+    ] {
+        let mut reader =
+            Reader::new_with_encoding(Cursor::new(bytes.clone()), yore::code_pages::CP1253)?;
+        reader.set_options(dbase::ReadingOptions::default().character_decode(mode));
+        let records = reader.read()?;
+        assert_eq!(
+            records[0].get("NAME"),
+            Some(&FieldValue::Character(Some("Αθήνα".to_string())))
+        );
+        assert_eq!(
+            records[1].get("NAME"),
+            Some(&FieldValue::Character(Some("Caf\u{FFFD}".to_string())))
+        );
+        assert_eq!(reader.decode_issues(), expected_issues.as_slice());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_coerce_types() -> Result<(), Box<dyn std::error::Error>> {
+    let builder = || {
+        TableWriterBuilder::new()
+            .add_numeric_field("FROM_FLOAT".try_into().unwrap(), 10, 2)
+            .add_float_field("FROM_NUM".try_into().unwrap(), 10, 2)
+            .add_numeric_field("FROM_INT".try_into().unwrap(), 10, 0)
+            .add_double_field("INT_DOUBLE".try_into().unwrap())
+            .add_currency_field("FROM_DBL".try_into().unwrap())
+            .add_double_field("FROM_CUR".try_into().unwrap())
+            .add_character_field("FROM_DATE".try_into().unwrap(), 8)
+            .add_date_field("FROM_TEXT".try_into().unwrap())
+            .add_logical_field("TEXT_BOOL".try_into().unwrap())
+    };
+    let values = [
+        FieldValue::Float(Some(1.5)),
+        FieldValue::Numeric(Some(2.25)),
+        FieldValue::Integer(42),
+        FieldValue::Integer(-7),
+        FieldValue::Double(3.5),
+        FieldValue::Currency(4.25),
+        FieldValue::Date(Some(dbase::Date::new(1, 3, 2024))),
+        FieldValue::Character(Some("2024-03-01".to_string())),
+        FieldValue::Character(Some("Y".to_string())),
+    ];
+    let names = [
+        "FROM_FLOAT",
+        "FROM_NUM",
+        "FROM_INT",
+        "INT_DOUBLE",
+        "FROM_DBL",
+        "FROM_CUR",
+        "FROM_DATE",
+        "FROM_TEXT",
+        "TEXT_BOOL",
+    ];
+    let mut record = Record::default();
+    for (name, value) in names.iter().zip(values.iter()) {
+        record.insert(name.to_string(), value.clone());
+    }
+
+    // The strict default
+    let mut writer = builder().build_with_dest(Cursor::new(Vec::<u8>::new()))?;
+    let error = writer.write_record(&record).unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::IncompatibleType));
+
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = builder()
+        .coerce_types(true)
+        .precheck_records(true)
+        .build_with_dest(&mut dst)?;
+    writer.write_record(&record)?;
+    writer.write_record(&values[..])?;
+    drop(writer);
+
+    dst.set_position(0);
+    let records = Reader::new(dst)?.read()?;
+    assert_eq!(records.len(), 2);
+    let expected = [
+        FieldValue::Numeric(Some(1.5)),
+        FieldValue::Float(Some(2.25)),
+        FieldValue::Numeric(Some(42.0)),
+        FieldValue::Double(-7.0),
+        FieldValue::Currency(3.5),
+        FieldValue::Double(4.25),
+        FieldValue::Character(Some("20240301".to_string())),
+        FieldValue::Date(Some(dbase::Date::new(1, 3, 2024))),
+        FieldValue::Logical(Some(true)),
+    ];
+    for read in &records {
+        for (name, value) in names.iter().zip(expected.iter()) {
+            assert_eq!(read.get(name), Some(value), "{}", name);
+        }
+    }
+
+    // Logical values are not converted to text, even with coercion
+    let builder = || TableWriterBuilder::new().add_character_field("NAME".try_into().unwrap(), 8);
+    let logical = [FieldValue::Logical(Some(true))];
+    let mut writer = builder().build_with_dest(Cursor::new(Vec::<u8>::new()))?;
+    let error = writer.write_record(&logical[..]).unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::IncompatibleType));
+    let mut writer = builder()
+        .coerce_types(true)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()))?;
+    let error = writer.write_record(&logical[..]).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::BadConversion(dbase::FieldConversionError::FieldTypeNotAsExpected { .. })
+    ));
+    Ok(())
+}
+
+/// Stored as a one letter code in a Character field
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Status {
+    Active,
+    Closed,
+}
+
+impl FromFieldValue for Status {
+    fn from_field(
+        value: FieldValue,
+        info: &dbase::FieldInfo,
+    ) -> Result<Self, dbase::FieldConversionError> {
+        let code = String::from_field(value, info)?;
+        match code.as_str() {
+            "A" => Ok(Status::Active),
+            "C" => Ok(Status::Closed),
+            code => Err(dbase::FieldConversionError::Custom(format!(
+                "invalid status '{}' in field {}",
+                code,
+                info.name()
+            ))),
+        }
+    }
+}
+
+impl dbase::ToFieldValue for Status {
+    fn to_field_value(
+        &self,
+        _info: &dbase::FieldInfo,
+    ) -> Result<FieldValue, dbase::FieldConversionError> {
+        let code = match self {
+            Status::Active => "A",
+            Status::Closed => "C",
+        };
+        Ok(FieldValue::Character(Some(code.to_string())))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Account {
+    name: String,
+    status: Status,
+}
+
+impl ReadableRecord for Account {
+    fn read_using<T, R>(field_iterator: &mut FieldIterator<T, R>) -> Result<Self, FieldIOError>
+    where
+        T: Read + Seek,
+        R: Read + Seek,
+    {
+        Ok(Self {
+            name: field_iterator.read_next_field_as()?.value,
+            status: field_iterator.read_next_field_as()?.value,
+        })
+    }
+}
+
+impl WritableRecord for Account {
+    fn write_using<'a, W: Write>(
+        &self,
+        field_writer: &mut FieldWriter<'a, W>,
+    ) -> Result<(), FieldIOError> {
+        field_writer.write_next_field_as(&self.name)?;
+        field_writer.write_next_field_as(&self.status)?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_custom_field_conversions() -> Result<(), Box<dyn std::error::Error>> {
+    let accounts = vec![
+        Account {
+            name: "Alice".to_string(),
+            status: Status::Active,
+        },
+        Account {
+            name: "Bob".to_string(),
+            status: Status::Closed,
+        },
+    ];
+    let builder = || {
+        TableWriterBuilder::new()
+            .add_character_field("NAME".try_into().unwrap(), 10)
+            .add_character_field("STATUS".try_into().unwrap(), 1)
+    };
+
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = builder().build_with_dest(&mut dst)?;
+    for account in &accounts {
+        writer.write_record(account)?;
+    }
+    writer.write_record(
+        &[
+            FieldValue::Character(Some("Carol".to_string())),
+            FieldValue::Character(Some("X".to_string())),
+        ][..],
+    )?;
+    drop(writer);
+
+    dst.set_position(0);
+    let mut reader = Reader::new(dst)?;
+    let mut records = reader.iter_records_as::<Account>();
+    assert_eq!(records.next().unwrap()?, accounts[0]);
+    assert_eq!(records.next().unwrap()?, accounts[1]);
+    let error = records.next().unwrap().unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::BadConversion(dbase::FieldConversionError::Custom(_))
+    ));
+    assert!(error
+        .to_string()
+        .contains("invalid status 'X' in field STATUS"));
+    Ok(())
+}
+
+#[test]
+fn test_read_table_info_without_records() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = std::fs::read(STATIONS)?;
+    let offset = usize::from(u16::from_le_bytes([bytes[8], bytes[9]]));
+
+    let table_info = dbase::read_table_info(&bytes[..offset])?;
+    let names = table_info
+        .fields()
+        .iter()
+        .map(|info| info.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["name", "marker-col", "marker-sym", "line"]);
+    assert_eq!(table_info.header().num_records, 86);
+    assert_eq!(usize::from(table_info.offset_to_first_record()), offset);
+
+    // Only the bytes before the first record are consumed
+    let mut source = Cursor::new(&bytes);
+    dbase::read_table_info(&mut source)?;
+    assert_eq!(source.position() as usize, offset);
+
+    for len in [0, 20, offset - 1] {
+        let error = dbase::read_table_info(&bytes[..len]).err().unwrap();
+        assert!(
+            matches!(error.kind(), dbase::ErrorKind::IoError(_)),
+            "{}",
+            len
+        );
+    }
+
+    let mut corrupted = bytes[..offset].to_vec();
+    corrupted[offset - 1] = b' ';
+    let error = dbase::read_table_info(&corrupted[..]).err().unwrap();
+    assert!(matches!(error.kind(), dbase::ErrorKind::InvalidHeader(_)));
+    Ok(())
+}
+
+#[test]
+fn test_deterministic_output_with_fixed_date() -> Result<(), Box<dyn std::error::Error>> {
+    let write = || -> Result<Vec<u8>, dbase::Error> {
+        let mut writer = TableWriterBuilder::new()
+            .set_last_update(Date::new(2, 1, 2023))
+            .add_character_field("NAME".try_into().unwrap(), 20)
+            .add_numeric_field("VALUE".try_into().unwrap(), 10, 2)
+            .add_date_field("DAY".try_into().unwrap())
+            .build_with_dest(Cursor::new(Vec::<u8>::new()))?;
+        writer.write_record(&["Yoshi", "1.5", "2024-03-01"][..])?;
+        writer.write_record(&["Mario", "", ""][..])?;
+        Ok(writer.into_inner()?.into_inner())
+    };
+    let first = write()?;
+    assert_eq!(first[1..4], [123, 1, 2]);
+    assert_eq!(first, write()?);
+
+    let reader = Reader::from_path(STATIONS)?;
+    let source_date = reader.header().last_update;
+    let writer = TableWriterBuilder::from_reader(reader)
+        .preserve_last_update()
+        .build_with_dest(Cursor::new(Vec::<u8>::new()))?;
+    let bytes = writer.into_inner()?.into_inner();
+    let reader = Reader::new(Cursor::new(bytes))?;
+    assert_eq!(reader.header().last_update, source_date);
+    Ok(())
+}
+
+#[test]
+fn test_memory_usage_estimate_and_limit() -> Result<(), Box<dyn std::error::Error>> {
+    let mut data = std::fs::read(STATIONS)?;
+    let num_records = Reader::new(Cursor::new(data.clone()))?.header().num_records;
+    let estimate = Reader::new(Cursor::new(data.clone()))?.estimated_memory_usage();
+    assert!(estimate > 0);
+
+    // A header claiming a thousand times more records
+    data[4..8].copy_from_slice(&(num_records * 1000).to_le_bytes());
+    let mut reader = Reader::new(Cursor::new(data))?;
+    assert_eq!(reader.estimated_memory_usage(), estimate * 1000);
+
+    reader.set_options(dbase::ReadingOptions::default().max_materialized_records(Some(1000)));
+    let error = reader.read().unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::TooManyRecords { max: 1000, .. }
+    ));
+    assert!(reader.read_as::<Record>().is_err());
+    // The iterators are not limited
+    assert_eq!(reader.iter_records().take(2).count(), 2);
+
+    let mut reader = Reader::from_path(STATIONS)?;
+    reader
+        .set_options(dbase::ReadingOptions::default().max_materialized_records(Some(num_records)));
+    assert_eq!(reader.read()?.len(), num_records as usize);
+    Ok(())
+}
+
+/// Upper case text, written without going through a `FieldValue`
+struct Shouted(&'static str);
+
+impl dbase::DynWritableField for Shouted {
+    fn write_dyn(
+        &self,
+        _field_info: &dbase::FieldInfo,
+        encoding: &dyn dbase::Encoding,
+        dst: &mut dyn Write,
+    ) -> Result<(), dbase::ErrorKind> {
+        let text = self.0.to_uppercase();
+        let bytes = encoding.encode(&text)?;
+        dst.write_all(&bytes)?;
+        Ok(())
+    }
+}
+
+struct DynRow(Vec<Box<dyn dbase::DynWritableField>>);
+
+impl WritableRecord for DynRow {
+    fn write_using<'a, W: Write>(
+        &self,
+        field_writer: &mut FieldWriter<'a, W>,
+    ) -> Result<(), FieldIOError> {
+        for value in &self.0 {
+            field_writer.write_next_field_dyn(value.as_ref())?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_write_dyn_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("NAME".try_into().unwrap(), 20)
+        .add_numeric_field("VALUE".try_into().unwrap(), 10, 2)
+        .add_logical_field("OPEN".try_into().unwrap())
+        .add_date_field("DAY".try_into().unwrap())
+        .add_character_field("CODE".try_into().unwrap(), 10)
+        .build_with_dest(&mut dst)?;
+    let row = DynRow(vec![
+        Box::new("Yoshi"),
+        Box::new(1.5f64),
+        Box::new(true),
+        Box::new(Some(Date::new(1, 3, 2024))),
+        Box::new(Shouted("abc")),
+    ]);
+    writer.write_record(&row)?;
+    drop(writer);
+
+    dst.set_position(0);
+    let records = Reader::new(dst)?.read()?;
+    assert_eq!(records.len(), 1);
+    let record = &records[0];
+    assert_eq!(
+        record.get("NAME"),
+        Some(&FieldValue::Character(Some("Yoshi".to_string())))
+    );
+    assert_eq!(record.get("VALUE"), Some(&FieldValue::Numeric(Some(1.5))));
+    assert_eq!(record.get("OPEN"), Some(&FieldValue::Logical(Some(true))));
+    assert_eq!(
+        record.get("DAY"),
+        Some(&FieldValue::Date(Some(Date::new(1, 3, 2024))))
+    );
+    assert_eq!(
+        record.get("CODE"),
+        Some(&FieldValue::Character(Some("ABC".to_string())))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_read_null_flags_and_timestamp_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let at = DateTime::new(Date::new(15, 6, 2023), Time::new(10, 20, 30));
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("NAME".try_into().unwrap(), 10)
+        .add_binary_character_field("_NULLFLAGS".try_into().unwrap(), 1)
+        .add_datetime_field("CREATED".try_into().unwrap())
+        .build_with_dest(&mut dst)?;
+    let values = [
+        FieldValue::from("Yoshi".to_string()),
+        FieldValue::CharacterBinary(vec![0b10]),
+        FieldValue::DateTime(at),
+    ];
+    writer.write_record(&values[..])?;
+    drop(writer);
+
+    // The second and third fields become a '0' and a '@' field
+    let mut data = dst.into_inner();
+    data[32 + 32 + 11] = b'0';
+    data[32 + 2 * 32 + 11] = b'@';
+
+    let mut reader = Reader::new(Cursor::new(data))?;
+    assert_eq!(
+        reader.fields()[1].field_type(),
+        dbase::FieldType::SystemNullFlags
+    );
+    assert_eq!(reader.fields()[2].field_type(), dbase::FieldType::Timestamp);
+    let records = reader.read()?;
+    assert_eq!(
+        records[0].get("_NULLFLAGS"),
+        Some(&FieldValue::CharacterBinary(vec![0b10]))
+    );
+    assert_eq!(records[0].get("CREATED"), Some(&FieldValue::DateTime(at)));
+    Ok(())
+}
+
+#[test]
+fn test_filter_records_by_date_range() -> Result<(), Box<dyn std::error::Error>> {
+    let days = [
+        Some(Date::new(30, 12, 2023)),
+        Some(Date::new(31, 12, 2023)),
+        None,
+        Some(Date::new(1, 1, 2024)),
+        Some(Date::new(2, 1, 2024)),
+    ];
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("NAME".try_into().unwrap(), 10)
+        .add_date_field("DAY".try_into().unwrap())
+        .build_with_dest(&mut dst)?;
+    for (i, day) in days.iter().enumerate() {
+        let values = [
+            FieldValue::from(format!("event{}", i)),
+            FieldValue::Date(*day),
+        ];
+        writer.write_record(&values[..])?;
+    }
+    drop(writer);
+    dst.set_position(0);
+    let mut reader = Reader::new(dst)?;
+
+    let around_new_year = reader
+        .filter_by_date_range(
+            "day",
+            Some(Date::new(31, 12, 2023)),
+            Some(Date::new(1, 1, 2024)),
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(around_new_year.len(), 2);
+    assert_eq!(
+        around_new_year[0].get("DAY"),
+        Some(&FieldValue::Date(Some(Date::new(31, 12, 2023))))
+    );
+    assert_eq!(
+        around_new_year[1].get("DAY"),
+        Some(&FieldValue::Date(Some(Date::new(1, 1, 2024))))
+    );
+
+    let in_2024 = reader
+        .filter_by_date_range("DAY", Some(Date::new(1, 1, 2024)), None)?
+        .count();
+    assert_eq!(in_2024, 2);
+    let in_2023 = reader
+        .filter_by_date_range("DAY", None, Some(Date::new(31, 12, 2023)))?
+        .count();
+    assert_eq!(in_2023, 2);
+    let all = reader.filter_by_date_range("DAY", None, None)?.count();
+    assert_eq!(all, days.len());
+
+    let error = reader
+        .filter_by_date_range("NAME", None, None)
+        .err()
+        .unwrap();
+    assert!(matches!(error.kind(), dbase::ErrorKind::IncompatibleType));
+    assert!(reader.filter_by_date_range("MISSING", None, None).is_err());
+
+    let empty_dates = reader
+        .filter_records(|record| record.get("DAY") == Some(&FieldValue::Date(None)))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(empty_dates.len(), 1);
+    assert_eq!(
+        empty_dates[0].get("NAME"),
+        Some(&FieldValue::from("event2".to_string()))
+    );
+    Ok(())
+}
+
+#[test]
+fn copy_records_with_deleted_policy() -> Result<(), dbase::Error> {
+    use dbase::{CopyOptions, DeletedPolicy, DeletionFlag};
+
+    let copy = |options: CopyOptions| -> Result<(usize, Vec<u8>), dbase::Error> {
+        let mut reader = Reader::from_path(STATIONS_WITH_DELETED)?;
+        let mut cursor = Cursor::new(Vec::<u8>::new());
+        let mut writer = TableWriterBuilder::from_table_info(reader.table_info())
+            .build_with_dest(&mut cursor)?;
+        let num_copied = writer.copy_records_from(&mut reader, options)?;
+        writer.close()?;
+        drop(writer);
+        Ok((num_copied, cursor.into_inner()))
+    };
+    let original = Reader::from_path(STATIONS_WITH_DELETED)?
+        .iter_records_with_deletion()
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(original.len(), 6);
+
+    assert_eq!(CopyOptions::default().deleted, DeletedPolicy::Drop);
+    let (num_copied, dropped) = copy(CopyOptions::default())?;
+    assert_eq!(num_copied, 5);
+    let counts = Reader::new(Cursor::new(dropped.clone()))?.count_records()?;
+    assert_eq!((counts.header_count, counts.deleted_count), (5, 0));
+    let records = Reader::new(Cursor::new(dropped))?.read()?;
+    let kept = original
+        .iter()
+        .filter(|(_, flag)| *flag == DeletionFlag::NotDeleted)
+        .map(|(record, _)| record.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(records, kept);
+
+    let (num_copied, preserved) = copy(CopyOptions {
+        deleted: DeletedPolicy::Preserve,
+    })?;
+    assert_eq!(num_copied, 6);
+    let counts = Reader::new(Cursor::new(preserved.clone()))?.count_records()?;
+    assert_eq!((counts.header_count, counts.deleted_count), (6, 1));
+    let records = Reader::new(Cursor::new(preserved))?
+        .iter_records_with_deletion()
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(records, original);
+    Ok(())
+}
+
+#[test]
+fn table_flags_follow_the_memo_fields() -> Result<(), dbase::Error> {
+    use dbase::TableFlags;
+
+    let header_flags = |builder: TableWriterBuilder| -> Result<u8, dbase::Error> {
+        let bytes = builder
+            .build_with_dest(Cursor::new(Vec::<u8>::new()))?
+            .into_inner()?
+            .into_inner();
+        // Table flags are the byte 28 of the header
+        Ok(bytes[28])
+    };
+
+    // The autoincrement field makes them Visual FoxPro tables
+    let with_memo = TableWriterBuilder::from_reader(Reader::from_path(MEMO_DBF)?)
+        .add_autoincrement_field("ID".try_into().unwrap(), 1, 1);
+    assert_eq!(header_flags(with_memo)?, 0x02);
+
+    let without_memo =
+        TableWriterBuilder::new().add_autoincrement_field("ID".try_into().unwrap(), 1, 1);
+    assert_eq!(header_flags(without_memo)?, 0x00);
+
+    // The memo flag requested is not kept without Memo fields
+    let requested = TableWriterBuilder::new()
+        .set_table_flags(TableFlags::from_bits(0x07))
+        .add_autoincrement_field("ID".try_into().unwrap(), 1, 1);
+    assert_eq!(header_flags(requested)?, 0x05);
+
+    // Other versions have no memo flag
+    let dbase3 = TableWriterBuilder::from_reader(Reader::from_path(MEMO_DBF)?);
+    assert_eq!(header_flags(dbase3)?, 0x00);
+
+    let flags = TableFlags::new(true, false, true);
+    assert_eq!(flags.bits(), 0x05);
+    assert!(flags.has_structural_cdx() && !flags.has_memo_field() && flags.is_a_database());
+    Ok(())
+}
+
+#[test]
+fn test_warnings_of_the_fallbacks() -> Result<(), dbase::Error> {
+    use dbase::{CodePageMark, Warning};
+
+    let mut bytes = std::fs::read(STATIONS).unwrap();
+    let offset = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+    let record_size = u16::from_le_bytes([bytes[10], bytes[11]]) as usize;
+    bytes[offset + 2 * record_size] = 0x00;
+
+    // The header of stations.dbf has no code page
+    let mut reader = Reader::new(Cursor::new(bytes.clone()))?;
+    assert_eq!(
+        reader.take_warnings(),
+        vec![Warning::UnknownCodePage(CodePageMark::Undefined)]
+    );
+    assert!(reader.take_warnings().is_empty());
+    assert_eq!(reader.read()?.len(), 86);
+    let unexpected_flag = Warning::UnexpectedDeletionFlag {
+        record_index: 2,
+        byte: 0x00,
+        count: 1,
+    };
+    assert_eq!(reader.take_warnings(), vec![unexpected_flag.clone()]);
+    assert_eq!(
+        unexpected_flag.to_string(),
+        "The deletion flag of the record 2 is 0x00, the records are read as not deleted"
+    );
+
+    // One warning counts all the records with such a flag
+    let mut many_bytes = bytes.clone();
+    for i in 3..10 {
+        many_bytes[offset + i * record_size] = b'?';
+    }
+    let mut many_reader = Reader::new(Cursor::new(many_bytes.clone()))?;
+    many_reader.take_warnings();
+    assert_eq!(many_reader.read()?.len(), 86);
+    let unexpected_flags = Warning::UnexpectedDeletionFlag {
+        record_index: 2,
+        byte: 0x00,
+        count: 8,
+    };
+    assert_eq!(many_reader.take_warnings(), vec![unexpected_flags.clone()]);
+    assert_eq!(
+        unexpected_flags.to_string(),
+        "The deletion flag of the record 2 is 0x00, \
+         and 7 other records have such flags, the records are read as not deleted"
+    );
+
+    // The flag is an error, not a fallback
+    reader.set_options(dbase::ReadingOptions::default().strict_deletion_flag(true));
+    reader.seek(0)?;
+    assert_eq!(reader.iter_records().count(), 86);
+    assert!(reader.take_warnings().is_empty());
+
+    // No warning with a given encoding
+    let mut reader = Reader::new_with_encoding(Cursor::new(bytes.clone()), dbase::Unicode)?;
+    assert!(reader.take_warnings().is_empty());
+
+    let mut file = dbase::File::open(Cursor::new(bytes))?;
+    assert_eq!(
+        file.take_warnings(),
+        vec![Warning::UnknownCodePage(CodePageMark::Undefined)]
+    );
+    assert!(!file.record(2).unwrap().is_deleted()?);
+    assert!(!file.record(2).unwrap().is_deleted()?);
+    assert_eq!(file.take_warnings(), vec![unexpected_flag]);
+
+    let mut file = dbase::File::open(Cursor::new(many_bytes))?;
+    file.take_warnings();
+    for _ in 0..2 {
+        for i in 0..10 {
+            file.record(i).unwrap().is_deleted()?;
+        }
+    }
+    assert_eq!(file.take_warnings(), vec![unexpected_flags]);
+
+    let mut reader = Reader::from_path(STATIONS_INFLATED_NUM_RECORDS)?;
+    reader.take_warnings();
+    let counts = reader.count_records()?;
+    reader.read()?;
+    assert_eq!(
+        reader.take_warnings(),
+        vec![Warning::MissingRecords {
+            num_records: counts.header_count,
+            records_found: counts.physical_count,
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_field_writer_skip_fields() -> Result<(), Box<dyn std::error::Error>> {
+    struct Partial {
+        name: String,
+        line: String,
+    }
+
+    impl WritableRecord for Partial {
+        fn write_using<W: Write>(&self, writer: &mut FieldWriter<W>) -> Result<(), FieldIOError> {
+            writer.write_next_field_value(&self.name)?;
+            assert_eq!((writer.current_index(), writer.remaining()), (1, 3));
+            assert!(writer.skip_fields(4).is_err());
+            writer.skip_fields(2)?;
+            assert_eq!(writer.peek_info().map(|info| info.name()), Some("LINE"));
+            writer.write_next_field_value(&self.line)?;
+            assert!(writer.peek_info().is_none());
+            Ok(())
+        }
+    }
+
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::new()
+        .add_character_field("NAME".try_into().unwrap(), 20)
+        .add_integer_field("COUNT".try_into().unwrap())
+        .add_numeric_field("LENGTH".try_into().unwrap(), 10, 2)
+        .add_character_field("LINE".try_into().unwrap(), 10)
+        .build_with_dest(&mut dst)?
+        .write_records(&[Partial {
+            name: "Van Dorn Street".to_string(),
+            line: "blue".to_string(),
+        }])?;
+
+    dst.set_position(0);
+    let records = Reader::new(dst)?.read()?;
+    assert_eq!(
+        records,
+        vec![Record::from_iter([
+            (
+                "NAME",
+                FieldValue::Character(Some("Van Dorn Street".to_string()))
+            ),
+            ("COUNT", FieldValue::Integer(0)),
+            ("LENGTH", FieldValue::Numeric(None)),
+            ("LINE", FieldValue::Character(Some("blue".to_string()))),
+        ])]
+    );
+    Ok(())
+}